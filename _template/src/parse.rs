@@ -0,0 +1,39 @@
+use nom::{
+    character::complete::{self, space1},
+    error::context,
+    multi::separated_list1,
+};
+
+use aoclib::parse::{lines, parse_all, ParseResult, Span};
+use aoclib::Error;
+
+/// Parses every line of the input as a list of numbers. Replace this with the puzzle's format.
+pub fn parse_input(input: &str) -> Result<Vec<Vec<i64>>, Error> {
+    parse_all(input, lines(numbers))
+}
+
+// 0 3 6 9 12 15
+fn numbers(input: Span) -> ParseResult<Vec<i64>> {
+    context("numbers", separated_list1(space1, complete::i64))(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_input() {
+        assert_eq!(
+            parse_input("0 3 6\n1 3\n"),
+            Ok(vec![vec![0, 3, 6], vec![1, 3]])
+        );
+    }
+
+    #[test]
+    fn test_parse_error() {
+        assert_eq!(
+            parse_input("0 3 6\n1 3 x\n").unwrap_err().to_string(),
+            "line 2, column 4: expected the end of the line, found \" x\""
+        );
+    }
+}
@@ -1,17 +1,34 @@
-pub fn process<'a>(
-    _input: &'a str,
-) -> u32 {
+use crate::parse::parse_input;
+
+use aoclib::{Answer, Error, Solution};
+
+pub fn process(input: &str) -> u32 {
+    answer(&parse_input(input).unwrap_or_else(|error| panic!("should parse: {}", error)))
+}
+
+/// Like [`process`], but reports malformed input as an error.
+pub fn try_process(input: &str) -> Result<Answer, Error> {
+    Ok(answer(&parse_input(input)?).into())
+}
+
+fn answer(_lines: &[Vec<i64>]) -> u32 {
     todo!()
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+pub struct Part2;
+
+impl Solution for Part2 {
+    const DAY: u8 = {{day}};
+    const PART: u8 = 2;
+
+    type Input<'a> = Vec<Vec<i64>>;
+    type Params = ();
+
+    fn parse(input: &str) -> Result<Vec<Vec<i64>>, Error> {
+        parse_input(input)
+    }
 
-    #[test]
-    fn test_process() {
-        let lines = "<input>";
-        let result = process(lines);
-        assert_eq!(result, 8);
+    fn solve(input: Vec<Vec<i64>>, _: &()) -> Result<Answer, Error> {
+        Ok(answer(&input).into())
     }
 }
@@ -0,0 +1,118 @@
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::process::ExitCode;
+
+use aoc::solvers;
+use aoclib::bench::{Summary, Timings};
+use clap::{value_parser, Args};
+use serde::Serialize;
+
+use crate::table::Table;
+use crate::Selection;
+
+#[derive(Args)]
+pub struct BenchArgs {
+    #[command(flatten)]
+    selection: Selection,
+
+    /// How many times to parse and solve each input
+    #[arg(short = 'n', long, default_value_t = 10, value_parser = value_parser!(u32).range(1..))]
+    iterations: u32,
+
+    /// Print the results as JSON rather than a table
+    #[arg(long)]
+    json: bool,
+}
+
+#[derive(Serialize)]
+struct Report {
+    day: u8,
+    part: u8,
+    iterations: u32,
+    #[serde(flatten)]
+    outcome: Outcome,
+}
+
+#[derive(Serialize)]
+#[serde(untagged)]
+enum Outcome {
+    Timed { parse: Summary, solve: Summary },
+    Failed { error: String },
+}
+
+pub fn bench(args: BenchArgs) -> ExitCode {
+    let inputs = args.selection.inputs();
+
+    let solvers = args.selection.solvers();
+    let reports = solvers
+        .iter()
+        .map(|solver| {
+            let timings = args.selection.params(solver).and_then(|params| {
+                let input = inputs.load(solver)?;
+                time(|| solver.time(&input, &params, args.iterations as usize))
+            });
+            Report {
+                day: solver.day,
+                part: solver.part,
+                iterations: args.iterations,
+                outcome: match timings {
+                    Ok(timings) => Outcome::Timed {
+                        parse: timings.parse_summary(),
+                        solve: timings.solve_summary(),
+                    },
+                    Err(error) => Outcome::Failed { error },
+                },
+            }
+        })
+        .collect::<Vec<_>>();
+
+    if args.json {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&reports).expect("reports should serialise")
+        );
+    } else {
+        print!("{}", table(&reports));
+    }
+
+    if solvers.iter().zip(&reports).any(|(solver, report)| {
+        solvers::is_implemented(solver) && matches!(report.outcome, Outcome::Failed { .. })
+    }) {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+fn time(timings: impl FnOnce() -> Result<Timings, aoclib::Error>) -> Result<Timings, String> {
+    catch_unwind(AssertUnwindSafe(timings))
+        .map_err(|_| "solver panicked".to_string())?
+        .map_err(|error| error.to_string())
+}
+
+fn table(reports: &[Report]) -> Table {
+    let mut table = Table::new(&[
+        "Day",
+        "Part",
+        "Parse (min / median / max)",
+        "Solve (min / median / max)",
+    ]);
+    for report in reports {
+        let mut row = vec![report.day.to_string(), report.part.to_string()];
+        match &report.outcome {
+            Outcome::Timed { parse, solve } => {
+                row.push(format_summary(parse));
+                row.push(format_summary(solve));
+            }
+            Outcome::Failed { error } => row.push(format!("error: {}", error)),
+        }
+        table.push(row);
+    }
+    table
+}
+
+fn format_summary(summary: &Summary) -> String {
+    format!(
+        "{:.2?} / {:.2?} / {:.2?}",
+        summary.min, summary.median, summary.max
+    )
+}
@@ -0,0 +1,70 @@
+use std::io::{self, Write};
+use std::process::ExitCode;
+
+use aoclib::gen::{self, GENERATORS};
+use clap::{value_parser, Args};
+
+#[derive(Args)]
+#[command(after_help = sizes())]
+pub struct GenArgs {
+    /// The day to generate an input for
+    #[arg(value_parser = value_parser!(u8).range(1..=25))]
+    day: u8,
+
+    /// How big an input to generate, which means something different for each day (see below)
+    #[arg(long, default_value_t = 100)]
+    size: usize,
+
+    /// The seed for the random choices, so the same seed always gives the same input
+    #[arg(long, default_value_t = 0)]
+    seed: u64,
+}
+
+/// What the size counts for each day, for the help.
+fn sizes() -> String {
+    GENERATORS
+        .iter()
+        .fold("Sizes:".to_string(), |help, generator| {
+            format!(
+                "{help}\n  {:>2}  {}: {}",
+                generator.day, generator.format, generator.size
+            )
+        })
+}
+
+pub fn generate(args: GenArgs) -> ExitCode {
+    let generator = gen::generator(args.day).expect("every day should have a generator");
+    let input = generator.generate(args.size, args.seed);
+    match io::stdout().lock().write_all(input.as_bytes()) {
+        Ok(()) => ExitCode::SUCCESS,
+        // Piping into something like `head` is not a failure.
+        Err(error) if error.kind() == io::ErrorKind::BrokenPipe => ExitCode::SUCCESS,
+        Err(error) => {
+            eprintln!("error: cannot write the input: {}", error);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solvers;
+    use aoclib::Params;
+
+    #[test]
+    fn test_generated_inputs_solve() {
+        for solver in solvers::all().into_iter().filter(solvers::is_implemented) {
+            let params = solver.params(&Params::new(), true).unwrap();
+            for (size, seed) in [1, 5, 30].into_iter().zip(0..3) {
+                let input = gen::generator(solver.day).unwrap().generate(size, seed);
+                if let Err(error) = solver.solve(&input, &params) {
+                    panic!(
+                        "day {} part {} at size {size} with seed {seed}: {error}\n{input}",
+                        solver.day, solver.part
+                    );
+                }
+            }
+        }
+    }
+}
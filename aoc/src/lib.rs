@@ -0,0 +1,5 @@
+//! The registry of every day's solvers, and the limits on running them, shared by the `aoc`
+//! runner and the `server`.
+
+pub mod limits;
+pub mod solvers;
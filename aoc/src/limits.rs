@@ -0,0 +1,39 @@
+use std::time::Duration;
+
+use aoclib::budget::Budget;
+use clap::Args;
+
+/// The `--timeout` and `--max-steps` flags, which give each part a budget.
+#[derive(Args, Debug, Clone, Copy, Default)]
+pub struct Limits {
+    /// Give up on a part after this many seconds, for the solvers which can run away on
+    /// adversarial input
+    #[arg(long, value_name = "SECONDS", value_parser = parse_seconds)]
+    pub timeout: Option<Duration>,
+
+    /// Give up on a part after this many steps of its longest-running loop
+    #[arg(long, value_name = "STEPS")]
+    pub max_steps: Option<u64>,
+}
+
+impl Limits {
+    /// A fresh budget for one part, so the timeout counts from now.
+    pub fn budget(&self) -> Budget {
+        let mut budget = Budget::unlimited();
+        if let Some(timeout) = self.timeout {
+            budget = budget.with_timeout(timeout);
+        }
+        if let Some(max_steps) = self.max_steps {
+            budget = budget.with_max_steps(max_steps);
+        }
+        budget
+    }
+}
+
+/// Reads a number of seconds, which need not be whole.
+pub fn parse_seconds(seconds: &str) -> Result<Duration, String> {
+    let seconds: f64 = seconds
+        .parse()
+        .map_err(|_| format!("{:?} is not a number of seconds", seconds))?;
+    Duration::try_from_secs_f64(seconds).map_err(|error| error.to_string())
+}
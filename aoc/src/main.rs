@@ -0,0 +1,386 @@
+use std::cell::OnceCell;
+use std::fmt::Display;
+use std::fs;
+use std::io::{self, BufRead, IsTerminal};
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+use aoc::limits::Limits;
+use aoc::solvers;
+use aoclib::answers::{self, Answers, Check};
+use aoclib::input::{self, Source};
+use aoclib::params::{parse_assignment, Params};
+use aoclib::{Answer, Solver};
+use clap::{value_parser, ArgAction, Args, Parser, Subcommand};
+use tracing_subscriber::filter::{EnvFilter, LevelFilter};
+
+mod bench;
+mod gen;
+mod table;
+mod visualise;
+
+use table::Table;
+use visualise::Format;
+
+#[derive(Parser)]
+#[command(name = "aoc", about = "Runs the Advent of Code 2023 solutions")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+
+    /// Log what the solvers do to stderr, -v for each step of the way and -vv for every detail.
+    /// RUST_LOG narrows it down, e.g. `RUST_LOG=day_22=trace` for one day's internals
+    #[arg(short, long, action = ArgAction::Count, global = true)]
+    verbose: u8,
+
+    /// Log as JSON, one event per line, rather than as text
+    #[arg(long, global = true)]
+    log_json: bool,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Runs the solvers for a single day, or for every day
+    Run(RunArgs),
+    /// Times parsing and solving for a single day, or for every day
+    Bench(bench::BenchArgs),
+    /// Prints a generated input for a day, of any size, for testing how the solvers scale
+    Gen(gen::GenArgs),
+}
+
+/// Which solvers to run, and what to run them on.
+#[derive(Args)]
+pub struct Selection {
+    /// The day to run
+    #[arg(
+        required_unless_present = "all",
+        conflicts_with = "all",
+        value_parser = value_parser!(u8).range(1..=25)
+    )]
+    day: Option<u8>,
+
+    /// Run every registered day
+    #[arg(long)]
+    all: bool,
+
+    /// Only run this part
+    #[arg(long, value_parser = value_parser!(u8).range(1..=2))]
+    part: Option<u8>,
+
+    /// Read the puzzle input from this file, or from stdin if `-`, rather than the day's input.txt
+    #[arg(long, value_name = "PATH", conflicts_with = "all")]
+    input: Option<String>,
+
+    /// Read each day's input from `DIR/day-XX/input.txt` [env: AOC_INPUT_DIR]
+    #[arg(long, value_name = "DIR", conflicts_with = "input")]
+    input_dir: Option<PathBuf>,
+
+    /// Override a solver parameter, e.g. `--param steps=100`
+    #[arg(short = 'p', long = "param", value_name = "KEY=VALUE", value_parser = parse_assignment)]
+    params: Vec<(String, String)>,
+}
+
+impl Selection {
+    pub fn solvers(&self) -> Vec<Solver> {
+        solvers::all()
+            .into_iter()
+            .filter(|solver| self.day.is_none_or(|day| solver.day == day))
+            .filter(|solver| self.part.is_none_or(|part| solver.part == part))
+            .collect()
+    }
+
+    /// The parameters for `solver`, with any overrides applied. Overrides only need to apply to
+    /// some of the solvers when running every day.
+    pub fn params(&self, solver: &Solver) -> Result<Params, String> {
+        let mut overrides = Params::new();
+        for (key, value) in &self.params {
+            overrides.set(key, value);
+        }
+        solver
+            .params(&overrides, !self.all)
+            .map_err(|error| error.to_string())
+    }
+
+    pub fn inputs(&self) -> Inputs {
+        let source = match (&self.input, &self.input_dir) {
+            (Some(input), _) => Source::from_arg(input),
+            (None, Some(directory)) => Source::Directory(directory.clone()),
+            (None, None) => Source::Default,
+        };
+        Inputs {
+            source,
+            stdin: OnceCell::new(),
+        }
+    }
+}
+
+pub struct Inputs {
+    source: Source,
+    /// Both parts share whatever was piped in, so it is only read once, when first loaded.
+    stdin: OnceCell<Result<String, String>>,
+}
+
+impl Inputs {
+    pub fn load(&self, solver: &Solver) -> Result<String, String> {
+        if self.source == Source::Stdin {
+            return self
+                .stdin
+                .get_or_init(|| input::read_stdin().map_err(|error| error.to_string()))
+                .clone();
+        }
+        self.source
+            .load(solver.day, solver.part)
+            .map_err(|error| error.to_string())
+    }
+
+    /// Opens the input for `solver` to be streamed rather than loaded.
+    pub fn open(&self, solver: &Solver) -> Result<Box<dyn BufRead>, String> {
+        self.source
+            .open(solver.day, solver.part)
+            .map_err(|error| error.to_string())
+    }
+
+    /// What to call the input for `solver` in diagnostics.
+    pub fn name(&self, solver: &Solver) -> String {
+        self.source.name(solver.day, solver.part)
+    }
+}
+
+#[derive(Args)]
+struct RunArgs {
+    #[command(flatten)]
+    selection: Selection,
+
+    /// Record each answer in the answers file
+    #[arg(long, conflicts_with_all = ["check", "input", "params"])]
+    record: bool,
+
+    /// Check each answer against the answers file, failing on any mismatch
+    #[arg(long, conflicts_with_all = ["input", "params"])]
+    check: bool,
+
+    /// The answers file used by `--record` and `--check` [default: answers.toml in the input directory]
+    #[arg(long, value_name = "PATH")]
+    answers: Option<PathBuf>,
+
+    /// Draw each solver's state on the way to its answer, for the days which can
+    #[arg(long, value_name = "FORMAT")]
+    visualise: Option<Format>,
+
+    /// Where `--visualise` writes images
+    #[arg(long, value_name = "DIR", default_value = "visualisations")]
+    visualise_dir: PathBuf,
+
+    /// Write the puzzle's graph as a Graphviz `.dot` file, for the days which have one
+    #[arg(long)]
+    export_graph: bool,
+
+    /// Where `--export-graph` writes `.dot` files
+    #[arg(long, value_name = "DIR", default_value = "graphs")]
+    graph_dir: PathBuf,
+
+    /// Read the input a line at a time rather than loading it, for the days which can, so that
+    /// inputs too large for memory can be solved. Only one part can stream stdin
+    #[arg(long, conflicts_with_all = ["visualise", "export_graph"])]
+    stream: bool,
+
+    #[command(flatten)]
+    limits: Limits,
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    init_logging(cli.verbose, cli.log_json);
+    match cli.command {
+        Command::Run(args) => run(args),
+        Command::Bench(args) => bench::bench(args),
+        Command::Gen(args) => gen::generate(args),
+    }
+}
+
+/// Sends the solvers' logs to stderr, keeping stdout for the answers. Nothing below a warning
+/// is logged without `-v` or `RUST_LOG`.
+fn init_logging(verbose: u8, json: bool) {
+    let level = match verbose {
+        0 => LevelFilter::WARN,
+        1 => LevelFilter::DEBUG,
+        _ => LevelFilter::TRACE,
+    };
+    let filter = EnvFilter::builder()
+        .with_default_directive(level.into())
+        .from_env_lossy();
+    let logs = tracing_subscriber::fmt()
+        .with_env_filter(filter)
+        .with_ansi(io::stderr().is_terminal())
+        .with_writer(io::stderr);
+    if json {
+        logs.json().init();
+    } else {
+        logs.init();
+    }
+}
+
+fn run(args: RunArgs) -> ExitCode {
+    let inputs = args.selection.inputs();
+    let solvers = args.selection.solvers();
+    if args.stream && inputs.source == Source::Stdin && solvers.len() > 1 {
+        eprintln!("error: only one part can stream stdin, so choose one with --part");
+        return ExitCode::FAILURE;
+    }
+
+    let answers_path = match (&args.answers, &args.selection.input_dir) {
+        (Some(path), _) => path.clone(),
+        (None, Some(directory)) => directory.join("answers.toml"),
+        (None, None) => answers::default_path(),
+    };
+    let mut answers = if args.record || args.check {
+        match Answers::load(&answers_path) {
+            Ok(answers) => answers,
+            Err(error) => {
+                eprintln!("error: {}", error);
+                return ExitCode::FAILURE;
+            }
+        }
+    } else {
+        Answers::default()
+    };
+
+    let mut table = if args.check {
+        Table::new(&["Day", "Part", "Answer", "Check"])
+    } else {
+        Table::new(&["Day", "Part", "Answer"])
+    };
+    let mut failed = false;
+    let mut diagnostics = vec![];
+    let mut visualisations = vec![];
+    for solver in &solvers {
+        let _budget = args.limits.budget().enter();
+        let answer = args.selection.params(solver).and_then(|params| {
+            if args.stream {
+                return stream(solver, &inputs, &params);
+            }
+            let input = inputs.load(solver)?;
+            if args.visualise.is_some() {
+                match visualise::draw(solver, &input, &params) {
+                    Ok(visualisation) => visualisations.push(visualisation),
+                    Err(error) => diagnostics.push(format!(
+                        "cannot visualise day {} part {}: {}",
+                        solver.day, solver.part, error
+                    )),
+                }
+            }
+            if args.export_graph {
+                if let Err(error) = export_graph(solver, &input, &params, &args.graph_dir) {
+                    diagnostics.push(format!(
+                        "cannot export the graph for day {} part {}: {}",
+                        solver.day, solver.part, error
+                    ));
+                }
+            }
+            solve(solver, &input, &params).map_err(|error| {
+                if let Failure::Solver(error @ aoclib::Error::Parse { .. }) = &error {
+                    diagnostics.push(error.render(&inputs.name(solver), &input));
+                }
+                error.to_string()
+            })
+        });
+        // The last day's second part has no puzzle, so it always errors and is not a failure.
+        failed |= answer.is_err() && solvers::is_implemented(solver);
+
+        let mut row = vec![
+            solver.day.to_string(),
+            solver.part.to_string(),
+            answer.as_ref().map_or_else(
+                |error| format!("error: {}", error),
+                |answer| answer.to_string(),
+            ),
+        ];
+        if let Ok(answer) = &answer {
+            if args.record {
+                answers.record(solver.day, solver.part, answer);
+            }
+            if args.check {
+                let check = answers.check(solver.day, solver.part, answer);
+                failed |= matches!(check, Check::Mismatch { .. });
+                row.push(check.to_string());
+            }
+        }
+        table.push(row);
+    }
+    print!("{}", table);
+    for diagnostic in &diagnostics {
+        eprintln!("{}", diagnostic);
+    }
+    if let Some(format) = args.visualise {
+        if let Err(error) = visualise::output(format, &args.visualise_dir, &visualisations) {
+            eprintln!("error: {}", error);
+            failed = true;
+        }
+    }
+
+    if args.record {
+        if let Err(error) = answers.save(&answers_path) {
+            eprintln!("error: {}", error);
+            return ExitCode::FAILURE;
+        }
+    }
+
+    if failed {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+/// Why a solver gave no answer.
+enum Failure {
+    Panicked,
+    Solver(aoclib::Error),
+}
+
+impl Display for Failure {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Failure::Panicked => write!(f, "solver panicked"),
+            Failure::Solver(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+/// Writes `solver`'s graph to `directory/day-XX-part-N.dot`, if it has one.
+fn export_graph(
+    solver: &Solver,
+    input: &str,
+    params: &Params,
+    directory: &Path,
+) -> Result<(), String> {
+    let graph = catch_unwind(AssertUnwindSafe(|| solver.export_graph(input, params)))
+        .map_err(|_| Failure::Panicked.to_string())?
+        .map_err(|error| error.to_string())?;
+    let Some(graph) = graph else {
+        return Ok(());
+    };
+    fs::create_dir_all(directory)
+        .map_err(|error| format!("cannot create {}: {}", directory.display(), error))?;
+    let path = directory.join(format!("day-{:02}-part-{}.dot", solver.day, solver.part));
+    fs::write(&path, graph.to_string())
+        .map_err(|error| format!("cannot write {}: {}", path.display(), error))?;
+    eprintln!("wrote {}", path.display());
+    Ok(())
+}
+
+/// Solves `solver`'s input as it is read, without loading it, if the solver can.
+fn stream(solver: &Solver, inputs: &Inputs, params: &Params) -> Result<Answer, String> {
+    let mut reader = inputs.open(solver)?;
+    catch_unwind(AssertUnwindSafe(|| solver.solve_reader(reader.as_mut(), params)))
+        .map_err(|_| Failure::Panicked.to_string())?
+        .map_err(|error| error.to_string())?
+        .ok_or_else(|| "this part cannot stream its input".to_string())
+}
+
+fn solve(solver: &Solver, input: &str, params: &Params) -> Result<Answer, Failure> {
+    catch_unwind(AssertUnwindSafe(|| solver.solve(input, params)))
+        .map_err(|_| Failure::Panicked)?
+        .map_err(Failure::Solver)
+}
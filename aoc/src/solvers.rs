@@ -0,0 +1,86 @@
+use aoclib::Solver;
+
+/// Every day/part solver, in calendar order.
+pub fn all() -> Vec<Solver> {
+    vec![
+        Solver::of::<day_01::part1::Part1>(),
+        Solver::of::<day_01::part2::Part2>(),
+        Solver::of::<day_02::part1::Part1>(),
+        Solver::of::<day_02::part2::Part2>(),
+        Solver::of::<day_03::part1::Part1>(),
+        Solver::of::<day_03::part2::Part2>(),
+        Solver::of::<day_04::part1::Part1>(),
+        Solver::of::<day_04::part2::Part2>(),
+        Solver::of::<day_05::part1::Part1>(),
+        Solver::of::<day_05::part2::Part2>(),
+        Solver::of::<day_06::part1::Part1>(),
+        Solver::of::<day_06::part2::Part2>(),
+        Solver::of::<day_07::part1::Part1>(),
+        Solver::of::<day_07::part2::Part2>(),
+        Solver::of::<day_08::part1::Part1>(),
+        Solver::of::<day_08::part2::Part2>(),
+        Solver::of::<day_09::part1::Part1>(),
+        Solver::of::<day_09::part2::Part2>(),
+        Solver::of::<day_10::part1::Part1>(),
+        Solver::of::<day_10::part2::Part2>(),
+        Solver::of::<day_11::part1::Part1>(),
+        Solver::of::<day_11::part2::Part2>(),
+        Solver::of::<day_12::part1::Part1>(),
+        Solver::of::<day_12::part2::Part2>(),
+        Solver::of::<day_13::part1::Part1>(),
+        Solver::of::<day_13::part2::Part2>(),
+        Solver::of::<day_14::part1::Part1>(),
+        Solver::of::<day_14::part2::Part2>(),
+        Solver::of::<day_15::part1::Part1>(),
+        Solver::of::<day_15::part2::Part2>(),
+        Solver::of::<day_16::part1::Part1>(),
+        Solver::of::<day_16::part2::Part2>(),
+        Solver::of::<day_17::part1::Part1>(),
+        Solver::of::<day_17::part2::Part2>(),
+        Solver::of::<day_18::part1::Part1>(),
+        Solver::of::<day_18::part2::Part2>(),
+        Solver::of::<day_19::part1::Part1>(),
+        Solver::of::<day_19::part2::Part2>(),
+        Solver::of::<day_20::part1::Part1>(),
+        Solver::of::<day_20::part2::Part2>(),
+        Solver::of::<day_21::part1::Part1>(),
+        Solver::of::<day_21::part2::Part2>(),
+        Solver::of::<day_22::part1::Part1>(),
+        Solver::of::<day_22::part2::Part2>(),
+        Solver::of::<day_23::part1::Part1>(),
+        Solver::of::<day_23::part2::Part2>(),
+        Solver::of::<day_24::part1::Part1>(),
+        Solver::of::<day_24::part2::Part2>(),
+        Solver::of::<day_25::part1::Part1>(),
+        Solver::of::<day_25::part2::Part2>(),
+    ]
+}
+
+/// Whether `solver` has a puzzle to solve. The last day has only one, so its second part just
+/// reports that there is nothing to do.
+pub fn is_implemented(solver: &Solver) -> bool {
+    (solver.day, solver.part) != (25, 2)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoclib::input::normalise;
+    use aoclib::Params;
+
+    #[test]
+    fn test_streaming_empty_input() {
+        for solver in all() {
+            let params = solver.params(&Params::new(), true).unwrap();
+            if let Some(streamed) = solver.solve_reader(&mut "".as_bytes(), &params).transpose() {
+                assert_eq!(
+                    streamed,
+                    solver.solve(&normalise(""), &params),
+                    "day {} part {}",
+                    solver.day,
+                    solver.part
+                );
+            }
+        }
+    }
+}
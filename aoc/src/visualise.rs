@@ -0,0 +1,103 @@
+use std::fs;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::path::{Path, PathBuf};
+
+use aoclib::params::Params;
+use aoclib::viz::{self, Frame};
+use aoclib::Solver;
+use clap::ValueEnum;
+
+/// How many pixels wide each cell is drawn in images.
+const SCALE: usize = 4;
+
+/// How long each frame of a GIF is shown, in hundredths of a second.
+const DELAY: u16 = 10;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Format {
+    /// Plain text, printed after the answers
+    Ascii,
+    /// Text coloured for the terminal, printed after the answers
+    Ansi,
+    /// A PNG image per frame
+    Png,
+    /// An animated GIF of every frame
+    Gif,
+}
+
+/// The frames drawn by one solver.
+pub struct Visualisation {
+    day: u8,
+    part: u8,
+    frames: Vec<Frame>,
+}
+
+/// Draws `solver`'s state for `input`, or says why it could not.
+pub fn draw(solver: &Solver, input: &str, params: &Params) -> Result<Visualisation, String> {
+    let frames = catch_unwind(AssertUnwindSafe(|| solver.visualise(input, params)))
+        .map_err(|_| "solver panicked".to_string())?
+        .map_err(|error| error.to_string())?;
+    Ok(Visualisation {
+        day: solver.day,
+        part: solver.part,
+        frames,
+    })
+}
+
+/// Prints or writes out every visualisation in `format`, with images going in `directory`.
+pub fn output(
+    format: Format,
+    directory: &Path,
+    visualisations: &[Visualisation],
+) -> Result<(), String> {
+    for visualisation in visualisations {
+        let Visualisation { day, part, frames } = visualisation;
+        if frames.is_empty() {
+            eprintln!("day {} part {} has nothing to visualise", day, part);
+            continue;
+        }
+        match format {
+            Format::Ascii | Format::Ansi => {
+                for (index, frame) in frames.iter().enumerate() {
+                    println!(
+                        "\nDay {} part {}, frame {} of {}:",
+                        day,
+                        part,
+                        index + 1,
+                        frames.len()
+                    );
+                    if format == Format::Ansi {
+                        print!("{}", frame.to_ansi());
+                    } else {
+                        print!("{}", frame.to_ascii());
+                    }
+                }
+            }
+            Format::Png => {
+                for (index, frame) in frames.iter().enumerate() {
+                    let path = image_path(
+                        directory,
+                        &format!("day-{:02}-part-{}-{:03}.png", day, part, index + 1),
+                    )?;
+                    viz::write_png(frame, &path, SCALE)
+                        .map_err(|error| format!("cannot write {}: {}", path.display(), error))?;
+                    eprintln!("wrote {}", path.display());
+                }
+            }
+            Format::Gif => {
+                let path = image_path(directory, &format!("day-{:02}-part-{}.gif", day, part))?;
+                viz::write_gif(frames, &path, SCALE, DELAY)
+                    .map_err(|error| format!("cannot write {}: {}", path.display(), error))?;
+                eprintln!("wrote {}", path.display());
+            }
+        }
+    }
+    Ok(())
+}
+
+/// The path of the image called `name` in `directory`, which is created if need be.
+fn image_path(directory: &Path, name: &str) -> Result<PathBuf, String> {
+    fs::create_dir_all(directory)
+        .map_err(|error| format!("cannot create {}: {}", directory.display(), error))?;
+    Ok(directory.join(name))
+}
@@ -0,0 +1,194 @@
+use std::collections::BTreeMap;
+use std::fmt::Display;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{input, Answer};
+
+/// Known-good answers for the real puzzle inputs, kept as TOML alongside the inputs:
+///
+/// ```toml
+/// [day-07]
+/// part1 = "246912307"
+/// part2 = "246894760"
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Answers(BTreeMap<String, Day>);
+
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct Day {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    part1: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    part2: Option<String>,
+}
+
+impl Day {
+    fn part(&mut self, part: u8) -> &mut Option<String> {
+        match part {
+            1 => &mut self.part1,
+            2 => &mut self.part2,
+            _ => panic!("there is no part {}", part),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum AnswersError {
+    Io {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    Parse {
+        path: PathBuf,
+        source: toml::de::Error,
+    },
+}
+
+impl Display for AnswersError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AnswersError::Io { path, source } => {
+                write!(f, "cannot access {}: {}", path.display(), source)
+            }
+            AnswersError::Parse { path, source } => {
+                write!(f, "cannot parse {}: {}", path.display(), source)
+            }
+        }
+    }
+}
+
+impl std::error::Error for AnswersError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            AnswersError::Io { source, .. } => Some(source),
+            AnswersError::Parse { source, .. } => Some(source),
+        }
+    }
+}
+
+/// The outcome of checking an answer against the recorded one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Check {
+    Match,
+    Mismatch { recorded: String, actual: String },
+    Unrecorded,
+}
+
+impl Display for Check {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Check::Match => write!(f, "ok"),
+            Check::Mismatch { recorded, actual } => {
+                write!(f, "MISMATCH: was {}, now {}", recorded, actual)
+            }
+            Check::Unrecorded => write!(f, "unrecorded"),
+        }
+    }
+}
+
+impl Answers {
+    /// Loads the answers at `path`; a missing file has no answers recorded.
+    pub fn load(path: &Path) -> Result<Self, AnswersError> {
+        let contents = match std::fs::read_to_string(path) {
+            Ok(contents) => contents,
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => {
+                return Ok(Self::default())
+            }
+            Err(source) => {
+                return Err(AnswersError::Io {
+                    path: path.to_path_buf(),
+                    source,
+                })
+            }
+        };
+        toml::from_str(&contents).map_err(|source| AnswersError::Parse {
+            path: path.to_path_buf(),
+            source,
+        })
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), AnswersError> {
+        let contents = toml::to_string(self).expect("answers should serialise");
+        std::fs::write(path, contents).map_err(|source| AnswersError::Io {
+            path: path.to_path_buf(),
+            source,
+        })
+    }
+
+    pub fn get(&self, day: u8, part: u8) -> Option<&str> {
+        let day = self.0.get(&key(day))?;
+        match part {
+            1 => day.part1.as_deref(),
+            2 => day.part2.as_deref(),
+            _ => None,
+        }
+    }
+
+    pub fn record(&mut self, day: u8, part: u8, answer: &Answer) {
+        *self.0.entry(key(day)).or_default().part(part) = Some(answer.to_string());
+    }
+
+    pub fn check(&self, day: u8, part: u8, answer: &Answer) -> Check {
+        let actual = answer.to_string();
+        match self.get(day, part) {
+            Some(recorded) if recorded == actual => Check::Match,
+            Some(recorded) => Check::Mismatch {
+                recorded: recorded.to_string(),
+                actual,
+            },
+            None => Check::Unrecorded,
+        }
+    }
+}
+
+/// `answers.toml` in the default input directory.
+pub fn default_path() -> PathBuf {
+    input::default_directory().join("answers.toml")
+}
+
+fn key(day: u8) -> String {
+    format!("day-{:02}", day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip() {
+        let mut answers = Answers::default();
+        answers.record(7, 1, &246912307_u32.into());
+        answers.record(24, 2, &(-1033770143421619_i64).into());
+
+        let contents = toml::to_string(&answers).unwrap();
+        assert_eq!(
+            contents,
+            "[day-07]
+part1 = \"246912307\"
+
+[day-24]
+part2 = \"-1033770143421619\"
+"
+        );
+        assert_eq!(toml::from_str::<Answers>(&contents).unwrap(), answers);
+    }
+
+    #[test]
+    fn test_check() {
+        let mut answers = Answers::default();
+        answers.record(17, 2, &930_u32.into());
+
+        assert_eq!(answers.check(17, 2, &930_u32.into()), Check::Match);
+        assert_eq!(
+            answers.check(17, 2, &931_u32.into()),
+            Check::Mismatch {
+                recorded: "930".to_string(),
+                actual: "931".to_string()
+            }
+        );
+        assert_eq!(answers.check(17, 1, &771_u32.into()), Check::Unrecorded);
+    }
+}
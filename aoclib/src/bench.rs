@@ -0,0 +1,100 @@
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use serde::{Serialize, Serializer};
+
+use crate::error::Error;
+use crate::Solution;
+
+/// How long each iteration spent parsing and solving.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Timings {
+    pub parse: Vec<Duration>,
+    pub solve: Vec<Duration>,
+}
+
+/// The spread of a set of timings, serialised in nanoseconds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct Summary {
+    #[serde(serialize_with = "nanoseconds")]
+    pub min: Duration,
+    #[serde(serialize_with = "nanoseconds")]
+    pub median: Duration,
+    #[serde(serialize_with = "nanoseconds")]
+    pub max: Duration,
+}
+
+impl Summary {
+    /// Summarises `samples`, which must not be empty.
+    pub fn of(samples: &[Duration]) -> Self {
+        let mut samples = samples.to_vec();
+        samples.sort();
+        let length = samples.len();
+        let median = (samples[(length - 1) / 2] + samples[length / 2]) / 2;
+        Self {
+            min: samples[0],
+            median,
+            max: samples[length - 1],
+        }
+    }
+}
+
+impl Timings {
+    pub fn parse_summary(&self) -> Summary {
+        Summary::of(&self.parse)
+    }
+
+    pub fn solve_summary(&self) -> Summary {
+        Summary::of(&self.solve)
+    }
+}
+
+/// Parses and solves `input` `iterations` times, timing each step separately, and stopping at
+/// the first error.
+pub fn time<S: Solution>(
+    input: &str,
+    params: &S::Params,
+    iterations: usize,
+) -> Result<Timings, Error> {
+    let mut timings = Timings::default();
+    for _ in 0..iterations {
+        let start = Instant::now();
+        let parsed = S::parse(black_box(input))?;
+        timings.parse.push(start.elapsed());
+
+        let start = Instant::now();
+        black_box(S::solve(parsed, params)?);
+        timings.solve.push(start.elapsed());
+    }
+    Ok(timings)
+}
+
+fn nanoseconds<S: Serializer>(duration: &Duration, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_u64(u64::try_from(duration.as_nanos()).unwrap_or(u64::MAX))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case(&[3, 1, 2], (1, 2, 3))]
+    #[case(&[4, 1, 2, 8], (1, 3, 8))]
+    #[case(&[5], (5, 5, 5))]
+    fn test_summary(#[case] samples: &[u64], #[case] expected: (u64, u64, u64)) {
+        let samples = samples
+            .iter()
+            .map(|&sample| Duration::from_millis(sample))
+            .collect::<Vec<_>>();
+        let (min, median, max) = expected;
+        assert_eq!(
+            Summary::of(&samples),
+            Summary {
+                min: Duration::from_millis(min),
+                median: Duration::from_millis(median),
+                max: Duration::from_millis(max),
+            }
+        );
+    }
+}
@@ -0,0 +1,175 @@
+//! Limits on how long a solver may run, so that adversarial input which would otherwise loop
+//! forever, or take exponential time, fails with [`Error::BudgetExceeded`] instead.
+//!
+//! Cancellation is cooperative: long-running loops call [`tick`] once per step and return its
+//! error, and the caller sets the limits by running the solver inside [`Budget::run`]. Without
+//! a budget, [`tick`] never fails.
+
+use std::cell::RefCell;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use crate::error::Error;
+
+thread_local! {
+    static CURRENT: RefCell<Option<Budget>> = const { RefCell::new(None) };
+}
+
+/// A wall-clock deadline and a maximum number of steps, either of which may be unlimited,
+/// along with the number of steps taken so far. Clones share the count, so work split across
+/// threads is counted against the same budget.
+#[derive(Debug, Clone, Default)]
+pub struct Budget {
+    deadline: Option<Instant>,
+    max_steps: Option<u64>,
+    steps: Arc<AtomicU64>,
+}
+
+impl Budget {
+    pub fn unlimited() -> Self {
+        Self::default()
+    }
+
+    /// Limits the budget to `timeout` from now.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.deadline = Some(Instant::now() + timeout);
+        self
+    }
+
+    pub fn with_max_steps(mut self, max_steps: u64) -> Self {
+        self.max_steps = Some(max_steps);
+        self
+    }
+
+    /// The number of steps taken against this budget so far.
+    pub fn steps(&self) -> u64 {
+        self.steps.load(Ordering::Relaxed)
+    }
+
+    /// Runs `f` with this as the budget [`tick`] checks on the current thread, restoring the
+    /// previous budget afterwards. Work `f` hands to other threads, such as rayon's thread pool,
+    /// needs the budget passed along from [`Budget::current`] and run again there.
+    pub fn run<T>(&self, f: impl FnOnce() -> T) -> T {
+        let _entered = self.enter();
+        f()
+    }
+
+    /// Like [`Budget::run`], but for the rest of the scope, until the guard is dropped.
+    pub fn enter(&self) -> Entered {
+        Entered(CURRENT.with(|current| current.replace(Some(self.clone()))))
+    }
+
+    /// The budget in force on the current thread, or an unlimited one.
+    pub fn current() -> Self {
+        CURRENT
+            .with(|current| current.borrow().clone())
+            .unwrap_or_default()
+    }
+
+    /// Counts one step, failing if that takes the budget past its maximum or it is past its
+    /// deadline.
+    pub fn tick(&self) -> Result<(), Error> {
+        let steps = self.steps.fetch_add(1, Ordering::Relaxed) + 1;
+        let out_of_steps = self.max_steps.is_some_and(|max_steps| steps > max_steps);
+        let out_of_time = self
+            .deadline
+            .is_some_and(|deadline| Instant::now() >= deadline);
+        if out_of_steps || out_of_time {
+            Err(Error::BudgetExceeded { steps: steps - 1 })
+        } else {
+            Ok(())
+        }
+    }
+}
+
+/// The guard returned by [`Budget::enter`], which puts back the budget it replaced when dropped,
+/// even if the solver panics.
+#[must_use = "the budget is only in force until the guard is dropped"]
+pub struct Entered(Option<Budget>);
+
+impl Drop for Entered {
+    fn drop(&mut self) {
+        CURRENT.with(|current| *current.borrow_mut() = self.0.take());
+    }
+}
+
+/// Counts one step against the current thread's budget, if it has one. Loops which may run for
+/// a long time call this once per step and return its error.
+pub fn tick() -> Result<(), Error> {
+    CURRENT.with(|current| match &*current.borrow() {
+        Some(budget) => budget.tick(),
+        None => Ok(()),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn count_to(n: u64) -> Result<u64, Error> {
+        for _ in 0..n {
+            tick()?;
+        }
+        Ok(n)
+    }
+
+    #[test]
+    fn test_unlimited() {
+        assert_eq!(count_to(1000), Ok(1000));
+        assert_eq!(Budget::unlimited().run(|| count_to(1000)), Ok(1000));
+    }
+
+    #[test]
+    fn test_max_steps() {
+        let budget = Budget::unlimited().with_max_steps(10);
+        assert_eq!(budget.run(|| count_to(10)), Ok(10));
+        assert_eq!(
+            budget.run(|| count_to(10)),
+            Err(Error::BudgetExceeded { steps: 10 })
+        );
+        assert_eq!(
+            Error::BudgetExceeded { steps: 10 }.to_string(),
+            "budget exceeded after 10 steps"
+        );
+        assert_eq!(count_to(20), Ok(20));
+    }
+
+    #[test]
+    fn test_timeout() {
+        let budget = Budget::unlimited().with_timeout(Duration::ZERO);
+        assert_eq!(
+            budget.run(|| count_to(5)),
+            Err(Error::BudgetExceeded { steps: 0 })
+        );
+    }
+
+    #[test]
+    fn test_nested() {
+        let outer = Budget::unlimited().with_max_steps(100);
+        outer.run(|| {
+            let inner = Budget::unlimited().with_max_steps(1);
+            assert!(inner.run(|| count_to(2)).is_err());
+            assert_eq!(count_to(3), Ok(3));
+            assert_eq!(Budget::current().steps(), 3);
+        });
+        assert_eq!(outer.steps(), 3);
+    }
+
+    #[test]
+    fn test_other_threads() {
+        let budget = Budget::unlimited().with_max_steps(10);
+        budget
+            .run(|| {
+                let budget = Budget::current();
+                std::thread::spawn(move || budget.run(|| count_to(8)))
+                    .join()
+                    .unwrap()
+            })
+            .unwrap();
+        assert_eq!(
+            budget.run(|| count_to(3)),
+            Err(Error::BudgetExceeded { steps: 10 })
+        );
+    }
+}
@@ -0,0 +1,192 @@
+//! Cycle detection for state machines which are stepped far more times than can be simulated,
+//! but which eventually repeat.
+//!
+//! Each step counts against the current [`budget`](crate::budget), so a state machine which
+//! never repeats fails with [`Error::BudgetExceeded`] rather than running forever.
+
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use crate::budget;
+use crate::error::Error;
+
+/// The shape of the sequence `initial, step(initial), step(step(initial)), ...`: the first
+/// `tail` states never recur, and every state after them repeats every `period` steps.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cycle {
+    pub tail: usize,
+    pub period: usize,
+}
+
+impl Cycle {
+    /// The earliest step whose state is the same as the state after `step` steps.
+    pub fn equivalent_step(&self, step: usize) -> usize {
+        if step < self.tail {
+            step
+        } else {
+            self.tail + (step - self.tail) % self.period
+        }
+    }
+
+    /// The state after `step` steps, found by simulating no further than the end of the first
+    /// period.
+    pub fn state_at<S>(&self, initial: S, mut step_fn: impl FnMut(&S) -> S, step: usize) -> S {
+        (0..self.equivalent_step(step)).fold(initial, |state, _| step_fn(&state))
+    }
+}
+
+/// Finds the cycle with Floyd's tortoise and hare, which holds only two states at a time.
+pub fn floyd<S: PartialEq>(initial: S, mut step: impl FnMut(&S) -> S) -> Result<Cycle, Error> {
+    let mut step = |state: &S| budget::tick().map(|_| step(state));
+    let mut tortoise = step(&initial)?;
+    let mut hare = step(&tortoise)?;
+    while tortoise != hare {
+        tortoise = step(&tortoise)?;
+        hare = step(&hare)?;
+        hare = step(&hare)?;
+    }
+
+    let mut tortoise = initial;
+    let mut tail = 0;
+    while tortoise != hare {
+        tortoise = step(&tortoise)?;
+        hare = step(&hare)?;
+        tail += 1;
+    }
+
+    let mut hare = step(&tortoise)?;
+    let mut period = 1;
+    while tortoise != hare {
+        hare = step(&hare)?;
+        period += 1;
+    }
+    Ok(Cycle { tail, period })
+}
+
+/// Finds the cycle with Brent's algorithm, which holds only a few states at a time and usually
+/// calls `step` fewer times than [`floyd`].
+pub fn brent<S: Clone + PartialEq>(
+    initial: S,
+    mut step: impl FnMut(&S) -> S,
+) -> Result<Cycle, Error> {
+    let mut step = |state: &S| budget::tick().map(|_| step(state));
+    let mut power = 1;
+    let mut period = 1;
+    let mut tortoise = initial.clone();
+    let mut hare = step(&initial)?;
+    while tortoise != hare {
+        if power == period {
+            tortoise = hare.clone();
+            power *= 2;
+            period = 0;
+        }
+        hare = step(&hare)?;
+        period += 1;
+    }
+
+    let mut tortoise = initial.clone();
+    let mut hare = (0..period).try_fold(initial, |state, _| step(&state))?;
+    let mut tail = 0;
+    while tortoise != hare {
+        tortoise = step(&tortoise)?;
+        hare = step(&hare)?;
+        tail += 1;
+    }
+    Ok(Cycle { tail, period })
+}
+
+/// Every distinct state of a sequence, as recorded by [`history`].
+#[derive(Debug, Clone)]
+pub struct History<S> {
+    pub cycle: Cycle,
+    states: Vec<S>,
+}
+
+impl<S> History<S> {
+    /// The state after `step` steps.
+    pub fn get(&self, step: usize) -> &S {
+        &self.states[self.cycle.equivalent_step(step)]
+    }
+
+    /// Every distinct state, in the order they were first reached.
+    pub fn states(&self) -> &[S] {
+        &self.states
+    }
+}
+
+/// Finds the cycle by hashing every state until one repeats. This calls `step` the fewest times
+/// of all, and keeps every state so any of them can be looked up afterwards without
+/// re-simulating.
+pub fn history<S: Clone + Eq + Hash>(
+    initial: S,
+    mut step: impl FnMut(&S) -> S,
+) -> Result<History<S>, Error> {
+    let mut step = |state: &S| budget::tick().map(|_| step(state));
+    let mut seen = HashMap::new();
+    let mut states = vec![];
+    let mut state = initial;
+    loop {
+        match seen.entry(state.clone()) {
+            Entry::Occupied(entry) => {
+                let tail = *entry.get();
+                let period = states.len() - tail;
+                return Ok(History {
+                    cycle: Cycle { tail, period },
+                    states,
+                });
+            }
+            Entry::Vacant(entry) => {
+                entry.insert(states.len());
+            }
+        }
+        let next = step(&state)?;
+        states.push(state);
+        state = next;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::budget::Budget;
+    use rstest::rstest;
+
+    /// Counts up to `last`, then jumps back to `restart`.
+    fn counter(restart: u32, last: u32) -> impl Fn(&u32) -> u32 {
+        move |&n| if n < last { n + 1 } else { restart }
+    }
+
+    #[rstest]
+    #[case(0, 0, Cycle { tail: 0, period: 1 })]
+    #[case(0, 2, Cycle { tail: 0, period: 3 })]
+    #[case(2, 5, Cycle { tail: 2, period: 4 })]
+    #[case(5, 5, Cycle { tail: 5, period: 1 })]
+    #[case(3, 100, Cycle { tail: 3, period: 98 })]
+    fn test_cycle(#[case] restart: u32, #[case] last: u32, #[case] expected: Cycle) {
+        assert_eq!(floyd(0, counter(restart, last)), Ok(expected));
+        assert_eq!(brent(0, counter(restart, last)), Ok(expected));
+        assert_eq!(history(0, counter(restart, last)).unwrap().cycle, expected);
+    }
+
+    #[rstest]
+    #[case(1, 1)]
+    #[case(5, 5)]
+    #[case(6, 2)]
+    #[case(1_000_000_000, 4)]
+    fn test_state_at(#[case] step: usize, #[case] expected: u32) {
+        let cycle = brent(0, counter(2, 5)).unwrap();
+        assert_eq!(cycle.state_at(0, counter(2, 5), step), expected);
+        assert_eq!(*history(0, counter(2, 5)).unwrap().get(step), expected);
+    }
+
+    #[test]
+    fn test_budget() {
+        let budget = Budget::unlimited().with_max_steps(50);
+        let never_repeats = |n: &u32| n + 1;
+        assert_eq!(
+            budget.run(|| brent(0, never_repeats)),
+            Err(Error::BudgetExceeded { steps: 50 })
+        );
+    }
+}
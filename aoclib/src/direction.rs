@@ -0,0 +1,362 @@
+//! Compass directions on a grid, with y increasing downwards as in [`crate::grid`], so north is
+//! `(0, -1)`.
+
+use std::ops::{Add, AddAssign};
+
+use glam::{I64Vec2, IVec2};
+
+/// One of the four orthogonal directions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Direction {
+    North,
+    East,
+    South,
+    West,
+}
+
+impl Direction {
+    /// Every direction, clockwise from north, in the same order as
+    /// [`NEIGHBOURS_4`](crate::grid::NEIGHBOURS_4).
+    pub const ALL: [Direction; 4] = [
+        Direction::North,
+        Direction::East,
+        Direction::South,
+        Direction::West,
+    ];
+
+    /// The direction a quarter turn clockwise.
+    pub fn turn_right(self) -> Self {
+        Self::ALL[(self as usize + 1) % 4]
+    }
+
+    /// The direction a quarter turn anticlockwise.
+    pub fn turn_left(self) -> Self {
+        Self::ALL[(self as usize + 3) % 4]
+    }
+
+    pub fn reverse(self) -> Self {
+        Self::ALL[(self as usize + 2) % 4]
+    }
+
+    /// The direction a beam heading this way leaves a `/` mirror, so east turns north.
+    pub fn reflect_slash(self) -> Self {
+        match self {
+            Direction::North | Direction::South => self.turn_right(),
+            Direction::East | Direction::West => self.turn_left(),
+        }
+    }
+
+    /// The direction a beam heading this way leaves a `\` mirror, so east turns south.
+    pub fn reflect_backslash(self) -> Self {
+        match self {
+            Direction::North | Direction::South => self.turn_left(),
+            Direction::East | Direction::West => self.turn_right(),
+        }
+    }
+
+    pub fn is_horizontal(self) -> bool {
+        matches!(self, Direction::East | Direction::West)
+    }
+
+    pub fn is_vertical(self) -> bool {
+        !self.is_horizontal()
+    }
+
+    /// The unit step in this direction.
+    pub const fn to_ivec2(self) -> IVec2 {
+        match self {
+            Direction::North => IVec2::NEG_Y,
+            Direction::East => IVec2::X,
+            Direction::South => IVec2::Y,
+            Direction::West => IVec2::NEG_X,
+        }
+    }
+
+    /// The unit step in this direction.
+    pub const fn to_i64vec2(self) -> I64Vec2 {
+        match self {
+            Direction::North => I64Vec2::NEG_Y,
+            Direction::East => I64Vec2::X,
+            Direction::South => I64Vec2::Y,
+            Direction::West => I64Vec2::NEG_X,
+        }
+    }
+
+    /// The direction of a unit step, if `step` is one.
+    pub fn from_ivec2(step: IVec2) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|direction| direction.to_ivec2() == step)
+    }
+
+    /// Reads `U`, `R`, `D` or `L`, for up, right, down or left, as in a dig plan, or `N`, `E`,
+    /// `S` or `W`.
+    pub fn from_letter(letter: char) -> Option<Self> {
+        match letter {
+            'U' | 'N' => Some(Direction::North),
+            'R' | 'E' => Some(Direction::East),
+            'D' | 'S' => Some(Direction::South),
+            'L' | 'W' => Some(Direction::West),
+            _ => None,
+        }
+    }
+
+    /// Reads `0`, `1`, `2` or `3`, for right, down, left or up, as in the last digit of a dig
+    /// plan's colour.
+    pub fn from_digit(digit: char) -> Option<Self> {
+        match digit {
+            '0' => Some(Direction::East),
+            '1' => Some(Direction::South),
+            '2' => Some(Direction::West),
+            '3' => Some(Direction::North),
+            _ => None,
+        }
+    }
+}
+
+impl From<Direction> for IVec2 {
+    fn from(direction: Direction) -> Self {
+        direction.to_ivec2()
+    }
+}
+
+impl From<Direction> for I64Vec2 {
+    fn from(direction: Direction) -> Self {
+        direction.to_i64vec2()
+    }
+}
+
+impl TryFrom<IVec2> for Direction {
+    type Error = IVec2;
+
+    /// Fails with `step` if it is not a unit step.
+    fn try_from(step: IVec2) -> Result<Self, IVec2> {
+        Direction::from_ivec2(step).ok_or(step)
+    }
+}
+
+/// Steps a position one tile in a direction.
+impl Add<Direction> for IVec2 {
+    type Output = IVec2;
+
+    fn add(self, direction: Direction) -> IVec2 {
+        self + direction.to_ivec2()
+    }
+}
+
+impl AddAssign<Direction> for IVec2 {
+    fn add_assign(&mut self, direction: Direction) {
+        *self += direction.to_ivec2();
+    }
+}
+
+/// Steps a position one tile in a direction.
+impl Add<Direction> for I64Vec2 {
+    type Output = I64Vec2;
+
+    fn add(self, direction: Direction) -> I64Vec2 {
+        self + direction.to_i64vec2()
+    }
+}
+
+impl AddAssign<Direction> for I64Vec2 {
+    fn add_assign(&mut self, direction: Direction) {
+        *self += direction.to_i64vec2();
+    }
+}
+
+/// One of the eight orthogonal and diagonal directions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Direction8 {
+    North,
+    NorthEast,
+    East,
+    SouthEast,
+    South,
+    SouthWest,
+    West,
+    NorthWest,
+}
+
+impl Direction8 {
+    /// Every direction, clockwise from north, in the same order as
+    /// [`NEIGHBOURS_8`](crate::grid::NEIGHBOURS_8).
+    pub const ALL: [Direction8; 8] = [
+        Direction8::North,
+        Direction8::NorthEast,
+        Direction8::East,
+        Direction8::SouthEast,
+        Direction8::South,
+        Direction8::SouthWest,
+        Direction8::West,
+        Direction8::NorthWest,
+    ];
+
+    /// The direction an eighth of a turn clockwise.
+    pub fn turn_right(self) -> Self {
+        Self::ALL[(self as usize + 1) % 8]
+    }
+
+    /// The direction an eighth of a turn anticlockwise.
+    pub fn turn_left(self) -> Self {
+        Self::ALL[(self as usize + 7) % 8]
+    }
+
+    pub fn reverse(self) -> Self {
+        Self::ALL[(self as usize + 4) % 8]
+    }
+
+    pub fn is_diagonal(self) -> bool {
+        self as usize % 2 == 1
+    }
+
+    /// The step in this direction, one tile along each axis it moves on.
+    pub const fn to_ivec2(self) -> IVec2 {
+        match self {
+            Direction8::North => IVec2::new(0, -1),
+            Direction8::NorthEast => IVec2::new(1, -1),
+            Direction8::East => IVec2::new(1, 0),
+            Direction8::SouthEast => IVec2::new(1, 1),
+            Direction8::South => IVec2::new(0, 1),
+            Direction8::SouthWest => IVec2::new(-1, 1),
+            Direction8::West => IVec2::new(-1, 0),
+            Direction8::NorthWest => IVec2::new(-1, -1),
+        }
+    }
+
+    /// The step in this direction, one tile along each axis it moves on.
+    pub const fn to_i64vec2(self) -> I64Vec2 {
+        let step = self.to_ivec2();
+        I64Vec2::new(step.x as i64, step.y as i64)
+    }
+
+    /// The direction of a step, if `step` is one tile along one or both axes.
+    pub fn from_ivec2(step: IVec2) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|direction| direction.to_ivec2() == step)
+    }
+}
+
+impl From<Direction> for Direction8 {
+    fn from(direction: Direction) -> Self {
+        Direction8::ALL[direction as usize * 2]
+    }
+}
+
+impl From<Direction8> for IVec2 {
+    fn from(direction: Direction8) -> Self {
+        direction.to_ivec2()
+    }
+}
+
+impl From<Direction8> for I64Vec2 {
+    fn from(direction: Direction8) -> Self {
+        direction.to_i64vec2()
+    }
+}
+
+impl TryFrom<IVec2> for Direction8 {
+    type Error = IVec2;
+
+    /// Fails with `step` if it is not a step of one tile.
+    fn try_from(step: IVec2) -> Result<Self, IVec2> {
+        Direction8::from_ivec2(step).ok_or(step)
+    }
+}
+
+/// Steps a position one tile in a direction, diagonally if it is diagonal.
+impl Add<Direction8> for IVec2 {
+    type Output = IVec2;
+
+    fn add(self, direction: Direction8) -> IVec2 {
+        self + direction.to_ivec2()
+    }
+}
+
+impl AddAssign<Direction8> for IVec2 {
+    fn add_assign(&mut self, direction: Direction8) {
+        *self += direction.to_ivec2();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grid::{NEIGHBOURS_4, NEIGHBOURS_8};
+
+    #[test]
+    fn test_turns() {
+        for direction in Direction::ALL {
+            assert_eq!(direction.turn_right().turn_left(), direction);
+            assert_eq!(direction.turn_right().turn_right(), direction.reverse());
+            assert_eq!(direction.reverse().to_ivec2(), -direction.to_ivec2());
+        }
+        assert_eq!(Direction::North.turn_right(), Direction::East);
+        assert_eq!(Direction::North.turn_left(), Direction::West);
+        for direction in Direction8::ALL {
+            assert_eq!(direction.turn_right().turn_left(), direction);
+            assert_eq!(direction.reverse().to_ivec2(), -direction.to_ivec2());
+        }
+        assert_eq!(Direction8::NorthWest.turn_right(), Direction8::North);
+    }
+
+    #[test]
+    fn test_reflections() {
+        use Direction::*;
+        assert_eq!(
+            [North, East, South, West].map(Direction::reflect_slash),
+            [East, North, West, South]
+        );
+        assert_eq!(
+            [North, East, South, West].map(Direction::reflect_backslash),
+            [West, South, East, North]
+        );
+        for direction in Direction::ALL {
+            assert_eq!(direction.reflect_slash().reflect_slash(), direction);
+            assert_eq!(direction.reflect_backslash().reflect_backslash(), direction);
+        }
+    }
+
+    #[test]
+    fn test_vectors() {
+        assert_eq!(Direction::ALL.map(IVec2::from), NEIGHBOURS_4);
+        assert_eq!(Direction8::ALL.map(IVec2::from), NEIGHBOURS_8);
+        for direction in Direction::ALL {
+            assert_eq!(Direction::try_from(direction.to_ivec2()), Ok(direction));
+            assert_eq!(direction.to_i64vec2(), direction.to_ivec2().as_i64vec2());
+            assert_eq!(
+                IVec2::from(Direction8::from(direction)),
+                direction.to_ivec2()
+            );
+        }
+        assert_eq!(Direction::try_from(IVec2::ONE), Err(IVec2::ONE));
+        assert_eq!(Direction8::try_from(IVec2::ONE), Ok(Direction8::SouthEast));
+        assert_eq!(IVec2::new(3, 3) + Direction::North, IVec2::new(3, 2));
+        assert_eq!(I64Vec2::ZERO + Direction::West, I64Vec2::NEG_X);
+        assert_eq!(IVec2::ZERO + Direction8::NorthEast, IVec2::new(1, -1));
+    }
+
+    #[test]
+    fn test_parsing() {
+        assert_eq!(
+            "URDL"
+                .chars()
+                .map(Direction::from_letter)
+                .collect::<Vec<_>>(),
+            "NESW"
+                .chars()
+                .map(Direction::from_letter)
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(
+            "3012"
+                .chars()
+                .map(Direction::from_digit)
+                .collect::<Vec<_>>(),
+            Direction::ALL.map(Some)
+        );
+        assert_eq!(Direction::from_letter('X'), None);
+        assert_eq!(Direction::from_digit('4'), None);
+    }
+}
@@ -0,0 +1,206 @@
+//! Graphs written out in Graphviz's DOT language, e.g. for `dot -Tsvg day-20-part-1.dot`.
+
+use std::collections::HashMap;
+use std::fmt::Display;
+
+/// `key="value"` pairs styling a node, an edge or the whole graph.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Attributes(Vec<(String, String)>);
+
+impl Attributes {
+    /// Sets `key`, replacing any value it already had.
+    pub fn set(&mut self, key: &str, value: impl Display) -> &mut Self {
+        let value = value.to_string();
+        match self.0.iter_mut().find(|(existing, _)| existing == key) {
+            Some((_, existing)) => *existing = value,
+            None => self.0.push((key.to_string(), value)),
+        }
+        self
+    }
+
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.0
+            .iter()
+            .find(|(existing, _)| existing == key)
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+impl Display for Attributes {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.0.is_empty() {
+            return Ok(());
+        }
+        write!(f, " [")?;
+        for (index, (key, value)) in self.0.iter().enumerate() {
+            if index > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{}={}", key, quote(value))?;
+        }
+        write!(f, "]")
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Edge {
+    from: String,
+    to: String,
+    attributes: Attributes,
+}
+
+/// A directed or undirected graph. Nodes are kept in the order they were first mentioned, and
+/// edges in the order they were added, so the output is the same from run to run.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Graph {
+    name: String,
+    directed: bool,
+    attributes: Attributes,
+    nodes: Vec<(String, Attributes)>,
+    /// Where each node is in `nodes`.
+    indices: HashMap<String, usize>,
+    edges: Vec<Edge>,
+}
+
+impl Graph {
+    pub fn directed(name: &str) -> Self {
+        Self::new(name, true)
+    }
+
+    pub fn undirected(name: &str) -> Self {
+        Self::new(name, false)
+    }
+
+    fn new(name: &str, directed: bool) -> Self {
+        Self {
+            name: name.to_string(),
+            directed,
+            attributes: Attributes::default(),
+            nodes: vec![],
+            indices: HashMap::new(),
+            edges: vec![],
+        }
+    }
+
+    /// The attributes of the graph as a whole, such as `rankdir`.
+    pub fn attributes(&mut self) -> &mut Attributes {
+        &mut self.attributes
+    }
+
+    /// The attributes of the node `id`, adding it if it is new.
+    pub fn node(&mut self, id: &str) -> &mut Attributes {
+        let index = match self.indices.get(id) {
+            Some(index) => *index,
+            None => {
+                self.indices.insert(id.to_string(), self.nodes.len());
+                self.nodes.push((id.to_string(), Attributes::default()));
+                self.nodes.len() - 1
+            }
+        };
+        &mut self.nodes[index].1
+    }
+
+    /// Adds an edge from `from` to `to`, adding either node if it is new, and returns the edge's
+    /// attributes.
+    pub fn edge(&mut self, from: &str, to: &str) -> &mut Attributes {
+        self.node(from);
+        self.node(to);
+        self.edges.push(Edge {
+            from: from.to_string(),
+            to: to.to_string(),
+            attributes: Attributes::default(),
+        });
+        &mut self
+            .edges
+            .last_mut()
+            .expect("an edge was just added")
+            .attributes
+    }
+
+    pub fn node_count(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn edge_count(&self) -> usize {
+        self.edges.len()
+    }
+}
+
+impl Display for Graph {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let (keyword, arrow) = if self.directed {
+            ("digraph", "->")
+        } else {
+            ("graph", "--")
+        };
+        writeln!(f, "{} {} {{", keyword, quote(&self.name))?;
+        if !self.attributes.0.is_empty() {
+            writeln!(f, "    graph{};", self.attributes)?;
+        }
+        for (id, attributes) in &self.nodes {
+            writeln!(f, "    {}{};", quote(id), attributes)?;
+        }
+        for edge in &self.edges {
+            writeln!(
+                f,
+                "    {} {} {}{};",
+                quote(&edge.from),
+                arrow,
+                quote(&edge.to),
+                edge.attributes
+            )?;
+        }
+        writeln!(f, "}}")
+    }
+}
+
+/// `text` as a DOT string literal.
+fn quote(text: &str) -> String {
+    format!("\"{}\"", text.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_directed() {
+        let mut graph = Graph::directed("day 20");
+        graph.attributes().set("rankdir", "LR");
+        graph.node("broadcaster").set("shape", "doubleoctagon");
+        graph.edge("broadcaster", "a");
+        graph.edge("a", "inv").set("label", 2).set("label", 3);
+        graph.node("a").set("shape", "box");
+        assert_eq!(
+            graph.to_string(),
+            "digraph \"day 20\" {\n\
+             \x20   graph [rankdir=\"LR\"];\n\
+             \x20   \"broadcaster\" [shape=\"doubleoctagon\"];\n\
+             \x20   \"a\" [shape=\"box\"];\n\
+             \x20   \"inv\";\n\
+             \x20   \"broadcaster\" -> \"a\";\n\
+             \x20   \"a\" -> \"inv\" [label=\"3\"];\n\
+             }\n"
+        );
+        assert_eq!((graph.node_count(), graph.edge_count()), (3, 2));
+    }
+
+    #[test]
+    fn test_undirected() {
+        let mut graph = Graph::undirected("day25");
+        graph.edge("jqt", "rhn");
+        assert_eq!(
+            graph.to_string(),
+            "graph \"day25\" {\n    \"jqt\";\n    \"rhn\";\n    \"jqt\" -- \"rhn\";\n}\n"
+        );
+    }
+
+    #[test]
+    fn test_quote() {
+        assert_eq!(quote(r#"x<"a\b">"#), r#""x<\"a\\b\">""#);
+        let mut attributes = Attributes::default();
+        attributes.set("label", "m>2090");
+        assert_eq!(attributes.get("label"), Some("m>2090"));
+        assert_eq!(attributes.get("shape"), None);
+    }
+}
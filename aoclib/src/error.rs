@@ -0,0 +1,264 @@
+use std::fmt::Display;
+
+use crate::params::ParamError;
+
+/// The longest snippet of the input quoted by a parse error.
+const SNIPPET_LENGTH: usize = 20;
+
+/// Why a solver could not produce an answer for its input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    /// The input did not match the puzzle's format. `line` and `column` count from one, and
+    /// `snippet` is the input from that point to the end of the line.
+    Parse {
+        line: usize,
+        column: usize,
+        snippet: String,
+        expected: String,
+    },
+    /// The input had no start marker, such as the `S` on a map.
+    MissingStart(String),
+    /// The input was well-formed but has no answer, e.g. because a search never reached its goal.
+    NoSolution(String),
+    /// The answer, or some value on the way to it, does not fit in the type used for it.
+    Overflow(String),
+    /// The solver ran out of the [`Budget`](crate::budget::Budget) it was given, after this many
+    /// steps.
+    BudgetExceeded {
+        steps: u64,
+    },
+    Params(ParamError),
+    /// The input could not be read while it was being streamed, such as when it is not UTF-8.
+    Read(String),
+}
+
+impl Error {
+    /// A parse error at the start of `at`, which must be a slice of `input`, such as the input
+    /// left over when a parser failed or the token it could not make sense of.
+    pub fn parse(input: &str, at: &str, expected: impl Into<String>) -> Self {
+        let offset = offset_of(input, at);
+        let before = &input[..offset];
+        let line_start = before.rfind('\n').map_or(0, |newline| newline + 1);
+        Error::Parse {
+            line: before.matches('\n').count() + 1,
+            column: before[line_start..].chars().count() + 1,
+            snippet: snippet(&input[offset..]),
+            expected: expected.into(),
+        }
+    }
+
+    /// A parse error at the start of `at`, which must be a slice of `line`, the `number`th line
+    /// of the input counting from one. This is for solvers which only see the input a line at a
+    /// time.
+    pub fn parse_line(number: usize, line: &str, at: &str, expected: impl Into<String>) -> Self {
+        Error::parse(line, at, expected).on_line(number)
+    }
+
+    /// Moves a parse error found by parsing the `number`th line of the input on its own to that
+    /// line of the whole input.
+    pub fn on_line(self, number: usize) -> Self {
+        match self {
+            Error::Parse {
+                line,
+                column,
+                snippet,
+                expected,
+            } => Error::Parse {
+                line: line + number - 1,
+                column,
+                snippet,
+                expected,
+            },
+            error => error,
+        }
+    }
+
+    /// Moves a parse error found by parsing a piece of a line on its own, which starts at
+    /// `column` of that line counting from one, to that column of the whole line. The snippet
+    /// still ends at the end of the piece.
+    pub fn on_column(self, column: usize) -> Self {
+        match self {
+            Error::Parse {
+                line,
+                column: offset,
+                snippet,
+                expected,
+            } => Error::Parse {
+                line,
+                column: column + offset - 1,
+                snippet,
+                expected,
+            },
+            error => error,
+        }
+    }
+
+    /// Renders the error as a diagnostic about the input called `name`. Parse errors read
+    /// `name:line:column: expected ...`, followed by the offending line with a caret under the
+    /// column; other errors are just prefixed with `name`.
+    pub fn render(&self, name: &str, input: &str) -> String {
+        match self {
+            Error::Parse {
+                line,
+                column,
+                expected,
+                ..
+            } => {
+                let source = input
+                    .lines()
+                    .nth(line.saturating_sub(1))
+                    .unwrap_or_default();
+                format!(
+                    "{}:{}:{}: expected {}\n{}\n{}^",
+                    name,
+                    line,
+                    column,
+                    expected,
+                    source,
+                    " ".repeat(column.saturating_sub(1))
+                )
+            }
+            error => format!("{}: {}", name, error),
+        }
+    }
+}
+
+/// The byte offset of `slice` within `text`.
+fn offset_of(text: &str, slice: &str) -> usize {
+    (slice.as_ptr() as usize)
+        .checked_sub(text.as_ptr() as usize)
+        .filter(|offset| *offset + slice.len() <= text.len())
+        .unwrap_or_else(|| panic!("{:?} is not a slice of {:?}", slice, text))
+}
+
+/// The start of `rest`, up to the end of its line.
+pub(crate) fn snippet(rest: &str) -> String {
+    rest.lines()
+        .next()
+        .unwrap_or_default()
+        .chars()
+        .take(SNIPPET_LENGTH)
+        .collect()
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Parse {
+                line,
+                column,
+                snippet,
+                expected,
+            } => {
+                write!(f, "line {}, column {}: expected {}", line, column, expected)?;
+                if snippet.is_empty() {
+                    write!(f, ", found the end of the line")
+                } else {
+                    write!(f, ", found {:?}", snippet)
+                }
+            }
+            Error::MissingStart(marker) => write!(f, "the input has no start ({})", marker),
+            Error::NoSolution(reason) => write!(f, "no solution: {}", reason),
+            Error::Overflow(what) => write!(f, "{} overflowed", what),
+            Error::BudgetExceeded { steps } => write!(f, "budget exceeded after {} steps", steps),
+            Error::Params(error) => write!(f, "{}", error),
+            Error::Read(reason) => write!(f, "cannot read the input: {}", reason),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<ParamError> for Error {
+    fn from(error: ParamError) -> Self {
+        Error::Params(error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case(0, 1, 1, "abc")]
+    #[case(2, 1, 3, "c")]
+    #[case(4, 2, 1, "de")]
+    #[case(6, 2, 3, "")]
+    #[case(7, 3, 1, "")]
+    fn test_parse(
+        #[case] offset: usize,
+        #[case] line: usize,
+        #[case] column: usize,
+        #[case] snippet: &str,
+    ) {
+        let input = "abc\nde\n";
+        assert_eq!(
+            Error::parse(input, &input[offset..], "a digit"),
+            Error::Parse {
+                line,
+                column,
+                snippet: snippet.to_string(),
+                expected: "a digit".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_line() {
+        let line = "Card 2 13 32";
+        assert_eq!(
+            Error::parse_line(2, line, &line[6..], "\":\""),
+            Error::Parse {
+                line: 2,
+                column: 7,
+                snippet: " 13 32".to_string(),
+                expected: "\":\"".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn test_on_column() {
+        let step = "ab=6";
+        assert_eq!(
+            Error::parse_line(1, step, &step[2..], "an ASCII character").on_column(9),
+            Error::Parse {
+                line: 1,
+                column: 11,
+                snippet: "=6".to_string(),
+                expected: "an ASCII character".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn test_display() {
+        let input = "Card 1: 41 48 | 83 86\nCard 2 13 32 | 61 30\n";
+        let error = Error::parse(input, &input[28..], "\":\"");
+        assert_eq!(
+            error.to_string(),
+            "line 2, column 7: expected \":\", found \" 13 32 | 61 30\""
+        );
+        assert_eq!(
+            Error::parse(input, &input[input.len()..], "a card").to_string(),
+            "line 3, column 1: expected a card, found the end of the line"
+        );
+    }
+
+    #[test]
+    fn test_render() {
+        let input = "in{s<1351:px,qqz}\npx{a<2006:qkq,m-2090:A,rfg}\n";
+        let error = Error::parse(input, &input[32..], "\"<\" or \">\" in workflow rule");
+        assert_eq!(
+            error.render("input.txt", input),
+            "input.txt:2:15: expected \"<\" or \">\" in workflow rule\n\
+             px{a<2006:qkq,m-2090:A,rfg}\n\
+             \x20             ^"
+        );
+        assert_eq!(
+            Error::MissingStart("S".to_string()).render("input.txt", input),
+            "input.txt: the input has no start (S)"
+        );
+    }
+}
@@ -0,0 +1,286 @@
+//! Worked examples kept as files, e.g. `day-07/examples/example.txt`. Each file starts with a
+//! header of `key = value` lines, ended by a `---` line, and the rest is the puzzle input:
+//!
+//! ```text
+//! part1 = 16
+//! steps = 6
+//! ---
+//! ...........
+//! .....###.#.
+//! ```
+//!
+//! `part1` and `part2` are the expected answers, and are optional since many examples only apply
+//! to one part. Any other keys are parameters for the solvers. Header lines starting with `#` are
+//! comments, such as why an answer is left out.
+//!
+//! A day gets a `#[test]` for each of its example files by calling [`build`] from its build
+//! script and invoking [`examples!`](crate::examples) with its solutions.
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::error::Error;
+use crate::input::normalise;
+use crate::params::{parse_assignment, Params};
+use crate::solution::Solution;
+use crate::solver::Solver;
+
+/// The line which ends the header.
+pub const SEPARATOR: &str = "---";
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Example {
+    pub input: String,
+    pub params: Params,
+    answers: [Option<String>; 2],
+}
+
+impl Example {
+    pub fn parse(text: &str) -> Result<Self, Error> {
+        let text = normalise(text);
+        let mut params = Params::new();
+        let mut answers = [None, None];
+        let mut lines = text.split_inclusive('\n').enumerate();
+        for (index, line) in lines.by_ref() {
+            let line = line.trim_end_matches('\n');
+            if line == SEPARATOR {
+                let input = lines.map(|(_, line)| line).collect::<String>();
+                return Ok(Self {
+                    input: normalise(&input),
+                    params,
+                    answers,
+                });
+            }
+            if line.starts_with('#') {
+                continue;
+            }
+            let (key, value) = parse_assignment(line)
+                .map_err(|_| Error::parse_line(index + 1, line, line, "key = value"))?;
+            match key.as_str() {
+                "part1" => answers[0] = Some(value),
+                "part2" => answers[1] = Some(value),
+                _ => params.set(key, value),
+            }
+        }
+        Err(Error::parse(
+            &text,
+            &text[text.len()..],
+            format!("a {:?} line to end the header", SEPARATOR),
+        ))
+    }
+
+    /// The expected answer for `part`, if the example gives one.
+    pub fn answer(&self, part: u8) -> Option<&str> {
+        self.answers[usize::from(part) - 1].as_deref()
+    }
+
+    /// Solves the example with `S`, using the example's parameters in place of the defaults, and
+    /// panics unless the answer is the expected one. Parts which can stream their input must get
+    /// the same answer, of the same type, from the example streamed. Does nothing if the example gives no answer for
+    /// `S`'s part.
+    pub fn assert<S: Solution>(&self) {
+        let Some(expected) = self.answer(S::PART) else {
+            return;
+        };
+        let solver = Solver::of::<S>();
+        let params = solver
+            .params(&self.params, false)
+            .unwrap_or_else(|error| panic!("day {} part {}: {}", S::DAY, S::PART, error));
+        let answers = [
+            solver.solve(&self.input, &params).map(Some),
+            solver.solve_reader(&mut self.input.as_bytes(), &params),
+        ];
+        for answer in &answers {
+            match answer {
+                Ok(Some(answer)) => assert_eq!(
+                    answer.to_string(),
+                    expected,
+                    "day {} part {}",
+                    S::DAY,
+                    S::PART
+                ),
+                Ok(None) => (),
+                Err(error) => panic!("day {} part {}: {}", S::DAY, S::PART, error),
+            }
+        }
+        if let [Ok(Some(solved)), Ok(Some(streamed))] = &answers {
+            assert_eq!(solved, streamed, "day {} part {} streamed", S::DAY, S::PART);
+        }
+    }
+}
+
+/// Generates a `#[test]` for each of the day's example files which checks every one of the given
+/// solutions against it, e.g. `aoclib::examples!(crate::part1::Part1, crate::part2::Part2);`.
+/// The day's build script must call [`example::build`](crate::example::build).
+#[macro_export]
+macro_rules! examples {
+    ($($solution:path),+ $(,)?) => {
+        #[cfg(test)]
+        mod examples {
+            fn check(text: &str) {
+                let example = $crate::example::Example::parse(text)
+                    .unwrap_or_else(|error| panic!("{}", error));
+                $(example.assert::<$solution>();)+
+            }
+
+            include!(concat!(env!("OUT_DIR"), "/examples.rs"));
+        }
+    };
+}
+
+/// For a day's build script: writes the tests for the `.txt` files in the day's `examples`
+/// directory to `$OUT_DIR/examples.rs`, where [`examples!`](crate::examples) includes them from.
+pub fn build() {
+    let manifest_directory = std::env::var_os("CARGO_MANIFEST_DIR").expect("run by cargo");
+    let out_directory = std::env::var_os("OUT_DIR").expect("run by cargo");
+    let directory = Path::new(&manifest_directory).join("examples");
+    println!("cargo:rerun-if-changed={}", directory.display());
+    let tests = generate_tests(&directory)
+        .unwrap_or_else(|error| panic!("cannot read {}: {}", directory.display(), error));
+    let path = Path::new(&out_directory).join("examples.rs");
+    fs::write(&path, tests)
+        .unwrap_or_else(|error| panic!("cannot write {}: {}", path.display(), error));
+}
+
+/// A test for each `.txt` file in `directory`, in order of their names. A missing directory has
+/// no examples.
+fn generate_tests(directory: &Path) -> io::Result<String> {
+    if !directory.exists() {
+        return Ok(String::new());
+    }
+    let mut paths = fs::read_dir(directory)?
+        .map(|entry| entry.map(|entry| entry.path()))
+        .collect::<io::Result<Vec<PathBuf>>>()?;
+    paths.retain(|path| path.extension().is_some_and(|extension| extension == "txt"));
+    paths.sort();
+    Ok(paths
+        .iter()
+        .map(|path| {
+            format!(
+                "#[test]\nfn {}() {{\n    check(include_str!({:?}));\n}}\n",
+                test_name(path),
+                path.display().to_string()
+            )
+        })
+        .collect())
+}
+
+/// The file's name with anything which cannot be in an identifier replaced by `_`.
+fn test_name(path: &Path) -> String {
+    let stem = path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+    let name = stem
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect::<String>();
+    if name.starts_with(|c: char| c.is_ascii_alphabetic()) {
+        name
+    } else {
+        format!("example_{}", name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::params::ParamError;
+    use crate::{Answer, FromParams};
+
+    struct Steps(usize);
+
+    impl FromParams for Steps {
+        const DEFAULTS: &'static [(&'static str, &'static str)] = &[("steps", "64")];
+
+        fn from_params(params: &Params) -> Result<Self, ParamError> {
+            Ok(Steps(params.get("steps")?))
+        }
+    }
+
+    /// The number of lines in the input, times the number of steps.
+    struct Walk;
+
+    impl Solution for Walk {
+        const DAY: u8 = 21;
+        const PART: u8 = 1;
+
+        type Input<'a> = &'a str;
+        type Params = Steps;
+
+        fn parse(input: &str) -> Result<&str, Error> {
+            Ok(input)
+        }
+
+        fn solve(input: &str, params: &Steps) -> Result<Answer, Error> {
+            Ok((input.lines().count() * params.0).into())
+        }
+    }
+
+    #[test]
+    fn test_parse() {
+        let example =
+            Example::parse("part1 = 12\r\n# a comment\nsteps = 6\n---\n..#\n.S.\n\n").unwrap();
+        assert_eq!(example.input, "..#\n.S.\n");
+        assert_eq!(example.params, Params::from_pairs(&[("steps", "6")]));
+        assert_eq!(example.answer(1), Some("12"));
+        assert_eq!(example.answer(2), None);
+    }
+
+    #[test]
+    fn test_parse_error() {
+        assert_eq!(
+            Example::parse("part1 = 12\npart2\n---\n")
+                .unwrap_err()
+                .to_string(),
+            "line 2, column 1: expected key = value, found \"part2\""
+        );
+        assert_eq!(
+            Example::parse("part1 = 12\n").unwrap_err().to_string(),
+            "line 2, column 1: expected a \"---\" line to end the header, found the end of the line"
+        );
+    }
+
+    #[test]
+    fn test_assert() {
+        Example::parse("part1 = 12\nsteps = 6\n---\n..#\n.S.\n")
+            .unwrap()
+            .assert::<Walk>();
+        Example::parse("part2 = 0\n---\n..#\n")
+            .unwrap()
+            .assert::<Walk>();
+    }
+
+    #[test]
+    #[should_panic(expected = "day 21 part 1")]
+    fn test_assert_wrong_answer() {
+        Example::parse("part1 = 12\n---\n..#\n.S.\n")
+            .unwrap()
+            .assert::<Walk>();
+    }
+
+    #[test]
+    fn test_test_name() {
+        assert_eq!(test_name(Path::new("examples/example.txt")), "example");
+        assert_eq!(test_name(Path::new("examples/Loop 2-b.txt")), "loop_2_b");
+        assert_eq!(test_name(Path::new("examples/1.txt")), "example_1");
+    }
+
+    #[test]
+    fn test_generate_tests() {
+        let directory =
+            std::env::temp_dir().join(format!("aoclib-examples-{}", std::process::id()));
+        fs::create_dir_all(&directory).unwrap();
+        fs::write(directory.join("b.txt"), "---\n").unwrap();
+        fs::write(directory.join("a.txt"), "---\n").unwrap();
+        fs::write(directory.join("notes.md"), "").unwrap();
+        let tests = generate_tests(&directory);
+        fs::remove_dir_all(&directory).unwrap();
+
+        let tests = tests.unwrap();
+        assert_eq!(tests.matches("#[test]").count(), 2);
+        assert!(tests.find("fn a()").unwrap() < tests.find("fn b()").unwrap());
+        assert_eq!(generate_tests(&directory).unwrap(), "");
+    }
+}
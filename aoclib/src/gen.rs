@@ -0,0 +1,1291 @@
+//! Seeded generators of puzzle inputs of any size, for seeing how each day's solution scales
+//! beyond the one real input. Each generator builds its input so that whatever the solutions rely
+//! on still holds, such as day 8's ghosts looping back to their first `Z` node or day 24's
+//! hailstones all lying on one rock's path, and the same day, size and seed always give the same
+//! input.
+
+use std::cmp::Ordering;
+use std::collections::hash_map::Entry;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::fmt::Write;
+
+/// A SplitMix64 generator. It lives here rather than coming from a crate so that a seed keeps
+/// giving the same input whatever happens to the dependencies.
+#[derive(Debug, Clone)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// A number from `low` to `high` inclusive.
+    pub fn between(&mut self, low: i64, high: i64) -> i64 {
+        assert!(low <= high, "{low}..={high} is empty");
+        let span = high.abs_diff(low).wrapping_add(1);
+        let offset = if span == 0 {
+            self.next_u64()
+        } else {
+            self.next_u64() % span
+        };
+        low.wrapping_add(offset as i64)
+    }
+
+    /// An index into something `len` long, which must not be empty.
+    pub fn index(&mut self, len: usize) -> usize {
+        assert!(len > 0, "cannot pick from nothing");
+        (self.next_u64() % len as u64) as usize
+    }
+
+    /// True `percent` times in a hundred.
+    pub fn chance(&mut self, percent: u64) -> bool {
+        self.next_u64() % 100 < percent
+    }
+
+    pub fn choose<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.index(items.len())]
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.index(i + 1));
+        }
+    }
+}
+
+/// How to generate one day's input.
+pub struct Generator {
+    pub day: u8,
+    /// What the input is, for the runner's help.
+    pub format: &'static str,
+    /// What the size counts.
+    pub size: &'static str,
+    generate: fn(&mut Rng, usize) -> String,
+}
+
+impl Generator {
+    /// An input of `size`, which is at least one whatever is asked for.
+    pub fn generate(&self, size: usize, seed: u64) -> String {
+        (self.generate)(&mut Rng::new(seed), size.max(1))
+    }
+}
+
+/// The generator for `day`, if it is one of the 25.
+pub fn generator(day: u8) -> Option<&'static Generator> {
+    GENERATORS.iter().find(|generator| generator.day == day)
+}
+
+pub static GENERATORS: [Generator; 25] = [
+    Generator {
+        day: 1,
+        format: "calibration lines",
+        size: "lines",
+        generate: calibration,
+    },
+    Generator {
+        day: 2,
+        format: "cube games",
+        size: "games",
+        generate: cube_games,
+    },
+    Generator {
+        day: 3,
+        format: "an engine schematic",
+        size: "width and height",
+        generate: engine_schematic,
+    },
+    Generator {
+        day: 4,
+        format: "scratchcards",
+        size: "cards",
+        generate: scratchcards,
+    },
+    Generator {
+        day: 5,
+        format: "a seed almanac",
+        size: "seed ranges, and ranges per map",
+        generate: almanac,
+    },
+    Generator {
+        day: 6,
+        format: "boat races",
+        size: "races, at most 4",
+        generate: races,
+    },
+    Generator {
+        day: 7,
+        format: "camel hands",
+        size: "hands",
+        generate: camel_hands,
+    },
+    Generator {
+        day: 8,
+        format: "a node network",
+        size: "nodes, roughly",
+        generate: node_network,
+    },
+    Generator {
+        day: 9,
+        format: "oasis sequences",
+        size: "sequences",
+        generate: sequences,
+    },
+    Generator {
+        day: 10,
+        format: "a pipe loop",
+        size: "a third of the width and height, less the edges",
+        generate: pipe_loop,
+    },
+    Generator {
+        day: 11,
+        format: "a galaxy image",
+        size: "width and height",
+        generate: galaxies,
+    },
+    Generator {
+        day: 12,
+        format: "spring records",
+        size: "records",
+        generate: spring_records,
+    },
+    Generator {
+        day: 13,
+        format: "mirror patterns",
+        size: "patterns",
+        generate: mirror_patterns,
+    },
+    Generator {
+        day: 14,
+        format: "a rock platform",
+        size: "width and height",
+        generate: rock_platform,
+    },
+    Generator {
+        day: 15,
+        format: "an initialisation sequence",
+        size: "steps",
+        generate: initialisation_sequence,
+    },
+    Generator {
+        day: 16,
+        format: "a mirror contraption",
+        size: "width and height",
+        generate: contraption,
+    },
+    Generator {
+        day: 17,
+        format: "a heat loss map",
+        size: "width and height, at least 5",
+        generate: heat_loss_map,
+    },
+    Generator {
+        day: 18,
+        format: "a dig plan",
+        size: "columns of the lagoon, up to 1048575",
+        generate: dig_plan,
+    },
+    Generator {
+        day: 19,
+        format: "workflows and parts",
+        size: "workflows, and parts",
+        generate: workflows,
+    },
+    Generator {
+        day: 20,
+        format: "a module configuration",
+        size: "counters feeding rx, from 2 to 5",
+        generate: module_configuration,
+    },
+    Generator {
+        day: 21,
+        format: "a garden map",
+        size: "distance from the start to the edge",
+        generate: garden,
+    },
+    Generator {
+        day: 22,
+        format: "a brick snapshot",
+        size: "bricks",
+        generate: brick_snapshot,
+    },
+    Generator {
+        day: 23,
+        format: "hiking trails",
+        size: "maze cells across",
+        generate: hiking_trails,
+    },
+    Generator {
+        day: 24,
+        format: "hailstones",
+        size: "hailstones, at least 3",
+        generate: hailstones,
+    },
+    Generator {
+        day: 25,
+        format: "a component graph",
+        size: "components",
+        generate: component_graph,
+    },
+];
+
+/// `count` different names of `letters`, each long enough for there to be that many.
+fn names(rng: &mut Rng, count: usize, letters: &[u8], min_length: usize) -> Vec<String> {
+    let mut length = min_length;
+    while letters.len().pow(length as u32) < count * 4 {
+        length += 1;
+    }
+    let mut seen = HashSet::new();
+    let mut names = vec![];
+    while names.len() < count {
+        let name: String = (0..length).map(|_| *rng.choose(letters) as char).collect();
+        if seen.insert(name.clone()) {
+            names.push(name);
+        }
+    }
+    names
+}
+
+const LOWERCASE: &[u8] = b"abcdefghijklmnopqrstuvwxyz";
+
+const DIGIT_WORDS: [&str; 9] = [
+    "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+];
+
+fn calibration(rng: &mut Rng, lines: usize) -> String {
+    let mut input = String::new();
+    for _ in 0..lines {
+        let mut pieces: Vec<String> = (0..rng.between(0, 5))
+            .map(|_| match rng.between(0, 2) {
+                0 => (0..rng.between(1, 4))
+                    .map(|_| *rng.choose(LOWERCASE) as char)
+                    .collect(),
+                1 => rng.between(1, 9).to_string(),
+                _ => rng.choose(&DIGIT_WORDS).to_string(),
+            })
+            .collect();
+        // Part 1 needs a digit on every line, not just a spelt out one.
+        let at = rng.index(pieces.len() + 1);
+        pieces.insert(at, rng.between(1, 9).to_string());
+        writeln!(input, "{}", pieces.concat()).unwrap();
+    }
+    input
+}
+
+fn cube_games(rng: &mut Rng, games: usize) -> String {
+    let mut input = String::new();
+    for game in 1..=games {
+        let draws: Vec<String> = (0..rng.between(1, 6))
+            .map(|_| {
+                let mut colours = ["red", "green", "blue"];
+                rng.shuffle(&mut colours);
+                let shown = rng.between(1, 3) as usize;
+                colours[..shown]
+                    .iter()
+                    .map(|colour| format!("{} {colour}", rng.between(1, 20)))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            })
+            .collect();
+        writeln!(input, "Game {game}: {}", draws.join("; ")).unwrap();
+    }
+    input
+}
+
+fn engine_schematic(rng: &mut Rng, side: usize) -> String {
+    let mut grid = vec![vec![b'.'; side]; side];
+    for row in grid.iter_mut() {
+        let mut x = 0;
+        while x < side {
+            let length = rng.between(1, 3) as usize;
+            if rng.chance(15) && x + length <= side {
+                row[x] = b'0' + rng.between(1, 9) as u8;
+                for cell in &mut row[x + 1..x + length] {
+                    *cell = b'0' + rng.between(0, 9) as u8;
+                }
+                // Leave a gap so the next number is not read as part of this one.
+                x += length + 1;
+            } else {
+                x += 1;
+            }
+        }
+    }
+    for cell in grid.iter_mut().flatten() {
+        if *cell == b'.' && rng.chance(6) {
+            *cell = *rng.choose(b"*#+$/@=%&-");
+        }
+    }
+    render(grid)
+}
+
+fn scratchcards(rng: &mut Rng, cards: usize) -> String {
+    // Every card won wins copies of the cards after it, so the number of cards can grow
+    // exponentially. Wins which would take the total past what part 2 counts in are dropped.
+    const BUDGET: u64 = u32::MAX as u64 / 2;
+    let mut copies = vec![1u64; cards];
+    let mut total = cards as u64;
+    let width = cards.to_string().len().max(3);
+    let mut input = String::new();
+    for card in 0..cards {
+        let mut wins = (rng.between(0, 5) as usize).min(cards - card - 1);
+        while wins > 0 && total + copies[card] * wins as u64 > BUDGET {
+            wins -= 1;
+        }
+        let won = copies[card];
+        for later in &mut copies[card + 1..=card + wins] {
+            *later += won;
+        }
+        total += won * wins as u64;
+
+        let mut numbers: Vec<i64> = (1..=99).collect();
+        rng.shuffle(&mut numbers);
+        let winning = &numbers[..10];
+        let mut have: Vec<i64> = winning[..wins]
+            .iter()
+            .chain(&numbers[10..35 - wins])
+            .copied()
+            .collect();
+        rng.shuffle(&mut have);
+        let list = |numbers: &[i64]| {
+            numbers
+                .iter()
+                .map(|number| format!("{number:>2}"))
+                .collect::<Vec<_>>()
+                .join(" ")
+        };
+        writeln!(
+            input,
+            "Card {:>width$}: {} | {}",
+            card + 1,
+            list(winning),
+            list(&have)
+        )
+        .unwrap();
+    }
+    input
+}
+
+const ALMANAC: [&str; 8] = [
+    "seed",
+    "soil",
+    "fertilizer",
+    "water",
+    "light",
+    "temperature",
+    "humidity",
+    "location",
+];
+
+fn almanac(rng: &mut Rng, ranges: usize) -> String {
+    // Everything stays well inside a u32, as in the real almanac, so no range can overflow.
+    const LIMIT: i64 = u32::MAX as i64;
+    let seeds: Vec<String> = (0..ranges)
+        .map(|_| {
+            let start = rng.between(0, LIMIT / 2);
+            let length = rng.between(1, LIMIT / 2 / ranges as i64);
+            format!("{start} {length}")
+        })
+        .collect();
+    let mut input = format!("seeds: {}\n", seeds.join(" "));
+    for pair in ALMANAC.windows(2) {
+        writeln!(input, "\n{}-to-{} map:", pair[0], pair[1]).unwrap();
+        // The source ranges must not overlap, so they are cut from sorted points.
+        let mut points: Vec<i64> = (0..ranges * 2).map(|_| rng.between(0, LIMIT / 2)).collect();
+        points.sort_unstable();
+        for index in 1..points.len() {
+            points[index] = points[index].max(points[index - 1] + 1);
+        }
+        for bounds in points.chunks_exact(2) {
+            let length = bounds[1] - bounds[0] + 1;
+            let destination = rng.between(0, LIMIT - length);
+            writeln!(input, "{destination} {} {length}", bounds[0]).unwrap();
+        }
+    }
+    input
+}
+
+fn races(rng: &mut Rng, races: usize) -> String {
+    // Part 2 reads the digits of every race as one race, which has to fit in a u64.
+    let races = races.min(4);
+    let mut times = vec![];
+    let mut records = vec![];
+    for _ in 0..races {
+        let time = rng.between(20, 99);
+        let best = (time / 2) * (time - time / 2);
+        times.push(time.to_string());
+        records.push(rng.between(best / 3, best - 1).to_string());
+    }
+    let row = |label: &str, numbers: &[String]| {
+        numbers.iter().fold(format!("{label:<9}"), |row, number| {
+            format!("{row} {number:>6}")
+        })
+    };
+    format!("{}\n{}\n", row("Time:", &times), row("Distance:", &records))
+}
+
+fn camel_hands(rng: &mut Rng, hands: usize) -> String {
+    // There are only so many different hands.
+    let hands = hands.min(13usize.pow(5));
+    let mut seen = HashSet::new();
+    let mut input = String::new();
+    while seen.len() < hands {
+        let hand: String = (0..5)
+            .map(|_| *rng.choose(b"23456789TJQKA") as char)
+            .collect();
+        if seen.insert(hand.clone()) {
+            writeln!(input, "{hand} {}", rng.between(1, 1000)).unwrap();
+        }
+    }
+    input
+}
+
+fn node_network(rng: &mut Rng, nodes: usize) -> String {
+    // Part 2 takes the lowest common multiple of each ghost's steps to its first Z node, which is
+    // only the answer if each ghost then loops back to that node in as many steps again. So each
+    // ghost walks a chain of nodes `cycle` times as long as the directions, and the Z node at its
+    // end leads back to the chain's second node. Every node is always reached at the same point
+    // in the directions, so the branch not taken can go anywhere.
+    const CYCLES: [usize; 6] = [1, 2, 3, 5, 7, 11];
+    let length = (nodes / CYCLES.iter().sum::<usize>()).max(1);
+    let directions: Vec<char> = (0..length).map(|_| *rng.choose(&['L', 'R'])).collect();
+
+    // Every name is a different prefix of the letters between A and Z, and a last letter, so Z
+    // and A only end the names which should, and none is AAA or ZZZ.
+    let chains: usize = CYCLES.iter().map(|cycle| cycle * length - 1).sum();
+    let middle: Vec<u8> = (b'B'..=b'Y').collect();
+    let mut prefixes = names(rng, chains + 2 * CYCLES.len(), &middle, 2).into_iter();
+    let mut name = |end: u8| format!("{}{}", prefixes.next().unwrap(), end as char);
+
+    let mut edges: Vec<(String, Option<String>, Option<String>)> = vec![];
+    let mut all = vec![];
+    for (ghost, cycle) in CYCLES.iter().enumerate() {
+        let (start, end) = if ghost == 0 {
+            ("AAA".to_string(), "ZZZ".to_string())
+        } else {
+            (name(b'A'), name(b'Z'))
+        };
+        let mut chain = vec![start];
+        for _ in 1..cycle * length {
+            let end = *rng.choose(&middle);
+            chain.push(name(end));
+        }
+        chain.push(end);
+        for (step, node) in chain.iter().enumerate() {
+            let next = if step + 1 < chain.len() {
+                &chain[step + 1]
+            } else {
+                &chain[1]
+            };
+            let taken = Some(next.clone());
+            edges.push(match directions[step % length] {
+                'L' => (node.clone(), taken, None),
+                _ => (node.clone(), None, taken),
+            });
+        }
+        all.extend(chain);
+    }
+
+    let mut lines: Vec<String> = edges
+        .into_iter()
+        .map(|(node, left, right)| {
+            let left = left.unwrap_or_else(|| rng.choose(&all).clone());
+            let right = right.unwrap_or_else(|| rng.choose(&all).clone());
+            format!("{node} = ({left}, {right})")
+        })
+        .collect();
+    rng.shuffle(&mut lines);
+    format!(
+        "{}\n\n{}\n",
+        directions.iter().collect::<String>(),
+        lines.join("\n")
+    )
+}
+
+fn sequences(rng: &mut Rng, sequences: usize) -> String {
+    let mut input = String::new();
+    for _ in 0..sequences {
+        // Newton's forward differences: the k-th differences of the sequence start at `starts[k]`,
+        // and the last of them are constant.
+        let starts: Vec<i64> = (0..=rng.between(0, 6))
+            .map(|_| rng.between(-10, 10))
+            .collect();
+        let values: Vec<String> = (0..21i64)
+            .map(|x| {
+                let mut choose = 1;
+                let mut value = 0;
+                for (k, start) in starts.iter().enumerate() {
+                    value += start * choose;
+                    choose = choose * (x - k as i64) / (k as i64 + 1);
+                }
+                value.to_string()
+            })
+            .collect();
+        writeln!(input, "{}", values.join(" ")).unwrap();
+    }
+    input
+}
+
+fn pipe_loop(rng: &mut Rng, blocks: usize) -> String {
+    // The loop runs around a random spanning tree of a grid of blocks, each three tiles across,
+    // so it never crosses itself and encloses the middle of every block. A ring of ground around
+    // it keeps the start's neighbours on the map.
+    let (right, down) = spanning_tree(rng, blocks);
+    let side = blocks * 3;
+    let mut grid = vec![vec![b'.'; side + 2]; side + 2];
+    let mut tiles = vec![];
+    for y in 0..side {
+        for x in 0..side {
+            let (bx, by, sx, sy) = (x / 3, y / 3, x % 3, y % 3);
+            let north = by > 0 && down[by - 1][bx];
+            let south = down[by][bx];
+            let west = bx > 0 && right[by][bx - 1];
+            let east = right[by][bx];
+            let exits = match (sx, sy) {
+                (1, 1) => None,
+                (1, 0) => (!north).then_some(('W', 'E')),
+                (1, 2) => (!south).then_some(('W', 'E')),
+                (0, 1) => (!west).then_some(('N', 'S')),
+                (2, 1) => (!east).then_some(('N', 'S')),
+                (0, 0) => Some((if north { 'N' } else { 'E' }, if west { 'W' } else { 'S' })),
+                (2, 0) => Some((if north { 'N' } else { 'W' }, if east { 'E' } else { 'S' })),
+                (0, 2) => Some((if south { 'S' } else { 'E' }, if west { 'W' } else { 'N' })),
+                _ => Some((if south { 'S' } else { 'W' }, if east { 'E' } else { 'N' })),
+            };
+            if let Some(exits) = exits {
+                grid[y + 1][x + 1] = match exits {
+                    ('N', 'S') | ('S', 'N') => b'|',
+                    ('W', 'E') | ('E', 'W') => b'-',
+                    ('N', 'E') | ('E', 'N') => b'L',
+                    ('N', 'W') | ('W', 'N') => b'J',
+                    ('S', 'W') | ('W', 'S') => b'7',
+                    _ => b'F',
+                };
+                tiles.push((x + 1, y + 1));
+            }
+        }
+    }
+    let (x, y) = *rng.choose(&tiles);
+    grid[y][x] = b'S';
+    render(grid)
+}
+
+/// A random spanning tree over a `side` by `side` grid, as whether each cell is joined to the
+/// cell to its right, and to the cell below it, indexed by row then column.
+fn spanning_tree(rng: &mut Rng, side: usize) -> (Vec<Vec<bool>>, Vec<Vec<bool>>) {
+    let mut right = vec![vec![false; side]; side];
+    let mut down = vec![vec![false; side]; side];
+    let mut visited = vec![vec![false; side]; side];
+    visited[0][0] = true;
+    let mut stack = vec![(0usize, 0usize)];
+    while let Some(&(x, y)) = stack.last() {
+        let mut next = vec![];
+        if x > 0 && !visited[y][x - 1] {
+            next.push((x - 1, y));
+        }
+        if x + 1 < side && !visited[y][x + 1] {
+            next.push((x + 1, y));
+        }
+        if y > 0 && !visited[y - 1][x] {
+            next.push((x, y - 1));
+        }
+        if y + 1 < side && !visited[y + 1][x] {
+            next.push((x, y + 1));
+        }
+        if next.is_empty() {
+            stack.pop();
+            continue;
+        }
+        let (nx, ny) = *rng.choose(&next);
+        if ny == y {
+            right[y][x.min(nx)] = true;
+        } else {
+            down[y.min(ny)][x] = true;
+        }
+        visited[ny][nx] = true;
+        stack.push((nx, ny));
+    }
+    (right, down)
+}
+
+fn galaxies(rng: &mut Rng, side: usize) -> String {
+    let empty_rows: Vec<bool> = (0..side).map(|_| rng.chance(10)).collect();
+    let empty_columns: Vec<bool> = (0..side).map(|_| rng.chance(10)).collect();
+    let mut grid = vec![vec![b'.'; side]; side];
+    for (y, row) in grid.iter_mut().enumerate() {
+        for (x, cell) in row.iter_mut().enumerate() {
+            if !empty_rows[y] && !empty_columns[x] && rng.chance(4) {
+                *cell = b'#';
+            }
+        }
+    }
+    if !grid.iter().flatten().any(|cell| *cell == b'#') {
+        grid[0][0] = b'#';
+    }
+    render(grid)
+}
+
+fn spring_records(rng: &mut Rng, records: usize) -> String {
+    let mut input = String::new();
+    for _ in 0..records {
+        // A row of known springs, so there is always at least one arrangement, some of which
+        // are then forgotten.
+        let length = rng.between(5, 20) as usize;
+        let mut springs: Vec<u8> = (0..length)
+            .map(|_| if rng.chance(50) { b'#' } else { b'.' })
+            .collect();
+        let at = rng.index(length);
+        springs[at] = b'#';
+        let groups: Vec<String> = springs
+            .split(|spring| *spring == b'.')
+            .filter(|group| !group.is_empty())
+            .map(|group| group.len().to_string())
+            .collect();
+        let row: String = springs
+            .iter()
+            .map(|spring| if rng.chance(50) { '?' } else { *spring as char })
+            .collect();
+        writeln!(input, "{row} {}", groups.join(",")).unwrap();
+    }
+    input
+}
+
+fn mirror_patterns(rng: &mut Rng, patterns: usize) -> String {
+    (0..patterns)
+        .map(|_| mirror_pattern(rng))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// A pattern with exactly one perfect reflection, down a column, and exactly one reflection off
+/// by a single smudge, across a row, swapped round half the time.
+fn mirror_pattern(rng: &mut Rng) -> String {
+    loop {
+        let width = rng.between(5, 17) as usize;
+        let height = rng.between(5, 17) as usize;
+        let column = rng.between(1, width as i64 - 1) as usize;
+        let row = rng.between(1, height as i64 - 1) as usize;
+
+        let mut classes = UnionFind::new(width * height);
+        let cell = |x: usize, y: usize| y * width + x;
+        for y in 0..height {
+            for x in 0..column {
+                let mirror = 2 * column - 1 - x;
+                if mirror < width {
+                    classes.union(cell(x, y), cell(mirror, y));
+                }
+            }
+        }
+        let mut pairs = vec![];
+        for y in 0..row {
+            let mirror = 2 * row - 1 - y;
+            if mirror < height {
+                pairs.extend((0..width).map(|x| (cell(x, y), cell(x, mirror))));
+            }
+        }
+        let smudge = pairs.swap_remove(rng.index(pairs.len()));
+        for (a, b) in pairs {
+            classes.union(a, b);
+        }
+        if classes.find(smudge.0) == classes.find(smudge.1) {
+            continue;
+        }
+
+        let mut values: HashMap<usize, bool> = HashMap::new();
+        let smudged = classes.find(smudge.1);
+        let first = classes.find(smudge.0);
+        values.insert(first, rng.chance(50));
+        values.insert(smudged, !values[&first]);
+        let grid: Vec<Vec<u8>> = (0..height)
+            .map(|y| {
+                (0..width)
+                    .map(|x| {
+                        let class = classes.find(cell(x, y));
+                        let rock = *values.entry(class).or_insert_with(|| rng.chance(50));
+                        if rock {
+                            b'#'
+                        } else {
+                            b'.'
+                        }
+                    })
+                    .collect()
+            })
+            .collect();
+
+        let transposed: Vec<Vec<u8>> = (0..width)
+            .map(|x| grid.iter().map(|line| line[x]).collect())
+            .collect();
+        let mut lines = [(0, 0), (0, 0)];
+        for (orientation, rows) in [&grid, &transposed].into_iter().enumerate() {
+            for line in 1..rows.len() {
+                match reflection_errors(rows, line) {
+                    0 => lines[orientation].0 += 1,
+                    1 => lines[orientation].1 += 1,
+                    _ => {}
+                }
+            }
+        }
+        // Down a column is across a row of the transposed pattern.
+        if lines != [(0, 1), (1, 0)] {
+            continue;
+        }
+        return render(if rng.chance(50) { grid } else { transposed });
+    }
+}
+
+/// How many tiles differ from their reflection across the line above row `line`.
+fn reflection_errors(rows: &[Vec<u8>], line: usize) -> usize {
+    (0..line)
+        .rev()
+        .zip(line..rows.len())
+        .map(|(above, below)| {
+            rows[above]
+                .iter()
+                .zip(&rows[below])
+                .filter(|(a, b)| a != b)
+                .count()
+        })
+        .sum()
+}
+
+struct UnionFind(Vec<usize>);
+
+impl UnionFind {
+    fn new(size: usize) -> Self {
+        Self((0..size).collect())
+    }
+
+    fn find(&mut self, item: usize) -> usize {
+        let parent = self.0[item];
+        if parent == item {
+            return item;
+        }
+        let root = self.find(parent);
+        self.0[item] = root;
+        root
+    }
+
+    fn union(&mut self, a: usize, b: usize) {
+        let (a, b) = (self.find(a), self.find(b));
+        self.0[a] = b;
+    }
+}
+
+fn rock_platform(rng: &mut Rng, side: usize) -> String {
+    grid(rng, side, side, |rng| match rng.between(0, 99) {
+        0..=9 => b'#',
+        10..=34 => b'O',
+        _ => b'.',
+    })
+}
+
+fn initialisation_sequence(rng: &mut Rng, steps: usize) -> String {
+    let labels = names(rng, steps / 4 + 1, LOWERCASE, 2);
+    let steps: Vec<String> = (0..steps)
+        .map(|_| {
+            let label = rng.choose(&labels);
+            if rng.chance(60) {
+                format!("{label}={}", rng.between(1, 9))
+            } else {
+                format!("{label}-")
+            }
+        })
+        .collect();
+    format!("{}\n", steps.join(","))
+}
+
+fn contraption(rng: &mut Rng, side: usize) -> String {
+    grid(rng, side, side, |rng| {
+        if rng.chance(85) {
+            b'.'
+        } else {
+            *rng.choose(b"/\\-|")
+        }
+    })
+}
+
+fn heat_loss_map(rng: &mut Rng, side: usize) -> String {
+    // The ultra crucible has to move four blocks before it can stop at the factory.
+    let side = side.max(5);
+    grid(rng, side, side, |rng| b'0' + rng.between(1, 9) as u8)
+}
+
+/// The longest distance five hex digits can give a colour's instruction.
+const MAX_HEX_DISTANCE: i64 = 0xf_ffff;
+
+fn dig_plan(rng: &mut Rng, columns: usize) -> String {
+    // Both parts dig a lagoon shaped like a bar chart, which can never cross itself, and both
+    // have the same number of instructions. The colours' bars are narrow enough that the
+    // instruction back along the bottom still fits in five hex digits.
+    let columns = columns.min(MAX_HEX_DISTANCE as usize);
+    let first = bar_chart(rng, columns, 10, 20);
+    let max_width = (MAX_HEX_DISTANCE / columns as i64).min(100_000);
+    let second = bar_chart(rng, columns, max_width, 1_000_000);
+    let mut input = String::new();
+    for ((direction, length), (hidden, distance)) in first.into_iter().zip(second) {
+        let code = match hidden {
+            'R' => 0,
+            'D' => 1,
+            'L' => 2,
+            _ => 3,
+        };
+        writeln!(input, "{direction} {length} (#{distance:05x}{code})").unwrap();
+    }
+    input
+}
+
+/// The outline of `columns` bars side by side, as directions and distances, with neighbouring
+/// bars never the same height.
+fn bar_chart(rng: &mut Rng, columns: usize, max_width: i64, max_height: i64) -> Vec<(char, i64)> {
+    let mut heights = vec![rng.between(1, max_height)];
+    while heights.len() < columns {
+        let height = rng.between(1, max_height);
+        if height != heights[heights.len() - 1] {
+            heights.push(height);
+        }
+    }
+    let mut moves = vec![('U', heights[0])];
+    let mut total = 0;
+    for (index, height) in heights.iter().enumerate() {
+        let width = rng.between(1, max_width);
+        total += width;
+        moves.push(('R', width));
+        if let Some(next) = heights.get(index + 1) {
+            moves.push((if next > height { 'U' } else { 'D' }, (next - height).abs()));
+        }
+    }
+    moves.push(('D', heights[heights.len() - 1]));
+    moves.push(('L', total));
+    moves
+}
+
+fn workflows(rng: &mut Rng, workflows: usize) -> String {
+    // The workflows form a tree from `in`, since a loop would send a part round for ever.
+    let mut names = names(rng, workflows, LOWERCASE, 2);
+    names.retain(|name| name != "in");
+    names.truncate(workflows - 1);
+    names.insert(0, "in".to_string());
+    let mut destinations: Vec<Vec<String>> = vec![vec![]; names.len()];
+    for (child, name) in names.iter().enumerate().skip(1) {
+        destinations[rng.index(child)].push(name.clone());
+    }
+    let mut lines = vec![];
+    for (name, mut destinations) in names.iter().zip(destinations) {
+        for _ in 0..rng.between(1, 2) {
+            destinations.push(rng.choose(&["A", "R"]).to_string());
+        }
+        rng.shuffle(&mut destinations);
+        let default = destinations.pop().unwrap();
+        let rules: Vec<String> = destinations
+            .into_iter()
+            .map(|destination| {
+                let category = *rng.choose(b"xmas") as char;
+                let comparison = *rng.choose(b"<>") as char;
+                format!(
+                    "{category}{comparison}{}:{destination}",
+                    rng.between(2, 3999)
+                )
+            })
+            .chain([default])
+            .collect();
+        lines.push(format!("{name}{{{}}}", rules.join(",")));
+    }
+    rng.shuffle(&mut lines);
+    let parts: Vec<String> = (0..workflows)
+        .map(|_| {
+            let [x, m, a, s] = [(); 4].map(|_| rng.between(1, 4000));
+            format!("{{x={x},m={m},a={a},s={s}}}")
+        })
+        .collect();
+    format!("{}\n\n{}\n", lines.join("\n"), parts.join("\n"))
+}
+
+fn module_configuration(rng: &mut Rng, counters: usize) -> String {
+    // Part 2 only finds the counters when there are at least two, and their primes have to
+    // multiply to fit in a u64.
+    counter_network(rng, counters.clamp(2, 5), 12)
+}
+
+/// A module configuration where rx is fed, through one conjunction, by inverters which each
+/// fire when a counter of `bits` flip-flops reaches its own prime, as day 20's part 2 assumes.
+/// There must be at least `counters` primes of `bits` bits.
+pub fn counter_network(rng: &mut Rng, counters: usize, bits: usize) -> String {
+    let mut primes = vec![];
+    while primes.len() < counters {
+        let candidate = rng.between(1 << (bits - 1), (1 << bits) - 1) as u64;
+        if is_prime(candidate) && !primes.contains(&candidate) {
+            primes.push(candidate);
+        }
+    }
+
+    let mut names = names(rng, counters * (bits + 2) + 2, LOWERCASE, 2);
+    names.retain(|name| name != "rx");
+    let mut names = names.into_iter();
+    let last = names.next().unwrap();
+    let mut lines = vec![];
+    let mut starts = vec![];
+    for prime in primes {
+        let flip_flops: Vec<String> = names.by_ref().take(bits).collect();
+        let hub = names.next().unwrap();
+        let inverter = names.next().unwrap();
+        let mut from_hub = vec![flip_flops[0].clone(), inverter.clone()];
+        for (bit, flip_flop) in flip_flops.iter().enumerate() {
+            let mut outputs = vec![];
+            if let Some(next) = flip_flops.get(bit + 1) {
+                outputs.push(next.clone());
+            }
+            if prime & (1 << bit) != 0 {
+                outputs.push(hub.clone());
+            } else {
+                from_hub.push(flip_flop.clone());
+            }
+            rng.shuffle(&mut outputs);
+            lines.push(format!("%{flip_flop} -> {}", outputs.join(", ")));
+        }
+        rng.shuffle(&mut from_hub);
+        lines.push(format!("&{hub} -> {}", from_hub.join(", ")));
+        lines.push(format!("&{inverter} -> {last}"));
+        starts.push(flip_flops[0].clone());
+    }
+    lines.push(format!("&{last} -> rx"));
+    lines.push(format!("broadcaster -> {}", starts.join(", ")));
+    rng.shuffle(&mut lines);
+    format!("{}\n", lines.join("\n"))
+}
+
+fn is_prime(number: u64) -> bool {
+    number > 1
+        && (2..)
+            .take_while(|divisor| divisor * divisor <= number)
+            .all(|divisor| !number.is_multiple_of(divisor))
+}
+
+fn garden(rng: &mut Rng, radius: usize) -> String {
+    // Part 2 extrapolates from whole copies of the map, which needs a clear path from the start
+    // in the middle straight out to each edge, and clear edges.
+    let side = 2 * radius + 1;
+    let mut grid = vec![vec![b'.'; side]; side];
+    for (y, row) in grid.iter_mut().enumerate() {
+        for (x, cell) in row.iter_mut().enumerate() {
+            let clear =
+                x == radius || y == radius || x == 0 || y == 0 || x == side - 1 || y == side - 1;
+            if !clear && rng.chance(12) {
+                *cell = b'#';
+            }
+        }
+    }
+    grid[radius][radius] = b'S';
+    render(grid)
+}
+
+fn brick_snapshot(rng: &mut Rng, bricks: usize) -> String {
+    const FOOTPRINT: i64 = 10;
+    let height = (bricks as i64).max(10);
+    let mut filled = HashSet::new();
+    let mut lines = vec![];
+    while lines.len() < bricks {
+        let start = [
+            rng.between(0, FOOTPRINT - 1),
+            rng.between(0, FOOTPRINT - 1),
+            rng.between(1, height),
+        ];
+        let axis = rng.index(3);
+        let mut end = start;
+        end[axis] += rng.between(0, 3);
+        if axis < 2 && end[axis] >= FOOTPRINT {
+            continue;
+        }
+        let cubes: Vec<[i64; 3]> = (start[axis]..=end[axis])
+            .map(|along| {
+                let mut cube = start;
+                cube[axis] = along;
+                cube
+            })
+            .collect();
+        if cubes.iter().any(|cube| filled.contains(cube)) {
+            continue;
+        }
+        filled.extend(cubes);
+        lines.push(format!(
+            "{},{},{}~{},{},{}",
+            start[0], start[1], start[2], end[0], end[1], end[2]
+        ));
+    }
+    format!("{}\n", lines.join("\n"))
+}
+
+fn hiking_trails(rng: &mut Rng, cells: usize) -> String {
+    // A maze with a few walls knocked through, since the longest hike is only hard to find
+    // where trails meet. Slopes next to junctions point away from the start, as they do in the
+    // real map, so there is always a way down.
+    let side = 2 * cells + 1;
+    let mut grid = vec![vec![b'#'; side]; side];
+    let (right, down) = spanning_tree(rng, cells);
+    for y in 0..cells {
+        for x in 0..cells {
+            grid[2 * y + 1][2 * x + 1] = b'.';
+            if right[y][x] {
+                grid[2 * y + 1][2 * x + 2] = b'.';
+            }
+            if down[y][x] {
+                grid[2 * y + 2][2 * x + 1] = b'.';
+            }
+        }
+    }
+    for _ in 0..cells {
+        let (x, y) = (rng.index(cells), rng.index(cells));
+        if rng.chance(50) && x + 1 < cells {
+            grid[2 * y + 1][2 * x + 2] = b'.';
+        } else if y + 1 < cells {
+            grid[2 * y + 2][2 * x + 1] = b'.';
+        }
+    }
+    grid[0][1] = b'.';
+    grid[side - 1][side - 2] = b'.';
+
+    let open = |grid: &Vec<Vec<u8>>, x: usize, y: usize| {
+        let mut neighbours = vec![];
+        if x > 0 && grid[y][x - 1] != b'#' {
+            neighbours.push((x - 1, y));
+        }
+        if x + 1 < side && grid[y][x + 1] != b'#' {
+            neighbours.push((x + 1, y));
+        }
+        if y > 0 && grid[y - 1][x] != b'#' {
+            neighbours.push((x, y - 1));
+        }
+        if y + 1 < side && grid[y + 1][x] != b'#' {
+            neighbours.push((x, y + 1));
+        }
+        neighbours
+    };
+    let mut distances = HashMap::from([((1, 0), 0)]);
+    let mut queue = VecDeque::from([(1, 0)]);
+    while let Some((x, y)) = queue.pop_front() {
+        let distance = distances[&(x, y)];
+        for next in open(&grid, x, y) {
+            if let Entry::Vacant(entry) = distances.entry(next) {
+                entry.insert(distance + 1);
+                queue.push_back(next);
+            }
+        }
+    }
+    let mut slopes = vec![];
+    for y in (1..side - 1).step_by(2) {
+        for x in (1..side - 1).step_by(2) {
+            if open(&grid, x, y).len() < 3 {
+                continue;
+            }
+            for (px, py) in open(&grid, x, y) {
+                if py == 0 || py == side - 1 {
+                    continue;
+                }
+                // Between this junction and the next cell on.
+                let (nx, ny) = (2 * px - x, 2 * py - y);
+                match distances[&(nx, ny)].cmp(&distances[&(x, y)]) {
+                    Ordering::Greater => slopes.push((px, py, nx > x, nx < x, ny > y)),
+                    Ordering::Less => slopes.push((px, py, nx < x, nx > x, ny < y)),
+                    Ordering::Equal => {}
+                }
+            }
+        }
+    }
+    for (x, y, east, west, south) in slopes {
+        grid[y][x] = if east {
+            b'>'
+        } else if west {
+            b'<'
+        } else if south {
+            b'v'
+        } else {
+            b'^'
+        };
+    }
+    render(grid)
+}
+
+fn hailstones(rng: &mut Rng, hailstones: usize) -> String {
+    // Part 2 throws a rock which hits every hailstone, so there has to be one: each hailstone is
+    // placed wherever it needs to start to meet the rock at its own time. Pinning down the
+    // rock's path takes three of them.
+    let hailstones = hailstones.max(3);
+    let rock: [i64; 3] = [(); 3].map(|_| rng.between(200_000_000_000_000, 400_000_000_000_000));
+    let velocity: [i64; 3] = [(); 3].map(|_| rng.between(-250, 250));
+    let mut times = HashSet::new();
+    let mut input = String::new();
+    while times.len() < hailstones {
+        let time = rng.between(1_000_000_000, 300_000_000_000);
+        let own: [i64; 3] = [(); 3].map(|_| rng.between(-250, 250));
+        if own == velocity || !times.insert(time) {
+            continue;
+        }
+        let position: [i64; 3] =
+            [0, 1, 2].map(|axis| rock[axis] + (velocity[axis] - own[axis]) * time);
+        writeln!(
+            input,
+            "{}, {}, {} @ {}, {}, {}",
+            position[0], position[1], position[2], own[0], own[1], own[2]
+        )
+        .unwrap();
+    }
+    input
+}
+
+fn component_graph(rng: &mut Rng, components: usize) -> String {
+    // Two groups, each joined to itself by at least four wires between any split of it, and to
+    // the other group by exactly three, so those three are the only cut which divides the graph.
+    let components = components.max(10);
+    let names = names(rng, components, LOWERCASE, 3);
+    let halves = [0..components / 2, components / 2..components];
+    let wire = |a: usize, b: usize| (a.min(b), a.max(b));
+    let mut wires = HashSet::new();
+    for half in &halves {
+        let size = half.len();
+        let member = |offset: usize| half.start + offset % size;
+        for offset in 0..size {
+            wires.insert(wire(member(offset), member(offset + 1)));
+            wires.insert(wire(member(offset), member(offset + 2)));
+        }
+        for _ in 0..size / 4 {
+            let (a, b) = (member(rng.index(size)), member(rng.index(size)));
+            if a != b {
+                wires.insert(wire(a, b));
+            }
+        }
+    }
+    let mut left: Vec<usize> = halves[0].clone().collect();
+    let mut right: Vec<usize> = halves[1].clone().collect();
+    rng.shuffle(&mut left);
+    rng.shuffle(&mut right);
+    wires.extend(left.into_iter().zip(right).take(3));
+
+    // Each wire is listed once, from either end, with the lines sorted before they are shuffled so
+    // that the order does not depend on the hash map's.
+    let mut connections: HashMap<usize, Vec<usize>> = HashMap::new();
+    let mut wires: Vec<(usize, usize)> = wires.into_iter().collect();
+    wires.sort_unstable();
+    for (a, b) in wires {
+        let (from, to) = if rng.chance(50) { (a, b) } else { (b, a) };
+        connections.entry(from).or_default().push(to);
+    }
+    let mut lines: Vec<String> = connections
+        .into_iter()
+        .map(|(from, to)| {
+            let to: Vec<&str> = to.iter().map(|to| names[*to].as_str()).collect();
+            format!("{}: {}", names[from], to.join(" "))
+        })
+        .collect();
+    lines.sort_unstable();
+    rng.shuffle(&mut lines);
+    format!("{}\n", lines.join("\n"))
+}
+
+/// A `width` by `height` grid with each tile from `tile`.
+fn grid(
+    rng: &mut Rng,
+    width: usize,
+    height: usize,
+    mut tile: impl FnMut(&mut Rng) -> u8,
+) -> String {
+    render(
+        (0..height)
+            .map(|_| (0..width).map(|_| tile(rng)).collect())
+            .collect(),
+    )
+}
+
+fn render(grid: Vec<Vec<u8>>) -> String {
+    grid.into_iter()
+        .map(|row| String::from_utf8(row).unwrap() + "\n")
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rng() {
+        let mut rng = Rng::new(7);
+        let first: Vec<u64> = (0..4).map(|_| rng.next_u64()).collect();
+        let mut again = Rng::new(7);
+        assert_eq!((0..4).map(|_| again.next_u64()).collect::<Vec<_>>(), first);
+        assert!((0..1000)
+            .map(|_| rng.between(-3, 3))
+            .all(|n| (-3..=3).contains(&n)));
+        assert_eq!(rng.between(5, 5), 5);
+        let mut items = [1, 2, 3, 4, 5];
+        rng.shuffle(&mut items);
+        items.sort_unstable();
+        assert_eq!(items, [1, 2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn test_generators() {
+        for (day, generator) in (1..=25).zip(&GENERATORS) {
+            assert_eq!(generator.day, day);
+            let input = generator.generate(12, 42);
+            assert_eq!(
+                generator.generate(12, 42),
+                input,
+                "day {day} should be deterministic"
+            );
+            assert_ne!(
+                generator.generate(12, 43),
+                input,
+                "day {day} should depend on the seed"
+            );
+            assert!(
+                input.ends_with('\n') && !input.ends_with("\n\n"),
+                "day {day}: {input:?}"
+            );
+        }
+        assert!(generator(26).is_none());
+    }
+
+    #[test]
+    fn test_dig_plan() {
+        for columns in [1, 11, 1000] {
+            for line in dig_plan(&mut Rng::new(1), columns).lines() {
+                let (_, colour) = line.split_once('#').unwrap();
+                assert_eq!(colour.len(), "fffff0)".len(), "{columns} columns: {line}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_pipe_loop() {
+        let input = pipe_loop(&mut Rng::new(1), 4);
+        assert_eq!(input.lines().count(), 14);
+        assert_eq!(input.matches('S').count(), 1);
+        // Every block's middle is enclosed rather than part of the loop.
+        assert!(input.lines().skip(2).step_by(3).all(|row| row
+            .chars()
+            .skip(2)
+            .step_by(3)
+            .all(|tile| tile == '.')));
+    }
+
+    #[test]
+    fn test_mirror_pattern() {
+        let pattern = mirror_pattern(&mut Rng::new(3));
+        let rows: Vec<Vec<u8>> = pattern.lines().map(|line| line.bytes().collect()).collect();
+        let columns: Vec<Vec<u8>> = (0..rows[0].len())
+            .map(|x| rows.iter().map(|row| row[x]).collect())
+            .collect();
+        let errors: Vec<usize> = (1..rows.len())
+            .map(|line| reflection_errors(&rows, line))
+            .chain((1..columns.len()).map(|line| reflection_errors(&columns, line)))
+            .collect();
+        assert_eq!(errors.iter().filter(|errors| **errors == 0).count(), 1);
+        assert_eq!(errors.iter().filter(|errors| **errors == 1).count(), 1);
+    }
+}
@@ -0,0 +1,182 @@
+//! Areas of polygons with integer vertices, such as loops traced around a grid.
+
+use glam::I64Vec2;
+
+use crate::math::gcd;
+
+/// A simple polygon with integer vertices, which is implicitly closed from the last vertex back
+/// to the first.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Polygon {
+    vertices: Vec<I64Vec2>,
+}
+
+impl Polygon {
+    pub fn new(vertices: impl IntoIterator<Item = I64Vec2>) -> Self {
+        Self {
+            vertices: vertices.into_iter().collect(),
+        }
+    }
+
+    /// Traces the polygon from the origin, moving `distance` times by `direction` for each step.
+    pub fn from_steps(steps: impl IntoIterator<Item = (I64Vec2, i64)>) -> Self {
+        Self::new(
+            steps
+                .into_iter()
+                .scan(I64Vec2::ZERO, |position, (direction, distance)| {
+                    *position += direction * distance;
+                    Some(*position)
+                }),
+        )
+    }
+
+    pub fn vertices(&self) -> &[I64Vec2] {
+        &self.vertices
+    }
+
+    fn edges(&self) -> impl Iterator<Item = (I64Vec2, I64Vec2)> + '_ {
+        self.vertices
+            .iter()
+            .copied()
+            .zip(self.vertices.iter().copied().cycle().skip(1))
+    }
+
+    /// Twice the area, which is always a whole number.
+    fn doubled_area(&self) -> i128 {
+        self.edges()
+            .map(|(a, b)| i128::from(a.x) * i128::from(b.y) - i128::from(b.x) * i128::from(a.y))
+            .sum::<i128>()
+            .abs()
+    }
+
+    /// The area given by the shoelace formula, rounded down to a whole number.
+    ///
+    /// Panics if it does not fit in an `i64`; see [`Polygon::area_i128`].
+    pub fn area(&self) -> i64 {
+        narrow(self.area_i128(), "area")
+    }
+
+    pub fn area_i128(&self) -> i128 {
+        self.doubled_area() / 2
+    }
+
+    /// The number of lattice points on the edges.
+    pub fn boundary_points(&self) -> i64 {
+        narrow(self.boundary_points_i128(), "boundary")
+    }
+
+    fn boundary_points_i128(&self) -> i128 {
+        self.edges()
+            .map(|(a, b)| {
+                let edge = b - a;
+                i128::from(gcd(edge.x.unsigned_abs(), edge.y.unsigned_abs()))
+            })
+            .sum()
+    }
+
+    /// The number of lattice points strictly inside, by Pick's theorem.
+    pub fn interior_points(&self) -> i64 {
+        narrow(self.interior_points_i128(), "interior")
+    }
+
+    fn interior_points_i128(&self) -> i128 {
+        (self.doubled_area() - self.boundary_points_i128() + 2) / 2
+    }
+
+    /// The number of lattice points inside or on the edges, which is the area of the polygon
+    /// when each lattice point is a whole cell, such as a trench dug around a grid.
+    pub fn area_including_boundary(&self) -> i64 {
+        narrow(
+            self.interior_points_i128() + self.boundary_points_i128(),
+            "area including the boundary",
+        )
+    }
+}
+
+fn narrow(value: i128, what: &str) -> i64 {
+    value
+        .try_into()
+        .unwrap_or_else(|_| panic!("the {} should fit in an i64", what))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    fn polygon(vertices: &[(i64, i64)]) -> Polygon {
+        Polygon::new(vertices.iter().map(|&(x, y)| I64Vec2::new(x, y)))
+    }
+
+    #[rstest]
+    #[case(&[(0, 0), (2, 0), (2, 2), (0, 2)], 4, 8, 1)]
+    #[case(&[(0, 0), (0, 2), (2, 2), (2, 0)], 4, 8, 1)]
+    #[case(&[(0, 0), (3, 0), (0, 3)], 4, 9, 1)]
+    #[case(&[(0, 0), (4, 0), (4, 1), (1, 1), (1, 3), (0, 3)], 6, 14, 0)]
+    fn test_polygon(
+        #[case] vertices: &[(i64, i64)],
+        #[case] area: i64,
+        #[case] boundary: i64,
+        #[case] interior: i64,
+    ) {
+        let polygon = polygon(vertices);
+        assert_eq!(polygon.area(), area);
+        assert_eq!(polygon.boundary_points(), boundary);
+        assert_eq!(polygon.interior_points(), interior);
+        assert_eq!(polygon.area_including_boundary(), boundary + interior);
+    }
+
+    #[test]
+    fn test_pipe_loop() {
+        // The loop from the first day 10 example, by its corners.
+        let polygon = polygon(&[
+            (1, 1),
+            (9, 1),
+            (9, 7),
+            (6, 7),
+            (6, 5),
+            (8, 5),
+            (8, 2),
+            (2, 2),
+            (2, 5),
+            (4, 5),
+            (4, 7),
+            (1, 7),
+        ]);
+        assert_eq!(polygon.boundary_points(), 46);
+        assert_eq!(polygon.interior_points(), 4);
+    }
+
+    #[rstest]
+    #[case(&[('R', 6), ('D', 2), ('L', 6), ('U', 2)], 21)]
+    #[case(&[
+        ('R', 6), ('D', 5), ('L', 2), ('D', 2), ('R', 2), ('D', 2), ('L', 5),
+        ('U', 2), ('L', 1), ('U', 2), ('R', 2), ('U', 3), ('L', 2), ('U', 2),
+    ], 62)]
+    #[case(&[
+        ('R', 461937), ('D', 56407), ('R', 356671), ('D', 863240), ('R', 367720),
+        ('D', 266681), ('L', 577262), ('U', 829975), ('L', 112010), ('D', 829975),
+        ('L', 491645), ('U', 686074), ('L', 5411), ('U', 500254),
+    ], 952408144115)]
+    fn test_trench(#[case] steps: &[(char, i64)], #[case] expected: i64) {
+        let polygon = Polygon::from_steps(steps.iter().map(|&(direction, distance)| {
+            let direction = match direction {
+                'R' => I64Vec2::X,
+                'D' => I64Vec2::Y,
+                'L' => I64Vec2::NEG_X,
+                'U' => I64Vec2::NEG_Y,
+                _ => unreachable!(),
+            };
+            (direction, distance)
+        }));
+        assert_eq!(polygon.area_including_boundary(), expected);
+    }
+
+    #[test]
+    fn test_large_area() {
+        let side = 1 << 40;
+        let polygon = polygon(&[(0, 0), (side, 0), (side, side), (0, side)]);
+        assert_eq!(polygon.area_i128(), 1 << 80);
+        assert_eq!(polygon.boundary_points(), 4 * side);
+    }
+}
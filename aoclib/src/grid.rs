@@ -0,0 +1,428 @@
+use std::fmt::Display;
+use std::ops::{Index, IndexMut};
+
+use glam::IVec2;
+
+use crate::error::Error;
+
+/// The orthogonal offsets, clockwise from north (with y increasing downwards).
+pub const NEIGHBOURS_4: [IVec2; 4] = [
+    IVec2::new(0, -1),
+    IVec2::new(1, 0),
+    IVec2::new(0, 1),
+    IVec2::new(-1, 0),
+];
+
+/// The orthogonal and diagonal offsets, clockwise from north.
+pub const NEIGHBOURS_8: [IVec2; 8] = [
+    IVec2::new(0, -1),
+    IVec2::new(1, -1),
+    IVec2::new(1, 0),
+    IVec2::new(1, 1),
+    IVec2::new(0, 1),
+    IVec2::new(-1, 1),
+    IVec2::new(-1, 0),
+    IVec2::new(-1, -1),
+];
+
+/// A dense, row-major grid of cells, addressed by `IVec2 { x: column, y: row }`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GridError {
+    /// A row of the input was not the same width as the first row.
+    Ragged {
+        row: usize,
+        expected: usize,
+        found: usize,
+    },
+}
+
+impl Display for GridError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GridError::Ragged {
+                row,
+                expected,
+                found,
+            } => write!(
+                f,
+                "row {} has {} cells, expected {}",
+                row + 1,
+                found,
+                expected
+            ),
+        }
+    }
+}
+
+impl std::error::Error for GridError {}
+
+impl<T> Grid<T> {
+    /// Builds a grid from its cells in row-major order.
+    ///
+    /// Panics if there are not `width * height` cells.
+    pub fn new(width: usize, height: usize, cells: Vec<T>) -> Self {
+        assert_eq!(
+            cells.len(),
+            width * height,
+            "a {}x{} grid needs {} cells",
+            width,
+            height,
+            width * height
+        );
+        Self {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    /// Parses one row per line of `input`, mapping each character to a cell with `cell`.
+    pub fn parse(input: &str, mut cell: impl FnMut(char) -> T) -> Result<Self, GridError> {
+        let mut width = None;
+        let mut height = 0;
+        let mut cells = vec![];
+        for (row, line) in input.lines().enumerate() {
+            let before = cells.len();
+            cells.extend(line.chars().map(&mut cell));
+            let found = cells.len() - before;
+            match width {
+                None => width = Some(found),
+                Some(expected) if expected != found => {
+                    return Err(GridError::Ragged {
+                        row,
+                        expected,
+                        found,
+                    })
+                }
+                Some(_) => {}
+            }
+            height += 1;
+        }
+        Ok(Self::new(width.unwrap_or_default(), height, cells))
+    }
+
+    /// Parses like [`Grid::parse`], but reports an empty input, a ragged row, or any character
+    /// which `cell` rejects as a parse error, describing the characters accepted as `expected`.
+    pub fn try_parse(
+        input: &str,
+        expected: &str,
+        mut cell: impl FnMut(char) -> Option<T>,
+    ) -> Result<Self, Error> {
+        let mut width = None;
+        let mut height = 0;
+        let mut cells = vec![];
+        for line in input.lines() {
+            for (index, c) in line.char_indices() {
+                cells.push(cell(c).ok_or_else(|| Error::parse(input, &line[index..], expected))?);
+            }
+            let found = line.chars().count();
+            match width {
+                None => width = Some(found),
+                Some(expected) if found < expected => {
+                    return Err(Error::parse(
+                        input,
+                        &line[line.len()..],
+                        format!("{} more cells", expected - found),
+                    ))
+                }
+                Some(expected) if found > expected => {
+                    let (extra, _) = line.char_indices().nth(expected).expect("the row is long");
+                    return Err(Error::parse(
+                        input,
+                        &line[extra..],
+                        format!("the row to end after {} cells", expected),
+                    ));
+                }
+                Some(_) => {}
+            }
+            height += 1;
+        }
+        match width {
+            Some(width) if width > 0 => Ok(Self::new(width, height, cells)),
+            _ => Err(Error::parse(input, input, "a grid")),
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn dimensions(&self) -> IVec2 {
+        IVec2::new(self.width as i32, self.height as i32)
+    }
+
+    pub fn contains(&self, position: IVec2) -> bool {
+        (0..self.width as i32).contains(&position.x)
+            && (0..self.height as i32).contains(&position.y)
+    }
+
+    fn index_of(&self, position: IVec2) -> Option<usize> {
+        self.contains(position)
+            .then(|| position.y as usize * self.width + position.x as usize)
+    }
+
+    pub fn get(&self, position: IVec2) -> Option<&T> {
+        self.index_of(position).map(|index| &self.cells[index])
+    }
+
+    pub fn get_mut(&mut self, position: IVec2) -> Option<&mut T> {
+        self.index_of(position).map(|index| &mut self.cells[index])
+    }
+
+    /// Looks up `position` as if the grid repeated infinitely in every direction.
+    pub fn get_wrapped(&self, position: IVec2) -> &T {
+        &self[position.rem_euclid(self.dimensions())]
+    }
+
+    /// Every position, in row-major order.
+    pub fn positions(&self) -> impl Iterator<Item = IVec2> {
+        let width = self.width as i32;
+        (0..self.height as i32).flat_map(move |y| (0..width).map(move |x| IVec2::new(x, y)))
+    }
+
+    /// Every cell along with its position, in row-major order.
+    pub fn iter(&self) -> impl Iterator<Item = (IVec2, &T)> {
+        self.positions().zip(&self.cells)
+    }
+
+    /// The position of the first cell, in row-major order, for which `predicate` is true.
+    pub fn find(&self, mut predicate: impl FnMut(&T) -> bool) -> Option<IVec2> {
+        self.iter()
+            .find(|(_, cell)| predicate(cell))
+            .map(|(position, _)| position)
+    }
+
+    /// The orthogonal neighbours of `position` which are on the grid.
+    pub fn neighbours(&self, position: IVec2) -> impl Iterator<Item = (IVec2, &T)> {
+        self.offsets(position, &NEIGHBOURS_4)
+    }
+
+    /// The orthogonal and diagonal neighbours of `position` which are on the grid.
+    pub fn neighbours8(&self, position: IVec2) -> impl Iterator<Item = (IVec2, &T)> {
+        self.offsets(position, &NEIGHBOURS_8)
+    }
+
+    fn offsets<'a>(
+        &'a self,
+        position: IVec2,
+        offsets: &'static [IVec2],
+    ) -> impl Iterator<Item = (IVec2, &'a T)> {
+        offsets.iter().filter_map(move |offset| {
+            let neighbour = position + *offset;
+            self.get(neighbour).map(|cell| (neighbour, cell))
+        })
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        (0..self.height).map(|y| self.row(y))
+    }
+
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        assert!(x < self.width, "column {} is off the grid", x);
+        self.cells.iter().skip(x).step_by(self.width)
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(|x| self.column(x))
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid::new(self.width, self.height, self.cells.iter().map(f).collect())
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    pub fn filled(width: usize, height: usize, value: T) -> Self {
+        Self::new(width, height, vec![value; width * height])
+    }
+
+    /// Reflects the grid across its leading diagonal, so rows become columns.
+    pub fn transpose(&self) -> Self {
+        self.rebuild(self.height, self.width, |x, y| IVec2::new(y, x))
+    }
+
+    pub fn rotate_clockwise(&self) -> Self {
+        let height = self.height as i32;
+        self.rebuild(self.height, self.width, |x, y| {
+            IVec2::new(y, height - 1 - x)
+        })
+    }
+
+    pub fn rotate_anticlockwise(&self) -> Self {
+        let width = self.width as i32;
+        self.rebuild(self.height, self.width, |x, y| IVec2::new(width - 1 - y, x))
+    }
+
+    /// Builds a `width` by `height` grid whose cell at `(x, y)` is this grid's cell at
+    /// `source(x, y)`.
+    fn rebuild(&self, width: usize, height: usize, source: impl Fn(i32, i32) -> IVec2) -> Self {
+        let cells = (0..height as i32)
+            .flat_map(|y| (0..width as i32).map(move |x| (x, y)))
+            .map(|(x, y)| self[source(x, y)].clone())
+            .collect();
+        Self::new(width, height, cells)
+    }
+}
+
+impl<T> Index<IVec2> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, position: IVec2) -> &T {
+        self.get(position)
+            .unwrap_or_else(|| panic!("{} is off the grid", position))
+    }
+}
+
+impl<T> IndexMut<IVec2> for Grid<T> {
+    fn index_mut(&mut self, position: IVec2) -> &mut T {
+        self.get_mut(position)
+            .unwrap_or_else(|| panic!("{} is off the grid", position))
+    }
+}
+
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    fn grid() -> Grid<char> {
+        Grid::parse("abc\ndef\n", |c| c).unwrap()
+    }
+
+    #[test]
+    fn test_parse() {
+        let grid = grid();
+        assert_eq!(grid.dimensions(), IVec2::new(3, 2));
+        assert_eq!(grid[IVec2::new(2, 1)], 'f');
+        assert_eq!(
+            Grid::parse("abc\nde", |c| c),
+            Err(GridError::Ragged {
+                row: 1,
+                expected: 3,
+                found: 2
+            })
+        );
+    }
+
+    #[rstest]
+    #[case("ab\ncd\n", Ok(2))]
+    #[case("ab\nc\n", Err((2, 2, "", "1 more cells")))]
+    #[case("ab\ncda\n", Err((2, 3, "a", "the row to end after 2 cells")))]
+    #[case("ab\ncx\n", Err((2, 2, "x", "a letter from a to d")))]
+    #[case("\n", Err((1, 1, "", "a grid")))]
+    fn test_try_parse(
+        #[case] input: &str,
+        #[case] expected: Result<usize, (usize, usize, &str, &str)>,
+    ) {
+        let grid = Grid::try_parse(input, "a letter from a to d", |c| {
+            ('a'..='d').contains(&c).then_some(c)
+        });
+        assert_eq!(
+            grid.map(|grid| grid.height()),
+            expected.map_err(|(line, column, snippet, expected)| Error::Parse {
+                line,
+                column,
+                snippet: snippet.to_string(),
+                expected: expected.to_string(),
+            })
+        );
+    }
+
+    #[rstest]
+    #[case(IVec2::new(0, 0), Some('a'))]
+    #[case(IVec2::new(1, 1), Some('e'))]
+    #[case(IVec2::new(3, 0), None)]
+    #[case(IVec2::new(0, -1), None)]
+    fn test_get(#[case] position: IVec2, #[case] expected: Option<char>) {
+        assert_eq!(grid().get(position).copied(), expected);
+    }
+
+    #[rstest]
+    #[case(IVec2::new(3, 0), 'a')]
+    #[case(IVec2::new(-1, -1), 'f')]
+    #[case(IVec2::new(7, 5), 'e')]
+    fn test_get_wrapped(#[case] position: IVec2, #[case] expected: char) {
+        assert_eq!(*grid().get_wrapped(position), expected);
+    }
+
+    #[test]
+    fn test_neighbours() {
+        let grid = grid();
+        let neighbours = |position| {
+            grid.neighbours(position)
+                .map(|(_, c)| *c)
+                .collect::<String>()
+        };
+        assert_eq!(neighbours(IVec2::new(0, 0)), "bd");
+        assert_eq!(neighbours(IVec2::new(1, 1)), "bfd");
+
+        let neighbours8 = grid
+            .neighbours8(IVec2::new(1, 0))
+            .map(|(_, c)| *c)
+            .collect::<String>();
+        assert_eq!(neighbours8, "cfeda");
+    }
+
+    #[test]
+    fn test_rows_and_columns() {
+        let grid = grid();
+        assert_eq!(
+            grid.rows().collect::<Vec<_>>(),
+            [['a', 'b', 'c'], ['d', 'e', 'f']]
+        );
+        assert_eq!(
+            grid.columns()
+                .map(|column| column.collect::<String>())
+                .collect::<Vec<_>>(),
+            ["ad", "be", "cf"]
+        );
+    }
+
+    #[test]
+    fn test_transform() {
+        let grid = grid();
+        assert_eq!(grid.transpose().to_string(), "ad\nbe\ncf\n");
+        assert_eq!(grid.rotate_clockwise().to_string(), "da\neb\nfc\n");
+        assert_eq!(grid.rotate_anticlockwise().to_string(), "cf\nbe\nad\n");
+        assert_eq!(
+            grid.rotate_clockwise()
+                .rotate_clockwise()
+                .rotate_clockwise()
+                .rotate_clockwise(),
+            grid
+        );
+    }
+
+    #[test]
+    fn test_find() {
+        assert_eq!(grid().find(|&c| c == 'e'), Some(IVec2::new(1, 1)));
+        assert_eq!(grid().find(|&c| c == 'z'), None);
+    }
+}
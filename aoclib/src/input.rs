@@ -0,0 +1,216 @@
+use std::fmt::Display;
+use std::fs::File;
+use std::io::{BufRead, BufReader, Read};
+use std::path::{Path, PathBuf};
+
+/// Overrides the directory holding the `day-XX` input directories.
+pub const INPUT_DIR_VAR: &str = "AOC_INPUT_DIR";
+
+#[derive(Debug)]
+pub enum InputError {
+    Missing {
+        day: u8,
+        path: PathBuf,
+    },
+    Io {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    Stdin(std::io::Error),
+}
+
+impl Display for InputError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InputError::Missing { day, path } => {
+                write!(f, "no input for day {} at {}", day, path.display())
+            }
+            InputError::Io { path, source } => {
+                write!(f, "cannot read {}: {}", path.display(), source)
+            }
+            InputError::Stdin(source) => write!(f, "cannot read stdin: {}", source),
+        }
+    }
+}
+
+impl std::error::Error for InputError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            InputError::Missing { .. } => None,
+            InputError::Io { source, .. } | InputError::Stdin(source) => Some(source),
+        }
+    }
+}
+
+/// Where to read a day's puzzle input from.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum Source {
+    /// `day-XX/input.txt` under `$AOC_INPUT_DIR` if set, otherwise under the workspace root.
+    #[default]
+    Default,
+    /// `day-XX/input.txt` under the given directory.
+    Directory(PathBuf),
+    File(PathBuf),
+    Stdin,
+}
+
+impl Source {
+    /// `-` is stdin, anything else is a file.
+    pub fn from_arg(arg: &str) -> Self {
+        match arg {
+            "-" => Source::Stdin,
+            path => Source::File(PathBuf::from(path)),
+        }
+    }
+
+    /// Reads and normalises the input for `day`/`part`.
+    pub fn load(&self, day: u8, part: u8) -> Result<String, InputError> {
+        let raw = match self {
+            Source::Default => read_day(&default_directory(), day, part)?,
+            Source::Directory(directory) => read_day(directory, day, part)?,
+            Source::File(path) => read_file(path)?,
+            Source::Stdin => return read_stdin(),
+        };
+        Ok(normalise(&raw))
+    }
+
+    /// Opens the input for `day`/`part` to be read a piece at a time, rather than loading it,
+    /// for the readers in [`crate::stream`].
+    pub fn open(&self, day: u8, part: u8) -> Result<Box<dyn BufRead>, InputError> {
+        let path = match self {
+            Source::Default => day_path(&default_directory(), day, part),
+            Source::Directory(directory) => day_path(directory, day, part),
+            Source::File(path) => path.clone(),
+            Source::Stdin => return Ok(Box::new(std::io::stdin().lock())),
+        };
+        if !path.exists() && !matches!(self, Source::File(_)) {
+            return Err(InputError::Missing { day, path });
+        }
+        let file = File::open(&path).map_err(|source| InputError::Io { path, source })?;
+        Ok(Box::new(BufReader::new(file)))
+    }
+
+    /// What to call the input for `day`/`part` in diagnostics: its path, relative to the input
+    /// directory when it is in the default location.
+    pub fn name(&self, day: u8, part: u8) -> String {
+        match self {
+            Source::Default => {
+                let directory = default_directory();
+                let path = day_path(&directory, day, part);
+                path.strip_prefix(&directory)
+                    .unwrap_or(&path)
+                    .display()
+                    .to_string()
+            }
+            Source::Directory(directory) => day_path(directory, day, part).display().to_string(),
+            Source::File(path) => path.display().to_string(),
+            Source::Stdin => "<stdin>".to_string(),
+        }
+    }
+}
+
+/// Reads and normalises everything piped in on stdin.
+pub fn read_stdin() -> Result<String, InputError> {
+    let mut raw = String::new();
+    std::io::stdin()
+        .read_to_string(&mut raw)
+        .map_err(InputError::Stdin)?;
+    Ok(normalise(&raw))
+}
+
+/// Loads the input for `day`/`part` from the default location.
+pub fn load(day: u8, part: u8) -> Result<String, InputError> {
+    Source::Default.load(day, part)
+}
+
+/// The path of the input for `day`/`part` under `directory`. Days with a separate input for each
+/// part keep them in `inputN.txt`.
+pub fn day_path(directory: &Path, day: u8, part: u8) -> PathBuf {
+    let day_directory = directory.join(format!("day-{:02}", day));
+    let part_input = day_directory.join(format!("input{}.txt", part));
+    if part_input.exists() {
+        part_input
+    } else {
+        day_directory.join("input.txt")
+    }
+}
+
+/// Converts CRLF line endings to LF and ends the input with exactly one newline.
+pub fn normalise(raw: &str) -> String {
+    let mut input = raw.replace("\r\n", "\n");
+    input.truncate(input.trim_end_matches('\n').len());
+    input.push('\n');
+    input
+}
+
+/// `$AOC_INPUT_DIR` if set, otherwise the workspace root.
+pub fn default_directory() -> PathBuf {
+    std::env::var_os(INPUT_DIR_VAR)
+        .map(PathBuf::from)
+        .unwrap_or_else(|| Path::new(env!("CARGO_MANIFEST_DIR")).join(".."))
+}
+
+fn read_day(directory: &Path, day: u8, part: u8) -> Result<String, InputError> {
+    let path = day_path(directory, day, part);
+    if !path.exists() {
+        return Err(InputError::Missing { day, path });
+    }
+    read_file(&path)
+}
+
+fn read_file(path: &Path) -> Result<String, InputError> {
+    std::fs::read_to_string(path).map_err(|source| InputError::Io {
+        path: path.to_path_buf(),
+        source,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("1\n2", "1\n2\n")]
+    #[case("1\r\n2\r\n", "1\n2\n")]
+    #[case("1\n2\n\n\n", "1\n2\n")]
+    #[case("", "\n")]
+    fn test_normalise(#[case] raw: &str, #[case] expected: &str) {
+        assert_eq!(normalise(raw), expected);
+    }
+
+    #[test]
+    fn test_day_path() {
+        let root = Path::new(env!("CARGO_MANIFEST_DIR")).join("..");
+        assert_eq!(day_path(&root, 1, 2), root.join("day-01/input2.txt"));
+        assert_eq!(day_path(&root, 7, 2), root.join("day-07/input.txt"));
+    }
+
+    #[test]
+    fn test_name() {
+        assert_eq!(Source::Default.name(7, 1), "day-07/input.txt");
+        assert_eq!(
+            Source::Directory(PathBuf::from("/inputs")).name(7, 1),
+            "/inputs/day-07/input.txt"
+        );
+        assert_eq!(Source::from_arg("example.txt").name(7, 1), "example.txt");
+        assert_eq!(Source::from_arg("-").name(7, 1), "<stdin>");
+    }
+
+    #[test]
+    fn test_missing() {
+        let source = Source::Directory(PathBuf::from("/nonexistent"));
+        assert!(matches!(
+            source.load(7, 1),
+            Err(InputError::Missing { day: 7, .. })
+        ));
+        assert!(matches!(
+            source.open(7, 1),
+            Err(InputError::Missing { day: 7, .. })
+        ));
+        assert!(matches!(
+            Source::from_arg("/nonexistent.txt").open(7, 1),
+            Err(InputError::Io { .. })
+        ));
+    }
+}
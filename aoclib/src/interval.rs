@@ -0,0 +1,493 @@
+//! Intervals of integers, normalised sets of them, and boxes built from one interval per axis.
+
+use std::cmp::{max, min};
+use std::fmt::Debug;
+use std::ops::{Range, RangeInclusive};
+
+/// An integer type which intervals can be built from.
+pub trait Bound: Copy + Ord + Debug {
+    /// The number of values in `start..end`, which is zero if `end <= start`.
+    fn width(start: Self, end: Self) -> u64;
+
+    /// `self + by`, or `None` if that is out of range.
+    fn checked_offset(self, by: i64) -> Option<Self>;
+
+    /// `self + 1`, or `None` if that is out of range.
+    fn successor(self) -> Option<Self>;
+}
+
+macro_rules! impl_bound {
+    ($($t:ty),*) => {
+        $(
+            impl Bound for $t {
+                fn width(start: Self, end: Self) -> u64 {
+                    if end <= start {
+                        0
+                    } else {
+                        (end as i128 - start as i128) as u64
+                    }
+                }
+
+                fn checked_offset(self, by: i64) -> Option<Self> {
+                    (self as i128 + by as i128).try_into().ok()
+                }
+
+                fn successor(self) -> Option<Self> {
+                    self.checked_add(1)
+                }
+            }
+        )*
+    };
+}
+
+impl_bound!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize);
+
+/// The half-open interval `start..end`, which is empty when `end <= start`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Interval<T> {
+    pub start: T,
+    pub end: T,
+}
+
+impl<T: Bound> Interval<T> {
+    pub fn new(start: T, end: T) -> Self {
+        Self { start, end }
+    }
+
+    /// The interval `start..=end`.
+    ///
+    /// Panics if `end` is the largest value of `T`, as the interval could not be half-open.
+    pub fn inclusive(start: T, end: T) -> Self {
+        let end = end
+            .successor()
+            .unwrap_or_else(|| panic!("{:?}..={:?} has no half-open equivalent", start, end));
+        Self::new(start, end)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.end <= self.start
+    }
+
+    /// The number of values, which always fits in a `u64` even when the interval spans all of
+    /// an `i64`.
+    pub fn len(&self) -> u64 {
+        T::width(self.start, self.end)
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        self.start <= value && value < self.end
+    }
+
+    pub fn intersects(&self, other: &Self) -> bool {
+        !self.intersection(other).is_empty()
+    }
+
+    /// The values in both intervals, which may be empty.
+    pub fn intersection(&self, other: &Self) -> Self {
+        Self::new(max(self.start, other.start), min(self.end, other.end))
+    }
+
+    /// The values in this interval but not in `other`.
+    pub fn difference(&self, other: &Self) -> IntervalSet<T> {
+        let (below, _) = self.split_at(other.start);
+        let (_, above) = self.split_at(other.end);
+        [below, above].into_iter().collect()
+    }
+
+    /// Splits into the values below `at` and the values from `at` upwards, either of which may
+    /// be empty.
+    pub fn split_at(&self, at: T) -> (Self, Self) {
+        (
+            Self::new(self.start, min(at, self.end)),
+            Self::new(max(at, self.start), self.end),
+        )
+    }
+
+    /// Moves every value by `by`, or returns `None` if any would be out of range.
+    pub fn shift(&self, by: i64) -> Option<Self> {
+        Some(Self::new(
+            self.start.checked_offset(by)?,
+            self.end.checked_offset(by)?,
+        ))
+    }
+
+    pub fn range(&self) -> Range<T> {
+        self.start..self.end
+    }
+}
+
+impl<T: Bound> From<Range<T>> for Interval<T> {
+    fn from(range: Range<T>) -> Self {
+        Self::new(range.start, range.end)
+    }
+}
+
+impl<T: Bound> From<RangeInclusive<T>> for Interval<T> {
+    fn from(range: RangeInclusive<T>) -> Self {
+        Self::inclusive(*range.start(), *range.end())
+    }
+}
+
+impl<T: Bound> IntoIterator for Interval<T>
+where
+    Range<T>: Iterator<Item = T>,
+{
+    type Item = T;
+    type IntoIter = Range<T>;
+
+    fn into_iter(self) -> Range<T> {
+        self.range()
+    }
+}
+
+/// A set of values stored as sorted intervals, none of which are empty, overlap, or touch.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct IntervalSet<T> {
+    intervals: Vec<Interval<T>>,
+}
+
+impl<T: Bound> IntervalSet<T> {
+    pub fn new() -> Self {
+        Self { intervals: vec![] }
+    }
+
+    /// Adds every value in `interval`, merging it with any intervals it overlaps or touches.
+    pub fn insert(&mut self, interval: Interval<T>) {
+        if interval.is_empty() {
+            return;
+        }
+        let first = self
+            .intervals
+            .partition_point(|existing| existing.end < interval.start);
+        let last = self
+            .intervals
+            .partition_point(|existing| existing.start <= interval.end);
+        let mut merged = interval;
+        if first < last {
+            merged.start = min(merged.start, self.intervals[first].start);
+            merged.end = max(merged.end, self.intervals[last - 1].end);
+        }
+        self.intervals.splice(first..last, [merged]);
+    }
+
+    pub fn union(&self, other: &Self) -> Self {
+        let mut union = self.clone();
+        union.extend(other.iter());
+        union
+    }
+
+    pub fn intersection(&self, other: &Self) -> Self {
+        let mut intervals = vec![];
+        let (mut i, mut j) = (0, 0);
+        while let (Some(a), Some(b)) = (self.intervals.get(i), other.intervals.get(j)) {
+            let both = a.intersection(b);
+            if !both.is_empty() {
+                intervals.push(both);
+            }
+            if a.end < b.end {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+        Self { intervals }
+    }
+
+    /// The values in this set but not in `other`.
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut intervals = vec![];
+        for interval in self.iter() {
+            let mut rest = interval;
+            for removed in other.iter().filter(|removed| removed.intersects(&interval)) {
+                let (below, _) = rest.split_at(removed.start);
+                let (_, above) = rest.split_at(removed.end);
+                if !below.is_empty() {
+                    intervals.push(below);
+                }
+                rest = above;
+            }
+            if !rest.is_empty() {
+                intervals.push(rest);
+            }
+        }
+        Self { intervals }
+    }
+
+    /// Splits into the values below `at` and the values from `at` upwards.
+    pub fn split_at(&self, at: T) -> (Self, Self) {
+        let (below, above): (Vec<_>, Vec<_>) =
+            self.iter().map(|interval| interval.split_at(at)).unzip();
+        (below.into_iter().collect(), above.into_iter().collect())
+    }
+
+    /// Moves every value by `by`, or returns `None` if any would be out of range.
+    pub fn shift(&self, by: i64) -> Option<Self> {
+        let intervals = self
+            .iter()
+            .map(|interval| interval.shift(by))
+            .collect::<Option<_>>()?;
+        Some(Self { intervals })
+    }
+
+    /// The number of values, which always fits in a `u64` as the intervals are disjoint.
+    pub fn len(&self) -> u64 {
+        self.iter().map(|interval| interval.len()).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        let index = self
+            .intervals
+            .partition_point(|interval| interval.end <= value);
+        self.intervals
+            .get(index)
+            .is_some_and(|interval| interval.contains(value))
+    }
+
+    /// The smallest value in the set.
+    pub fn min(&self) -> Option<T> {
+        self.intervals.first().map(|interval| interval.start)
+    }
+
+    /// The intervals, in ascending order.
+    pub fn iter(&self) -> impl Iterator<Item = Interval<T>> + '_ {
+        self.intervals.iter().copied()
+    }
+
+    /// Every value, in ascending order.
+    pub fn values(&self) -> impl Iterator<Item = T> + '_
+    where
+        Range<T>: Iterator<Item = T>,
+    {
+        self.iter().flatten()
+    }
+}
+
+impl<T: Bound> Default for IntervalSet<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Bound> From<Interval<T>> for IntervalSet<T> {
+    fn from(interval: Interval<T>) -> Self {
+        [interval].into_iter().collect()
+    }
+}
+
+impl<T: Bound> Extend<Interval<T>> for IntervalSet<T> {
+    fn extend<I: IntoIterator<Item = Interval<T>>>(&mut self, intervals: I) {
+        for interval in intervals {
+            self.insert(interval);
+        }
+    }
+}
+
+impl<T: Bound> FromIterator<Interval<T>> for IntervalSet<T> {
+    fn from_iter<I: IntoIterator<Item = Interval<T>>>(intervals: I) -> Self {
+        let mut set = Self::new();
+        set.extend(intervals);
+        set
+    }
+}
+
+/// An axis-aligned box in `N` dimensions, which is empty if any of its axes are.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Cuboid<T, const N: usize> {
+    pub axes: [Interval<T>; N],
+}
+
+impl<T: Bound, const N: usize> Cuboid<T, N> {
+    pub fn new(axes: [Interval<T>; N]) -> Self {
+        Self { axes }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.axes.iter().any(Interval::is_empty)
+    }
+
+    /// The number of points inside.
+    ///
+    /// Panics if it does not fit in a `u64`.
+    pub fn volume(&self) -> u64 {
+        self.axes
+            .iter()
+            .try_fold(1_u64, |volume, axis| volume.checked_mul(axis.len()))
+            .unwrap_or_else(|| panic!("the volume of {:?} should fit in a u64", self))
+    }
+
+    pub fn contains(&self, point: [T; N]) -> bool {
+        self.axes
+            .iter()
+            .zip(point)
+            .all(|(axis, value)| axis.contains(value))
+    }
+
+    pub fn intersects(&self, other: &Self) -> bool {
+        !self.intersection(other).is_empty()
+    }
+
+    /// The points in both boxes, which may be empty.
+    pub fn intersection(&self, other: &Self) -> Self {
+        Self::new(std::array::from_fn(|axis| {
+            self.axes[axis].intersection(&other.axes[axis])
+        }))
+    }
+
+    /// Splits into the points whose value along `axis` is below `at` and those where it is `at`
+    /// or above, either of which may be empty.
+    pub fn split_at(&self, axis: usize, at: T) -> (Self, Self) {
+        let (below, above) = self.axes[axis].split_at(at);
+        let (mut lower, mut upper) = (*self, *self);
+        lower.axes[axis] = below;
+        upper.axes[axis] = above;
+        (lower, upper)
+    }
+
+    /// Moves the box by `by` along each axis, or returns `None` if it would be out of range.
+    pub fn shift(&self, by: [i64; N]) -> Option<Self> {
+        let mut shifted = *self;
+        for (axis, by) in shifted.axes.iter_mut().zip(by) {
+            *axis = axis.shift(by)?;
+        }
+        Some(shifted)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    fn set(intervals: &[(i64, i64)]) -> IntervalSet<i64> {
+        intervals
+            .iter()
+            .map(|&(start, end)| Interval::new(start, end))
+            .collect()
+    }
+
+    #[test]
+    fn test_interval() {
+        let interval = Interval::from(3..=7_u32);
+        assert_eq!(interval, Interval::new(3, 8));
+        assert_eq!(interval.len(), 5);
+        assert!(interval.contains(7));
+        assert!(!interval.contains(8));
+        assert_eq!(Interval::new(5, 2).len(), 0);
+        assert_eq!(Interval::new(i64::MIN, i64::MAX).len(), u64::MAX);
+        assert_eq!(interval.into_iter().collect::<Vec<_>>(), [3, 4, 5, 6, 7]);
+    }
+
+    #[rstest]
+    #[case(0, (3, 0), (3, 8))]
+    #[case(5, (3, 5), (5, 8))]
+    #[case(10, (3, 8), (10, 8))]
+    fn test_interval_split_at(
+        #[case] at: u32,
+        #[case] below: (u32, u32),
+        #[case] above: (u32, u32),
+    ) {
+        assert_eq!(
+            Interval::new(3, 8).split_at(at),
+            (
+                Interval::new(below.0, below.1),
+                Interval::new(above.0, above.1)
+            )
+        );
+    }
+
+    #[rstest]
+    #[case(0..10, 3..5, &[(0, 3), (5, 10)])]
+    #[case(0..10, 0..5, &[(5, 10)])]
+    #[case(0..10, 10..20, &[(0, 10)])]
+    #[case(0..10, -5..15, &[])]
+    fn test_interval_difference(
+        #[case] lhs: Range<i64>,
+        #[case] rhs: Range<i64>,
+        #[case] expected: &[(i64, i64)],
+    ) {
+        assert_eq!(Interval::from(lhs).difference(&rhs.into()), set(expected));
+    }
+
+    #[test]
+    fn test_interval_shift() {
+        assert_eq!(
+            Interval::new(5_u64, 10).shift(-5),
+            Some(Interval::new(0, 5))
+        );
+        assert_eq!(Interval::new(5_u64, 10).shift(-6), None);
+        assert_eq!(
+            Interval::new(0_u8, 250).shift(5),
+            Some(Interval::new(5, 255))
+        );
+    }
+
+    #[rstest]
+    #[case(&[(0, 5), (5, 10)], &[(0, 10)])]
+    #[case(&[(8, 10), (0, 2), (4, 6)], &[(0, 2), (4, 6), (8, 10)])]
+    #[case(&[(0, 2), (4, 6), (8, 10), (1, 9)], &[(0, 10)])]
+    #[case(&[(0, 2), (3, 3), (4, 6)], &[(0, 2), (4, 6)])]
+    fn test_set_insert(#[case] intervals: &[(i64, i64)], #[case] expected: &[(i64, i64)]) {
+        assert_eq!(
+            set(intervals)
+                .iter()
+                .map(|interval| (interval.start, interval.end))
+                .collect::<Vec<_>>(),
+            expected
+        );
+    }
+
+    #[test]
+    fn test_set_operations() {
+        let lhs = set(&[(0, 10), (20, 30)]);
+        let rhs = set(&[(5, 25), (28, 40)]);
+        assert_eq!(lhs.union(&rhs), set(&[(0, 40)]));
+        assert_eq!(lhs.intersection(&rhs), set(&[(5, 10), (20, 25), (28, 30)]));
+        assert_eq!(lhs.difference(&rhs), set(&[(0, 5), (25, 28)]));
+        assert_eq!(rhs.difference(&lhs), set(&[(10, 20), (30, 40)]));
+        assert_eq!(
+            lhs.split_at(25),
+            (set(&[(0, 10), (20, 25)]), set(&[(25, 30)]))
+        );
+        assert_eq!(lhs.shift(-10), Some(set(&[(-10, 0), (10, 20)])));
+        assert_eq!(lhs.len(), 20);
+        assert_eq!(lhs.min(), Some(0));
+        assert_eq!(
+            lhs.values().filter(|v| v % 7 == 0).collect::<Vec<_>>(),
+            [0, 7, 21, 28]
+        );
+    }
+
+    #[rstest]
+    #[case(-1, false)]
+    #[case(0, true)]
+    #[case(9, true)]
+    #[case(10, false)]
+    #[case(20, true)]
+    #[case(30, false)]
+    fn test_set_contains(#[case] value: i64, #[case] expected: bool) {
+        assert_eq!(set(&[(0, 10), (20, 30)]).contains(value), expected);
+    }
+
+    #[test]
+    fn test_cuboid() {
+        let xmas = Cuboid::new([Interval::inclusive(1_u32, 4000); 4]);
+        assert_eq!(xmas.volume(), 256000000000000);
+
+        let (lower, upper) = xmas.split_at(1, 2091);
+        assert_eq!(lower.axes[1], Interval::new(1, 2091));
+        assert_eq!(upper.axes[1], Interval::new(2091, 4001));
+        assert_eq!(lower.volume() + upper.volume(), xmas.volume());
+        assert!(!lower.intersects(&upper));
+
+        let brick = Cuboid::new([(0..3).into(), (0..1).into(), (1..2).into()]);
+        let below = brick.shift([0, 0, -1]).unwrap();
+        assert!(brick.contains([2, 0, 1]));
+        assert!(!brick.intersects(&below));
+        assert_eq!(brick.intersection(&below).volume(), 0);
+        assert!(brick.intersects(&brick.shift([1, 0, 0]).unwrap()));
+    }
+}
@@ -1,12 +1,30 @@
-use std::fs::File;
-use std::io::BufRead;
-use std::io::BufReader;
+pub mod answers;
+pub mod bench;
+pub mod budget;
+pub mod cycle;
+pub mod direction;
+pub mod dot;
+pub mod error;
+pub mod example;
+pub mod gen;
+pub mod geometry;
+pub mod grid;
+pub mod input;
+pub mod interval;
+pub mod math;
+pub mod parallel;
+pub mod params;
+pub mod parse;
+pub mod reference;
+pub mod search;
+pub mod solution;
+pub mod solver;
+pub mod stream;
+pub mod viz;
 
-pub fn read_file_line_by_line(filepath: &str) -> Vec<String> {
-    let file = match File::open(filepath) {
-        Ok(file) => file,
-        Err(e) => panic!("Panic! {}", e),
-    };
-    let reader = BufReader::new(file);
-    reader.lines().map(|l| l.unwrap()).collect()
-}
+pub use direction::{Direction, Direction8};
+pub use error::Error;
+pub use grid::Grid;
+pub use params::Params;
+pub use solution::{Answer, FromParams, Solution};
+pub use solver::Solver;
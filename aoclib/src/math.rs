@@ -0,0 +1,143 @@
+//! Number theory for the puzzles whose answer is when several cycles line up.
+
+use std::borrow::Borrow;
+
+pub fn gcd(a: u64, b: u64) -> u64 {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+/// The lowest common multiple of `a` and `b`, or `None` if it overflows.
+pub fn lcm(a: u64, b: u64) -> Option<u64> {
+    if a == 0 || b == 0 {
+        return Some(0);
+    }
+    (a / gcd(a, b)).checked_mul(b)
+}
+
+/// The greatest common divisor of every value, which is zero if there are none.
+pub fn gcd_all<T: Borrow<u64>>(values: impl IntoIterator<Item = T>) -> u64 {
+    values
+        .into_iter()
+        .fold(0, |result, value| gcd(result, *value.borrow()))
+}
+
+/// The lowest common multiple of every value, which is one if there are none, or `None` if it
+/// overflows.
+pub fn lcm_all<T: Borrow<u64>>(values: impl IntoIterator<Item = T>) -> Option<u64> {
+    values
+        .into_iter()
+        .try_fold(1, |result, value| lcm(result, *value.borrow()))
+}
+
+/// Returns `(g, x, y)` such that `a * x + b * y == g`, where `g` is the greatest common divisor
+/// of `a` and `b`.
+pub fn extended_gcd(a: i64, b: i64) -> (i64, i64, i64) {
+    let (g, x, y) = extended_gcd_i128(a.into(), b.into());
+    (g as i64, x as i64, y as i64)
+}
+
+fn extended_gcd_i128(a: i128, b: i128) -> (i128, i128, i128) {
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_s, mut s) = (1, 0);
+    let (mut old_t, mut t) = (0, 1);
+    while r != 0 {
+        let quotient = old_r / r;
+        (old_r, r) = (r, old_r - quotient * r);
+        (old_s, s) = (s, old_s - quotient * s);
+        (old_t, t) = (t, old_t - quotient * t);
+    }
+    if old_r < 0 {
+        (-old_r, -old_s, -old_t)
+    } else {
+        (old_r, old_s, old_t)
+    }
+}
+
+/// The `x` in `0..modulus` with `a * x ≡ 1 (mod modulus)`, if `a` and `modulus` are coprime.
+pub fn mod_inverse(a: i64, modulus: i64) -> Option<i64> {
+    if modulus <= 0 {
+        return None;
+    }
+    let (g, x, _) = extended_gcd_i128(a.into(), modulus.into());
+    (g == 1).then(|| x.rem_euclid(modulus.into()) as i64)
+}
+
+/// Solves the simultaneous congruences `x ≡ residue (mod modulus)`, whose moduli need not be
+/// coprime. Returns `(x, m)` where every solution is `x` plus a multiple of `m`, with `x` in
+/// `0..m`, or `None` if the congruences contradict each other or `m` does not fit in an `i64`.
+pub fn crt(congruences: impl IntoIterator<Item = (i64, i64)>) -> Option<(i64, i64)> {
+    let (x, m) =
+        congruences
+            .into_iter()
+            .try_fold((0_i128, 1_i128), |(x, m), (residue, modulus)| {
+                if modulus <= 0 {
+                    return None;
+                }
+                let (residue, modulus) = (i128::from(residue), i128::from(modulus));
+                let (g, p, _) = extended_gcd_i128(m, modulus);
+                let difference = residue - x;
+                if difference % g != 0 {
+                    return None;
+                }
+                let step = modulus / g;
+                let combined = m.checked_mul(step)?;
+                i64::try_from(combined).ok()?;
+                let k = (difference / g % step * p % step).rem_euclid(step);
+                Some(((x + m * k).rem_euclid(combined), combined))
+            })?;
+    Some((x as i64, m as i64))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case(&[], 0, Some(1))]
+    #[case(&[4, 6], 2, Some(12))]
+    #[case(&[12, 18, 30], 6, Some(180))]
+    #[case(&[u64::MAX, u64::MAX - 1], 1, None)]
+    fn test_gcd_and_lcm(
+        #[case] values: &[u64],
+        #[case] expected_gcd: u64,
+        #[case] expected_lcm: Option<u64>,
+    ) {
+        assert_eq!(gcd_all(values), expected_gcd);
+        assert_eq!(lcm_all(values), expected_lcm);
+        assert_eq!(lcm_all(values.iter().copied()), expected_lcm);
+    }
+
+    #[rstest]
+    #[case(240, 46)]
+    #[case(-7, 3)]
+    #[case(0, 5)]
+    fn test_extended_gcd(#[case] a: i64, #[case] b: i64) {
+        let (g, x, y) = extended_gcd(a, b);
+        assert_eq!(g, gcd(a.unsigned_abs(), b.unsigned_abs()) as i64);
+        assert_eq!(a * x + b * y, g);
+    }
+
+    #[rstest]
+    #[case(3, 11, Some(4))]
+    #[case(-3, 11, Some(7))]
+    #[case(4, 8, None)]
+    fn test_mod_inverse(#[case] a: i64, #[case] modulus: i64, #[case] expected: Option<i64>) {
+        assert_eq!(mod_inverse(a, modulus), expected);
+    }
+
+    #[rstest]
+    #[case(&[(2, 3), (3, 5), (2, 7)], Some((23, 105)))]
+    #[case(&[(3, 4), (5, 6)], Some((11, 12)))]
+    #[case(&[(1, 4), (2, 6)], None)]
+    #[case(&[(-1, 5)], Some((4, 5)))]
+    #[case(&[], Some((0, 1)))]
+    #[case(&[(0, i64::MAX), (1, i64::MAX - 1)], None)]
+    fn test_crt(#[case] congruences: &[(i64, i64)], #[case] expected: Option<(i64, i64)>) {
+        assert_eq!(crt(congruences.iter().copied()), expected);
+    }
+}
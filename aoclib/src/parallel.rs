@@ -0,0 +1,57 @@
+//! Iteration over independent pieces of work, such as the entry points into a grid, which runs
+//! on rayon's thread pool when the `parallel` feature is enabled and sequentially otherwise.
+//!
+//! Bring the prelude into scope and call [`IntoMaybeParallelIterator::into_maybe_par_iter`] in
+//! place of `into_iter`; the adaptors which rayon and `std` share, such as `map`, `sum` and `max`,
+//! then work either way.
+
+pub mod prelude {
+    pub use super::IntoMaybeParallelIterator;
+    #[cfg(feature = "parallel")]
+    pub use rayon::iter::ParallelIterator;
+}
+
+pub trait IntoMaybeParallelIterator {
+    type Iter;
+
+    fn into_maybe_par_iter(self) -> Self::Iter;
+}
+
+#[cfg(feature = "parallel")]
+impl<T: rayon::iter::IntoParallelIterator> IntoMaybeParallelIterator for T {
+    type Iter = T::Iter;
+
+    fn into_maybe_par_iter(self) -> Self::Iter {
+        self.into_par_iter()
+    }
+}
+
+#[cfg(not(feature = "parallel"))]
+impl<T: IntoIterator> IntoMaybeParallelIterator for T {
+    type Iter = T::IntoIter;
+
+    fn into_maybe_par_iter(self) -> Self::Iter {
+        self.into_iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::prelude::*;
+
+    #[test]
+    fn test_into_maybe_par_iter() {
+        let values: Vec<u64> = (1..=100).collect();
+        assert_eq!(
+            values
+                .clone()
+                .into_maybe_par_iter()
+                .map(|value| value * value)
+                .sum::<u64>(),
+            338350
+        );
+        assert_eq!(values.into_maybe_par_iter().max(), Some(100));
+        let results: Result<Vec<u64>, String> = (1..=3u64).into_maybe_par_iter().map(Ok).collect();
+        assert_eq!(results, Ok(vec![1, 2, 3]));
+    }
+}
@@ -0,0 +1,130 @@
+use std::collections::BTreeMap;
+use std::fmt::Display;
+use std::str::FromStr;
+
+/// Named, string-valued parameters for solvers which need more than the puzzle input,
+/// e.g. the number of steps for day 21.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Params(BTreeMap<String, String>);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParamError {
+    Malformed(String),
+    Missing(String),
+    Unknown(String),
+    Invalid { key: String, value: String },
+}
+
+impl Display for ParamError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParamError::Malformed(value) => {
+                write!(
+                    f,
+                    "expected a parameter of the form key=value, got '{}'",
+                    value
+                )
+            }
+            ParamError::Missing(key) => write!(f, "missing parameter '{}'", key),
+            ParamError::Unknown(key) => write!(f, "unknown parameter '{}'", key),
+            ParamError::Invalid { key, value } => {
+                write!(f, "invalid value '{}' for parameter '{}'", value, key)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ParamError {}
+
+impl Params {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn from_pairs<'a>(pairs: impl IntoIterator<Item = &'a (&'a str, &'a str)>) -> Self {
+        Self(
+            pairs
+                .into_iter()
+                .map(|(key, value)| (key.to_string(), value.to_string()))
+                .collect(),
+        )
+    }
+
+    pub fn set(&mut self, key: impl Into<String>, value: impl Into<String>) {
+        self.0.insert(key.into(), value.into());
+    }
+
+    pub fn contains(&self, key: &str) -> bool {
+        self.0.contains_key(key)
+    }
+
+    pub fn get<T: FromStr>(&self, key: &str) -> Result<T, ParamError> {
+        let value = self
+            .0
+            .get(key)
+            .ok_or_else(|| ParamError::Missing(key.to_string()))?;
+        value.parse().map_err(|_| ParamError::Invalid {
+            key: key.to_string(),
+            value: value.clone(),
+        })
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.0
+            .iter()
+            .map(|(key, value)| (key.as_str(), value.as_str()))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+/// Parses a single `key=value` assignment, as given on the command line.
+pub fn parse_assignment(assignment: &str) -> Result<(String, String), ParamError> {
+    match assignment.split_once('=') {
+        Some((key, value)) if !key.is_empty() => {
+            Ok((key.trim().to_string(), value.trim().to_string()))
+        }
+        _ => Err(ParamError::Malformed(assignment.to_string())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_get() {
+        let params = Params::from_pairs(&[("steps", "64"), ("lower_bound", "7.5")]);
+        assert_eq!(params.get::<usize>("steps"), Ok(64));
+        assert_eq!(params.get::<f64>("lower_bound"), Ok(7.5));
+        assert_eq!(
+            params.get::<usize>("upper_bound"),
+            Err(ParamError::Missing("upper_bound".to_string()))
+        );
+        assert_eq!(
+            params.get::<usize>("lower_bound"),
+            Err(ParamError::Invalid {
+                key: "lower_bound".to_string(),
+                value: "7.5".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_assignment() {
+        assert_eq!(
+            parse_assignment("steps=100"),
+            Ok(("steps".to_string(), "100".to_string()))
+        );
+        assert_eq!(
+            parse_assignment("steps"),
+            Err(ParamError::Malformed("steps".to_string()))
+        );
+        assert_eq!(
+            parse_assignment("=100"),
+            Err(ParamError::Malformed("=100".to_string()))
+        );
+    }
+}
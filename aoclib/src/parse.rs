@@ -0,0 +1,233 @@
+//! A shared layer for the nom parsers. The input is a [`Span`], which tracks its line and column,
+//! and failures are a [`ParseError`], which records what was expected and what was being parsed,
+//! so that they convert to an [`Error`] pointing at the offending column.
+
+use std::cmp::Ordering;
+
+use nom::error::{ContextError, ErrorKind, FromExternalError};
+use nom::{Compare, CompareResult, IResult, InputTake};
+use nom_locate::LocatedSpan;
+
+use crate::error::{snippet, Error};
+
+/// Input which knows where it is in the whole of the puzzle input.
+pub type Span<'a> = LocatedSpan<&'a str>;
+
+pub type ParseResult<'a, T> = IResult<Span<'a>, T, ParseError<'a>>;
+
+/// Why a parser failed. Like nom's `VerboseError` it keeps the contexts added by enclosing
+/// parsers, but only for the failure which got furthest through the input.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseError<'a> {
+    at: Span<'a>,
+    expected: Vec<String>,
+    /// Innermost first.
+    context: Vec<&'static str>,
+}
+
+impl<'a> ParseError<'a> {
+    pub fn new(at: Span<'a>, expected: impl Into<String>) -> Self {
+        Self {
+            at,
+            expected: vec![expected.into()],
+            context: vec![],
+        }
+    }
+}
+
+impl<'a> nom::error::ParseError<Span<'a>> for ParseError<'a> {
+    fn from_error_kind(input: Span<'a>, kind: ErrorKind) -> Self {
+        Self::new(input, describe(kind))
+    }
+
+    fn append(_: Span<'a>, _: ErrorKind, other: Self) -> Self {
+        other
+    }
+
+    fn from_char(input: Span<'a>, c: char) -> Self {
+        Self::new(input, format!("{:?}", c))
+    }
+
+    /// Keeps whichever alternative got further, or everything either expected if they failed
+    /// at the same place.
+    fn or(self, other: Self) -> Self {
+        match self.at.location_offset().cmp(&other.at.location_offset()) {
+            Ordering::Greater => self,
+            Ordering::Less => other,
+            Ordering::Equal => {
+                let mut error = self;
+                for expected in other.expected {
+                    if !error.expected.contains(&expected) {
+                        error.expected.push(expected);
+                    }
+                }
+                error
+            }
+        }
+    }
+}
+
+impl<'a> ContextError<Span<'a>> for ParseError<'a> {
+    fn add_context(_: Span<'a>, context: &'static str, mut other: Self) -> Self {
+        other.context.push(context);
+        other
+    }
+}
+
+impl<'a, E> FromExternalError<Span<'a>, E> for ParseError<'a> {
+    fn from_external_error(input: Span<'a>, kind: ErrorKind, _: E) -> Self {
+        Self::new(input, describe(kind))
+    }
+}
+
+impl From<ParseError<'_>> for Error {
+    fn from(error: ParseError<'_>) -> Self {
+        let mut expected = error.expected.join(" or ");
+        if let Some(context) = error.context.first() {
+            expected = format!("{} in {}", expected, context);
+        }
+        Error::Parse {
+            line: error.at.location_line() as usize,
+            column: error.at.get_utf8_column(),
+            snippet: snippet(error.at.fragment()),
+            expected,
+        }
+    }
+}
+
+fn describe(kind: ErrorKind) -> String {
+    match kind {
+        ErrorKind::Digit => "a number".to_string(),
+        ErrorKind::Alpha => "a letter".to_string(),
+        ErrorKind::AlphaNumeric => "a letter or a digit".to_string(),
+        ErrorKind::Space | ErrorKind::MultiSpace => "whitespace".to_string(),
+        ErrorKind::CrLf => "the end of the line".to_string(),
+        ErrorKind::Eof => "the end of the input".to_string(),
+        kind => kind.description().to_string(),
+    }
+}
+
+/// Like nom's `tag`, but a failure says which tag was expected.
+pub fn tag<'a>(tag: &'static str) -> impl FnMut(Span<'a>) -> ParseResult<'a, Span<'a>> {
+    move |input: Span<'a>| match input.compare(tag) {
+        CompareResult::Ok => Ok(input.take_split(tag.len())),
+        _ => Err(nom::Err::Error(ParseError::new(
+            input,
+            format!("{:?}", tag),
+        ))),
+    }
+}
+
+/// Parses every line of the input with `parser`, which must consume the whole line.
+pub fn lines<'a, T>(
+    mut parser: impl FnMut(Span<'a>) -> ParseResult<'a, T>,
+) -> impl FnMut(Span<'a>) -> ParseResult<'a, Vec<T>> {
+    move |mut input: Span<'a>| {
+        let mut items = vec![];
+        while !input.fragment().is_empty() {
+            let (rest, item) = parser(input)?;
+            (input, _) = end_of_line(rest)?;
+            items.push(item);
+        }
+        Ok((input, items))
+    }
+}
+
+/// A newline, or the end of the input.
+fn end_of_line(input: Span) -> ParseResult<()> {
+    if input.fragment().is_empty() {
+        Ok((input, ()))
+    } else if input.fragment().starts_with('\n') {
+        Ok((input.take_split(1).0, ()))
+    } else {
+        Err(nom::Err::Error(ParseError::new(
+            input,
+            "the end of the line",
+        )))
+    }
+}
+
+/// Runs `parser` over the start of `input`, returning what it parsed and the input it left.
+pub fn parse_prefix<'a, T>(
+    input: &'a str,
+    mut parser: impl FnMut(Span<'a>) -> ParseResult<'a, T>,
+) -> Result<(Span<'a>, T), Error> {
+    parser(Span::new(input)).map_err(|error| match error {
+        nom::Err::Incomplete(_) => Error::parse(input, &input[input.len()..], "more input"),
+        nom::Err::Error(error) | nom::Err::Failure(error) => error.into(),
+    })
+}
+
+/// Runs `parser` over `input`, which it must consume apart from any trailing newlines.
+pub fn parse_all<'a, T>(
+    input: &'a str,
+    parser: impl FnMut(Span<'a>) -> ParseResult<'a, T>,
+) -> Result<T, Error> {
+    let (rest, output) = parse_prefix(input, parser)?;
+    let trailing = rest.fragment().trim_start_matches('\n');
+    if !trailing.is_empty() {
+        let (at, _) = rest.take_split(rest.fragment().len() - trailing.len());
+        return Err(ParseError::new(at, "the end of the input").into());
+    }
+    Ok(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nom::branch::alt;
+    use nom::character::complete::{self, alpha1};
+    use nom::error::context;
+    use nom::sequence::separated_pair;
+    use rstest::rstest;
+
+    fn rule(input: Span) -> ParseResult<(Span, u32)> {
+        context(
+            "workflow rule",
+            separated_pair(alpha1, alt((tag("<"), tag(">"))), complete::u32),
+        )(input)
+    }
+
+    #[rstest]
+    #[case("a<1\nb>2\n", Ok(vec![1, 2]))]
+    #[case("a<1\nb>2", Ok(vec![1, 2]))]
+    #[case(
+        "a<1\nb=2\n",
+        Err("line 2, column 2: expected \"<\" or \">\" in workflow rule, found \"=2\"")
+    )]
+    #[case(
+        "a<1\nb>x\n",
+        Err("line 2, column 3: expected a number in workflow rule, found \"x\"")
+    )]
+    #[case(
+        "a<1\nb>2 c\n",
+        Err("line 2, column 4: expected the end of the line, found \" c\"")
+    )]
+    fn test_lines(#[case] input: &str, #[case] expected: Result<Vec<u32>, &str>) {
+        let result = parse_all(input, lines(rule))
+            .map(|rules| {
+                rules
+                    .into_iter()
+                    .map(|(_, value)| value)
+                    .collect::<Vec<_>>()
+            })
+            .map_err(|error| error.to_string());
+        assert_eq!(result, expected.map_err(str::to_string));
+    }
+
+    #[test]
+    fn test_parse_all() {
+        assert_eq!(
+            parse_all("a<1\n\nb>2", rule).unwrap_err().to_string(),
+            "line 3, column 1: expected the end of the input, found \"b>2\""
+        );
+        assert_eq!(parse_all("a<1\n\n", rule).unwrap().1, 1);
+    }
+
+    #[test]
+    fn test_span() {
+        let (rest, parsed) = tag("ab\nc")(Span::new("ab\ncd")).unwrap();
+        assert_eq!(*parsed.fragment(), "ab\nc");
+        assert_eq!((rest.location_line(), rest.get_utf8_column()), (2, 2));
+    }
+}
@@ -0,0 +1,72 @@
+//! Differential checks of the solutions against slow but obviously correct reference solutions,
+//! such as the full simulations in each day's `reference` module, over small random inputs.
+//! Where they disagree is where a solution's shortcut stops being valid.
+
+use std::fmt::Display;
+
+use crate::params::Params;
+use crate::solution::Solution;
+use crate::solver::Solver;
+
+/// Solves `input` with `S`, with `params` applied on top of its defaults, and panics unless it
+/// gives the `reference` answer. Answers are compared as the runner prints them, so a reference
+/// need not return the same type as the solution.
+pub fn assert_agrees<S: Solution>(input: &str, params: &[(&str, &str)], reference: impl Display) {
+    let solver = Solver::of::<S>();
+    let params = solver
+        .params(&Params::from_pairs(params), true)
+        .unwrap_or_else(|error| panic!("day {} part {}: {}", S::DAY, S::PART, error));
+    match solver.solve(input, &params) {
+        Ok(answer) => assert_eq!(
+            answer.to_string(),
+            reference.to_string(),
+            "day {} part {} disagrees with the reference for:\n{}",
+            S::DAY,
+            S::PART,
+            input
+        ),
+        Err(error) => panic!(
+            "day {} part {} failed where the reference did not: {}\n{}",
+            S::DAY,
+            S::PART,
+            error,
+            input
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::Error;
+    use crate::solution::Answer;
+
+    struct Length;
+
+    impl Solution for Length {
+        const DAY: u8 = 1;
+        const PART: u8 = 1;
+
+        type Input<'a> = &'a str;
+        type Params = ();
+
+        fn parse(input: &str) -> Result<&str, Error> {
+            Ok(input)
+        }
+
+        fn solve(input: &str, _: &()) -> Result<Answer, Error> {
+            Ok((input.len() as u32).into())
+        }
+    }
+
+    #[test]
+    fn test_assert_agrees() {
+        assert_agrees::<Length>("abc", &[], 3u64);
+    }
+
+    #[test]
+    #[should_panic(expected = "disagrees with the reference")]
+    fn test_assert_disagrees() {
+        assert_agrees::<Length>("abc", &[], 4);
+    }
+}
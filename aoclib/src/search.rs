@@ -0,0 +1,415 @@
+//! Generic graph searches over nodes of any hashable type, with the graph given implicitly by a
+//! successor function.
+
+use std::cmp::Ordering;
+use std::collections::hash_map::Entry;
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
+use std::hash::Hash;
+use std::ops::Add;
+
+/// The cheapest known cost to every node reached by a search, along with how it was reached.
+#[derive(Debug, Clone)]
+pub struct Paths<N, C> {
+    /// The cost of each node, and the node it was reached from (which is `None` for the starts).
+    reached: HashMap<N, (C, Option<N>)>,
+}
+
+impl<N: Clone + Eq + Hash, C: Copy> Paths<N, C> {
+    fn new(starts: impl IntoIterator<Item = N>, zero: C) -> Self {
+        Self {
+            reached: starts
+                .into_iter()
+                .map(|start| (start, (zero, None)))
+                .collect(),
+        }
+    }
+
+    pub fn cost(&self, node: &N) -> Option<C> {
+        self.reached.get(node).map(|(cost, _)| *cost)
+    }
+
+    pub fn contains(&self, node: &N) -> bool {
+        self.reached.contains_key(node)
+    }
+
+    /// Every node reached, along with its cost.
+    pub fn iter(&self) -> impl Iterator<Item = (&N, C)> {
+        self.reached.iter().map(|(node, (cost, _))| (node, *cost))
+    }
+
+    pub fn len(&self) -> usize {
+        self.reached.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.reached.is_empty()
+    }
+
+    /// The nodes visited on the way to `node`, from the start it was reached from to `node`
+    /// itself.
+    pub fn path_to(&self, node: &N) -> Option<Vec<N>> {
+        if !self.contains(node) {
+            return None;
+        }
+        let mut path = vec![node.clone()];
+        while let Some((_, Some(parent))) =
+            self.reached.get(path.last().expect("path is never empty"))
+        {
+            path.push(parent.clone());
+        }
+        path.reverse();
+        Some(path)
+    }
+}
+
+/// A route to a goal found by a search.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Path<N, C> {
+    /// The nodes visited, from the start to the goal inclusive.
+    pub nodes: Vec<N>,
+    pub cost: C,
+}
+
+/// Breadth-first search from `starts` to every reachable node, where each step costs one.
+pub fn bfs_all<N, I>(
+    starts: impl IntoIterator<Item = N>,
+    successors: impl FnMut(&N) -> I,
+) -> Paths<N, usize>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    bfs_impl(starts, successors, |_| false, usize::MAX).0
+}
+
+/// Breadth-first search from `starts` to every node at most `max_depth` steps away.
+pub fn bfs_within<N, I>(
+    starts: impl IntoIterator<Item = N>,
+    successors: impl FnMut(&N) -> I,
+    max_depth: usize,
+) -> Paths<N, usize>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    bfs_impl(starts, successors, |_| false, max_depth).0
+}
+
+/// The shortest path, in number of steps, from any of `starts` to a node satisfying `is_goal`.
+pub fn bfs<N, I>(
+    starts: impl IntoIterator<Item = N>,
+    successors: impl FnMut(&N) -> I,
+    is_goal: impl FnMut(&N) -> bool,
+) -> Option<Path<N, usize>>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let (paths, goal) = bfs_impl(starts, successors, is_goal, usize::MAX);
+    let goal = goal?;
+    Some(Path {
+        cost: paths.cost(&goal)?,
+        nodes: paths.path_to(&goal)?,
+    })
+}
+
+fn bfs_impl<N, I>(
+    starts: impl IntoIterator<Item = N>,
+    mut successors: impl FnMut(&N) -> I,
+    mut is_goal: impl FnMut(&N) -> bool,
+    max_depth: usize,
+) -> (Paths<N, usize>, Option<N>)
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut paths = Paths::new(starts, 0);
+    let mut frontier = paths.reached.keys().cloned().collect::<VecDeque<_>>();
+
+    while let Some(node) = frontier.pop_front() {
+        if is_goal(&node) {
+            return (paths, Some(node));
+        }
+        let (depth, _) = paths.reached[&node];
+        if depth == max_depth {
+            continue;
+        }
+        for next in successors(&node) {
+            if let Entry::Vacant(entry) = paths.reached.entry(next.clone()) {
+                entry.insert((depth + 1, Some(node.clone())));
+                frontier.push_back(next);
+            }
+        }
+    }
+    (paths, None)
+}
+
+/// Every node reachable from `starts` in exactly `steps` steps, allowing nodes to be revisited.
+pub fn reachable_in_exactly<N, I>(
+    starts: impl IntoIterator<Item = N>,
+    mut successors: impl FnMut(&N) -> I,
+    steps: usize,
+) -> HashSet<N>
+where
+    N: Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut frontier = starts.into_iter().collect::<HashSet<_>>();
+    for _ in 0..steps {
+        frontier = frontier.iter().flat_map(&mut successors).collect();
+    }
+    frontier
+}
+
+/// The cheapest path from any of `starts` to a node satisfying `is_goal`, where `successors`
+/// gives each neighbour along with the cost of moving to it.
+pub fn dijkstra<N, C, I>(
+    starts: impl IntoIterator<Item = N>,
+    successors: impl FnMut(&N) -> I,
+    is_goal: impl FnMut(&N) -> bool,
+) -> Option<Path<N, C>>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Add<Output = C> + Default,
+    I: IntoIterator<Item = (N, C)>,
+{
+    astar(starts, successors, |_| C::default(), is_goal)
+}
+
+/// The cheapest cost to every node reachable from `starts`.
+pub fn dijkstra_all<N, C, I>(
+    starts: impl IntoIterator<Item = N>,
+    successors: impl FnMut(&N) -> I,
+) -> Paths<N, C>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Add<Output = C> + Default,
+    I: IntoIterator<Item = (N, C)>,
+{
+    astar_impl(starts, successors, |_| C::default(), |_| false).0
+}
+
+/// As [`dijkstra`], but guided by `heuristic`, which must never overestimate the remaining cost
+/// to the goal.
+pub fn astar<N, C, I>(
+    starts: impl IntoIterator<Item = N>,
+    successors: impl FnMut(&N) -> I,
+    heuristic: impl FnMut(&N) -> C,
+    is_goal: impl FnMut(&N) -> bool,
+) -> Option<Path<N, C>>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Add<Output = C> + Default,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let (paths, goal) = astar_impl(starts, successors, heuristic, is_goal);
+    let goal = goal?;
+    Some(Path {
+        cost: paths.cost(&goal)?,
+        nodes: paths.path_to(&goal)?,
+    })
+}
+
+fn astar_impl<N, C, I>(
+    starts: impl IntoIterator<Item = N>,
+    mut successors: impl FnMut(&N) -> I,
+    mut heuristic: impl FnMut(&N) -> C,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> (Paths<N, C>, Option<N>)
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Add<Output = C> + Default,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let mut paths = Paths::new(starts, C::default());
+    let mut frontier = paths
+        .reached
+        .keys()
+        .map(|start| Queued {
+            priority: heuristic(start),
+            cost: C::default(),
+            node: start.clone(),
+        })
+        .collect::<BinaryHeap<_>>();
+
+    while let Some(Queued { cost, node, .. }) = frontier.pop() {
+        // a cheaper route to this node has already been expanded
+        if paths.reached[&node].0 < cost {
+            continue;
+        }
+        if is_goal(&node) {
+            return (paths, Some(node));
+        }
+        for (next, step) in successors(&node) {
+            let next_cost = cost + step;
+            match paths.reached.entry(next.clone()) {
+                Entry::Occupied(entry) if entry.get().0 <= next_cost => continue,
+                Entry::Occupied(mut entry) => {
+                    entry.insert((next_cost, Some(node.clone())));
+                }
+                Entry::Vacant(entry) => {
+                    entry.insert((next_cost, Some(node.clone())));
+                }
+            }
+            frontier.push(Queued {
+                priority: next_cost + heuristic(&next),
+                cost: next_cost,
+                node: next,
+            });
+        }
+    }
+    (paths, None)
+}
+
+/// A node on the frontier, ordered so that the cheapest is popped first from a max-heap.
+struct Queued<N, C> {
+    priority: C,
+    cost: C,
+    node: N,
+}
+
+impl<N, C: Ord> Ord for Queued<N, C> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.priority.cmp(&self.priority)
+    }
+}
+
+impl<N, C: Ord> PartialOrd for Queued<N, C> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<N, C: Ord> PartialEq for Queued<N, C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.priority == other.priority
+    }
+}
+
+impl<N, C: Ord> Eq for Queued<N, C> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grid::NEIGHBOURS_4;
+    use crate::Grid;
+    use glam::IVec2;
+
+    const MAZE: &str = "\
+S.#.....
+.##.###.
+....#.E.
+.##...#.";
+
+    fn maze() -> Grid<char> {
+        Grid::parse(MAZE, |c| c).unwrap()
+    }
+
+    fn open_neighbours(grid: &Grid<char>, position: IVec2) -> Vec<IVec2> {
+        grid.neighbours(position)
+            .filter(|(_, &c)| c != '#')
+            .map(|(neighbour, _)| neighbour)
+            .collect()
+    }
+
+    #[test]
+    fn test_bfs() {
+        let grid = maze();
+        let goal = grid.find(|&c| c == 'E').unwrap();
+        let path = bfs(
+            [IVec2::ZERO],
+            |&position| open_neighbours(&grid, position),
+            |&position| position == goal,
+        )
+        .unwrap();
+        assert_eq!(path.cost, 10);
+        assert_eq!(path.nodes.len(), 11);
+        assert_eq!(path.nodes.first(), Some(&IVec2::ZERO));
+        assert_eq!(path.nodes.last(), Some(&goal));
+        assert!(path
+            .nodes
+            .windows(2)
+            .all(|step| NEIGHBOURS_4.contains(&(step[1] - step[0]))));
+    }
+
+    #[test]
+    fn test_bfs_unreachable() {
+        let grid = maze();
+        let path = bfs(
+            [IVec2::ZERO],
+            |&position| open_neighbours(&grid, position),
+            |&position| position == IVec2::new(2, 0),
+        );
+        assert_eq!(path, None);
+    }
+
+    #[test]
+    fn test_bfs_within() {
+        let grid = maze();
+        let paths = bfs_within(
+            [IVec2::ZERO],
+            |&position| open_neighbours(&grid, position),
+            2,
+        );
+        assert_eq!(paths.len(), 4);
+        assert_eq!(paths.cost(&IVec2::new(0, 2)), Some(2));
+        assert_eq!(paths.cost(&IVec2::new(0, 3)), None);
+        assert_eq!(
+            paths.path_to(&IVec2::new(0, 2)),
+            Some(vec![IVec2::ZERO, IVec2::new(0, 1), IVec2::new(0, 2)])
+        );
+        assert_eq!(
+            bfs_all([IVec2::ZERO], |&p| open_neighbours(&grid, p)).len(),
+            22
+        );
+    }
+
+    #[test]
+    fn test_reachable_in_exactly() {
+        let grid = maze();
+        let reachable = reachable_in_exactly([IVec2::ZERO], |&p| open_neighbours(&grid, p), 2);
+        assert_eq!(reachable, HashSet::from([IVec2::ZERO, IVec2::new(0, 2)]));
+    }
+
+    /// Moving down costs five, and every other move costs one.
+    fn weighted(grid: &Grid<char>, position: IVec2) -> Vec<(IVec2, u32)> {
+        open_neighbours(grid, position)
+            .into_iter()
+            .map(|neighbour| {
+                let cost = if neighbour.y > position.y { 5 } else { 1 };
+                (neighbour, cost)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_dijkstra_and_astar() {
+        let grid = maze();
+        let goal = grid.find(|&c| c == 'E').unwrap();
+
+        let path = dijkstra(
+            [IVec2::ZERO],
+            |&position| weighted(&grid, position),
+            |&position| position == goal,
+        )
+        .unwrap();
+        assert_eq!(path.cost, 22);
+
+        let all = dijkstra_all([IVec2::ZERO], |&position| weighted(&grid, position));
+        assert_eq!(all.cost(&goal), Some(22));
+        assert_eq!(all.path_to(&goal), Some(path.nodes.clone()));
+
+        let manhattan = |position: &IVec2| {
+            let distance = (goal - *position).abs();
+            (distance.x + distance.y) as u32
+        };
+        let guided = astar(
+            [IVec2::ZERO],
+            |&position| weighted(&grid, position),
+            manhattan,
+            |&position| position == goal,
+        )
+        .unwrap();
+        assert_eq!(guided.cost, 22);
+    }
+}
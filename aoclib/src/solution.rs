@@ -0,0 +1,168 @@
+use std::fmt::Display;
+use std::io::BufRead;
+
+use crate::dot::Graph;
+use crate::error::Error;
+use crate::params::{ParamError, Params};
+use crate::viz::Frame;
+
+/// The answer to one part of a day's puzzle.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Answer {
+    U32(u32),
+    U64(u64),
+    I32(i32),
+    I64(i64),
+    Usize(usize),
+    F64(f64),
+    String(String),
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Answer::U32(answer) => write!(f, "{}", answer),
+            Answer::U64(answer) => write!(f, "{}", answer),
+            Answer::I32(answer) => write!(f, "{}", answer),
+            Answer::I64(answer) => write!(f, "{}", answer),
+            Answer::Usize(answer) => write!(f, "{}", answer),
+            Answer::F64(answer) => write!(f, "{}", answer),
+            Answer::String(answer) => write!(f, "{}", answer),
+        }
+    }
+}
+
+macro_rules! impl_from {
+    ($($variant:ident($t:ty)),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(answer: $t) -> Self {
+                    Answer::$variant(answer)
+                }
+            }
+        )*
+    };
+}
+
+impl_from!(
+    U32(u32),
+    U64(u64),
+    I32(i32),
+    I64(i64),
+    Usize(usize),
+    F64(f64),
+    String(String)
+);
+
+impl From<&str> for Answer {
+    fn from(answer: &str) -> Self {
+        Answer::String(answer.to_string())
+    }
+}
+
+/// The typed parameters of a [`Solution`], built from the runner's `key=value` pairs.
+pub trait FromParams: Sized {
+    /// Every parameter accepted, along with the value used for the real puzzle input.
+    const DEFAULTS: &'static [(&'static str, &'static str)];
+
+    fn from_params(params: &Params) -> Result<Self, ParamError>;
+}
+
+impl FromParams for () {
+    const DEFAULTS: &'static [(&'static str, &'static str)] = &[];
+
+    fn from_params(_: &Params) -> Result<Self, ParamError> {
+        Ok(())
+    }
+}
+
+/// One part of a day's puzzle.
+pub trait Solution {
+    const DAY: u8;
+    const PART: u8;
+
+    type Input<'a>;
+    type Params: FromParams;
+
+    /// Parses `input` into what the part solves, reporting input it cannot solve as an error.
+    /// The runner's `--bench` times this separately from [`Solution::solve`].
+    fn parse(input: &str) -> Result<Self::Input<'_>, Error>;
+
+    fn solve(input: Self::Input<'_>, params: &Self::Params) -> Result<Answer, Error>;
+
+    /// Pictures of the solver's state on the way to the answer, such as the path it found, for
+    /// the runner's `--visualise`. Most parts have none.
+    fn visualise(_input: Self::Input<'_>, _params: &Self::Params) -> Result<Vec<Frame>, Error> {
+        Ok(vec![])
+    }
+
+    /// The graph the puzzle is about, such as a network of nodes, for the runner's
+    /// `--export-graph`. Only graph-shaped puzzles have one.
+    fn export_graph(
+        _input: Self::Input<'_>,
+        _params: &Self::Params,
+    ) -> Result<Option<Graph>, Error> {
+        Ok(None)
+    }
+
+    /// Solves the puzzle from `reader` a piece at a time, in constant memory, for inputs too
+    /// large to load, such as the runner's `--stream`. Only the parts which fold over their
+    /// input's lines can.
+    fn solve_reader(
+        _reader: &mut dyn BufRead,
+        _params: &Self::Params,
+    ) -> Result<Option<Answer>, Error> {
+        Ok(None)
+    }
+
+    /// Parses `input` and solves it with the default parameters.
+    fn run(input: &str) -> Result<Answer, Error> {
+        let params = Self::Params::from_params(&Params::from_pairs(Self::Params::DEFAULTS))?;
+        Self::solve(Self::parse(input)?, &params)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Steps(usize);
+
+    impl FromParams for Steps {
+        const DEFAULTS: &'static [(&'static str, &'static str)] = &[("steps", "3")];
+
+        fn from_params(params: &Params) -> Result<Self, ParamError> {
+            Ok(Steps(params.get("steps")?))
+        }
+    }
+
+    struct Repeat;
+
+    impl Solution for Repeat {
+        const DAY: u8 = 1;
+        const PART: u8 = 1;
+
+        type Input<'a> = &'a str;
+        type Params = Steps;
+
+        fn parse(input: &str) -> Result<&str, Error> {
+            Ok(input)
+        }
+
+        fn solve(input: &str, params: &Steps) -> Result<Answer, Error> {
+            Ok(input.repeat(params.0).into())
+        }
+    }
+
+    #[test]
+    fn test_run() {
+        assert_eq!(Repeat::run("ab"), Ok(Answer::String("ababab".to_string())));
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(Answer::from(-3_i64).to_string(), "-3");
+        assert_eq!(Answer::from(0.5).to_string(), "0.5");
+        assert_eq!(Answer::from("todo").to_string(), "todo");
+    }
+}
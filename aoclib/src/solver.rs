@@ -0,0 +1,215 @@
+use std::io::BufRead;
+
+use tracing::{info_span, Span};
+
+use crate::bench::{self, Timings};
+use crate::dot::Graph;
+use crate::error::Error;
+use crate::params::{ParamError, Params};
+use crate::solution::{Answer, FromParams, Solution};
+use crate::viz::Frame;
+
+type SolveFn = fn(&str, &Params) -> Result<Answer, Error>;
+type TimeFn = fn(&str, &Params, usize) -> Result<Timings, Error>;
+type VisualiseFn = fn(&str, &Params) -> Result<Vec<Frame>, Error>;
+type ExportGraphFn = fn(&str, &Params) -> Result<Option<Graph>, Error>;
+type SolveReaderFn = fn(&mut dyn BufRead, &Params) -> Result<Option<Answer>, Error>;
+
+/// A single day/part solver, as registered with the runner.
+pub struct Solver {
+    pub day: u8,
+    pub part: u8,
+    /// The parameters this solver accepts, along with the values used for the real input.
+    pub defaults: &'static [(&'static str, &'static str)],
+    solve: SolveFn,
+    time: TimeFn,
+    visualise: VisualiseFn,
+    export_graph: ExportGraphFn,
+    solve_reader: SolveReaderFn,
+}
+
+impl Solver {
+    pub fn of<S: Solution>() -> Self {
+        Self {
+            day: S::DAY,
+            part: S::PART,
+            defaults: S::Params::DEFAULTS,
+            solve: |input, params| {
+                let params = S::Params::from_params(params)?;
+                S::solve(S::parse(input)?, &params)
+            },
+            time: |input, params, iterations| {
+                let params = S::Params::from_params(params)?;
+                bench::time::<S>(input, &params, iterations)
+            },
+            visualise: |input, params| {
+                let params = S::Params::from_params(params)?;
+                S::visualise(S::parse(input)?, &params)
+            },
+            export_graph: |input, params| {
+                let params = S::Params::from_params(params)?;
+                S::export_graph(S::parse(input)?, &params)
+            },
+            solve_reader: |reader, params| {
+                let params = S::Params::from_params(params)?;
+                S::solve_reader(reader, &params)
+            },
+        }
+    }
+
+    /// Applies `overrides` on top of this solver's defaults. Overrides for parameters the solver
+    /// does not accept are an error when `strict`, and are ignored otherwise.
+    pub fn params(&self, overrides: &Params, strict: bool) -> Result<Params, ParamError> {
+        let mut params = Params::from_pairs(self.defaults);
+        for (key, value) in overrides.iter() {
+            if params.contains(key) {
+                params.set(key, value);
+            } else if strict {
+                return Err(ParamError::Unknown(key.to_string()));
+            }
+        }
+        Ok(params)
+    }
+
+    pub fn solve(&self, input: &str, params: &Params) -> Result<Answer, Error> {
+        let _span = self.span("solve").entered();
+        (self.solve)(input, params)
+    }
+
+    /// Parses and solves `input` `iterations` times, timing each step separately.
+    pub fn time(&self, input: &str, params: &Params, iterations: usize) -> Result<Timings, Error> {
+        let _span = self.span("time").entered();
+        (self.time)(input, params, iterations)
+    }
+
+    /// Parses `input` and draws the solver's state, which is empty for most solvers.
+    pub fn visualise(&self, input: &str, params: &Params) -> Result<Vec<Frame>, Error> {
+        let _span = self.span("visualise").entered();
+        (self.visualise)(input, params)
+    }
+
+    /// Parses `input` and builds the puzzle's graph, if the solver has one.
+    pub fn export_graph(&self, input: &str, params: &Params) -> Result<Option<Graph>, Error> {
+        let _span = self.span("export_graph").entered();
+        (self.export_graph)(input, params)
+    }
+
+    /// Solves the input read from `reader`, without loading it, if the solver can.
+    pub fn solve_reader(
+        &self,
+        reader: &mut dyn BufRead,
+        params: &Params,
+    ) -> Result<Option<Answer>, Error> {
+        let _span = self.span("solve_reader").entered();
+        (self.solve_reader)(reader, params)
+    }
+
+    /// The span around one of this solver's calls, so that each event a day logs says which
+    /// day and part it came from, and can be filtered on them, e.g. `[solver{day=22}]=trace`.
+    fn span(&self, call: &'static str) -> Span {
+        info_span!("solver", day = self.day, part = self.part, call)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Steps(usize);
+
+    impl FromParams for Steps {
+        const DEFAULTS: &'static [(&'static str, &'static str)] = &[("steps", "64")];
+
+        fn from_params(params: &Params) -> Result<Self, ParamError> {
+            Ok(Steps(params.get("steps")?))
+        }
+    }
+
+    struct Walk;
+
+    impl Solution for Walk {
+        const DAY: u8 = 21;
+        const PART: u8 = 1;
+
+        type Input<'a> = &'a str;
+        type Params = Steps;
+
+        fn parse(input: &str) -> Result<&str, Error> {
+            input
+                .lines()
+                .next()
+                .ok_or_else(|| Error::parse(input, input, "a line"))
+        }
+
+        fn solve(input: &str, params: &Steps) -> Result<Answer, Error> {
+            if params.0 == 0 {
+                return Err(Error::NoSolution("there are no steps to take".to_string()));
+            }
+            Ok(format!("{} {}", input, params.0).into())
+        }
+    }
+
+    #[test]
+    fn test_defaults() {
+        let solver = Solver::of::<Walk>();
+        let params = solver.params(&Params::new(), true).unwrap();
+        assert_eq!(solver.solve("input", &params), Ok("input 64".into()));
+    }
+
+    #[test]
+    fn test_overrides() {
+        let solver = Solver::of::<Walk>();
+        let mut overrides = Params::new();
+        overrides.set("steps", "6");
+        let params = solver.params(&overrides, true).unwrap();
+        assert_eq!(solver.solve("input", &params), Ok("input 6".into()));
+
+        overrides.set("lower_bound", "7");
+        assert_eq!(
+            solver.params(&overrides, true),
+            Err(ParamError::Unknown("lower_bound".to_string()))
+        );
+        assert!(solver.params(&overrides, false).is_ok());
+    }
+
+    #[test]
+    fn test_error() {
+        let solver = Solver::of::<Walk>();
+        let mut overrides = Params::new();
+        overrides.set("steps", "0");
+        let params = solver.params(&overrides, true).unwrap();
+        assert_eq!(
+            solver.solve("input", &params),
+            Err(Error::NoSolution("there are no steps to take".to_string()))
+        );
+        assert_eq!(
+            solver.solve("", &params),
+            Err(Error::parse("", "", "a line"))
+        );
+        assert_eq!(
+            solver.time("", &params, 3),
+            Err(Error::parse("", "", "a line"))
+        );
+    }
+
+    #[test]
+    fn test_time() {
+        let solver = Solver::of::<Walk>();
+        let params = solver.params(&Params::new(), true).unwrap();
+        let timings = solver.time("input", &params, 3).unwrap();
+        assert_eq!(timings.parse.len(), 3);
+        assert_eq!(timings.solve.len(), 3);
+    }
+
+    #[test]
+    fn test_no_extras() {
+        let solver = Solver::of::<Walk>();
+        let params = solver.params(&Params::new(), true).unwrap();
+        assert_eq!(solver.visualise("input", &params), Ok(vec![]));
+        assert_eq!(solver.export_graph("input", &params), Ok(None));
+        assert_eq!(
+            solver.solve_reader(&mut "input".as_bytes(), &params),
+            Ok(None)
+        );
+    }
+}
@@ -0,0 +1,265 @@
+//! Reading puzzle input a piece at a time, for generated inputs too large to load into memory.
+//!
+//! Both readers normalise the input the way [`crate::input::normalise`] does, so a streaming
+//! solver sees the same pieces as one given the loaded input. A read error ends the iteration
+//! early, and is reported by `finish` once the solver is done.
+
+use std::io::BufRead;
+
+use crate::error::Error;
+
+/// The lines of a reader, without their line endings. Blank lines at the end of the input are
+/// dropped, which means holding on to a count of blank lines until the next line with something
+/// on it, but an input with nothing on any line is still one empty line.
+pub struct Lines<R> {
+    reader: R,
+    blank: usize,
+    next: Option<String>,
+    started: bool,
+    error: Option<Error>,
+}
+
+impl<R: BufRead> Lines<R> {
+    pub fn new(reader: R) -> Self {
+        Self {
+            reader,
+            blank: 0,
+            next: None,
+            started: false,
+            error: None,
+        }
+    }
+
+    /// Reports the error which ended the lines early, if there was one.
+    pub fn finish(self) -> Result<(), Error> {
+        self.error.map_or(Ok(()), Err)
+    }
+}
+
+impl<R: BufRead> Iterator for Lines<R> {
+    type Item = String;
+
+    fn next(&mut self) -> Option<String> {
+        loop {
+            if self.next.is_some() {
+                self.started = true;
+                if self.blank > 0 {
+                    self.blank -= 1;
+                    return Some(String::new());
+                }
+                return self.next.take();
+            }
+
+            let mut line = String::new();
+            match self.reader.read_line(&mut line) {
+                Ok(0) if !self.started => {
+                    self.started = true;
+                    return Some(String::new());
+                }
+                Ok(0) => return None,
+                Ok(_) => {
+                    if line.ends_with('\n') {
+                        line.pop();
+                        if line.ends_with('\r') {
+                            line.pop();
+                        }
+                    }
+                    if line.is_empty() {
+                        self.blank += 1;
+                    } else {
+                        self.next = Some(line);
+                    }
+                }
+                Err(error) => {
+                    self.error = Some(Error::Read(error.to_string()));
+                    return None;
+                }
+            }
+        }
+    }
+}
+
+/// Runs `solve` over the lines of `reader`. A read error is reported ahead of whatever `solve`
+/// made of the lines before it.
+pub fn with_lines<R: BufRead, T>(
+    reader: R,
+    solve: impl FnOnce(&mut Lines<R>) -> Result<T, Error>,
+) -> Result<T, Error> {
+    let mut lines = Lines::new(reader);
+    let result = solve(&mut lines);
+    lines.finish()?;
+    result
+}
+
+/// Runs `solve` over the pieces of `reader` separated by `delimiter`. A read error is reported
+/// ahead of whatever `solve` made of the pieces before it.
+pub fn with_split<R: BufRead, T>(
+    reader: R,
+    delimiter: u8,
+    solve: impl FnOnce(&mut Split<R>) -> Result<T, Error>,
+) -> Result<T, Error> {
+    let mut pieces = Split::new(reader, delimiter);
+    let result = solve(&mut pieces);
+    pieces.finish()?;
+    result
+}
+
+/// The pieces of an input separated by `delimiter`, such as day 15's comma-separated steps. The
+/// line ending and any whitespace after the last piece are trimmed off.
+pub struct Split<R> {
+    reader: R,
+    delimiter: u8,
+    done: bool,
+    error: Option<Error>,
+    /// The line and column, counting from one, of the start of the last piece and of the next.
+    start: (usize, usize),
+    next: (usize, usize),
+}
+
+impl<R: BufRead> Split<R> {
+    pub fn new(reader: R, delimiter: u8) -> Self {
+        Self {
+            reader,
+            delimiter,
+            done: false,
+            error: None,
+            start: (1, 1),
+            next: (1, 1),
+        }
+    }
+
+    /// Moves a parse error found by parsing the last piece on its own to where that piece is in
+    /// the input.
+    pub fn locate(&self, error: Error) -> Error {
+        let (line, column) = self.start;
+        match error {
+            Error::Parse { line: 1, .. } => error.on_column(column).on_line(line),
+            error => error.on_line(line),
+        }
+    }
+
+    /// Reports the error which ended the pieces early, if there was one.
+    pub fn finish(self) -> Result<(), Error> {
+        self.error.map_or(Ok(()), Err)
+    }
+
+    /// Moves the start of the next piece past `text`.
+    fn advance(&mut self, text: &str) {
+        let (line, column) = &mut self.next;
+        match text.rsplit_once('\n') {
+            Some((before, after)) => {
+                *line += before.matches('\n').count() + 1;
+                *column = after.chars().count() + 1;
+            }
+            None => *column += text.chars().count(),
+        }
+    }
+}
+
+impl<R: BufRead> Iterator for Split<R> {
+    type Item = String;
+
+    fn next(&mut self) -> Option<String> {
+        if self.done {
+            return None;
+        }
+        let mut piece = vec![];
+        let result = self
+            .reader
+            .read_until(self.delimiter, &mut piece)
+            .map_err(|error| error.to_string())
+            .and_then(|_| String::from_utf8(piece).map_err(|error| error.to_string()));
+        match result {
+            Ok(mut piece) => {
+                self.start = self.next;
+                self.advance(&piece);
+                if piece.ends_with(self.delimiter as char) {
+                    piece.pop();
+                } else {
+                    self.done = true;
+                    piece.truncate(piece.trim_end().len());
+                }
+                Some(piece)
+            }
+            Err(error) => {
+                self.done = true;
+                self.error = Some(Error::Read(error));
+                None
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::input::normalise;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("1\n2")]
+    #[case("1\r\n\r\n2\r\n")]
+    #[case("1\n\n\n2\n\n\n")]
+    #[case("\n1\n")]
+    #[case("")]
+    #[case("\r\n\n")]
+    fn test_lines(#[case] raw: &str) {
+        let mut lines = Lines::new(raw.as_bytes());
+        assert_eq!(
+            lines.by_ref().collect::<Vec<_>>(),
+            normalise(raw).lines().collect::<Vec<_>>()
+        );
+        assert_eq!(lines.finish(), Ok(()));
+    }
+
+    #[test]
+    fn test_lines_error() {
+        let mut lines = Lines::new(&b"1\n\xff\n3\n"[..]);
+        assert_eq!(lines.by_ref().collect::<Vec<_>>(), vec!["1"]);
+        assert!(matches!(lines.finish(), Err(Error::Read(_))));
+    }
+
+    #[test]
+    fn test_with_lines() {
+        let count = |lines: &mut Lines<&[u8]>| -> Result<usize, Error> { Ok(lines.count()) };
+        assert_eq!(with_lines(&b"1\n2\n"[..], count), Ok(2));
+        assert!(matches!(
+            with_lines(&b"1\n\xff\n"[..], count),
+            Err(Error::Read(_))
+        ));
+    }
+
+    #[rstest]
+    #[case("rn=1,cm-\n", vec!["rn=1", "cm-"])]
+    #[case("rn=1,cm-", vec!["rn=1", "cm-"])]
+    #[case("rn=1\r\n", vec!["rn=1"])]
+    #[case("", vec![""])]
+    fn test_split(#[case] raw: &str, #[case] expected: Vec<&str>) {
+        let mut pieces = Split::new(raw.as_bytes(), b',');
+        assert_eq!(pieces.by_ref().collect::<Vec<_>>(), expected);
+        assert_eq!(pieces.finish(), Ok(()));
+    }
+
+    #[test]
+    fn test_locate() {
+        let input = "rn=1,cm-,qp=3\ncm=2,qé-";
+        let mut pieces = Split::new(input.as_bytes(), b',');
+        let piece = pieces.nth(3).unwrap();
+        assert_eq!(piece, "qé-");
+        assert_eq!(
+            pieces.locate(Error::parse(&piece, &piece[1..], "an ASCII character")),
+            Error::parse(input, &input[input.len() - 3..], "an ASCII character")
+        );
+        let mut pieces = Split::new(input.as_bytes(), b',');
+        let piece = pieces.nth(2).unwrap();
+        assert_eq!(
+            pieces.locate(Error::parse(&piece, &piece[8..], "a step")),
+            Error::Parse {
+                line: 2,
+                column: 4,
+                snippet: "2".to_string(),
+                expected: "a step".to_string(),
+            }
+        );
+    }
+}
@@ -0,0 +1,378 @@
+//! Pictures of a solver's state, such as a map with the path found across it. A [`Frame`] is a
+//! grid of glyphs, each optionally coloured, which renders as plain text, as text coloured for a
+//! terminal, or as pixels for PNG images and animated GIFs.
+
+use std::borrow::Cow;
+use std::fmt::Display;
+use std::fs::File;
+use std::io::{self, BufWriter};
+use std::path::Path;
+
+use glam::IVec2;
+
+use crate::grid::Grid;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Colour {
+    Black,
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+    White,
+    Grey,
+}
+
+impl Colour {
+    /// Every colour, in the order of the GIF palette.
+    pub const ALL: [Colour; 9] = [
+        Colour::Black,
+        Colour::Red,
+        Colour::Green,
+        Colour::Yellow,
+        Colour::Blue,
+        Colour::Magenta,
+        Colour::Cyan,
+        Colour::White,
+        Colour::Grey,
+    ];
+
+    /// The colours which stand out against the background, for telling things apart.
+    pub const BRIGHT: [Colour; 6] = [
+        Colour::Red,
+        Colour::Green,
+        Colour::Yellow,
+        Colour::Blue,
+        Colour::Magenta,
+        Colour::Cyan,
+    ];
+
+    pub fn rgb(self) -> [u8; 3] {
+        match self {
+            Colour::Black => [0x10, 0x10, 0x18],
+            Colour::Red => [0xe0, 0x40, 0x40],
+            Colour::Green => [0x40, 0xc0, 0x40],
+            Colour::Yellow => [0xf0, 0xd0, 0x30],
+            Colour::Blue => [0x40, 0x70, 0xe0],
+            Colour::Magenta => [0xc0, 0x50, 0xc0],
+            Colour::Cyan => [0x40, 0xc0, 0xd0],
+            Colour::White => [0xf0, 0xf0, 0xf0],
+            Colour::Grey => [0x70, 0x70, 0x78],
+        }
+    }
+
+    /// The SGR parameter which sets this as the terminal's foreground colour.
+    fn ansi(self) -> u8 {
+        match self {
+            Colour::Black => 30,
+            Colour::Red => 31,
+            Colour::Green => 32,
+            Colour::Yellow => 33,
+            Colour::Blue => 34,
+            Colour::Magenta => 35,
+            Colour::Cyan => 36,
+            Colour::White => 97,
+            Colour::Grey => 90,
+        }
+    }
+
+    fn index(self) -> u8 {
+        Colour::ALL
+            .iter()
+            .position(|colour| *colour == self)
+            .expect("every colour is in the palette") as u8
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cell {
+    pub glyph: char,
+    pub colour: Option<Colour>,
+}
+
+impl Cell {
+    pub fn new(glyph: char) -> Self {
+        Self {
+            glyph,
+            colour: None,
+        }
+    }
+
+    /// The colour of the cell's pixels: its own colour if it has one, otherwise the background
+    /// for `.` and spaces, and grey for anything else.
+    fn pixel(&self) -> Colour {
+        match (self.colour, self.glyph) {
+            (Some(colour), _) => colour,
+            (None, '.' | ' ') => Colour::Black,
+            (None, _) => Colour::Grey,
+        }
+    }
+}
+
+/// One picture of a solver's state.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Frame {
+    cells: Grid<Cell>,
+}
+
+impl Frame {
+    /// A frame showing each cell of `grid` as the glyph given by `glyph`.
+    pub fn new<T>(grid: &Grid<T>, mut glyph: impl FnMut(&T) -> char) -> Self {
+        Self {
+            cells: grid.map(|cell| Cell::new(glyph(cell))),
+        }
+    }
+
+    /// A `width` by `height` frame of `.`, for drawing state which is not already a grid.
+    pub fn blank(width: usize, height: usize) -> Self {
+        Self {
+            cells: Grid::filled(width, height, Cell::new('.')),
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.cells.width()
+    }
+
+    pub fn height(&self) -> usize {
+        self.cells.height()
+    }
+
+    pub fn cells(&self) -> &Grid<Cell> {
+        &self.cells
+    }
+
+    /// Colours the cells at `positions`, keeping their glyphs. Positions off the frame are
+    /// ignored, so overlays can be drawn without clipping them first.
+    pub fn highlight(
+        &mut self,
+        positions: impl IntoIterator<Item = IVec2>,
+        colour: Colour,
+    ) -> &mut Self {
+        for position in positions {
+            if let Some(cell) = self.cells.get_mut(position) {
+                cell.colour = Some(colour);
+            }
+        }
+        self
+    }
+
+    /// Replaces the cells at `positions` with `glyph` in `colour`, ignoring any off the frame.
+    pub fn mark(
+        &mut self,
+        positions: impl IntoIterator<Item = IVec2>,
+        glyph: char,
+        colour: Colour,
+    ) -> &mut Self {
+        for position in positions {
+            if let Some(cell) = self.cells.get_mut(position) {
+                *cell = Cell {
+                    glyph,
+                    colour: Some(colour),
+                };
+            }
+        }
+        self
+    }
+
+    /// Draws `path` in `colour`, with each position showing an arrow towards the next one. The
+    /// last position keeps its glyph.
+    pub fn path(&mut self, path: &[IVec2], colour: Colour) -> &mut Self {
+        for step in path.windows(2) {
+            let glyph = match (step[1] - step[0]).signum() {
+                IVec2 { x: 0, y: -1 } => '^',
+                IVec2 { x: 1, y: 0 } => '>',
+                IVec2 { x: 0, y: 1 } => 'v',
+                IVec2 { x: -1, y: 0 } => '<',
+                _ => '*',
+            };
+            self.mark([step[0]], glyph, colour);
+        }
+        self.highlight(path.last().copied(), colour)
+    }
+
+    /// The frame as plain text, one line per row.
+    pub fn to_ascii(&self) -> String {
+        self.to_string()
+    }
+
+    /// The frame as text with ANSI escape codes colouring the highlighted cells.
+    pub fn to_ansi(&self) -> String {
+        let mut text = String::new();
+        for row in self.cells.rows() {
+            for cell in row {
+                match cell.colour {
+                    Some(colour) => {
+                        text.push_str(&format!("\x1b[{}m{}\x1b[0m", colour.ansi(), cell.glyph))
+                    }
+                    None => text.push(cell.glyph),
+                }
+            }
+            text.push('\n');
+        }
+        text
+    }
+
+    /// The palette index of each pixel, in row-major order, with each cell drawn as a `scale` by
+    /// `scale` square in the top left of a `width` by `height` image.
+    fn pixels(&self, width: usize, height: usize, scale: usize) -> Vec<u8> {
+        let background = Colour::Black.index();
+        let mut pixels = vec![background; width * height];
+        for (position, cell) in self.cells.iter() {
+            let index = cell.pixel().index();
+            let (x, y) = (position.x as usize * scale, position.y as usize * scale);
+            for row in y..y + scale {
+                pixels[row * width + x..row * width + x + scale].fill(index);
+            }
+        }
+        pixels
+    }
+}
+
+impl Display for Frame {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for row in self.cells.rows() {
+            for cell in row {
+                write!(f, "{}", cell.glyph)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+/// Writes `frame` to `path` as a PNG, with each cell drawn as a `scale` by `scale` square.
+pub fn write_png(frame: &Frame, path: &Path, scale: usize) -> io::Result<()> {
+    let (width, height) = (frame.width() * scale, frame.height() * scale);
+    let mut encoder = png::Encoder::new(
+        BufWriter::new(File::create(path)?),
+        width as u32,
+        height as u32,
+    );
+    encoder.set_color(png::ColorType::Rgb);
+    encoder.set_depth(png::BitDepth::Eight);
+    let data = frame
+        .pixels(width, height, scale)
+        .into_iter()
+        .flat_map(|index| Colour::ALL[usize::from(index)].rgb())
+        .collect::<Vec<u8>>();
+    encoder
+        .write_header()
+        .and_then(|mut writer| writer.write_image_data(&data))
+        .map_err(io::Error::other)
+}
+
+/// Writes `frames` to `path` as an animated GIF which loops forever, showing each frame for
+/// `delay` hundredths of a second. Frames smaller than the largest are drawn in its top left.
+pub fn write_gif(frames: &[Frame], path: &Path, scale: usize, delay: u16) -> io::Result<()> {
+    let width = frames.iter().map(Frame::width).max().unwrap_or_default() * scale;
+    let height = frames.iter().map(Frame::height).max().unwrap_or_default() * scale;
+    let (Ok(gif_width), Ok(gif_height)) = (u16::try_from(width), u16::try_from(height)) else {
+        return Err(io::Error::other(format!(
+            "a {}x{} image is too large for a GIF",
+            width, height
+        )));
+    };
+    let palette = Colour::ALL
+        .iter()
+        .flat_map(|colour| colour.rgb())
+        .collect::<Vec<u8>>();
+    let mut encoder = gif::Encoder::new(
+        BufWriter::new(File::create(path)?),
+        gif_width,
+        gif_height,
+        &palette,
+    )
+    .map_err(io::Error::other)?;
+    encoder
+        .set_repeat(gif::Repeat::Infinite)
+        .map_err(io::Error::other)?;
+    for frame in frames {
+        let frame = gif::Frame {
+            width: gif_width,
+            height: gif_height,
+            delay,
+            buffer: Cow::Owned(frame.pixels(width, height, scale)),
+            ..gif::Frame::default()
+        };
+        encoder.write_frame(&frame).map_err(io::Error::other)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frame() -> Frame {
+        Frame::new(&Grid::parse("..#\n.#.\n", |c| c).unwrap(), |c| *c)
+    }
+
+    #[test]
+    fn test_overlays() {
+        let mut frame = frame();
+        frame
+            .highlight([IVec2::new(2, 0), IVec2::new(5, 5)], Colour::Red)
+            .mark([IVec2::new(0, 1)], 'S', Colour::Green);
+        assert_eq!(frame.to_ascii(), "..#\nS#.\n");
+        assert_eq!(frame.cells()[IVec2::new(2, 0)].colour, Some(Colour::Red));
+        assert_eq!(frame.to_ansi(), "..\x1b[31m#\x1b[0m\n\x1b[32mS\x1b[0m#.\n");
+    }
+
+    #[test]
+    fn test_path() {
+        let mut frame = Frame::blank(3, 2);
+        frame.path(
+            &[
+                IVec2::new(0, 0),
+                IVec2::new(1, 0),
+                IVec2::new(1, 1),
+                IVec2::new(0, 1),
+            ],
+            Colour::Yellow,
+        );
+        assert_eq!(frame.to_ascii(), ">v.\n.<.\n");
+        assert_eq!(frame.cells()[IVec2::new(0, 1)].colour, Some(Colour::Yellow));
+    }
+
+    #[test]
+    fn test_pixels() {
+        let mut frame = frame();
+        frame.highlight([IVec2::new(0, 0)], Colour::Red);
+        let (black, red, grey) = (
+            Colour::Black.index(),
+            Colour::Red.index(),
+            Colour::Grey.index(),
+        );
+        assert_eq!(
+            frame.pixels(4, 3, 1),
+            [
+                red, black, grey, black, //
+                black, grey, black, black, //
+                black, black, black, black,
+            ]
+        );
+        assert_eq!(
+            frame.pixels(6, 4, 2)[..6],
+            [red, red, black, black, grey, grey]
+        );
+    }
+
+    #[test]
+    fn test_write() {
+        let directory = std::env::temp_dir().join(format!("aoclib-viz-{}", std::process::id()));
+        std::fs::create_dir_all(&directory).unwrap();
+        let png = directory.join("frame.png");
+        let gif = directory.join("frames.gif");
+        let written = write_png(&frame(), &png, 2)
+            .and_then(|_| write_gif(&[frame(), Frame::blank(4, 4)], &gif, 2, 10))
+            .map(|_| (std::fs::read(&png).unwrap(), std::fs::read(&gif).unwrap()));
+        std::fs::remove_dir_all(&directory).unwrap();
+
+        let (png, gif) = written.unwrap();
+        assert_eq!(&png[1..4], b"PNG");
+        assert_eq!(&gif[..6], b"GIF89a");
+    }
+}
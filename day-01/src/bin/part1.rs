@@ -1,7 +1,9 @@
+use aoclib::input::{self, InputError};
 use day_01::part1::process;
 
-fn main() {
-    let file = include_str!("../../input1.txt");
+fn main() -> Result<(), InputError> {
+    let file = input::load(1, 1)?;
     let result = process(file.lines());
     println!("{}", result);
-}
\ No newline at end of file
+    Ok(())
+}
@@ -1,20 +1,62 @@
+use std::io::BufRead;
 use std::iter::Iterator;
+use std::str::Lines;
+
+use aoclib::stream;
+use aoclib::{Answer, Error, Solution};
 
 pub fn process<Item: AsRef<str>, Container: Iterator<Item=Item>>(lines: Container) -> u32 {
     lines.map(process_line).sum::<u32>()
 }
 
+/// Like [`process`], but reports a line without any digits as an error.
+pub fn try_process<Item: AsRef<str>, Container: Iterator<Item=Item>>(lines: Container) -> Result<Answer, Error> {
+    lines.enumerate().map(|(index, line)| {
+        let line = line.as_ref();
+        calibration_value(line).ok_or_else(|| Error::parse_line(index + 1, line, line, "a line containing a digit"))
+    })
+    .sum::<Result<u32, Error>>()
+    .map(Answer::from)
+}
+
 fn process_line<Item: AsRef<str>>(line: Item) -> u32 {
-    let mut iterator = line.as_ref().chars().filter(|c| c.is_digit(10));
+    calibration_value(line.as_ref()).expect("Should be a digit!")
+}
 
-    let first = iterator.next().expect("Should be a digit!");
+fn calibration_value(line: &str) -> Option<u32> {
+    let mut iterator = line.chars().filter(|c| c.is_digit(10));
 
-    match iterator.last() {
+    let first = iterator.next()?;
+
+    let value = match iterator.last() {
         Some(last_character) => format!("{first}{last_character}"),
         None => format!("{first}{first}"),
     }
     .parse::<u32>()
-    .expect("Should be a number")
+    .expect("Should be a number");
+    Some(value)
+}
+
+pub struct Part1;
+
+impl Solution for Part1 {
+    const DAY: u8 = 1;
+    const PART: u8 = 1;
+
+    type Input<'a> = Lines<'a>;
+    type Params = ();
+
+    fn parse(input: &str) -> Result<Lines<'_>, Error> {
+        Ok(input.lines())
+    }
+
+    fn solve(input: Lines<'_>, _: &()) -> Result<Answer, Error> {
+        try_process(input)
+    }
+
+    fn solve_reader(reader: &mut dyn BufRead, _: &()) -> Result<Option<Answer>, Error> {
+        stream::with_lines(reader, |lines| try_process(lines)).map(Some)
+    }
 }
 
 #[cfg(test)]
@@ -22,13 +64,19 @@ mod tests {
     use super::*;
 
     #[test]
-    fn test_day_01a() {
+    fn test_try_process() {
         let lines = "1abc2
 pqr3stu8vwx
-a1b2c3d4e5f
-treb7uchet".lines();
-        let result = process(lines);
-        assert_eq!(result, 142);
+abcdef".lines();
+        assert_eq!(
+            try_process(lines),
+            Err(Error::Parse {
+                line: 3,
+                column: 1,
+                snippet: "abcdef".to_string(),
+                expected: "a line containing a digit".to_string(),
+            })
+        );
     }
 }
 
@@ -1,11 +1,30 @@
+use std::io::BufRead;
 use std::iter::Iterator;
 
+use aoclib::stream;
+use aoclib::{Answer, Error, Solution};
+
 pub fn process<Line: AsRef<str>, Lines: Iterator<Item=Line>>(lines: Lines) -> u32 {
     lines.map(process_line).sum::<u32>()
 }
 
+/// Like [`process`], but reports a line without any digits, spelled out or otherwise, as an
+/// error.
+pub fn try_process<Line: AsRef<str>, Lines: Iterator<Item=Line>>(lines: Lines) -> Result<Answer, Error> {
+    lines.enumerate().map(|(index, line)| {
+        let line = line.as_ref();
+        calibration_value(line).ok_or_else(|| Error::parse_line(index + 1, line, line, "a line containing a digit"))
+    })
+    .sum::<Result<u32, Error>>()
+    .map(Answer::from)
+}
+
 fn process_line<Line: AsRef<str>>(line: Line) -> u32 {
-    let new_line = line.as_ref()
+    calibration_value(line.as_ref()).expect("Should be a digit!")
+}
+
+fn calibration_value(line: &str) -> Option<u32> {
+    let new_line = line
         .replace("one", "o1e")
         .replace("two", "t2o")
         .replace("three", "t3e")
@@ -18,16 +37,39 @@ fn process_line<Line: AsRef<str>>(line: Line) -> u32 {
 
     let mut iterator = new_line.chars().filter(|c| c.is_digit(10));
 
-    let first = iterator.next().expect("Should be a digit!");
+    let first = iterator.next()?;
 
-    iterator
+    let value = iterator
         .last()
         .map_or_else(
             || format!("{first}{first}"),
             |last| format!("{first}{last}"),
         )
         .parse::<u32>()
-        .expect("Should be a number")
+        .expect("Should be a number");
+    Some(value)
+}
+
+pub struct Part2;
+
+impl Solution for Part2 {
+    const DAY: u8 = 1;
+    const PART: u8 = 2;
+
+    type Input<'a> = std::str::Lines<'a>;
+    type Params = ();
+
+    fn parse(input: &str) -> Result<std::str::Lines<'_>, Error> {
+        Ok(input.lines())
+    }
+
+    fn solve(input: std::str::Lines<'_>, _: &()) -> Result<Answer, Error> {
+        try_process(input)
+    }
+
+    fn solve_reader(reader: &mut dyn BufRead, _: &()) -> Result<Option<Answer>, Error> {
+        stream::with_lines(reader, |lines| try_process(lines)).map(Some)
+    }
 }
 
 #[cfg(test)]
@@ -47,17 +89,4 @@ mod tests {
     fn line_test(#[case] input: String, #[case] output: u32) {
         assert_eq!(process_line(input), output)
     }
-
-    #[test]
-    fn test_day_01b() {
-        let lines = "two1nine
-eightwothree
-abcone2threexyz
-xtwone3four
-4nineeightseven2
-zoneight234
-7pqrstsixteen".lines();
-        let result = process(lines.into_iter());
-        assert_eq!(result, 281);
-    }
 }
\ No newline at end of file
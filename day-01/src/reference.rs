@@ -0,0 +1,59 @@
+//! Slow but obviously correct solutions, to check the real ones against on small inputs.
+
+const WORDS: [&str; 9] = [
+    "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+];
+
+/// The digit starting at each position of `line`, spelt out or not.
+fn digits(line: &str, spelt: bool) -> Vec<u32> {
+    (0..line.len())
+        .filter_map(|start| {
+            let rest = &line[start..];
+            let digit = rest.chars().next().and_then(|c| c.to_digit(10));
+            let word = (1..=9).find(|value| spelt && rest.starts_with(WORDS[*value as usize - 1]));
+            digit.or(word)
+        })
+        .collect()
+}
+
+fn calibrate(input: &str, spelt: bool) -> u32 {
+    input
+        .lines()
+        .map(|line| {
+            let digits = digits(line, spelt);
+            digits[0] * 10 + digits[digits.len() - 1]
+        })
+        .sum()
+}
+
+pub fn part1(input: &str) -> u32 {
+    calibrate(input, false)
+}
+
+pub fn part2(input: &str) -> u32 {
+    calibrate(input, true)
+}
+
+mod tests {
+    use aoclib::gen;
+    use aoclib::reference::assert_agrees;
+    use proptest::prelude::*;
+
+    use super::*;
+    use crate::part1::Part1;
+    use crate::part2::Part2;
+
+    proptest! {
+        #[test]
+        fn test_part1(seed: u64, size in 1..20usize) {
+            let input = gen::generator(1).unwrap().generate(size, seed);
+            assert_agrees::<Part1>(&input, &[], part1(&input));
+        }
+
+        #[test]
+        fn test_part2(seed: u64, size in 1..20usize) {
+            let input = gen::generator(1).unwrap().generate(size, seed);
+            assert_agrees::<Part2>(&input, &[], part2(&input));
+        }
+    }
+}
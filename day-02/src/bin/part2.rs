@@ -1,7 +1,9 @@
+use aoclib::input::{self, InputError};
 use day_02::part2::process;
 
-fn main() {
-    let file = include_str!("../../input2.txt");
+fn main() -> Result<(), InputError> {
+    let file = input::load(2, 2)?;
     let result = process(file.lines());
     println!("{}", result);
-}
\ No newline at end of file
+    Ok(())
+}
@@ -1,13 +1,15 @@
 use std::collections::HashMap;
 use nom::{
-    bytes::complete::tag,
-    character::complete::{self, alpha1, digit1},
-    combinator::map_res,
+    character::complete::{self, alpha1},
+    combinator::cut,
+    error::context,
     multi::separated_list1,
     sequence::{preceded, separated_pair},
-    IResult,
 };
 
+use aoclib::parse::{parse_all, tag, ParseResult, Span};
+use aoclib::Error;
+
 #[derive(Eq, PartialEq, Debug)]
 pub struct Round {
     pub red: u32,
@@ -22,26 +24,34 @@ pub struct Game {
 }
 
 // Game 1: 3 red, 4 blue; 5 green, 7 red, 3 blue
-pub fn game(input: &str) -> IResult<&str, Game> {
-    map_res(
+pub fn game(input: Span) -> ParseResult<Game> {
+    let (input, (id, rounds)) = context(
+        "game",
         separated_pair(
-            preceded(tag("Game "), digit1),
+            preceded(tag("Game "), complete::u32),
             tag(": "),
-            separated_list1(tag("; "), round),
+            separated_list1(tag("; "), cut(round)),
         ),
-        |(id_str, rounds)| match id_str.parse() {
-            Ok(id) => Ok(Game { id, rounds }),
-            Err(e) => Err(e),
-        },
-    )(input)
+    )(input)?;
+    Ok((input, Game { id, rounds }))
+}
+
+/// Parses every line of `input` as a whole game.
+pub fn parse_games(input: &str) -> Result<Vec<Game>, Error> {
+    input.lines().enumerate().map(|(index, line)| parse_game(index + 1, line)).collect()
+}
+
+/// Parses the `number`th line of the input, counting from one, as a whole game.
+pub fn parse_game(number: usize, line: &str) -> Result<Game, Error> {
+    parse_all(line, game).map_err(|error| error.on_line(number))
 }
 
 // 3 red, 4 blue
-fn round(input: &str) -> IResult<&str, Round> {
-    let (input, result) = separated_list1(tag(", "), cube)(input).expect("Should parse!");
+fn round(input: Span) -> ParseResult<Round> {
+    let (input, result) = separated_list1(tag(", "), cut(cube))(input)?;
     Ok((
         input,
-        Round::from_hash_map(result.iter().map(|x| (x.1, x.0)).collect()),
+        Round::from_hash_map(result.iter().map(|x| (*x.1.fragment(), x.0)).collect()),
     ))
 }
 
@@ -56,8 +66,8 @@ impl Round {
 }
 
 // 3 red
-fn cube(input: &str) -> IResult<&str, (u32, &str)> {
-    separated_pair(complete::u32, tag(" "), alpha1)(input)
+fn cube(input: Span) -> ParseResult<(u32, Span)> {
+    context("cube", separated_pair(complete::u32, tag(" "), alpha1))(input)
 }
 
 #[cfg(test)]
@@ -67,7 +77,7 @@ mod tests {
     #[test]
     fn test_parse_round() {
         assert_eq!(
-            round("32 red"),
+            round(Span::new("32 red")).map(|(rest, round)| (*rest.fragment(), round)),
             Ok((
                 "",
                 Round {
@@ -78,7 +88,7 @@ mod tests {
             ))
         );
         assert_eq!(
-            round("3 green"),
+            round(Span::new("3 green")).map(|(rest, round)| (*rest.fragment(), round)),
             Ok((
                 "",
                 Round {
@@ -89,7 +99,7 @@ mod tests {
             ))
         );
         assert_eq!(
-            round("126 blue"),
+            round(Span::new("126 blue")).map(|(rest, round)| (*rest.fragment(), round)),
             Ok((
                 "",
                 Round {
@@ -100,7 +110,7 @@ mod tests {
             ))
         );
         assert_eq!(
-            round("126 blue, 23 green, 8 red"),
+            round(Span::new("126 blue, 23 green, 8 red")).map(|(rest, round)| (*rest.fragment(), round)),
             Ok((
                 "",
                 Round {
@@ -112,10 +122,27 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_parse_game_error() {
+        assert_eq!(
+            parse_game(3, "Game 3: 1 blue; 2 green 1 red"),
+            Err(Error::Parse {
+                line: 3,
+                column: 24,
+                snippet: " 1 red".to_string(),
+                expected: "the end of the input".to_string(),
+            })
+        );
+        assert_eq!(
+            parse_game(3, "Game 3: 1 blue; green").unwrap_err().to_string(),
+            "line 3, column 17: expected a number in cube, found \"green\""
+        );
+    }
+
     #[test]
     fn test_parse_game() {
         assert_eq!(
-            game("Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue")
+            game(Span::new("Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue"))
                 .unwrap()
                 .1,
             Game {
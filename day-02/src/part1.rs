@@ -1,43 +1,71 @@
+use std::io::BufRead;
 use std::iter::Iterator;
 use std::fmt::Debug;
 
 use crate::parse::{
-    game,
+    parse_game,
+    parse_games,
+    Game,
     Round
 };
 
+use aoclib::stream;
+use aoclib::{Answer, Error, Solution};
+
 pub fn process<Item: AsRef<str> + Copy, Container: Iterator<Item = Item> + Debug>(
     lines: Container,
 ) -> u32 {
-    lines.map(|line| game(line.as_ref()).expect("Should Parse").1).filter_map(
-        |game| {
-            match game.rounds.iter().all(
-                is_possible
-            ) {
-                false => None,
-                true =>  Some(game.id)
-            }
-        }
-    ).sum()
+    sum_possible(lines.enumerate().map(|(index, line)| {
+        parse_game(index + 1, line.as_ref()).unwrap_or_else(|error| panic!("Should Parse: {}", error))
+    }))
+}
+
+/// Like [`process`], but reports a malformed game as an error.
+pub fn try_process<Item: AsRef<str>, Container: Iterator<Item = Item>>(
+    lines: Container,
+) -> Result<Answer, Error> {
+    lines.enumerate()
+        .map(|(index, line)| parse_game(index + 1, line.as_ref()).map(|game| possible_id(&game).unwrap_or(0)))
+        .sum::<Result<u32, Error>>()
+        .map(Answer::from)
+}
+
+fn sum_possible(games: impl Iterator<Item = Game>) -> u32 {
+    games.filter_map(|game| possible_id(&game)).sum()
+}
+
+/// The game's id, if every round in it is possible.
+fn possible_id(game: &Game) -> Option<u32> {
+    match game.rounds.iter().all(
+        is_possible
+    ) {
+        false => None,
+        true =>  Some(game.id)
+    }
 }
 
 pub fn is_possible(round: &Round) -> bool {
     round.red <= 12 && round.green <= 13 && round.blue <= 14
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_day_01a() {
-        let lines = "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
-Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
-Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
-Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
-Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green"
-            .lines();
-        let result = process(lines);
-        assert_eq!(result, 8);
+pub struct Part1;
+
+impl Solution for Part1 {
+    const DAY: u8 = 2;
+    const PART: u8 = 1;
+
+    type Input<'a> = Vec<Game>;
+    type Params = ();
+
+    fn parse(input: &str) -> Result<Vec<Game>, Error> {
+        parse_games(input)
+    }
+
+    fn solve(input: Vec<Game>, _: &()) -> Result<Answer, Error> {
+        Ok(sum_possible(input.into_iter()).into())
+    }
+
+    fn solve_reader(reader: &mut dyn BufRead, _: &()) -> Result<Option<Answer>, Error> {
+        stream::with_lines(reader, |lines| try_process(lines)).map(Some)
     }
 }
@@ -1,21 +1,39 @@
 use std::cmp::max;
 use std::fmt::Debug;
+use std::io::BufRead;
 use std::iter::Iterator;
 
 use crate::parse::{
-    game,
+    parse_game,
+    parse_games,
     Game,
     Round
 };
 
+use aoclib::stream;
+use aoclib::{Answer, Error, Solution};
+
 
 pub fn process<Item: AsRef<str> + Copy, Container: Iterator<Item = Item> + Debug>(
     lines: Container,
 ) -> u32 {
-    lines.map(|line| game(line.as_ref()).expect("Should Parse").1)
+    lines.enumerate()
+        .map(|(index, line)| {
+            parse_game(index + 1, line.as_ref()).unwrap_or_else(|error| panic!("Should Parse: {}", error))
+        })
         .map(power).sum()
 }
 
+/// Like [`process`], but reports a malformed game as an error.
+pub fn try_process<Item: AsRef<str>, Container: Iterator<Item = Item>>(
+    lines: Container,
+) -> Result<Answer, Error> {
+    lines.enumerate()
+        .map(|(index, line)| parse_game(index + 1, line.as_ref()).map(power))
+        .sum::<Result<u32, Error>>()
+        .map(Answer::from)
+}
+
 fn power(game: Game) -> u32 {
     let max_round = game.rounds.iter().fold(
         Round{red: 0,green: 0,blue: 0},
@@ -30,22 +48,32 @@ fn power(game: Game) -> u32 {
 
 
 
+pub struct Part2;
+
+impl Solution for Part2 {
+    const DAY: u8 = 2;
+    const PART: u8 = 2;
+
+    type Input<'a> = Vec<Game>;
+    type Params = ();
+
+    fn parse(input: &str) -> Result<Vec<Game>, Error> {
+        parse_games(input)
+    }
+
+    fn solve(input: Vec<Game>, _: &()) -> Result<Answer, Error> {
+        Ok(input.into_iter().map(power).sum::<u32>().into())
+    }
+
+    fn solve_reader(reader: &mut dyn BufRead, _: &()) -> Result<Option<Answer>, Error> {
+        stream::with_lines(reader, |lines| try_process(lines)).map(Some)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    #[test]
-    fn test_day_01b() {
-        let lines = "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
-Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
-Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
-Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
-Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green"
-            .lines();
-        let result = process(lines);
-        assert_eq!(result, 2286);
-    }
-
     #[test]
     fn test_game_power() {
         assert_eq!(
@@ -0,0 +1,61 @@
+//! Slow but obviously correct solutions, to check the real ones against on small inputs.
+
+/// The most cubes of each colour, red, green then blue, shown at once in each game.
+fn games(input: &str) -> Vec<(u32, [u32; 3])> {
+    input
+        .lines()
+        .map(|line| {
+            let (game, draws) = line.split_once(": ").unwrap();
+            let id = game.trim_start_matches("Game ").parse().unwrap();
+            let mut most = [0; 3];
+            for cubes in draws.split([';', ',']) {
+                let (count, colour) = cubes.trim().split_once(' ').unwrap();
+                let index = ["red", "green", "blue"]
+                    .iter()
+                    .position(|c| *c == colour)
+                    .unwrap();
+                most[index] = most[index].max(count.parse().unwrap());
+            }
+            (id, most)
+        })
+        .collect()
+}
+
+pub fn part1(input: &str) -> u32 {
+    games(input)
+        .into_iter()
+        .filter(|(_, [red, green, blue])| *red <= 12 && *green <= 13 && *blue <= 14)
+        .map(|(id, _)| id)
+        .sum()
+}
+
+pub fn part2(input: &str) -> u32 {
+    games(input)
+        .into_iter()
+        .map(|(_, most)| most.iter().product::<u32>())
+        .sum()
+}
+
+mod tests {
+    use aoclib::gen;
+    use aoclib::reference::assert_agrees;
+    use proptest::prelude::*;
+
+    use super::*;
+    use crate::part1::Part1;
+    use crate::part2::Part2;
+
+    proptest! {
+        #[test]
+        fn test_part1(seed: u64, size in 1..20usize) {
+            let input = gen::generator(2).unwrap().generate(size, seed);
+            assert_agrees::<Part1>(&input, &[], part1(&input));
+        }
+
+        #[test]
+        fn test_part2(seed: u64, size in 1..20usize) {
+            let input = gen::generator(2).unwrap().generate(size, seed);
+            assert_agrees::<Part2>(&input, &[], part2(&input));
+        }
+    }
+}
@@ -1,7 +1,9 @@
+use aoclib::input::{self, InputError};
 use day_03::part1::process;
 
-fn main() {
-    let file = include_str!("../../input.txt");
-    let result = process(file);
+fn main() -> Result<(), InputError> {
+    let file = input::load(3, 1)?;
+    let result = process(&file);
     println!("{}", result);
-}
\ No newline at end of file
+    Ok(())
+}
@@ -0,0 +1,96 @@
+use aoclib::{Error, Grid};
+
+/// A number in the schematic, with the column and row of its first digit.
+#[derive(Debug, PartialEq)]
+pub struct Number {
+    pub value: u32,
+    pub length: isize,
+    pub position: (isize, isize),
+}
+
+/// The numbers in the engine schematic, the symbols by where they are, and the schematic's width
+/// and height.
+#[derive(Debug)]
+pub struct Schematic {
+    pub numbers: Vec<Number>,
+    pub symbols: Vec<((isize, isize), char)>,
+    pub upper_bounds: (isize, isize),
+}
+
+/// Parses `input` as a rectangle of printable characters, whose numbers each fit in a `u32`.
+pub fn parse_schematic(input: &str) -> Result<Schematic, Error> {
+    let grid = Grid::try_parse(input, "a digit, a symbol or '.'", |c| c.is_ascii_graphic().then_some(c))?;
+
+    let mut numbers = vec![];
+    for (y, line) in input.lines().enumerate() {
+        for (digits, x) in get_numbers_in_line(line) {
+            let value = digits.parse().map_err(|_| Error::parse(input, digits, "a number which fits in 32 bits"))?;
+            numbers.push(Number { value, length: digits.len() as isize, position: (x, y as isize) });
+        }
+    }
+
+    let symbols = grid.iter()
+        .filter(|(_, c)| **c != '.' && !c.is_ascii_digit())
+        .map(|(location, c)| ((location.x as isize, location.y as isize), *c))
+        .collect();
+
+    Ok(Schematic { numbers, symbols, upper_bounds: (grid.width() as isize, grid.height() as isize) })
+}
+
+fn get_numbers_in_line(line: &str) -> Vec<(&str, isize)> {
+    let mut chars = line.chars().peekable();
+    let mut tuples = Vec::<(&str, isize)>::new();
+
+    let mut position: isize = 0;
+    while let Some(c) = chars.next() {
+        if c.is_ascii_digit() {
+            let start = position;
+            for c in chars.by_ref() {
+                position += 1;
+                if ! c.is_ascii_digit() {
+                    break;
+                }
+            }
+            if chars.peek().is_none() && line.ends_with(|c: char| c.is_ascii_digit()) {
+                position += 1;
+            }
+            tuples.push((&line[start as usize..position as usize], start));
+        }
+        position += 1;
+    }
+
+    tuples
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_get_numbers_in_line() {
+        assert_eq!(
+            get_numbers_in_line("467..114.."),
+            vec![("467", 0), ("114", 5)]
+        );
+    }
+
+    #[test]
+    fn test_get_numbers_at_end_of_line() {
+        assert_eq!(
+            get_numbers_in_line("467..114"),
+            vec![("467", 0), ("114", 5)]
+        );
+    }
+
+    #[test]
+    fn test_parse_schematic() {
+        let schematic = parse_schematic("467..\n..*..\n").unwrap();
+        assert_eq!(schematic.numbers, vec![Number { value: 467, length: 3, position: (0, 0) }]);
+        assert_eq!(schematic.symbols, vec![((2, 1), '*')]);
+        assert_eq!(schematic.upper_bounds, (5, 2));
+        assert_eq!(
+            parse_schematic("1..........\n.*.........\n.9876543210\n").unwrap_err().to_string(),
+            "line 3, column 2: expected a number which fits in 32 bits, found \"9876543210\""
+        );
+    }
+}
@@ -0,0 +1,99 @@
+//! Slow but obviously correct solutions, to check the real ones against on small inputs.
+
+use std::ops::Range;
+
+/// Each number in the schematic, with the row it is on and the columns it covers.
+fn numbers(grid: &[Vec<char>]) -> Vec<(u32, usize, Range<usize>)> {
+    let mut numbers = vec![];
+    for (y, row) in grid.iter().enumerate() {
+        let mut x = 0;
+        while x < row.len() {
+            let start = x;
+            while x < row.len() && row[x].is_ascii_digit() {
+                x += 1;
+            }
+            if x > start {
+                let number = row[start..x].iter().collect::<String>().parse().unwrap();
+                numbers.push((number, y, start..x));
+            } else {
+                x += 1;
+            }
+        }
+    }
+    numbers
+}
+
+/// Every tile touching the number on row `y` covering `columns`, diagonally included.
+fn around(grid: &[Vec<char>], y: usize, columns: &Range<usize>) -> Vec<(usize, usize)> {
+    let rows = y.saturating_sub(1)..=y + 1;
+    let mut tiles = vec![];
+    for (ny, row) in grid.iter().enumerate().filter(|(ny, _)| rows.contains(ny)) {
+        for nx in columns.start.saturating_sub(1)..=columns.end.min(row.len() - 1) {
+            tiles.push((nx, ny));
+        }
+    }
+    tiles
+}
+
+fn grid(input: &str) -> Vec<Vec<char>> {
+    input.lines().map(|line| line.chars().collect()).collect()
+}
+
+pub fn part1(input: &str) -> u32 {
+    let grid = grid(input);
+    numbers(&grid)
+        .into_iter()
+        .filter(|(_, y, columns)| {
+            around(&grid, *y, columns)
+                .into_iter()
+                .any(|(x, y)| grid[y][x] != '.' && !grid[y][x].is_ascii_digit())
+        })
+        .map(|(number, _, _)| number)
+        .sum()
+}
+
+pub fn part2(input: &str) -> u32 {
+    let grid = grid(input);
+    let numbers = numbers(&grid);
+    let mut total = 0;
+    for (y, row) in grid.iter().enumerate() {
+        for (x, tile) in row.iter().enumerate() {
+            if *tile != '*' {
+                continue;
+            }
+            let touching: Vec<u32> = numbers
+                .iter()
+                .filter(|(_, ny, columns)| around(&grid, *ny, columns).contains(&(x, y)))
+                .map(|(number, _, _)| *number)
+                .collect();
+            if touching.len() == 2 {
+                total += touching[0] * touching[1];
+            }
+        }
+    }
+    total
+}
+
+mod tests {
+    use aoclib::gen;
+    use aoclib::reference::assert_agrees;
+    use proptest::prelude::*;
+
+    use super::*;
+    use crate::part1::Part1;
+    use crate::part2::Part2;
+
+    proptest! {
+        #[test]
+        fn test_part1(seed: u64, size in 1..16usize) {
+            let input = gen::generator(3).unwrap().generate(size, seed);
+            assert_agrees::<Part1>(&input, &[], part1(&input));
+        }
+
+        #[test]
+        fn test_part2(seed: u64, size in 1..16usize) {
+            let input = gen::generator(3).unwrap().generate(size, seed);
+            assert_agrees::<Part2>(&input, &[], part2(&input));
+        }
+    }
+}
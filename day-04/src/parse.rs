@@ -1,4 +1,7 @@
-use nom::{multi::separated_list1, IResult, character::complete::{self, multispace1}, sequence::{separated_pair, pair, delimited}, bytes::complete::tag, combinator::map};
+use nom::{multi::separated_list1, character::complete::{self, space1}, sequence::{separated_pair, pair, delimited}, combinator::map, error::context};
+
+use aoclib::parse::{lines, parse_all, tag, ParseResult, Span};
+use aoclib::Error;
 
 #[derive(PartialEq, Debug, Clone)]
 pub struct Card {
@@ -8,34 +11,43 @@ pub struct Card {
 }
 
 // 83 86  6 31 17  9 48 53
-fn list_of_u32(input: &str) -> IResult<&str,Vec<u32>> {
-    separated_list1(multispace1, complete::u32)(input)
+fn list_of_u32(input: Span) -> ParseResult<Vec<u32>> {
+    separated_list1(space1, complete::u32)(input)
 }
 
 // 41 48 83 86 17 | 83 86  6 31 17  9 48 53
-fn numbers(input: &str) -> IResult<&str, (Vec<u32>,Vec<u32>)> {
+fn numbers(input: Span) -> ParseResult<(Vec<u32>,Vec<u32>)> {
     separated_pair(
         list_of_u32,
-        pair(tag(" |"), multispace1),
+        pair(tag(" |"), space1),
         list_of_u32
     )(input)
 }
 
 
 // Card 1
-fn card_id(input: &str) -> IResult<&str, u32> {
-    delimited(pair(tag("Card"), multispace1), complete::u32, pair(tag(":"), multispace1))(input)
+fn card_id(input: Span) -> ParseResult<u32> {
+    delimited(pair(tag("Card"), space1), complete::u32, pair(tag(":"), space1))(input)
 }
 
 // Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
-pub fn card(input: &str) -> IResult<&str, Card> {
-    map (
+pub fn card(input: Span) -> ParseResult<Card> {
+    context("card", map (
         pair(
             card_id,
             numbers
         ),
         |(id, (winning_numbers, numbers))| Card{id, winning_numbers, numbers}
-    )(input)
+    ))(input)
+}
+
+pub fn parse_cards(input: &str) -> Result<Vec<Card>, Error> {
+    parse_all(input, lines(card))
+}
+
+/// Parses the `number`th line of the input, counting from one, as a whole card.
+pub fn parse_card(number: usize, line: &str) -> Result<Card, Error> {
+    parse_all(line, card).map_err(|error| error.on_line(number))
 }
 
 #[cfg(test)]
@@ -45,7 +57,7 @@ mod tests {
     #[test]
     fn test_list_of_u32() {
         assert_eq!(
-            list_of_u32("83 86  6 31 17  9 48 53").expect("should parse"),
+            list_of_u32(Span::new("83 86  6 31 17  9 48 53")).map(|(rest, list)| (*rest.fragment(), list)).expect("should parse"),
             ("", vec![83,86,6,31,17,9,48,53])
         )
     }
@@ -53,7 +65,7 @@ mod tests {
     #[test]
     fn test_numbers() {
         assert_eq!(
-            numbers("41 48 83 86 17 | 83 86  6 31 17  9 48 53").expect("should parse"),
+            numbers(Span::new("41 48 83 86 17 | 83 86  6 31 17  9 48 53")).map(|(rest, numbers)| (*rest.fragment(), numbers)).expect("should parse"),
             ("", (vec![41,48,83,86,17], vec![83,86,6,31,17,9,48,53]))
         )
     }
@@ -61,7 +73,7 @@ mod tests {
     #[test]
     fn test_card_id() {
         assert_eq!(
-            card_id("Card 1: ").expect("Should parse"),
+            card_id(Span::new("Card 1: ")).map(|(rest, id)| (*rest.fragment(), id)).expect("Should parse"),
             ("", 1)
         )
     }
@@ -69,7 +81,7 @@ mod tests {
     #[test]
     fn test_card() {
         assert_eq!(
-            card("Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53").expect("it to parse"),
+            card(Span::new("Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53")).map(|(rest, card)| (*rest.fragment(), card)).expect("it to parse"),
             ("",
             Card{
                 id: 1,
@@ -79,4 +91,20 @@ mod tests {
         )
     }
 
+    #[test]
+    fn test_parse_cards() {
+        let input = "Card 1: 41 48 | 83 86
+Card 2: 13 32 | 61 30
+Card 3: 1 21 53 59 44
+";
+        assert_eq!(
+            parse_cards(input),
+            Err(Error::Parse {
+                line: 3,
+                column: 22,
+                snippet: "".to_string(),
+                expected: "\" |\" in card".to_string(),
+            })
+        );
+    }
 }
@@ -1,10 +1,27 @@
+use std::io::BufRead;
 use std::iter::Iterator;
 
-use crate::parse::{card, Card};
+use crate::parse::{parse_card, parse_cards, Card};
+
+use aoclib::stream;
+use aoclib::{Answer, Error, Solution};
 
 pub fn process( input: &str ) -> u32 {
-    let lines = input.lines();
-    lines.map(|line| card(line).expect("should parse").1).map(points).sum()
+    let cards = parse_cards(input).unwrap_or_else(|error| panic!("should parse: {}", error));
+    cards.into_iter().map(points).sum()
+}
+
+/// Like [`process`], but reports a malformed card as an error.
+pub fn try_process(input: &str) -> Result<Answer, Error> {
+    Ok(parse_cards(input)?.into_iter().map(points).sum::<u32>().into())
+}
+
+/// Like [`try_process`], but for the input a line at a time, so it needn't all be in memory.
+pub fn try_process_lines<Line: AsRef<str>>(lines: impl Iterator<Item = Line>) -> Result<Answer, Error> {
+    lines.enumerate()
+        .map(|(index, line)| parse_card(index + 1, line.as_ref()).map(points))
+        .sum::<Result<u32, Error>>()
+        .map(Answer::from)
 }
 
 fn points(card: Card) -> u32 {
@@ -18,6 +35,28 @@ fn points(card: Card) -> u32 {
     if number_of_winning_numbers == 0 { 0 } else { 2u32.pow(number_of_winning_numbers - 1) }
 }
 
+pub struct Part1;
+
+impl Solution for Part1 {
+    const DAY: u8 = 4;
+    const PART: u8 = 1;
+
+    type Input<'a> = Vec<Card>;
+    type Params = ();
+
+    fn parse(input: &str) -> Result<Vec<Card>, Error> {
+        parse_cards(input)
+    }
+
+    fn solve(input: Vec<Card>, _: &()) -> Result<Answer, Error> {
+        Ok(input.into_iter().map(points).sum::<u32>().into())
+    }
+
+    fn solve_reader(reader: &mut dyn BufRead, _: &()) -> Result<Option<Answer>, Error> {
+        stream::with_lines(reader, |lines| try_process_lines(lines)).map(Some)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -33,16 +72,4 @@ mod tests {
             8
         )
     }
-
-    #[test]
-    fn test_day_01a() {
-        let lines = "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
-Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19
-Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1
-Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83
-Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
-Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11";
-        let result = process(lines);
-        assert_eq!(result, 13);
-    }
 }
@@ -1,22 +1,77 @@
-use std::iter::{Iterator, repeat};
+use std::collections::VecDeque;
+use std::io::BufRead;
+use std::iter::Iterator;
 
-use crate::parse::{card, Card};
+use crate::parse::{parse_card, parse_cards, Card};
+
+use aoclib::stream;
+use aoclib::{Answer, Error, Solution};
 
 pub fn process( input: &str ) -> u32 {
-    let lines = input.lines();
-    let mut counts : Vec<usize> = repeat(1).take(lines.clone().count() + 1).collect();
-    let cards = lines
-        .map(|line| card(line).expect("should parse").1);
-    
-    for card in cards {
-        let number_of_winning_numbers = number_of_winning_numbers(&card);
-        let number_of_current_card = counts[card.id as usize];
-        for card_to_copy in (card.id + 1)..(card.id + number_of_winning_numbers + 1) {
-            counts[card_to_copy as usize] += number_of_current_card
+    let cards = parse_numbered_cards(input).unwrap_or_else(|error| panic!("should parse: {}", error));
+
+    count_cards(cards.into_iter()) as u32
+}
+
+/// The number of cards won, including the originals, which must be numbered in order from one.
+fn count_cards(cards: impl Iterator<Item = Card>) -> usize {
+    let mut copies = VecDeque::new();
+    cards.map(|card| count_card(&card, &mut copies)).sum()
+}
+
+/// The number of copies of `card` there are, including the original, given `copies` of each of
+/// the cards from it onwards won so far. Only as many cards ahead as can be won from one card are
+/// kept track of, so the cards can be counted as they are read.
+fn count_card(card: &Card, copies: &mut VecDeque<usize>) -> usize {
+    let number_of_current_card = 1 + copies.pop_front().unwrap_or(0);
+    for card_to_copy in 0..number_of_winning_numbers(card) as usize {
+        match copies.get_mut(card_to_copy) {
+            Some(count) => *count += number_of_current_card,
+            None => copies.push_back(number_of_current_card),
         }
     }
+    number_of_current_card
+}
+
+/// Like [`process`], but reports a malformed card, or cards which are not numbered in order
+/// from one, as an error.
+pub fn try_process(input: &str) -> Result<Answer, Error> {
+    total_cards(parse_numbered_cards(input)?)
+}
+
+/// Parses `input` as cards numbered in order from one.
+fn parse_numbered_cards(input: &str) -> Result<Vec<Card>, Error> {
+    let cards = parse_cards(input)?;
+    for ((index, line), card) in input.lines().enumerate().zip(&cards) {
+        if card.id as usize != index + 1 {
+            return Err(Error::parse(input, line, format!("card {}", index + 1)));
+        }
+    }
+    Ok(cards)
+}
 
-    counts.iter().sum::<usize>() as u32 - 1
+fn total_cards(cards: Vec<Card>) -> Result<Answer, Error> {
+    u32::try_from(count_cards(cards.into_iter()))
+        .map(Answer::from)
+        .map_err(|_| Error::Overflow("the number of scratchcards".to_string()))
+}
+
+/// Like [`try_process`], but for the input a line at a time, so it needn't all be in memory.
+pub fn try_process_lines<Line: AsRef<str>>(lines: impl Iterator<Item = Line>) -> Result<Answer, Error> {
+    let mut copies = VecDeque::new();
+    let count = lines.enumerate()
+        .map(|(index, line)| {
+            let line = line.as_ref();
+            let card = parse_card(index + 1, line)?;
+            if card.id as usize != index + 1 {
+                return Err(Error::parse_line(index + 1, line, line, format!("card {}", index + 1)));
+            }
+            Ok(count_card(&card, &mut copies))
+        })
+        .sum::<Result<usize, Error>>()?;
+    u32::try_from(count)
+        .map(Answer::from)
+        .map_err(|_| Error::Overflow("the number of scratchcards".to_string()))
 }
 
 fn number_of_winning_numbers<'a>(card: &'a Card) -> u32 {
@@ -27,6 +82,28 @@ fn number_of_winning_numbers<'a>(card: &'a Card) -> u32 {
     .count() as u32
 }
 
+pub struct Part2;
+
+impl Solution for Part2 {
+    const DAY: u8 = 4;
+    const PART: u8 = 2;
+
+    type Input<'a> = Vec<Card>;
+    type Params = ();
+
+    fn parse(input: &str) -> Result<Vec<Card>, Error> {
+        parse_numbered_cards(input)
+    }
+
+    fn solve(input: Vec<Card>, _: &()) -> Result<Answer, Error> {
+        total_cards(input)
+    }
+
+    fn solve_reader(reader: &mut dyn BufRead, _: &()) -> Result<Option<Answer>, Error> {
+        stream::with_lines(reader, |lines| try_process_lines(lines)).map(Some)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -44,14 +121,11 @@ mod tests {
     }
 
     #[test]
-    fn test_day_01a() {
-        let lines = "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
-Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19
-Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1
-Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83
-Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
-Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11";
-        let result = process(lines);
-        assert_eq!(result, 30);
+    fn test_try_process_lines() {
+        let lines = ["Card 1: 41 48 | 48 86", "Card 3: 13 32 | 61 30"];
+        assert_eq!(
+            try_process_lines(lines.iter()).unwrap_err().to_string(),
+            "line 2, column 1: expected card 2, found \"Card 3: 13 32 | 61 3\""
+        );
     }
 }
@@ -0,0 +1,63 @@
+//! Slow but obviously correct solutions, to check the real ones against on small inputs.
+
+use std::collections::VecDeque;
+
+/// How many of each card's numbers are winning numbers.
+fn wins(input: &str) -> Vec<usize> {
+    input
+        .lines()
+        .map(|line| {
+            let (_, numbers) = line.split_once(':').unwrap();
+            let (winning, have) = numbers.split_once('|').unwrap();
+            let winning: Vec<&str> = winning.split_whitespace().collect();
+            have.split_whitespace()
+                .filter(|number| winning.contains(number))
+                .count()
+        })
+        .collect()
+}
+
+pub fn part1(input: &str) -> u32 {
+    wins(input)
+        .into_iter()
+        .filter(|wins| *wins > 0)
+        .map(|wins| 2u32.pow(wins as u32 - 1))
+        .sum()
+}
+
+/// Scratches every card one at a time, queueing up each copy won.
+pub fn part2(input: &str) -> u32 {
+    let wins = wins(input);
+    let mut queue: VecDeque<usize> = (0..wins.len()).collect();
+    let mut scratched = 0;
+    while let Some(card) = queue.pop_front() {
+        scratched += 1;
+        queue.extend(card + 1..=card + wins[card]);
+    }
+    scratched
+}
+
+mod tests {
+    use aoclib::gen;
+    use aoclib::reference::assert_agrees;
+    use proptest::prelude::*;
+
+    use super::*;
+    use crate::part1::Part1;
+    use crate::part2::Part2;
+
+    proptest! {
+        #[test]
+        fn test_part1(seed: u64, size in 1..20usize) {
+            let input = gen::generator(4).unwrap().generate(size, seed);
+            assert_agrees::<Part1>(&input, &[], part1(&input));
+        }
+
+        // Every copy is scratched one by one, so the cards are kept few.
+        #[test]
+        fn test_part2(seed: u64, size in 1..10usize) {
+            let input = gen::generator(4).unwrap().generate(size, seed);
+            assert_agrees::<Part2>(&input, &[], part2(&input));
+        }
+    }
+}
@@ -1,33 +1,59 @@
 use std::ops::RangeInclusive;
 
 use nom::{
-    bytes::complete::tag,
     character::complete::{self, alpha1, newline, space1},
+    error::context,
     multi::{separated_list1, many1},
     sequence::{pair, preceded, separated_pair, terminated, tuple},
-    IResult,
 };
+use tracing::trace;
+
+use aoclib::parse::{parse_prefix, tag, ParseError, ParseResult, Span};
+use aoclib::{Answer, Error, Solution};
 
 pub fn process(input: &str) -> u64 {
-    let (_, (seeds, range_maps)) = parse_input(input).expect("Should Parse");
-    seeds.into_iter().map(|value| follow_map(value, "seed", range_maps.clone()).0).min().unwrap()
+    let (seeds, range_maps) = parse_almanac(input).unwrap_or_else(|error| panic!("Should Parse: {}", error));
+    lowest_location(seeds, range_maps).expect("the locations should fit in 64 bits")
+}
+
+fn lowest_location(seeds: Vec<u64>, range_maps: Vec<RangeMap>) -> Result<u64, Error> {
+    let locations = seeds.into_iter()
+        .map(|value| Ok(follow_map(value, "seed", range_maps.clone())?.0))
+        .collect::<Result<Vec<_>, Error>>()?;
+    locations.into_iter().min().ok_or_else(|| Error::NoSolution("there are no seeds".to_string()))
+}
+
+/// Like [`process`], but reports a malformed almanac, including an empty range or one which does
+/// not fit in 64 bits, or a number mapped beyond 64 bits, as an error.
+pub fn try_process(input: &str) -> Result<Answer, Error> {
+    let (seeds, range_maps) = parse_almanac(input)?;
+    Ok(lowest_location(seeds, range_maps)?.into())
+}
+
+/// Parses `input` as the seeds and the maps, with nothing after the last map.
+fn parse_almanac(input: &str) -> Result<(Vec<u64>, Vec<RangeMap<'_>>), Error> {
+    let (rest, almanac) = parse_prefix(input, parse_input)?;
+    if !rest.fragment().trim().is_empty() {
+        return Err(Error::parse(input, rest.fragment().trim_start(), "another map"));
+    }
+    Ok(almanac)
 }
 
-fn follow_map<'a>(value: u64, destination: &'a str, range_maps: Vec<RangeMap<'a>>) -> (u64, &'a str) {
-    print!("{} {:10} ", destination, value);
+fn follow_map<'a>(value: u64, destination: &'a str, range_maps: Vec<RangeMap<'a>>) -> Result<(u64, &'a str), Error> {
+    trace!(category = destination, value, "followed the maps");
     if let Some(range_map) = range_maps.iter().find(|range_map| range_map.source == destination) {
-        let new_value = range_map.get(value);
+        let new_value = range_map.get(value)?;
         let new_destination = range_map.destination;
         follow_map(new_value, new_destination, range_maps)
     } else {
-        println!();
-        (value, destination)
+        Ok((value, destination))
     }
 }
 
 
+/// One of the almanac's maps, from its source category to its destination.
 #[derive(Debug, PartialEq, Clone)]
-struct RangeMap<'a> {
+pub struct RangeMap<'a> {
     pub source: &'a str,
     pub destination: &'a str,
     data: Vec<(RangeInclusive<u64>, u64)>,
@@ -48,19 +74,19 @@ impl RangeMap<'_> {
         self.data.push((range, value));
     }
 
-    pub fn get(&self, index: u64) -> u64 {
+    /// The number `index` maps to, or an error if that does not fit in 64 bits.
+    pub fn get(&self, index: u64) -> Result<u64, Error> {
         for (inclusive_range, value) in self.data.iter() {
             if inclusive_range.contains(&index) {
-                if let Some(min) = inclusive_range.clone().min() {
-                    return value + (index - min);
-                }
+                return value.checked_add(index - inclusive_range.start())
+                    .ok_or_else(|| Error::Overflow("a mapped number".to_string()));
             }
         }
-        index
+        Ok(index)
     }
 }
 
-fn parse_input(input: &str) -> IResult<&str, (Vec<u64>, Vec<RangeMap>)> {
+fn parse_input(input: Span) -> ParseResult<(Vec<u64>, Vec<RangeMap>)> {
     separated_pair(
         seeds, 
         pair(
@@ -76,23 +102,23 @@ fn parse_input(input: &str) -> IResult<&str, (Vec<u64>, Vec<RangeMap>)> {
 }
 
 // seeds: 79 14 55 13
-fn seeds(input: &str) -> IResult<&str, Vec<u64>> {
-    preceded(
+fn seeds(input: Span) -> ParseResult<Vec<u64>> {
+    context("seeds", preceded(
         tag("seeds: "),
         separated_list1(
             space1,
             complete::u64
         )
-    )
+    ))
     (input)
 }
 
-fn range_map(input: &str) -> IResult<&str, RangeMap> {
-    let (input, ((source, destination), inclusive_range_values)) = separated_pair(
+fn range_map(input: Span) -> ParseResult<RangeMap> {
+    let (input, ((source, destination), inclusive_range_values)) = context("map", separated_pair(
         range_map_title,
         newline,
         many1(inclusive_range_value),
-    )(input)?;
+    ))(input)?;
 
     let mut range_map = RangeMap {
         source,
@@ -108,24 +134,51 @@ fn range_map(input: &str) -> IResult<&str, RangeMap> {
 }
 
 // seed-to-soil map:
-fn range_map_title(input: &str) -> IResult<&str, (&str, &str)> {
-    terminated(
+fn range_map_title<'a>(input: Span<'a>) -> ParseResult<'a, (&'a str, &'a str)> {
+    let (input, (source, destination)) = terminated(
         separated_pair(alpha1, tag("-to-"), alpha1),
         pair(space1, tag("map:")),
-    )(input)
+    )(input)?;
+    Ok((input, (*source.fragment(), *destination.fragment())))
 }
 
 // 50 98 2
-fn inclusive_range_value(input: &str) -> IResult<&str, (RangeInclusive<u64>, u64)> {
-    let (input, (value, min, length)) = tuple((terminated(complete::u64, space1), terminated(complete::u64, space1), terminated(complete::u64, newline)))(input)?;
-
-    Ok((
-        input,
-        (
-            min..=(min + length - 1),
-            value,
-        ),
-    ))
+fn inclusive_range_value(input: Span) -> ParseResult<(RangeInclusive<u64>, u64)> {
+    let (input, (value, range)) = tuple((terminated(complete::u64, space1), terminated(range, newline)))(input)?;
+
+    Ok((input, (range, value)))
+}
+
+// 98 2
+fn range(input: Span) -> ParseResult<RangeInclusive<u64>> {
+    let (rest, (min, length)) = separated_pair(complete::u64, space1, complete::u64)(input)?;
+    let expected = if length == 0 {
+        "a range of at least one number"
+    } else if let Some(max) = min.checked_add(length - 1) {
+        return Ok((rest, min..=max));
+    } else {
+        "a range which fits in 64 bits"
+    };
+    Err(nom::Err::Error(ParseError::new(input, expected)))
+}
+
+pub struct Part1;
+
+impl Solution for Part1 {
+    const DAY: u8 = 5;
+    const PART: u8 = 1;
+
+    type Input<'a> = (Vec<u64>, Vec<RangeMap<'a>>);
+    type Params = ();
+
+    fn parse(input: &str) -> Result<(Vec<u64>, Vec<RangeMap<'_>>), Error> {
+        parse_almanac(input)
+    }
+
+    fn solve(input: (Vec<u64>, Vec<RangeMap<'_>>), _: &()) -> Result<Answer, Error> {
+        let (seeds, range_maps) = input;
+        Ok(lowest_location(seeds, range_maps)?.into())
+    }
 }
 
 #[cfg(test)]
@@ -134,19 +187,19 @@ mod tests {
 
     #[test]
     fn test_seeds() {
-        let result = seeds("seeds: 79 14 55 13").expect("Should parse");
-        assert_eq!(result.0, "");
+        let result = seeds(Span::new("seeds: 79 14 55 13")).expect("Should parse");
+        assert_eq!(*result.0.fragment(), "");
         assert_eq!(result.1, vec![79, 14, 55, 13]);
-        let result = seeds("seeds: 79 14 55 13
+        let result = seeds(Span::new("seeds: 79 14 55 13
 
 seed-to-soil map:
 50 98 2
-52 50 48").expect("Should parse");
+52 50 48")).expect("Should parse");
     }
 
     #[test]
     fn test_input() {
-        let result = separated_list1(newline, range_map)("seed-to-soil map:
+        let result = separated_list1(newline, range_map)(Span::new("seed-to-soil map:
 50 98 2
 52 50 48
 
@@ -176,25 +229,25 @@ temperature-to-humidity map:
 
 humidity-to-location map:
 60 56 37
-56 93 4").expect("should parse");
+56 93 4")).expect("should parse");
     }
 
     #[test]
     fn test_range_map_again() {
-        let (input, result) = range_map(
+        let (input, result) = range_map(Span::new(
             "seed-to-soil map:
 50 98 2
 52 50 48
 ",
-        )
+        ))
         .expect("should parse");
-        assert_eq!(input, "");
+        assert_eq!(*input.fragment(), "");
         dbg!(result);
     }
     
     #[test]
     fn test_range_map() {
-        let (input, result) = range_map(
+        let (input, result) = range_map(Span::new(
             "seed-to-soil map:
 3788621315 24578909 268976974
 3633843608 2672619957 154777707
@@ -207,17 +260,17 @@ humidity-to-location map:
 2329903325 2629085177 43534780
 24578909 2475091832 153993345
 ",
-        )
+        ))
         .expect("should parse");
-        assert_eq!(input, "");
+        assert_eq!(*input.fragment(), "");
         dbg!(&result);
-        dbg!(result.get(929142010));
+        dbg!(result.get(929142010).unwrap());
     }
 
     #[test]
     fn test_range_map_title() {
-        let result = range_map_title("seed-to-soil map:").expect("should parse");
-        assert_eq!(result.0, "");
+        let result = range_map_title(Span::new("seed-to-soil map:")).expect("should parse");
+        assert_eq!(*result.0.fragment(), "");
         assert_eq!(result.1, ("seed", "soil"));
     }
 
@@ -230,49 +283,42 @@ humidity-to-location map:
             52,
         );
 
-        assert_eq!(under_test.get(98), 50);
-        assert_eq!(under_test.get(99), 51);
+        assert_eq!(under_test.get(98), Ok(50));
+        assert_eq!(under_test.get(99), Ok(51));
+
+        assert_eq!(under_test.get(53), Ok(55));
+        assert_eq!(under_test.get(10), Ok(10));
 
-        assert_eq!(under_test.get(53), 55);
-        assert_eq!(under_test.get(10), 10);
+        under_test.insert(0..=1, u64::MAX);
+        assert_eq!(under_test.get(1), Err(Error::Overflow("a mapped number".to_string())));
     }
 
     #[test]
-    fn test_process() {
-        let lines = "seeds: 79 14 55 13
+    fn test_try_process() {
+        let input = "seeds: 79 14 55 13
 
 seed-to-soil map:
 50 98 2
-52 50 48
+52 50
 
 soil-to-fertilizer map:
 0 15 37
-37 52 2
-39 0 15
-
-fertilizer-to-water map:
-49 53 8
-0 11 42
-42 0 7
-57 7 4
-
-water-to-light map:
-88 18 7
-18 25 70
-
-light-to-temperature map:
-45 77 23
-81 45 19
-68 64 13
-
-temperature-to-humidity map:
-0 69 1
-1 0 69
-
-humidity-to-location map:
-60 56 37
-56 93 4";
-        let result = process(lines);
-        assert_eq!(result, 35);
+";
+        assert_eq!(
+            try_process(input).unwrap_err().to_string(),
+            "line 5, column 1: expected another map, found \"52 50\""
+        );
+        assert_eq!(
+            try_process("seeds: x\n").unwrap_err().to_string(),
+            "line 1, column 8: expected a number in seeds, found \"x\""
+        );
+        assert_eq!(
+            try_process("seeds: 79\n\nseed-to-soil map:\n50 98 0\n").unwrap_err().to_string(),
+            "line 4, column 4: expected a range of at least one number in map, found \"98 0\""
+        );
+        assert_eq!(
+            try_process("seeds: 79\n\nseed-to-soil map:\n50 18446744073709551615 2\n").unwrap_err().to_string(),
+            "line 4, column 4: expected a range which fits in 64 bits in map, found \"18446744073709551615\""
+        );
     }
 }
@@ -1,38 +1,55 @@
 use std::ops::RangeInclusive;
 
 use nom::{
-    bytes::complete::tag,
     character::complete::{self, alpha1, newline, space1},
-    multi::{separated_list1, many1},
+    error::context,
+    multi::separated_list1,
     sequence::{pair, preceded, separated_pair, terminated, tuple},
-    IResult,
 };
 
+use aoclib::parallel::prelude::*;
+use aoclib::parse::{parse_all, tag, ParseError, ParseResult, Span};
+use aoclib::{Answer, Error, Solution};
+
 pub fn process(input: &str) -> u64 {
-    let (_, (seeds, range_maps)) = parse_input(input).expect("Should Parse");
-    *seeds.into_iter().flat_map(|value|
-        follow_map(vec![value], "seed", range_maps.clone()).0
-    )
-    .min_by(|a, b| a.start().cmp(b.start()))
-    .unwrap()
-    .start()
+    let (seeds, range_maps) = parse_all(input, parse_input).unwrap_or_else(|error| panic!("Should Parse: {}", error));
+    lowest_location(seeds, range_maps).expect("the locations should fit in 64 bits")
+}
+
+fn lowest_location(seeds: Vec<RangeInclusive<u64>>, range_maps: Vec<RangeMap>) -> Result<u64, Error> {
+    let locations = seeds.into_maybe_par_iter()
+        .map(|value| Ok(follow_map(vec![value], "seed", range_maps.clone())?.0))
+        .collect::<Result<Vec<_>, Error>>()?;
+    locations.into_iter().flatten().map(|range| *range.start()).min()
+        .ok_or_else(|| Error::NoSolution("there are no seeds".to_string()))
 }
 
-fn follow_map<'a>(ranges: Vec<RangeInclusive<u64>>, destination: &'a str, range_maps: Vec<RangeMap<'a>>) -> (Vec<RangeInclusive<u64>>, &'a str) {
+/// Like [`process`], but reports a malformed almanac, including an empty range or one which does
+/// not fit in 64 bits, or a number mapped beyond 64 bits, as an error.
+pub fn try_process(input: &str) -> Result<Answer, Error> {
+    let (seeds, range_maps) = parse_all(input, parse_input)?;
+    Ok(lowest_location(seeds, range_maps)?.into())
+}
+
+fn follow_map<'a>(ranges: Vec<RangeInclusive<u64>>, destination: &'a str, range_maps: Vec<RangeMap<'a>>) -> Result<(Vec<RangeInclusive<u64>>, &'a str), Error> {
     // print!("{} {:10} ", destination, value);
     if let Some(range_map) = range_maps.iter().find(|range_map| range_map.source == destination) {
-        let new_ranges = ranges.iter().flat_map(|range| range_map.get(range.clone())).collect();
+        let mut new_ranges = vec![];
+        for range in ranges {
+            new_ranges.append(&mut range_map.get(range)?);
+        }
         let new_destination = range_map.destination;
         follow_map(new_ranges, new_destination, range_maps)
     } else {
         // println!();
-        (ranges, destination)
+        Ok((ranges, destination))
     }
 }
 
 
+/// One of the almanac's maps, from its source category to its destination.
 #[derive(Debug, PartialEq, Clone)]
-struct RangeMap<'a> {
+pub struct RangeMap<'a> {
     pub source: &'a str,
     pub destination: &'a str,
     data: Vec<(RangeInclusive<u64>, u64)>,
@@ -53,47 +70,49 @@ impl RangeMap<'_> {
         self.data.push((range, value));
     }
 
-    pub fn get(&self, index_range: RangeInclusive<u64>) -> Vec<RangeInclusive<u64>> {
+    /// The ranges `index_range` maps to, or an error if any of them does not fit in 64 bits.
+    pub fn get(&self, index_range: RangeInclusive<u64>) -> Result<Vec<RangeInclusive<u64>>, Error> {
         // Either the lower bound is included in a mapped range, or not.
         if let Some((range, value)) = self.data.iter().find(|(range, _)| range.contains(index_range.start())) {
+            let start = range.start();
+            let offset = |index: u64| value.checked_add(index - start)
+                .ok_or_else(|| Error::Overflow("a mapped number".to_string()));
             if index_range.end() <= range.end() { // our range is entirely included within a range, so we are done.
-                let start = range.start();
-                vec![(value + index_range.start() - start)..=(value + index_range.end() - start)]
+                Ok(vec![offset(*index_range.start())?..=offset(*index_range.end())?])
             } else { // the first part of our range is included in a range, chop that off and recur
-                let start = range.start();
-                let mut retval = vec![(value + index_range.start() - start)..=(value + range.end() - start)];
-                retval.append(&mut self.get((*range.end() + 1)..=*index_range.end()));
-                retval
+                let mut retval = vec![offset(*index_range.start())?..=offset(*range.end())?];
+                retval.append(&mut self.get((*range.end() + 1)..=*index_range.end())?);
+                Ok(retval)
             }
         } else {
             match self.data.iter().find(|(range, _)| index_range.start() < range.start() && range.start() < index_range.end() ) {
                 Some((range, _)) => {
                     // there is an unmapped chunk at the start of our range, chop that off and recur
                     let mut retval = vec![*index_range.start()..=(range.start() - 1)];
-                    retval.append(&mut self.get(*range.start()..=*index_range.end()));
-                    retval
+                    retval.append(&mut self.get(*range.start()..=*index_range.end())?);
+                    Ok(retval)
 
                 }
                 None => { // our range doesn't intersect with any mapped ranges, so we are done.
-                    vec![index_range]
+                    Ok(vec![index_range])
                 }
             }
         }
     }
 
-    pub fn map(&self, index: u64) -> u64 {
+    /// The number `index` maps to, or an error if that does not fit in 64 bits.
+    pub fn map(&self, index: u64) -> Result<u64, Error> {
         for (inclusive_range, value) in self.data.iter() {
             if inclusive_range.contains(&index) {
-                if let Some(min) = inclusive_range.clone().min() {
-                    return value + (index - min);
-                }
+                return value.checked_add(index - inclusive_range.start())
+                    .ok_or_else(|| Error::Overflow("a mapped number".to_string()));
             }
         }
-        index
+        Ok(index)
     }
 }
 
-fn parse_input(input: &str) -> IResult<&str, (Vec<RangeInclusive<u64>>, Vec<RangeMap>)> {
+fn parse_input(input: Span) -> ParseResult<(Vec<RangeInclusive<u64>>, Vec<RangeMap>)> {
     separated_pair(
         seeds, 
         pair(
@@ -101,7 +120,7 @@ fn parse_input(input: &str) -> IResult<&str, (Vec<RangeInclusive<u64>>, Vec<Rang
             newline
         ),
         separated_list1(
-            newline,
+            pair(newline, newline),
             range_map
         )
     )
@@ -109,23 +128,23 @@ fn parse_input(input: &str) -> IResult<&str, (Vec<RangeInclusive<u64>>, Vec<Rang
 }
 
 // seeds: 79 14 55 13
-fn seeds(input: &str) -> IResult<&str, Vec<RangeInclusive<u64>>> {
-    let (input, ranges) = preceded(
+fn seeds(input: Span) -> ParseResult<Vec<RangeInclusive<u64>>> {
+    let (input, ranges) = context("seeds", preceded(
         tag("seeds: "),
         separated_list1(
             space1,
-            separated_pair(complete::u64, space1, complete::u64)
+            range
         )
-    )(input)?;
-    Ok((input, ranges.into_iter().map(|(start, length)| start..=(start + length - 1)).collect()))
+    ))(input)?;
+    Ok((input, ranges))
 }
 
-fn range_map(input: &str) -> IResult<&str, RangeMap> {
-    let (input, ((source, destination), inclusive_range_values)) = separated_pair(
+fn range_map(input: Span) -> ParseResult<RangeMap> {
+    let (input, ((source, destination), inclusive_range_values)) = context("map", separated_pair(
         range_map_title,
         newline,
-        many1(inclusive_range_value),
-    )(input)?;
+        separated_list1(newline, inclusive_range_value),
+    ))(input)?;
 
     let mut range_map = RangeMap {
         source,
@@ -141,24 +160,51 @@ fn range_map(input: &str) -> IResult<&str, RangeMap> {
 }
 
 // seed-to-soil map:
-fn range_map_title(input: &str) -> IResult<&str, (&str, &str)> {
-    terminated(
+fn range_map_title<'a>(input: Span<'a>) -> ParseResult<'a, (&'a str, &'a str)> {
+    let (input, (source, destination)) = terminated(
         separated_pair(alpha1, tag("-to-"), alpha1),
         pair(space1, tag("map:")),
-    )(input)
+    )(input)?;
+    Ok((input, (*source.fragment(), *destination.fragment())))
 }
 
 // 50 98 2
-fn inclusive_range_value(input: &str) -> IResult<&str, (RangeInclusive<u64>, u64)> {
-    let (input, (value, min, length)) = tuple((terminated(complete::u64, space1), terminated(complete::u64, space1), terminated(complete::u64, newline)))(input)?;
-
-    Ok((
-        input,
-        (
-            min..=(min + length - 1),
-            value,
-        ),
-    ))
+fn inclusive_range_value(input: Span) -> ParseResult<(RangeInclusive<u64>, u64)> {
+    let (input, (value, range)) = tuple((terminated(complete::u64, space1), range))(input)?;
+
+    Ok((input, (range, value)))
+}
+
+// 98 2
+fn range(input: Span) -> ParseResult<RangeInclusive<u64>> {
+    let (rest, (min, length)) = separated_pair(complete::u64, space1, complete::u64)(input)?;
+    let expected = if length == 0 {
+        "a range of at least one number"
+    } else if let Some(max) = min.checked_add(length - 1) {
+        return Ok((rest, min..=max));
+    } else {
+        "a range which fits in 64 bits"
+    };
+    Err(nom::Err::Error(ParseError::new(input, expected)))
+}
+
+pub struct Part2;
+
+impl Solution for Part2 {
+    const DAY: u8 = 5;
+    const PART: u8 = 2;
+
+    type Input<'a> = (Vec<RangeInclusive<u64>>, Vec<RangeMap<'a>>);
+    type Params = ();
+
+    fn parse(input: &str) -> Result<(Vec<RangeInclusive<u64>>, Vec<RangeMap<'_>>), Error> {
+        parse_all(input, parse_input)
+    }
+
+    fn solve(input: (Vec<RangeInclusive<u64>>, Vec<RangeMap<'_>>), _: &()) -> Result<Answer, Error> {
+        let (seeds, range_maps) = input;
+        Ok(lowest_location(seeds, range_maps)?.into())
+    }
 }
 
 #[cfg(test)]
@@ -167,19 +213,19 @@ mod tests {
 
     #[test]
     fn test_seeds() {
-        let result = seeds("seeds: 79 14 55 13").expect("Should parse");
-        assert_eq!(result.0, "");
+        let result = seeds(Span::new("seeds: 79 14 55 13")).expect("Should parse");
+        assert_eq!(*result.0.fragment(), "");
         assert_eq!(result.1, vec![79..=82, 55..=67]);
-        let result = seeds("seeds: 79 14 55 13
+        let result = seeds(Span::new("seeds: 79 14 55 13
 
 seed-to-soil map:
 50 98 2
-52 50 48").expect("Should parse");
+52 50 48")).expect("Should parse");
     }
 
     #[test]
     fn test_input() {
-        let result = separated_list1(newline, range_map)("seed-to-soil map:
+        let result = separated_list1(pair(newline, newline), range_map)(Span::new("seed-to-soil map:
 50 98 2
 52 50 48
 
@@ -209,25 +255,24 @@ temperature-to-humidity map:
 
 humidity-to-location map:
 60 56 37
-56 93 4").expect("should parse");
+56 93 4")).expect("should parse");
     }
 
     #[test]
     fn test_range_map_again() {
-        let (input, result) = range_map(
+        let (input, result) = range_map(Span::new(
             "seed-to-soil map:
 50 98 2
-52 50 48
-",
-        )
+52 50 48",
+        ))
         .expect("should parse");
-        assert_eq!(input, "");
+        assert_eq!(*input.fragment(), "");
         dbg!(result);
     }
     
     #[test]
     fn test_range_map() {
-        let (input, result) = range_map(
+        let (input, result) = range_map(Span::new(
             "seed-to-soil map:
 3788621315 24578909 268976974
 3633843608 2672619957 154777707
@@ -238,19 +283,18 @@ humidity-to-location map:
 2373438105 1435027522 118933864
 2492371969 1309268595 125758927
 2329903325 2629085177 43534780
-24578909 2475091832 153993345
-",
-        )
+24578909 2475091832 153993345",
+        ))
         .expect("should parse");
-        assert_eq!(input, "");
+        assert_eq!(*input.fragment(), "");
         dbg!(&result);
-        dbg!(result.map(929142010));
+        dbg!(result.map(929142010).unwrap());
     }
 
     #[test]
     fn test_range_map_title() {
-        let result = range_map_title("seed-to-soil map:").expect("should parse");
-        assert_eq!(result.0, "");
+        let result = range_map_title(Span::new("seed-to-soil map:")).expect("should parse");
+        assert_eq!(*result.0.fragment(), "");
         assert_eq!(result.1, ("seed", "soil"));
     }
 
@@ -263,9 +307,12 @@ humidity-to-location map:
             52,
         );
 
-        assert_eq!(under_test.get(1..=10), vec![1..=10]);
-        assert_eq!(under_test.get(2..=51), vec![2..=49,52..=53]);
-        assert_eq!(under_test.get(48..=100), vec![48..=97,50..=51, 100..=100]);
+        assert_eq!(under_test.get(1..=10), Ok(vec![1..=10]));
+        assert_eq!(under_test.get(2..=51), Ok(vec![2..=49,52..=53]));
+        assert_eq!(under_test.get(48..=100), Ok(vec![48..=97,50..=51, 100..=100]));
+
+        under_test.insert(0..=1, u64::MAX);
+        assert_eq!(under_test.get(0..=1), Err(Error::Overflow("a mapped number".to_string())));
 
     }
 
@@ -278,49 +325,45 @@ humidity-to-location map:
             52,
         );
 
-        assert_eq!(under_test.map(98), 50);
-        assert_eq!(under_test.map(99), 51);
+        assert_eq!(under_test.map(98), Ok(50));
+        assert_eq!(under_test.map(99), Ok(51));
 
-        assert_eq!(under_test.map(53), 55);
-        assert_eq!(under_test.map(10), 10);
+        assert_eq!(under_test.map(53), Ok(55));
+        assert_eq!(under_test.map(10), Ok(10));
     }
 
     #[test]
-    fn test_process() {
-        let lines = "seeds: 79 14 55 13
+    fn test_try_process_error() {
+        let input = "seeds: 79 14
 
 seed-to-soil map:
 50 98 2
-52 50 48
-
-soil-to-fertilizer map:
-0 15 37
-37 52 2
-39 0 15
-
-fertilizer-to-water map:
-49 53 8
-0 11 42
-42 0 7
-57 7 4
-
-water-to-light map:
-88 18 7
-18 25 70
-
-light-to-temperature map:
-45 77 23
-81 45 19
-68 64 13
+52 50
+";
+        assert_eq!(
+            try_process(input).unwrap_err().to_string(),
+            "line 5, column 1: expected the end of the input, found \"52 50\""
+        );
+    }
 
-temperature-to-humidity map:
-0 69 1
-1 0 69
+    #[test]
+    fn test_try_process_empty_range() {
+        let input = "seeds: 0 0
 
-humidity-to-location map:
-60 56 37
-56 93 4";
-        let result = process(lines);
-        assert_eq!(result, 46);
+seed-to-soil map:
+50 98 2
+";
+        assert_eq!(
+            try_process(input).unwrap_err().to_string(),
+            "line 1, column 8: expected a range of at least one number in seeds, found \"0 0\""
+        );
+        assert_eq!(
+            try_process("seeds: 79 14\n\nseed-to-soil map:\n50 98 0\n").unwrap_err().to_string(),
+            "line 4, column 4: expected a range of at least one number in map, found \"98 0\""
+        );
+        assert_eq!(
+            try_process("seeds: 18446744073709551615 2\n\nseed-to-soil map:\n50 98 2\n").unwrap_err().to_string(),
+            "line 1, column 8: expected a range which fits in 64 bits in seeds, found \"18446744073709551615\""
+        );
     }
 }
@@ -1,2 +1,6 @@
 pub mod part1;
-pub mod part2;
\ No newline at end of file
+pub mod part2;
+#[cfg(test)]
+mod reference;
+
+aoclib::examples!(crate::part1::Part1, crate::part2::Part2);
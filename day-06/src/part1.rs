@@ -1,18 +1,41 @@
 use std::iter::zip;
 
 use nom::{
-    bytes::complete::tag,
     character::complete::{self, newline, space1},
+    error::context,
     multi::separated_list1,
     sequence::{pair, preceded, separated_pair},
-    IResult,
 };
 
-pub fn process<'a>(input: &'a str) -> u32 {
-    let (_, races) = races(input).expect("should parse");
+use aoclib::parse::{parse_all, tag, ParseResult, Span};
+use aoclib::{Answer, Error, Solution};
+
+pub fn process(input: &str) -> u32 {
+    let races = parse_races(input).expect("should parse");
     races.iter().map(number_of_solutions).product()
 }
 
+/// Like [`process`], but reports a malformed sheet, or one without a distance for every time,
+/// as an error.
+pub fn try_process(input: &str) -> Result<Answer, Error> {
+    Ok(parse_races(input)?.iter().map(number_of_solutions).product::<u32>().into())
+}
+
+/// Parses `input` as a line of times and a line with a distance for each of them.
+fn parse_races(input: &str) -> Result<Vec<Race>, Error> {
+    let (times, distances) = parse_all(input, separated_pair(times, newline, distances))?;
+    if times.len() != distances.len() {
+        let distance_line = input.lines().nth(1).unwrap_or_default();
+        return Err(Error::parse(input, distance_line, format!("{} distances", times.len())));
+    }
+    Ok(zip(times, distances)
+        .map(|(allowed_time, record_distance)| Race {
+            allowed_time,
+            record_distance,
+        })
+        .collect())
+}
+
 fn number_of_solutions(race: &Race) -> u32 {
     (0..=race.allowed_time)
         .map(|t| distance_travelled(t, race.allowed_time))
@@ -24,41 +47,45 @@ fn distance_travelled(acceleration_time: u32, total_time: u32) -> u32 {
     (total_time - acceleration_time) * acceleration_time
 }
 
+/// How long a race lasts, and the furthest a boat has gone in it.
 #[derive(PartialEq, Debug)]
-struct Race {
+pub struct Race {
     allowed_time: u32,
     record_distance: u32,
 }
 
-fn races(input: &str) -> IResult<&str, Vec<Race>> {
-    let (input, (times, distances)) =
-        separated_pair(times, newline, distances)(input).expect("to parse");
-
-    Ok((
-        input,
-        zip(times, distances)
-            .map(|(allowed_time, record_distance)| Race {
-                allowed_time,
-                record_distance,
-            })
-            .collect(),
-    ))
-}
-
 // Time:      7  15   30
-fn times(input: &str) -> IResult<&str, Vec<u32>> {
-    preceded(
+fn times(input: Span) -> ParseResult<Vec<u32>> {
+    context("times", preceded(
         pair(tag("Time:"), space1),
         separated_list1(space1, complete::u32),
-    )(input)
+    ))(input)
 }
 
 // Distance:  9  40  200
-fn distances(input: &str) -> IResult<&str, Vec<u32>> {
-    preceded(
+fn distances(input: Span) -> ParseResult<Vec<u32>> {
+    context("distances", preceded(
         pair(tag("Distance:"), space1),
         separated_list1(space1, complete::u32),
-    )(input)
+    ))(input)
+}
+
+pub struct Part1;
+
+impl Solution for Part1 {
+    const DAY: u8 = 6;
+    const PART: u8 = 1;
+
+    type Input<'a> = Vec<Race>;
+    type Params = ();
+
+    fn parse(input: &str) -> Result<Vec<Race>, Error> {
+        parse_races(input)
+    }
+
+    fn solve(input: Vec<Race>, _: &()) -> Result<Answer, Error> {
+        Ok(input.iter().map(number_of_solutions).product::<u32>().into())
+    }
 }
 
 #[cfg(test)]
@@ -66,15 +93,14 @@ mod tests {
     use super::*;
 
     #[test]
-    fn test_races() {
-        let result = races(
+    fn test_parse_races() {
+        let result = parse_races(
             "Time:      7  15   30
 Distance:  9  40  200",
         )
         .expect("should parse");
-        assert_eq!(result.0, "");
         assert_eq!(
-            result.1,
+            result,
             vec![
                 Race {
                     allowed_time: 7,
@@ -94,23 +120,31 @@ Distance:  9  40  200",
 
     #[test]
     fn test_time() {
-        let result = times("Time:      7  15   30").expect("should parse");
-        assert_eq!(result.0, "");
+        let result = times(Span::new("Time:      7  15   30")).expect("should parse");
+        assert_eq!(*result.0.fragment(), "");
         assert_eq!(result.1, vec![7, 15, 30]);
     }
 
     #[test]
     fn test_ditance() {
-        let result = distances("Distance:      9  40  200").expect("should parse");
-        assert_eq!(result.0, "");
+        let result = distances(Span::new("Distance:      9  40  200")).expect("should parse");
+        assert_eq!(*result.0.fragment(), "");
         assert_eq!(result.1, vec![9, 40, 200]);
     }
 
     #[test]
-    fn test_process() {
-        let lines = "Time:      7  15   30
-Distance:  9  40  200";
-        let result = process(lines);
-        assert_eq!(result, 288);
+    fn test_try_process() {
+        assert_eq!(
+            try_process("Time:      7  15   30\nDistance:  9  40\n").unwrap_err().to_string(),
+            "line 2, column 1: expected 3 distances, found \"Distance:  9  40\""
+        );
+        assert_eq!(
+            try_process("Time:      7  15   30\nDistance:  9  x\n").unwrap_err().to_string(),
+            "line 2, column 13: expected the end of the input, found \"  x\""
+        );
+        assert_eq!(
+            try_process("Time:      7  15   30\nDistances: 9\n").unwrap_err().to_string(),
+            "line 2, column 1: expected \"Distance:\" in distances, found \"Distances: 9\""
+        );
     }
 }
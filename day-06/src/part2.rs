@@ -1,35 +1,61 @@
 use nom::{
-    bytes::complete::tag,
-    character::complete::{newline, space1, not_line_ending},
+    character::complete::{digit1, newline, space1},
+    error::context,
+    multi::separated_list1,
     sequence::{pair, preceded, separated_pair},
-    IResult,
 };
 
-pub fn process<'a>(input: &'a str) -> u64 {
-    let (_, race) = race(input).expect("should parse");
+use aoclib::parse::{parse_all, tag, ParseError, ParseResult, Span};
+use aoclib::{Answer, Error, Solution};
+
+pub fn process(input: &str) -> u64 {
+    let race = parse_all(input, race).expect("should parse");
     number_of_solutions(&race)
 }
 
+/// Like [`process`], but reports a malformed sheet as an error.
+pub fn try_process(input: &str) -> Result<Answer, Error> {
+    let race = parse_all(input, race)?;
+    Ok(number_of_solutions(&race).into())
+}
+
+/// The number of button presses which beat the record. The time can be too long to try every
+/// press, so this searches for the shortest winning one instead.
 fn number_of_solutions(race: &Race) -> u64 {
-    (0..=race.allowed_time)
-        .map(|t| distance_travelled(t, race.allowed_time))
-        .filter(|t| *t > race.record_distance)
-        .count() as u64
+    let beats_record = |t| distance_travelled(t, race.allowed_time) > race.record_distance as u128;
+
+    // the distance is furthest at half the time, and the same for a press as for the time left
+    // after it, so the winning presses are those from the shortest one to the time left after it
+    let half = race.allowed_time / 2;
+    if !beats_record(half) {
+        return 0;
+    }
+    let (mut shortest, mut longest) = (0, half);
+    while shortest < longest {
+        let middle = shortest + (longest - shortest) / 2;
+        if beats_record(middle) {
+            longest = middle;
+        } else {
+            shortest = middle + 1;
+        }
+    }
+    race.allowed_time - 2 * shortest + 1
 }
 
-fn distance_travelled(acceleration_time: u64, total_time: u64) -> u64 {
-    (total_time - acceleration_time) * acceleration_time
+fn distance_travelled(acceleration_time: u64, total_time: u64) -> u128 {
+    (total_time - acceleration_time) as u128 * acceleration_time as u128
 }
 
+/// How long the race lasts, and the furthest a boat has gone in it.
 #[derive(PartialEq, Debug)]
-struct Race {
+pub struct Race {
     allowed_time: u64,
     record_distance: u64,
 }
 
-fn race(input: &str) -> IResult<&str, Race> {
+fn race(input: Span) -> ParseResult<Race> {
     let (input, (allowed_time, record_distance)) =
-        separated_pair(times, newline, distances)(input).expect("to parse");
+        separated_pair(times, newline, distances)(input)?;
 
     Ok((
         input,
@@ -41,29 +67,47 @@ fn race(input: &str) -> IResult<&str, Race> {
 }
 
 // Time:      7  15   30
-fn times(input: &str) -> IResult<&str, u64> {
-    let result : Result<(_,_), nom::Err<nom::error::Error<_>>>= preceded(
+fn times(input: Span) -> ParseResult<u64> {
+    context("time", preceded(
         pair(tag("Time:"), space1),
-        not_line_ending,
-    )(input);
-    let (input, chars) = result.expect("should parse");
-
-    Ok((input, chars
-    .chars()
-    .filter(|c| !c.is_whitespace()).collect::<String>().parse().expect("Should be digit")))
+        kerned_number,
+    ))(input)
 }
 
 // Distance:  9  40  200
-fn distances(input: &str) -> IResult<&str, u64> {
-    let result : Result<(_,_), nom::Err<nom::error::Error<_>>>= preceded(
+fn distances(input: Span) -> ParseResult<u64> {
+    context("distance", preceded(
         pair(tag("Distance:"), space1),
-        not_line_ending,
-    )(input);
-    let (input, chars) = result.expect("should parse");
+        kerned_number,
+    ))(input)
+}
 
-    Ok((input, chars
-    .chars()
-    .filter(|c| !c.is_whitespace()).collect::<String>().parse().expect("Should be digit")))
+// 7  15   30, read as 71530
+fn kerned_number(input: Span) -> ParseResult<u64> {
+    let (rest, digits) = separated_list1(space1, digit1)(input)?;
+    let number = digits.iter().map(|digits| *digits.fragment()).collect::<String>();
+    match number.parse() {
+        Ok(number) => Ok((rest, number)),
+        Err(_) => Err(nom::Err::Failure(ParseError::new(input, "a number of at most 20 digits"))),
+    }
+}
+
+pub struct Part2;
+
+impl Solution for Part2 {
+    const DAY: u8 = 6;
+    const PART: u8 = 2;
+
+    type Input<'a> = Race;
+    type Params = ();
+
+    fn parse(input: &str) -> Result<Race, Error> {
+        parse_all(input, race)
+    }
+
+    fn solve(input: Race, _: &()) -> Result<Answer, Error> {
+        Ok(number_of_solutions(&input).into())
+    }
 }
 
 #[cfg(test)]
@@ -72,12 +116,12 @@ mod tests {
 
     #[test]
     fn test_race() {
-        let result = race(
+        let result = race(Span::new(
             "Time:      7  15   30
 Distance:  9  40  200",
-        )
+        ))
         .expect("should parse");
-        assert_eq!(result.0, "");
+        assert_eq!(*result.0.fragment(), "");
         assert_eq!(
             result.1,
             Race {
@@ -89,23 +133,37 @@ Distance:  9  40  200",
 
     #[test]
     fn test_time() {
-        let result = times("Time:      7  15   30").expect("should parse");
-        assert_eq!(result.0, "");
+        let result = times(Span::new("Time:      7  15   30")).expect("should parse");
+        assert_eq!(*result.0.fragment(), "");
         assert_eq!(result.1, 71530);
     }
 
     #[test]
     fn test_distance() {
-        let result = distances("Distance:      9  40  200").expect("should parse");
-        assert_eq!(result.0, "");
+        let result = distances(Span::new("Distance:      9  40  200")).expect("should parse");
+        assert_eq!(*result.0.fragment(), "");
         assert_eq!(result.1, 940200);
     }
 
     #[test]
-    fn test_process() {
-        let lines = "Time:      7  15   30
-Distance:  9  40  200";
-        let result = process(lines);
-        assert_eq!(result, 71503);
+    fn test_number_of_solutions() {
+        assert_eq!(number_of_solutions(&Race { allowed_time: 71530, record_distance: 940200 }), 71503);
+        assert_eq!(number_of_solutions(&Race { allowed_time: 7, record_distance: 12 }), 0);
+        assert_eq!(
+            number_of_solutions(&Race { allowed_time: u64::MAX, record_distance: u64::MAX }),
+            u64::MAX - 3
+        );
+    }
+
+    #[test]
+    fn test_try_process() {
+        assert_eq!(
+            try_process("Time:      7  15   30\nDistance:  x\n").unwrap_err().to_string(),
+            "line 2, column 12: expected a number in distance, found \"x\""
+        );
+        assert_eq!(
+            try_process("Time:  99999 99999 99999 99999 99999\nDistance:  9\n").unwrap_err().to_string(),
+            "line 1, column 8: expected a number of at most 20 digits in time, found \"99999 99999 99999 99\""
+        );
     }
 }
@@ -0,0 +1,75 @@
+//! Slow but obviously correct solutions, to check the real ones against on small inputs.
+
+/// Races the boat a millisecond at a time, after holding the button for `hold` of them.
+fn distance(hold: u64, time: u64) -> u64 {
+    let mut speed = 0;
+    let mut distance = 0;
+    for millisecond in 0..time {
+        if millisecond < hold {
+            speed += 1;
+        } else {
+            distance += speed;
+        }
+    }
+    distance
+}
+
+fn ways_to_win(time: u64, record: u64) -> u64 {
+    (0..=time)
+        .filter(|hold| distance(*hold, time) > record)
+        .count() as u64
+}
+
+fn numbers(line: &str) -> Vec<u64> {
+    line.split_whitespace()
+        .skip(1)
+        .map(|n| n.parse().unwrap())
+        .collect()
+}
+
+pub fn part1(input: &str) -> u64 {
+    let (times, records) = input.split_once('\n').unwrap();
+    numbers(times)
+        .into_iter()
+        .zip(numbers(records))
+        .map(|(time, record)| ways_to_win(time, record))
+        .product()
+}
+
+pub fn part2(input: &str) -> u64 {
+    let joined = input
+        .lines()
+        .map(|line| line.replace(' ', "").replace(':', ": "))
+        .collect::<Vec<_>>();
+    ways_to_win(numbers(&joined[0])[0], numbers(&joined[1])[0])
+}
+
+mod tests {
+    use aoclib::gen;
+    use aoclib::reference::assert_agrees;
+    use proptest::prelude::*;
+
+    use super::*;
+    use crate::part1::Part1;
+    use crate::part2::Part2;
+
+    proptest! {
+        #[test]
+        fn test_part1(seed: u64, size in 1..5usize) {
+            let input = gen::generator(6).unwrap().generate(size, seed);
+            assert_agrees::<Part1>(&input, &[], part1(&input));
+        }
+    }
+
+    proptest! {
+        // The race is simulated a millisecond at a time, so its time is kept to four digits, and
+        // there are fewer of them.
+        #![proptest_config(ProptestConfig::with_cases(16))]
+
+        #[test]
+        fn test_part2(seed: u64, size in 1..3usize) {
+            let input = gen::generator(6).unwrap().generate(size, seed);
+            assert_agrees::<Part2>(&input, &[], part2(&input));
+        }
+    }
+}
@@ -0,0 +1,60 @@
+use aoclib::Error;
+
+const CARDS: &str = "AKQJT98765432";
+
+/// Parses every line of `input` as a hand of five cards followed by a bid.
+pub fn parse_hands(input: &str) -> Result<Vec<(&str, u32)>, Error> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(index, line)| parse_line(index + 1, line))
+        .collect()
+}
+
+/// Parses the `number`th line of the input, counting from one, as a hand of five cards and a bid.
+pub fn parse_line(number: usize, line: &str) -> Result<(&str, u32), Error> {
+    let (hand, bid) = line
+        .split_once(' ')
+        .ok_or_else(|| Error::parse_line(number, line, line, "a hand and a bid"))?;
+    if let Some((index, _)) = hand.char_indices().find(|(_, c)| !CARDS.contains(*c)) {
+        return Err(Error::parse_line(number, line, &hand[index..], "a card"));
+    }
+    if hand.len() != 5 {
+        return Err(Error::parse_line(number, line, hand, "a hand of five cards"));
+    }
+    let bid = bid
+        .parse::<u32>()
+        .map_err(|_| Error::parse_line(number, line, bid, "a bid"))?;
+    Ok((hand, bid))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("32T3K 765\nT55J5 684\n", Ok(vec![("32T3K", 765), ("T55J5", 684)]))]
+    #[case(
+        "32T3K 765\nT55J5\n",
+        Err("line 2, column 1: expected a hand and a bid, found \"T55J5\"")
+    )]
+    #[case(
+        "32T3K 765\nT55X5 684\n",
+        Err("line 2, column 4: expected a card, found \"X5 684\"")
+    )]
+    #[case(
+        "32T3K 765\nT55J 684\n",
+        Err("line 2, column 1: expected a hand of five cards, found \"T55J 684\"")
+    )]
+    #[case(
+        "32T3K 765\nT55J5 -1\n",
+        Err("line 2, column 7: expected a bid, found \"-1\"")
+    )]
+    fn test_parse_hands(#[case] input: &str, #[case] expected: Result<Vec<(&str, u32)>, &str>) {
+        assert_eq!(
+            parse_hands(input).map_err(|error| error.to_string()),
+            expected.map_err(str::to_string)
+        );
+    }
+}
@@ -1,23 +1,46 @@
+use std::io::BufRead;
 use std::ops::Deref;
 
 use itertools::Itertools;
 
-pub fn process<'a>(input: &'a str) -> u32 {
-    input
-        .lines()
-        .map(|line| {
-            let (hand, bid) = line.split_once(" ").unwrap();
-            (
-                hand,
-                bid.parse::<u32>().expect("should be a number"),
-                score_hand(hand),
-            )
-        })
+use crate::parse::{parse_hands, parse_line};
+use crate::winnings::Winnings;
+
+use aoclib::stream;
+use aoclib::{Answer, Error, Solution};
+
+pub fn process<'a>(input: &'a str) -> u64 {
+    total_winnings(&parse_hands(input).expect("should parse"))
+        .expect("the total winnings should fit in 64 bits")
+}
+
+fn total_winnings(hands: &[(&str, u32)]) -> Result<u64, Error> {
+    hands
+        .iter()
+        .map(|&(hand, bid)| (hand, bid, score_hand(hand)))
         .sorted_by_key(|x| x.2)
         .enumerate()
         // .inspect(|x| { dbg!(x);} )
-        .map(|(rank, (_, bid, _))| (rank + 1) as u32 * bid)
-        .sum()
+        .try_fold(0u64, |total, (rank, (_, bid, _))| {
+            (rank as u64 + 1).checked_mul(u64::from(bid))?.checked_add(total)
+        })
+        .ok_or_else(|| Error::Overflow("the total winnings".to_string()))
+}
+
+/// Like [`process`], but reports a malformed hand or bid, or total winnings which do not fit in
+/// 64 bits, as an error.
+pub fn try_process(input: &str) -> Result<Answer, Error> {
+    Ok(total_winnings(&parse_hands(input)?)?.into())
+}
+
+/// Like [`try_process`], but for the input a line at a time, so it needn't all be in memory.
+pub fn try_process_lines<Line: AsRef<str>>(lines: impl Iterator<Item = Line>) -> Result<Answer, Error> {
+    let mut winnings = Winnings::default();
+    for (index, line) in lines.enumerate() {
+        let (hand, bid) = parse_line(index + 1, line.as_ref())?;
+        winnings.add(score_hand(hand), bid)?;
+    }
+    Ok(winnings.total()?.into())
 }
 #[derive(PartialEq, Eq, PartialOrd, Ord, Debug, Clone, Copy)]
 enum HandType {
@@ -91,18 +114,24 @@ fn score_hand(hand: &str) -> (HandType, (u32, u32, u32, u32, u32)) {
     (hand_type, to_tuple(hand))
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_process() {
-        let lines = "32T3K 765
-T55J5 684
-KK677 28
-KTJJT 220
-QQQJA 483";
-        let result = process(lines);
-        assert_eq!(result, 5905);
+pub struct Part2;
+
+impl Solution for Part2 {
+    const DAY: u8 = 7;
+    const PART: u8 = 2;
+
+    type Input<'a> = Vec<(&'a str, u32)>;
+    type Params = ();
+
+    fn parse(input: &str) -> Result<Vec<(&str, u32)>, Error> {
+        parse_hands(input)
+    }
+
+    fn solve(input: Vec<(&str, u32)>, _: &()) -> Result<Answer, Error> {
+        Ok(total_winnings(&input)?.into())
+    }
+
+    fn solve_reader(reader: &mut dyn BufRead, _: &()) -> Result<Option<Answer>, Error> {
+        stream::with_lines(reader, |lines| try_process_lines(lines)).map(Some)
     }
 }
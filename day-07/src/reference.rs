@@ -0,0 +1,79 @@
+//! Slow but obviously correct solutions, to check the real ones against on small inputs.
+
+/// How strong a hand's type is, from the counts of each card in it, largest first.
+fn strength(hand: &[u8]) -> Vec<usize> {
+    let mut counts: Vec<usize> = (0..hand.len())
+        .filter(|index| !hand[..*index].contains(&hand[*index]))
+        .map(|index| hand.iter().filter(|card| **card == hand[index]).count())
+        .collect();
+    counts.sort_unstable_by(|a, b| b.cmp(a));
+    counts
+}
+
+/// The total winnings, ranking hands by `key` and breaking ties card by card in `order`.
+fn winnings(input: &str, order: &[u8], key: impl Fn(&[u8]) -> Vec<usize>) -> u32 {
+    let mut hands: Vec<(Vec<usize>, Vec<usize>, u32)> = input
+        .lines()
+        .map(|line| {
+            let (hand, bid) = line.split_once(' ').unwrap();
+            let cards = hand
+                .bytes()
+                .map(|card| order.iter().position(|c| *c == card).unwrap())
+                .collect();
+            (key(hand.as_bytes()), cards, bid.parse().unwrap())
+        })
+        .collect();
+    hands.sort();
+    hands
+        .iter()
+        .enumerate()
+        .map(|(rank, (_, _, bid))| (rank as u32 + 1) * bid)
+        .sum()
+}
+
+pub fn part1(input: &str) -> u32 {
+    winnings(input, b"23456789TJQKA", strength)
+}
+
+/// Tries every card in place of the jokers. Making them all the same card is always best, as
+/// adding to the largest group beats anything else.
+pub fn part2(input: &str) -> u32 {
+    winnings(input, b"J23456789TQKA", |hand| {
+        b"23456789TQKA"
+            .iter()
+            .map(|joker| {
+                strength(
+                    &hand
+                        .iter()
+                        .map(|card| if *card == b'J' { *joker } else { *card })
+                        .collect::<Vec<_>>(),
+                )
+            })
+            .max()
+            .unwrap()
+    })
+}
+
+mod tests {
+    use aoclib::gen;
+    use aoclib::reference::assert_agrees;
+    use proptest::prelude::*;
+
+    use super::*;
+    use crate::part1::Part1;
+    use crate::part2::Part2;
+
+    proptest! {
+        #[test]
+        fn test_part1(seed: u64, size in 1..50usize) {
+            let input = gen::generator(7).unwrap().generate(size, seed);
+            assert_agrees::<Part1>(&input, &[], part1(&input));
+        }
+
+        #[test]
+        fn test_part2(seed: u64, size in 1..50usize) {
+            let input = gen::generator(7).unwrap().generate(size, seed);
+            assert_agrees::<Part2>(&input, &[], part2(&input));
+        }
+    }
+}
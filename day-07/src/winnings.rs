@@ -0,0 +1,85 @@
+use std::collections::BTreeMap;
+
+use aoclib::Error;
+
+/// The bids for the hands with the same score, in the order they were read.
+#[derive(Debug, Default, Clone, Copy)]
+struct Bids {
+    count: u64,
+    total: u64,
+    /// Each bid multiplied by how many hands with the same score came before it.
+    weighted: u64,
+}
+
+/// The total winnings of a set of hands, tallied as they are read. Only a tally of the bids for
+/// each distinct score is kept, rather than every hand, and there are at most 13^5 of those
+/// however long the input is. Hands with the same score are ranked in the order they were read.
+#[derive(Debug)]
+pub struct Winnings<S> {
+    scores: BTreeMap<S, Bids>,
+}
+
+impl<S> Default for Winnings<S> {
+    fn default() -> Self {
+        Self {
+            scores: BTreeMap::new(),
+        }
+    }
+}
+
+impl<S: Ord> Winnings<S> {
+    /// Tallies a hand with `score` and `bid`, or reports that the tally overflows.
+    pub fn add(&mut self, score: S, bid: u32) -> Result<(), Error> {
+        let bids = self.scores.entry(score).or_default();
+        bids.weighted = bids.count.checked_mul(u64::from(bid))
+            .and_then(|weighted| bids.weighted.checked_add(weighted))
+            .ok_or_else(overflow)?;
+        bids.total = bids.total.checked_add(u64::from(bid)).ok_or_else(overflow)?;
+        bids.count += 1;
+        Ok(())
+    }
+
+    /// The sum of each hand's bid multiplied by its rank, or an error if it overflows.
+    pub fn total(&self) -> Result<u64, Error> {
+        let mut ranked = 0u64;
+        let mut total = 0u64;
+        for bids in self.scores.values() {
+            total = (ranked + 1).checked_mul(bids.total)
+                .and_then(|winnings| winnings.checked_add(bids.weighted))
+                .and_then(|winnings| total.checked_add(winnings))
+                .ok_or_else(overflow)?;
+            ranked += bids.count;
+        }
+        Ok(total)
+    }
+}
+
+fn overflow() -> Error {
+    Error::Overflow("the total winnings".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_total() {
+        let mut winnings = Winnings::default();
+        for (score, bid) in [(2, 10), (1, 100), (2, 20), (3, 1)] {
+            winnings.add(score, bid).unwrap();
+        }
+        assert_eq!(winnings.total(), Ok(100 + 2 * 10 + 3 * 20 + 4));
+    }
+
+    #[test]
+    fn test_overflow() {
+        let mut winnings = Winnings::default();
+        winnings.scores.insert(1, Bids { count: u64::MAX / 2, total: 0, weighted: 0 });
+        assert_eq!(winnings.add(1, 3), Err(overflow()));
+
+        let mut winnings = Winnings::default();
+        winnings.scores.insert(1, Bids { count: u64::MAX / 2, total: 0, weighted: 0 });
+        winnings.scores.insert(2, Bids { count: 1, total: 3, weighted: 0 });
+        assert_eq!(winnings.total(), Err(overflow()));
+    }
+}
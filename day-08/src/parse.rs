@@ -0,0 +1,77 @@
+use std::collections::BTreeMap;
+use nom::{sequence::{separated_pair, delimited}, character::complete::alphanumeric1, error::context};
+
+use aoclib::parse::{parse_all, tag, ParseResult, Span};
+use aoclib::Error;
+
+/// The nodes reached from a node by going left and by going right.
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub struct Node<'a> {
+    pub left: &'a str,
+    pub right: &'a str
+}
+
+/// The directions to follow, and the network of nodes to follow them through.
+#[derive(Debug)]
+pub struct Map<'a> {
+    pub directions: &'a str,
+    pub nodes: BTreeMap::<&'a str, Node<'a>>,
+}
+
+impl<'a> Map<'a> {
+    /// The node reached from `node`, which must be on the map, by following `direction`.
+    pub fn next_node(&self, node: &str, direction: char) -> &'a str {
+        match direction {
+            'L' => self.nodes[node].left,
+            _ => self.nodes[node].right,
+        }
+    }
+}
+
+/// Parses `input` as a line of directions, a blank line and the nodes, checking that every node
+/// leads to another node on the map.
+pub fn parse_map(input: &str) -> Result<Map<'_>, Error> {
+    let mut lines = input.lines();
+    let directions = lines.next().unwrap_or_default();
+    if directions.is_empty() {
+        return Err(Error::parse(input, input, "a direction"));
+    }
+    if let Some(index) = directions.find(|c| c != 'L' && c != 'R') {
+        return Err(Error::parse(input, &directions[index..], "L or R"));
+    }
+    lines.next();
+
+    let mut nodes = BTreeMap::<&str, Node>::new();
+    // The nodes start on the third line, after the directions and a blank line.
+    for (number, l) in (3..).zip(lines) {
+        let (node, (left, right)) = parse_all(l, line).map_err(|e| e.on_line(number))?;
+        nodes.insert(node, Node{left, right});
+    }
+
+    for node in nodes.values() {
+        for next in [node.left, node.right] {
+            if !nodes.contains_key(next) {
+                return Err(Error::parse(input, next, "a node on the map"));
+            }
+        }
+    }
+
+    Ok(Map { directions, nodes })
+}
+
+fn line<'a>(line: Span<'a>) -> ParseResult<'a, (&'a str, (&'a str, &'a str))> {
+    let (line, (node, (left, right))) = context("node", separated_pair(
+        alphanumeric1,
+        tag(" = "),
+        delimited(
+            tag("("),
+            separated_pair(
+                alphanumeric1,
+                tag(", "),
+                alphanumeric1
+            ),
+            tag(")")
+        )
+    ))(line)?;
+    Ok((line, (*node.fragment(), (*left.fragment(), *right.fragment()))))
+}
@@ -1,101 +1,103 @@
-use std::collections::BTreeMap;
-use nom::{sequence::{separated_pair, delimited}, character::complete::alpha1, bytes::complete::tag, IResult};
+use aoclib::dot::Graph;
+use aoclib::{Answer, Error, Solution};
 
-#[derive(PartialEq, Eq, Debug)]
-struct Node<'a> {
-    left: &'a str,
-    right: &'a str
-}
+use crate::parse::{parse_map, Map, Node};
 
-struct Nodes<'a> {
-    nodes: BTreeMap::<&'a str, Node<'a>>,
+pub fn process(
+    input: &str,
+) -> u32 {
+    steps_to_zzz(&parse_map(input).expect("should parse")).expect("ZZZ should be reachable from AAA")
 }
 
-impl<'a> Nodes<'a> {
-    fn new(input: impl Iterator<Item = &'a str>) -> Nodes<'a> {
-        let mut nodes = Nodes{nodes: BTreeMap::<&str, Node>::new() };
-
-        for l in input {
-            let (_, (node, (left, right))) = line(l).expect("it should parse");
-            nodes.nodes.insert(node, Node{left, right});
-        }
+/// Like [`process`], but reports a malformed map, a missing AAA node or a ZZZ node which can
+/// never be reached as an error.
+pub fn try_process(input: &str) -> Result<Answer, Error> {
+    Ok(steps_to_zzz(&parse_map(input)?)?.into())
+}
 
-        nodes
+fn steps_to_zzz(map: &Map) -> Result<u32, Error> {
+    if !map.nodes.contains_key("AAA") {
+        return Err(Error::MissingStart("node AAA".to_string()));
     }
-    
-    pub fn next_node(&self, node: &str, direction: &char) -> &'a str {
-        match direction {
-            'L' => self.nodes[node].left,
-            'R' => self.nodes[node].right,
-            value => panic!("This should not happen - recieved {}", value),
+
+    // Once every direction has been followed from every node, the walk only goes round in circles.
+    let limit = map.directions.len() * map.nodes.len();
+    let mut node = "AAA";
+    for (steps, direction) in map.directions.chars().cycle().enumerate().take(limit + 1) {
+        if node == "ZZZ" {
+            return u32::try_from(steps)
+                .map_err(|_| Error::Overflow("the number of steps".to_string()));
         }
+        node = map.next_node(node, direction);
     }
+    Err(Error::NoSolution("ZZZ cannot be reached from AAA".to_string()))
 }
 
-pub fn process(
-    input: &str,
-) -> u32 {
-    let mut lines = input.lines();
-    let directions = lines.next().expect("there will be a line here");
-    lines.next();
-    let nodes = Nodes::new(lines);
 
+pub struct Part1;
 
-    directions.chars().cycle().scan("AAA", |node, direction| {
-        if *node == "ZZZ" { return None };
-        let next = nodes.next_node(node, &direction);
-        *node = next;
-        Some(next)
-    })
-    .count() as u32
+impl Solution for Part1 {
+    const DAY: u8 = 8;
+    const PART: u8 = 1;
 
-}
+    type Input<'a> = Map<'a>;
+    type Params = ();
 
+    fn parse(input: &str) -> Result<Map<'_>, Error> {
+        parse_map(input)
+    }
 
-fn line(line: &str) -> IResult<&str, (&str, (&str, &str))> {
-    separated_pair(
-        alpha1, 
-        tag(" = "), 
-        delimited(
-            tag("("), 
-            separated_pair(
-                alpha1, 
-                tag(", "), 
-                alpha1
-            ), 
-            tag(")")
-        ) 
-    )(line)
-}
+    fn solve(input: Map<'_>, _: &()) -> Result<Answer, Error> {
+        Ok(steps_to_zzz(&input)?.into())
+    }
 
+    /// The network, with each edge labelled by the direction which follows it and the start and
+    /// end of the walk drawn as double circles.
+    fn export_graph(map: Map<'_>, _: &()) -> Result<Option<Graph>, Error> {
+        let mut graph = Graph::directed("day08");
+        for (node, Node { left, right }) in &map.nodes {
+            graph.node(node);
+            graph.edge(node, left).set("label", "L");
+            graph.edge(node, right).set("label", "R");
+        }
+        for node in ["AAA", "ZZZ"] {
+            if map.nodes.contains_key(node) {
+                graph.node(node).set("shape", "doublecircle");
+            }
+        }
+        Ok(Some(graph))
+    }
+}
 
 #[cfg(test)]
 mod tests {
     use super::*;
-
-    #[test]
-    fn test_process() {
-        let lines = "RL
-
-AAA = (BBB, CCC)
-BBB = (DDD, EEE)
-CCC = (ZZZ, GGG)
-DDD = (DDD, DDD)
-EEE = (EEE, EEE)
-GGG = (GGG, GGG)
-ZZZ = (ZZZ, ZZZ)";
-        let result = process(lines);
-        assert_eq!(result, 2);
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("RX\n\nAAA = (ZZZ, ZZZ)\nZZZ = (ZZZ, ZZZ)", "line 1, column 2: expected L or R, found \"X\"")]
+    #[case("R\n\nAAA = (ZZZ, ZZZ)\nZZZ (ZZZ, ZZZ)", "line 4, column 4: expected \" = \" in node, found \" (ZZZ, ZZZ)\"")]
+    #[case("R\n\nAAA = (BBB, ZZZ)\nZZZ = (ZZZ, ZZZ)", "line 3, column 8: expected a node on the map, found \"BBB, ZZZ)\"")]
+    #[case("R\n\nBBB = (ZZZ, ZZZ)\nZZZ = (ZZZ, ZZZ)", "the input has no start (node AAA)")]
+    #[case("L\n\nAAA = (BBB, BBB)\nBBB = (AAA, ZZZ)\nZZZ = (ZZZ, ZZZ)", "no solution: ZZZ cannot be reached from AAA")]
+    fn test_try_process_errors(#[case] input: &str, #[case] expected: &str) {
+        assert_eq!(try_process(input).unwrap_err().to_string(), expected);
     }
-   #[test]
-    fn test_process_again() {
-        let lines = "LLR
 
-AAA = (BBB, BBB)
-BBB = (AAA, ZZZ)
-ZZZ = (ZZZ, ZZZ)";
-        let result = process(lines);
-        assert_eq!(result, 6);
+    #[test]
+    fn test_export_graph() {
+        let graph = Part1::export_graph(Part1::parse("LLR\n\nAAA = (BBB, BBB)\nBBB = (AAA, ZZZ)\nZZZ = (ZZZ, ZZZ)").unwrap(), &()).unwrap().unwrap();
+        assert_eq!(graph.to_string(), r#"digraph "day08" {
+    "AAA" [shape="doublecircle"];
+    "BBB";
+    "ZZZ" [shape="doublecircle"];
+    "AAA" -> "BBB" [label="L"];
+    "AAA" -> "BBB" [label="R"];
+    "BBB" -> "AAA" [label="L"];
+    "BBB" -> "ZZZ" [label="R"];
+    "ZZZ" -> "ZZZ" [label="L"];
+    "ZZZ" -> "ZZZ" [label="R"];
+}
+"#);
     }
-
 }
@@ -1,132 +1,111 @@
-use std::collections::BTreeMap;
-use nom::{sequence::{separated_pair, delimited}, character::complete::alphanumeric1, bytes::complete::tag, IResult};
+use aoclib::dot::Graph;
+use aoclib::math;
+use aoclib::parallel::prelude::*;
+use aoclib::{Answer, Error, Solution};
 
-#[derive(PartialEq, Eq, Debug, Clone, Copy)]
-struct Node<'a> {
-    left: &'a str,
-    right: &'a str
-}
+use crate::parse::{parse_map, Map, Node};
 
-#[derive(PartialEq, Eq, Debug)]
-struct Nodes<'a> {
-    nodes: BTreeMap::<&'a str, Node<'a>>,
+pub fn process(
+    input: &str,
+) -> u64 {
+    steps_to_all_z(&parse_map(input).expect("should parse")).expect("every start should reach a node ending in Z")
 }
 
-impl<'a> Nodes<'a> {
-    fn new(input: impl Iterator<Item = &'a str>) -> Nodes<'a> {
-        let mut nodes = Nodes{nodes: BTreeMap::<&str, Node>::new() };
-
-        for l in input {
-            let (_, (node, (left, right))) = line(l).expect("it should parse");
-            nodes.nodes.insert(node, Node{left, right});
-        }
+/// Like [`process`], but reports a malformed map, a map without start nodes, a start node which
+/// never reaches a node ending in Z, or an answer too large for a `u64` as an error.
+pub fn try_process(input: &str) -> Result<Answer, Error> {
+    Ok(steps_to_all_z(&parse_map(input)?)?.into())
+}
 
-        nodes
-    }
-    
-    pub fn next_node(&'a self, node: &str, direction: char) -> &'a &'a str {
-        match direction {
-            'L' => &self.nodes[node].left,
-            'R' => &self.nodes[node].right,
-            value => unreachable!("This should not happen - recieved {}", value),
-        }
+fn steps_to_all_z(map: &Map) -> Result<u64, Error> {
+    let start_nodes: Vec<&str> = map.nodes.keys().copied().filter(|s| s.ends_with('A')).collect();
+    if start_nodes.is_empty() {
+        return Err(Error::MissingStart("a node ending in A".to_string()));
     }
 
-    pub fn start_nodes(&self) -> Vec<&&str> {
-        self.nodes.keys().filter(|s| s.ends_with('A')).collect()
-    }
+    // Once every direction has been followed from every node, the walk only goes round in circles.
+    let limit = map.directions.len() * map.nodes.len();
+    let cycle_lengths = start_nodes.into_maybe_par_iter()
+        .map(|node| {
+            let mut current_node = node;
+            map.directions
+                .chars()
+                .cycle()
+                .take(limit)
+                .position(|direction| {
+                    current_node = map.next_node(current_node, direction);
+                    current_node.ends_with('Z')
+                })
+                .map(|index| (index + 1) as u64)
+                .ok_or_else(|| Error::NoSolution(format!("{} never reaches a node ending in Z", node)))
+        })
+        .collect::<Result<Vec<u64>, Error>>()?;
+
+    math::lcm_all(&cycle_lengths)
+        .ok_or_else(|| Error::Overflow("the number of steps".to_string()))
 }
 
-pub fn process(
-    input: &str,
-) -> u64 {
-    let mut lines = input.lines();
-    let directions = lines.next().expect("there will be a line here");
-    lines.next();
-    let nodes = Nodes::new(lines);
 
-    let start_nodes = nodes.start_nodes();
-    // dbg!(&start_nodes);
+pub struct Part2;
 
-    let cycle_lengths = start_nodes.into_iter()
-        .map(|node| {
-        let mut visited_nodes = vec![*node];
-        let mut current_node = *node;
-
-        directions
-            .chars()
-            .cycle()
-            .enumerate()
-            .find_map(|(index, direction)| {
-                let next_node = nodes.next_node(current_node, direction);
-                // println!("{} : {} = {}", current_node, direction, next_node);
-                if next_node.ends_with('Z') {
-                    Some((index + 1) as u64)
-                } else {
-                    visited_nodes.push(next_node);
-                    current_node = next_node;
-                    None
-                }
-            })
-            .expect("a cycle to exist")
-    })
-    // .inspect(|x| { dbg!(x); })
-    .collect::<Vec<u64>>();
-
-    lowest_common_multiple(&cycle_lengths)
-}
+impl Solution for Part2 {
+    const DAY: u8 = 8;
+    const PART: u8 = 2;
 
-pub fn lowest_common_multiple(numbers: &[u64]) -> u64 {
-    if numbers.len() == 1 {
-        return numbers[0];
+    type Input<'a> = Map<'a>;
+    type Params = ();
+
+    fn parse(input: &str) -> Result<Map<'_>, Error> {
+        parse_map(input)
     }
-    let a = numbers[0];
-    let b = lowest_common_multiple(&numbers[1..]);
-    a * b / gcd_of_two_numbers(a, b)
-}
 
-fn gcd_of_two_numbers(a: u64, b: u64) -> u64 {
-    if b == 0 {
-        return a;
+    fn solve(input: Map<'_>, _: &()) -> Result<Answer, Error> {
+        Ok(steps_to_all_z(&input)?.into())
     }
-    gcd_of_two_numbers(b, a % b)
-}
 
-fn line(line: &str) -> IResult<&str, (&str, (&str, &str))> {
-    separated_pair(
-        alphanumeric1, 
-        tag(" = "), 
-        delimited(
-            tag("("), 
-            separated_pair(
-                alphanumeric1, 
-                tag(", "), 
-                alphanumeric1
-            ), 
-            tag(")")
-        ) 
-    )(line)
+    /// The network, with each edge labelled by the direction which follows it, the nodes ending in
+    /// A drawn as double circles and the nodes ending in Z shaded.
+    fn export_graph(map: Map<'_>, _: &()) -> Result<Option<Graph>, Error> {
+        let mut graph = Graph::directed("day08");
+        for (node, Node { left, right }) in &map.nodes {
+            let attributes = graph.node(node);
+            if node.ends_with('A') {
+                attributes.set("shape", "doublecircle");
+            }
+            if node.ends_with('Z') {
+                attributes.set("style", "filled");
+            }
+            graph.edge(node, left).set("label", "L");
+            graph.edge(node, right).set("label", "R");
+        }
+        Ok(Some(graph))
+    }
 }
 
-
 #[cfg(test)]
 mod tests {
     use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("LR\n\n11B = (11Z, 11Z)\n11Z = (11B, 11B)", "the input has no start (a node ending in A)")]
+    #[case("LR\n\n11A = (11B, 11B)\n11B = (11A, 11A)", "no solution: 11A never reaches a node ending in Z")]
+    #[case("LR\n\n11A = (11B, 11C)\n11B = (11A, 11A)", "line 3, column 13: expected a node on the map, found \"11C)\"")]
+    fn test_try_process_errors(#[case] input: &str, #[case] expected: &str) {
+        assert_eq!(try_process(input).unwrap_err().to_string(), expected);
+    }
 
     #[test]
-    fn test_process() {
-        let lines = "LR
-
-11A = (11B, XXX)
-11B = (XXX, 11Z)
-11Z = (11B, XXX)
-22A = (22B, XXX)
-22B = (22C, 22C)
-22C = (22Z, 22Z)
-22Z = (22B, 22B)
-XXX = (XXX, XXX)";
-        let result = process(lines);
-        assert_eq!(result, 6);
+    fn test_export_graph() {
+        let graph = Part2::export_graph(Part2::parse("LR\n\n11A = (11Z, 11Z)\n11Z = (11A, 11A)").unwrap(), &()).unwrap().unwrap();
+        assert_eq!(graph.to_string(), r#"digraph "day08" {
+    "11A" [shape="doublecircle"];
+    "11Z" [style="filled"];
+    "11A" -> "11Z" [label="L"];
+    "11A" -> "11Z" [label="R"];
+    "11Z" -> "11A" [label="L"];
+    "11Z" -> "11A" [label="R"];
+}
+"#);
     }
-
 }
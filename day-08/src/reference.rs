@@ -0,0 +1,81 @@
+//! Slow but obviously correct solutions, to check the real ones against on small inputs.
+
+use std::collections::HashMap;
+
+fn network(input: &str) -> (&str, HashMap<&str, (&str, &str)>) {
+    let (directions, nodes) = input.split_once("\n\n").unwrap();
+    let nodes = nodes
+        .lines()
+        .map(|line| {
+            let (node, next) = line.split_once(" = ").unwrap();
+            let (left, right) = next.trim_matches(['(', ')']).split_once(", ").unwrap();
+            (node, (left, right))
+        })
+        .collect();
+    (directions, nodes)
+}
+
+/// Walks every ghost at once until they are all on a node ending in Z.
+fn walk(input: &str, start: impl Fn(&str) -> bool, end: impl Fn(&str) -> bool) -> u64 {
+    let (directions, nodes) = network(input);
+    let mut ghosts: Vec<&str> = nodes.keys().copied().filter(|node| start(node)).collect();
+    let mut steps = 0;
+    for direction in directions.chars().cycle() {
+        if ghosts.iter().all(|ghost| end(ghost)) {
+            break;
+        }
+        for ghost in ghosts.iter_mut() {
+            let (left, right) = nodes[*ghost];
+            *ghost = if direction == 'L' { left } else { right };
+        }
+        steps += 1;
+    }
+    steps
+}
+
+pub fn part1(input: &str) -> u64 {
+    walk(input, |node| node == "AAA", |node| node == "ZZZ")
+}
+
+pub fn part2(input: &str) -> u64 {
+    walk(
+        input,
+        |node| node.ends_with('A'),
+        |node| node.ends_with('Z'),
+    )
+}
+
+mod tests {
+    use aoclib::gen;
+    use aoclib::reference::assert_agrees;
+    use proptest::prelude::*;
+
+    use super::*;
+    use crate::part1::Part1;
+    use crate::part2::Part2;
+
+    proptest! {
+        #[test]
+        fn test_part1(seed: u64, size in 1..60usize) {
+            let input = gen::generator(8).unwrap().generate(size, seed);
+            assert_agrees::<Part1>(&input, &[], part1(&input));
+        }
+
+        #[test]
+        fn test_part2(seed: u64, size in 1..60usize) {
+            let input = gen::generator(8).unwrap().generate(size, seed);
+            assert_agrees::<Part2>(&input, &[], part2(&input));
+        }
+    }
+
+    // Part 2 takes the lowest common multiple of the steps to each ghost's first Z node, which
+    // is wrong once a ghost takes a different number of steps to get back to one.
+    #[test]
+    #[should_panic(expected = "disagrees with the reference")]
+    fn test_part2_uneven_cycle() {
+        let input = "L\n\n\
+                     11A = (11Z, 11Z)\n11Z = (11B, 11B)\n11B = (11C, 11C)\n11C = (11Z, 11Z)\n\
+                     22A = (22B, 22B)\n22B = (22Z, 22Z)\n22Z = (22B, 22B)\n";
+        assert_agrees::<Part2>(input, &[], part2(input));
+    }
+}
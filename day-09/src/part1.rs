@@ -1,21 +1,49 @@
-use nom::{bytes::complete::tag, character::complete, multi::separated_list1, IResult, sequence};
+use nom::{character::complete, combinator::cut, error::context, multi::separated_list1};
+use std::io::BufRead;
 use std::iter::repeat_with;
 
+use aoclib::parse::{parse_all, tag, ParseResult, Span};
+use aoclib::stream;
+use aoclib::{Answer, Error, Solution};
+
 pub fn process<'a>(
     input: &'a str,
 ) -> i32 {
-    let sequences: Vec<Vec<i32>> = input.lines()
-        .map(|x| sequence(x).expect("should parse").1
-        ).collect();
+    parse_histories(input).expect("should parse").iter().map(extend_sequence).sum()
+}
 
-    sequences.iter().map(extend_sequence).sum()
+/// Like [`process`], but reports a line which is not a list of numbers as an error.
+pub fn try_process(input: &str) -> Result<Answer, Error> {
+    Ok(parse_histories(input)?.iter().map(extend_sequence).sum::<i32>().into())
+}
+
+/// Like [`try_process`], but for the input a line at a time, so it needn't all be in memory.
+pub fn try_process_lines<Line: AsRef<str>>(lines: impl Iterator<Item = Line>) -> Result<Answer, Error> {
+    lines
+        .enumerate()
+        .map(|(index, line)| {
+            let line = line.as_ref();
+            parse_all(line, sequence)
+                .map(|sequence| extend_sequence(&sequence))
+                .map_err(|e| e.on_line(index + 1))
+        })
+        .sum::<Result<i32, Error>>()
+        .map(Answer::from)
 }
 
-fn sequence(input: &str) -> IResult<&str, Vec<i32>> {
-    separated_list1(
+/// Parses every line of `input` as a history of numbers.
+fn parse_histories(input: &str) -> Result<Vec<Vec<i32>>, Error> {
+    input.lines()
+        .enumerate()
+        .map(|(index, line)| parse_all(line, sequence).map_err(|e| e.on_line(index + 1)))
+        .collect()
+}
+
+fn sequence(input: Span) -> ParseResult<Vec<i32>> {
+    context("history", separated_list1(
         tag(" "), 
-        complete::i32
-    )(input)
+        cut(complete::i32)
+    ))(input)
 }
 
 fn extend_sequence(sequence: &Vec<i32>) -> i32 {
@@ -31,6 +59,28 @@ fn extend_sequence(sequence: &Vec<i32>) -> i32 {
         .collect::<Vec<i32>>().iter().rev().fold(0, |acc, x| acc + x)
 }
 
+pub struct Part1;
+
+impl Solution for Part1 {
+    const DAY: u8 = 9;
+    const PART: u8 = 1;
+
+    type Input<'a> = Vec<Vec<i32>>;
+    type Params = ();
+
+    fn parse(input: &str) -> Result<Vec<Vec<i32>>, Error> {
+        parse_histories(input)
+    }
+
+    fn solve(input: Vec<Vec<i32>>, _: &()) -> Result<Answer, Error> {
+        Ok(input.iter().map(extend_sequence).sum::<i32>().into())
+    }
+
+    fn solve_reader(reader: &mut dyn BufRead, _: &()) -> Result<Option<Answer>, Error> {
+        stream::with_lines(reader, |lines| try_process_lines(lines)).map(Some)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -42,11 +92,12 @@ mod tests {
     }
 
     #[test]
-    fn test_process() {
+    fn test_try_process() {
         let lines = "0 3 6 9 12 15
-1 3 6 10 15 21
-10 13 16 21 30 45";
-        let result = process(lines);
-        assert_eq!(result, 114);
+1 3 x 10 15 21";
+        assert_eq!(
+            try_process(lines).unwrap_err().to_string(),
+            "line 2, column 5: expected a number in history, found \"x 10 15 21\""
+        );
     }
 }
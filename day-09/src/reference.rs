@@ -0,0 +1,76 @@
+//! Slow but obviously correct solutions, to check the real ones against on small inputs.
+
+/// Every row of differences of `values`, down to one of all zeros.
+fn differences(values: Vec<i64>) -> Vec<Vec<i64>> {
+    let mut rows = vec![values];
+    while rows.last().unwrap().iter().any(|value| *value != 0) {
+        let row = rows
+            .last()
+            .unwrap()
+            .windows(2)
+            .map(|pair| pair[1] - pair[0])
+            .collect();
+        rows.push(row);
+    }
+    rows
+}
+
+fn sequences(input: &str) -> impl Iterator<Item = Vec<i64>> + '_ {
+    input.lines().map(|line| {
+        line.split_whitespace()
+            .map(|n| n.parse().unwrap())
+            .collect()
+    })
+}
+
+/// Extends every row of the table to the right, from the bottom up.
+pub fn part1(input: &str) -> i64 {
+    sequences(input)
+        .map(|values| {
+            let mut rows = differences(values);
+            for index in (0..rows.len() - 1).rev() {
+                let next = rows[index].last().unwrap() + rows[index + 1].last().unwrap();
+                rows[index].push(next);
+            }
+            *rows[0].last().unwrap()
+        })
+        .sum()
+}
+
+/// Extends every row of the table to the left, from the bottom up.
+pub fn part2(input: &str) -> i64 {
+    sequences(input)
+        .map(|values| {
+            let mut rows = differences(values);
+            for index in (0..rows.len() - 1).rev() {
+                let previous = rows[index][0] - rows[index + 1][0];
+                rows[index].insert(0, previous);
+            }
+            rows[0][0]
+        })
+        .sum()
+}
+
+mod tests {
+    use aoclib::gen;
+    use aoclib::reference::assert_agrees;
+    use proptest::prelude::*;
+
+    use super::*;
+    use crate::part1::Part1;
+    use crate::part2::Part2;
+
+    proptest! {
+        #[test]
+        fn test_part1(seed: u64, size in 1..20usize) {
+            let input = gen::generator(9).unwrap().generate(size, seed);
+            assert_agrees::<Part1>(&input, &[], part1(&input));
+        }
+
+        #[test]
+        fn test_part2(seed: u64, size in 1..20usize) {
+            let input = gen::generator(9).unwrap().generate(size, seed);
+            assert_agrees::<Part2>(&input, &[], part2(&input));
+        }
+    }
+}
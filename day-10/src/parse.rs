@@ -0,0 +1,17 @@
+use glam::IVec2;
+
+use aoclib::{Error, Grid};
+
+/// The field of pipes, and the tile the animal started on.
+pub struct Field {
+    pub tiles: Grid<char>,
+    pub start: IVec2,
+}
+
+/// Parses `input` as a rectangle of pipes, ground and a start, which the parts otherwise assume.
+pub fn parse_field(input: &str) -> Result<Field, Error> {
+    let tiles = Grid::try_parse(input, "a pipe, '.' or 'S'", |c| "|-LJ7F.S".contains(c).then_some(c))?;
+    let start = tiles.find(|c| *c == 'S').ok_or_else(|| Error::MissingStart("S".to_string()))?;
+    Ok(Field { tiles, start })
+}
+
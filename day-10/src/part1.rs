@@ -1,82 +1,109 @@
 use std::collections::HashMap;
 
 use glam::IVec2;
+use tracing::{debug, trace};
 
-const NORTH: &IVec2 = &IVec2{x:  0, y: -1};
-const SOUTH: &IVec2 = &IVec2{x:  0, y:  1};
-const EAST:  &IVec2 = &IVec2{x:  1, y:  0};
-const WEST:  &IVec2 = &IVec2{x: -1, y:  0};
+use aoclib::budget;
+use aoclib::{Answer, Direction, Error, Solution};
 
-fn symbol_to_exit(c: &char) -> Vec<&IVec2> {
+use crate::parse::{parse_field, Field};
+
+fn symbol_to_exit(c: &char) -> Vec<Direction> {
+    use Direction::*;
     match c {
-        '|' => vec![ NORTH, SOUTH ],
-        '-' => vec![  EAST,  WEST ],
-        'L' => vec![ NORTH,  EAST ],
-        'J' => vec![ NORTH,  WEST ],
-        '7' => vec![ SOUTH,  WEST ],
-        'F' => vec![ SOUTH,  EAST ],
-        'S' => vec![ NORTH, SOUTH, EAST, WEST ],
-        '.' => vec![],
-        value => panic!("Found invalid character: '{}'", value)
+        '|' => vec![ North, South ],
+        '-' => vec![  East,  West ],
+        'L' => vec![ North,  East ],
+        'J' => vec![ North,  West ],
+        '7' => vec![ South,  West ],
+        'F' => vec![ South,  East ],
+        'S' => vec![ North, South, East, West ],
+        // '.', as nothing else gets past the parser
+        _ => vec![],
     }
 }
 
 fn exits<'a>(location: &'a IVec2, symbol: &'a char) -> Vec<IVec2> {
-    symbol_to_exit(symbol).iter().map(|delta| (**delta + *location) ).collect()
+    symbol_to_exit(symbol).into_iter().map(|direction| *location + direction ).collect()
 }
 
 pub fn process<'a>(
     input: &'a str,
 ) -> u32 {
+    let field = parse_field(input).expect("should parse");
+    farthest_from_start(&field).expect("there should be a loop through the start")
+}
+
+/// The number of steps along the loop to the point farthest from the start.
+fn farthest_from_start(field: &Field) -> Result<u32, Error> {
 
-    let nodes: HashMap<IVec2, Vec<IVec2>> = input.lines()
-        .enumerate()
-        .map({ |(y, line)| 
-            line.chars()
-                .enumerate()
-                .map(move |(x, c)| {
-                    let x: i32 = x.try_into().unwrap();
-                    let y: i32 = y.try_into().unwrap();
-                    let location = IVec2{x, y};
-                    (location, exits(&location, &c))
-                })
-        })
-        .flatten()
+    let nodes: HashMap<IVec2, Vec<IVec2>> = field.tiles.iter()
+        .map(|(location, c)| (location, exits(&location, c)))
         .collect();
 
-    let start_node: IVec2 = *nodes.iter().find_map(|(location, exits)| if exits.len() == 4 {Some(location)} else {None} ).expect("there should be a start node");
-    dbg!(&start_node);
-    
-    (nodes[&start_node].iter().map(|next_node| {
-        // dbg!(&next_node);
-        if nodes[next_node].contains(&start_node) {
-            find_loop(vec![start_node], *next_node, &start_node, &nodes)
+    let start_node = field.start;
+    debug!(?start_node, "found the start");
+
+    // the start's exits, like those of any tile on the edge, can lead off the field
+    for next_node in nodes[&start_node].iter().filter(|x| nodes.contains_key(x) ) {
+        let path = if nodes[next_node].contains(&start_node) {
+            find_loop(vec![start_node], *next_node, &start_node, &nodes)?
         } else {
             None
+        };
+        trace!(length = path.as_ref().map(Vec::len), "followed a pipe out of the start");
+        if let Some(path) = path {
+            return Ok((path.len() as u32 + 1) / 2);
         }
-    })
-    .inspect(|x| {dbg!(x); })
-    .flatten().next().unwrap().len() as u32 + 1) / 2
+    }
+    Err(Error::NoSolution("there is no loop through the start".to_string()))
 }
 
-fn find_loop(visited_nodes: Vec<IVec2>, node: IVec2, start_node: &IVec2, nodes: &HashMap<IVec2, Vec<IVec2>> ) -> Option<Vec<IVec2>> {
-    // if we're back at the start then we're done
-    if nodes[&node].contains(start_node) && visited_nodes.len() > 2 { return Some(visited_nodes) };
-    
-    let next_nodes = nodes[&node].iter().filter(|n| ! visited_nodes.contains(n) ).collect::<Vec<&IVec2>>();
-    // if there's no-where else to go, then we're done;
-    if next_nodes.len() == 0 { return None };
+/// Like [`process`], but reports a map with tiles which are not pipes, without a start, or whose
+/// loop is not found within the budget, as an error.
+pub fn try_process(input: &str) -> Result<Answer, Error> {
+    Ok(farthest_from_start(&parse_field(input)?)?.into())
+}
+
+/// Follows the pipes from `node` until they lead back to the start, keeping a stack of the paths
+/// still to try rather than recursing, as the loop can be longer than the call stack is deep.
+fn find_loop(visited_nodes: Vec<IVec2>, node: IVec2, start_node: &IVec2, nodes: &HashMap<IVec2, Vec<IVec2>> ) -> Result<Option<Vec<IVec2>>, Error> {
+    let mut paths = vec![(visited_nodes, node)];
+    while let Some((visited_nodes, node)) = paths.pop() {
+        budget::tick()?;
+
+        let Some(exits) = nodes.get(&node) else { continue };
 
-    // dbg!(&next_nodes);
+        // if we're back at the start then we're done
+        if exits.contains(start_node) && visited_nodes.len() > 2 { return Ok(Some(visited_nodes)) };
 
-    let mut new_visited_nodes = visited_nodes.clone();
-    new_visited_nodes.push(node);
+        let mut new_visited_nodes = visited_nodes.clone();
+        new_visited_nodes.push(node);
 
-    for next_node in next_nodes {
-        let result = find_loop(new_visited_nodes.clone(), *next_node, start_node, nodes);
-        if result.is_some() { return result };
+        // pushed in reverse, so that the first way onwards is tried first
+        for next_node in exits.iter().rev().filter(|n| ! visited_nodes.contains(n) ) {
+            paths.push((new_visited_nodes.clone(), *next_node));
+        }
+    }
+    Ok(None)
+}
+
+pub struct Part1;
+
+impl Solution for Part1 {
+    const DAY: u8 = 10;
+    const PART: u8 = 1;
+
+    type Input<'a> = Field;
+    type Params = ();
+
+    fn parse(input: &str) -> Result<Field, Error> {
+        parse_field(input)
+    }
+
+    fn solve(input: Field, _: &()) -> Result<Answer, Error> {
+        Ok(farthest_from_start(&input)?.into())
     }
-    None
 }
 
 #[cfg(test)]
@@ -86,22 +113,15 @@ mod tests {
     use rstest::rstest;
 
     #[rstest]
-    #[case(".....
-.S-7.
-.|.|.
-.L-J.
-.....
-", 4)]
-    #[case("..F7.
-.FJ|.
-SJ.L7
-|F--J
-LJ...",8)]
-    fn test_process(
-        #[case] input: &str,
-        #[case] expected: u32,
-    ) {
-        let result = process(input);
-        assert_eq!(result, expected);
+    #[case(".....\n.S-7.\n.|x|.\n", "line 3, column 3: expected a pipe, '.' or 'S', found \"x|.\"")]
+    #[case(".....\n.F-7.\n.|.|.\n", "the input has no start (S)")]
+    #[case("S-\n|.\n", "no solution: there is no loop through the start")]
+    fn test_try_process(#[case] input: &str, #[case] expected: &str) {
+        assert_eq!(try_process(input).unwrap_err().to_string(), expected);
+    }
+
+    #[test]
+    fn test_start_on_the_edge() {
+        assert_eq!(try_process("S7\nLJ\n"), Ok(Answer::U32(2)));
     }
 }
@@ -1,143 +1,180 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 use glam::IVec2;
+use tracing::{debug, trace};
 
-const NORTH: &IVec2 = &IVec2{x:  0, y: -1};
-const SOUTH: &IVec2 = &IVec2{x:  0, y:  1};
-const EAST:  &IVec2 = &IVec2{x:  1, y:  0};
-const WEST:  &IVec2 = &IVec2{x: -1, y:  0};
+use aoclib::budget;
+use aoclib::geometry::Polygon;
+use aoclib::viz::{Colour, Frame};
+use aoclib::{Answer, Direction, Error, Grid, Solution};
 
-fn symbol_to_exit(c: &char) -> Vec<&IVec2> {
+use crate::parse::{parse_field, Field};
+
+fn symbol_to_exit(c: &char) -> Vec<Direction> {
+    use Direction::*;
     match c {
-        '|' => vec![ NORTH, SOUTH ],
-        '-' => vec![  EAST,  WEST ],
-        'L' => vec![ NORTH,  EAST ],
-        'J' => vec![ NORTH,  WEST ],
-        '7' => vec![ SOUTH,  WEST ],
-        'F' => vec![ SOUTH,  EAST ],
-        'S' => vec![ NORTH, SOUTH, EAST, WEST ],
-        '.' => vec![],
-        value => panic!("Found invalid character: '{}'", value)
+        '|' => vec![ North, South ],
+        '-' => vec![  East,  West ],
+        'L' => vec![ North,  East ],
+        'J' => vec![ North,  West ],
+        '7' => vec![ South,  West ],
+        'F' => vec![ South,  East ],
+        'S' => vec![ North, South, East, West ],
+        // '.', as nothing else gets past the parser
+        _ => vec![],
     }
 }
 
 fn exits<'a>(location: &'a IVec2, symbol: &'a char) -> Vec<IVec2> {
-    symbol_to_exit(symbol).iter().map(|delta| (**delta + *location) ).collect()
+    symbol_to_exit(symbol).into_iter().map(|direction| *location + direction ).collect()
 }
 
 pub fn process<'a>(
     input: &'a str,
 ) -> u32 {
+    let field = parse_field(input).expect("should parse");
+    let path = find_pipe_loop(&field).expect("there should be a loop through the start");
+
+    enclosed_area(&path)
+}
 
-    let nodes: HashMap<IVec2, Vec<IVec2>> = input.lines()
-        .enumerate()
-        .flat_map({ |(y, line)| 
-            line.chars()
-                .enumerate()
-                .map(move |(x, c)| {
-                    let x: i32 = x.try_into().unwrap();
-                    let y: i32 = y.try_into().unwrap();
-                    let location = IVec2{x, y};
-                    (location, exits(&location, &c))
-                })
-        })
+fn enclosed_area(path: &[IVec2]) -> u32 {
+    Polygon::new(path.iter().map(|location| location.as_i64vec2()))
+        .interior_points()
+        .try_into()
+        .expect("the enclosed area should fit in a u32")
+}
+
+/// The tiles of the loop through the start, in order.
+fn find_pipe_loop(field: &Field) -> Result<Vec<IVec2>, Error> {
+    let nodes: HashMap<IVec2, Vec<IVec2>> = field.tiles.iter()
+        .map(|(location, c)| (location, exits(&location, c)))
         .collect();
 
-    let start_node: IVec2 = *nodes.iter().find_map(|(location, exits)| if exits.len() == 4 {Some(location)} else {None} ).expect("there should be a start node");
-    dbg!(&start_node);
-    
-    let path = nodes[&start_node].iter()
-        .filter(|x| nodes.contains_key(&x) )
-        .flat_map(|next_node| {
-        if nodes[next_node].contains(&start_node) {
-            find_loop(vec![start_node], *next_node, &start_node, &nodes)
-        } else {
-            None
+    let start_node = field.start;
+    debug!(?start_node, "found the start");
+
+    for next_node in nodes[&start_node].iter().filter(|x| nodes.contains_key(&x) ) {
+        if !nodes[next_node].contains(&start_node) {
+            continue;
+        }
+        if let Some(path) = find_loop(vec![start_node], *next_node, &start_node, &nodes)? {
+            debug!(length = path.len(), "found the loop");
+            trace!(?path);
+            return Ok(path);
         }
-    }).next().expect("There should be a loop");
+    }
+    Err(Error::NoSolution("there is no loop through the start".to_string()))
+}
+
+/// Like [`process`], but reports a map with tiles which are not pipes, without a start, or whose
+/// loop is not found within the budget, as an error.
+pub fn try_process(input: &str) -> Result<Answer, Error> {
+    Ok(enclosed_area(&find_pipe_loop(&parse_field(input)?)?).into())
+}
 
-    dbg!(&path);
+/// The tiles inside the loop, found by counting how many times the loop crosses each row to the
+/// left of them. Only tiles with a pipe to the north count as crossings, so that running along a
+/// stretch of the loop counts once if it crosses the row and not at all if it turns back.
+fn enclosed_tiles(map: &Grid<char>, path: &[IVec2]) -> Vec<IVec2> {
+    let on_loop = path.iter().collect::<HashSet<_>>();
+    let start = path[0];
+    let start_goes_north = [path[1], path[path.len() - 1]].contains(&(start + Direction::North));
+
+    let mut enclosed = vec![];
+    for y in 0..map.height() as i32 {
+        let mut inside = false;
+        for x in 0..map.width() as i32 {
+            let location = IVec2{x, y};
+            if on_loop.contains(&location) {
+                let goes_north = if location == start { start_goes_north } else { matches!(map[location], '|' | 'L' | 'J') };
+                inside ^= goes_north;
+            } else if inside {
+                enclosed.push(location);
+            }
+        }
+    }
+    enclosed
+}
 
-    let pairs_of_points = path.iter().skip(1).zip(path.iter().skip(2));
-    let area_including_perimeter = pairs_of_points.fold(0,
-        |area: i32, (a, b): (&IVec2, &IVec2)| {
-            let vec1 = *a - start_node;
-            let vec2 = *b - start_node;
-            area + vec2.perp_dot(vec1)
-        }).abs() as u32;
+/// Follows the pipes from `node` until they lead back to the start, keeping a stack of the paths
+/// still to try rather than recursing, as the loop can be longer than the call stack is deep.
+fn find_loop(visited_nodes: Vec<IVec2>, node: IVec2, start_node: &IVec2, nodes: &HashMap<IVec2, Vec<IVec2>> ) -> Result<Option<Vec<IVec2>>, Error> {
+    let mut paths = vec![(visited_nodes, node)];
+    while let Some((visited_nodes, node)) = paths.pop() {
+        budget::tick()?;
 
-    ( area_including_perimeter - (path.len() as u32) + 1 ) / 2
+        let Some(exits) = nodes.get(&node) else { continue };
+
+        // if we're back at the start then we're done
+        if exits.contains(start_node) && visited_nodes.len() > 2 {
+            let mut path = visited_nodes;
+            path.push(node);
+            return Ok(Some(path));
+        };
+
+        let mut new_visited_nodes = visited_nodes.clone();
+        new_visited_nodes.push(node);
+
+        // pushed in reverse, so that the first way onwards is tried first
+        for next_node in exits.iter().rev().filter(|n| ! visited_nodes.contains(n) ) {
+            paths.push((new_visited_nodes.clone(), *next_node));
+        }
+    }
+    Ok(None)
 }
 
-fn find_loop(visited_nodes: Vec<IVec2>, node: IVec2, start_node: &IVec2, nodes: &HashMap<IVec2, Vec<IVec2>> ) -> Option<Vec<IVec2>> {
-    // if we're back at the start then we're done
-    if nodes[&node].contains(start_node) && visited_nodes.len() > 2 { return Some(visited_nodes) };
-    
-    let next_nodes = nodes[&node].iter().filter(|n| ! visited_nodes.contains(n) ).collect::<Vec<&IVec2>>();
-    // if there's no-where else to go, then we're done;
-    if next_nodes.len() == 0 { return None };
+pub struct Part2;
+
+impl Solution for Part2 {
+    const DAY: u8 = 10;
+    const PART: u8 = 2;
 
+    type Input<'a> = Field;
+    type Params = ();
 
-    let mut new_visited_nodes = visited_nodes.clone();
-    new_visited_nodes.push(node);
+    fn parse(input: &str) -> Result<Field, Error> {
+        parse_field(input)
+    }
 
-    for next_node in next_nodes {
-        let result = find_loop(new_visited_nodes.clone(), *next_node, start_node, nodes);
-        if result.is_some() { return result };
+    fn solve(input: Field, _: &()) -> Result<Answer, Error> {
+        Ok(enclosed_area(&find_pipe_loop(&input)?).into())
+    }
+
+    /// The map with the loop highlighted, and the tiles it encloses marked with `I`.
+    fn visualise(field: Field, _: &()) -> Result<Vec<Frame>, Error> {
+        let path = find_pipe_loop(&field)?;
+        let mut frame = Frame::new(&field.tiles, |c| *c);
+        frame.highlight(path.iter().copied(), Colour::Yellow)
+            .mark(enclosed_tiles(&field.tiles, &path), 'I', Colour::Green);
+        Ok(vec![frame])
     }
-    None
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use aoclib::budget::Budget;
+
+    #[test]
+    fn test_visualise() {
+        let frames = Part2::visualise(Part2::parse(".....\n.S-7.\n.|.|.\n.L-J.\n.....\n").unwrap(), &()).unwrap();
+        assert_eq!(frames[0].to_ascii(), ".....\n.S-7.\n.|I|.\n.L-J.\n.....\n");
+        assert_eq!(frames[0].cells()[IVec2::new(1, 3)].colour, Some(Colour::Yellow));
+    }
+
+    #[test]
+    fn test_start_on_the_edge() {
+        assert_eq!(try_process("S7\nLJ\n"), Ok(Answer::U32(0)));
+    }
 
-    use rstest::rstest;
-
-    #[rstest]
-    #[case("...........
-.S-------7.
-.|F-----7|.
-.||.....||.
-.||.....||.
-.|L-7.F-J|.
-.|..|.|..|.
-.L--J.L--J.
-...........", 4)]
-    #[case("...........
-.S-------7.
-.|F-----7|.
-.||.....||.
-.||.....||.
-.|L-7.F-J|.
-.|..|.|..|.
-.L--J.L--J.
-...........",4)]
-    #[case(".F----7F7F7F7F-7....
-.|F--7||||||||FJ....
-.||.FJ||||||||L7....
-FJL7L7LJLJ||LJ.L-7..
-L--J.L7...LJS7F-7L7.
-....F-J..F7FJ|L7L7L7
-....L7.F7||L7|.L7L7|
-.....|FJLJ|FJ|F7|.LJ
-....FJL-7.||.||||...
-....L---J.LJ.LJLJ...",8)]
-    #[case("FF7FSF7F7F7F7F7F---7
-L|LJ||||||||||||F--J
-FL-7LJLJ||||||LJL-77
-F--JF--7||LJLJ7F7FJ-
-L---JF-JLJ.||-FJLJJ7
-|F|F-JF---7F7-L7L|7|
-|FFJF7L7F-JF7|JL---7
-7-L-JL7||F7|L7F-7F7|
-L.L7LFJ|||||FJL7||LJ
-L7JLJL-JLJLJL--JLJ.L", 10)]
-    fn test_process(
-        #[case] input: &str,
-        #[case] expected: u32,
-    ) {
-        let result = process(input);
-        assert_eq!(result, expected);
+    #[test]
+    fn test_budget() {
+        let input = ".....\n.S-7.\n.|.|.\n.L-J.\n.....\n";
+        assert_eq!(Budget::unlimited().with_max_steps(10).run(|| try_process(input)), Ok(Answer::U32(1)));
+        assert_eq!(
+            Budget::unlimited().with_max_steps(3).run(|| try_process(input)),
+            Err(Error::BudgetExceeded { steps: 3 })
+        );
     }
 }
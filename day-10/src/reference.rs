@@ -0,0 +1,118 @@
+//! Slow but obviously correct solutions, to check the real ones against on small inputs.
+
+use std::collections::HashSet;
+
+/// The steps, right, down, left and up, each pipe connects.
+fn connections(tile: u8) -> Vec<(i64, i64)> {
+    let (right, down, left, up) = ((1, 0), (0, 1), (-1, 0), (0, -1));
+    match tile {
+        b'|' => vec![up, down],
+        b'-' => vec![left, right],
+        b'L' => vec![up, right],
+        b'J' => vec![up, left],
+        b'7' => vec![down, left],
+        b'F' => vec![down, right],
+        _ => vec![],
+    }
+}
+
+/// The map, with the start replaced by the pipe which joins its two neighbours, and the tiles
+/// of the loop through it.
+fn pipe_loop(input: &str) -> (Vec<Vec<u8>>, HashSet<(i64, i64)>) {
+    let mut map: Vec<Vec<u8>> = input.lines().map(|line| line.bytes().collect()).collect();
+    let tile = |map: &[Vec<u8>], (x, y): (i64, i64)| {
+        let row = usize::try_from(y).ok().and_then(|y| map.get(y));
+        row.and_then(|row| usize::try_from(x).ok().and_then(|x| row.get(x)))
+            .copied()
+            .unwrap_or(b'.')
+    };
+    let start = (0..map.len() as i64)
+        .flat_map(|y| (0..map[0].len() as i64).map(move |x| (x, y)))
+        .find(|position| tile(&map, *position) == b'S')
+        .unwrap();
+    let joined: HashSet<(i64, i64)> = [(1, 0), (0, 1), (-1, 0), (0, -1)]
+        .into_iter()
+        .filter(|(dx, dy)| {
+            connections(tile(&map, (start.0 + dx, start.1 + dy))).contains(&(-dx, -dy))
+        })
+        .collect();
+    let pipe = b"|-LJ7F"
+        .iter()
+        .find(|pipe| connections(**pipe).into_iter().collect::<HashSet<_>>() == joined)
+        .unwrap();
+    map[start.1 as usize][start.0 as usize] = *pipe;
+
+    let mut tiles = HashSet::from([start]);
+    let mut position = start;
+    loop {
+        let next = connections(tile(&map, position))
+            .into_iter()
+            .map(|(dx, dy)| (position.0 + dx, position.1 + dy))
+            .find(|next| !tiles.contains(next));
+        match next {
+            Some(next) => {
+                tiles.insert(next);
+                position = next;
+            }
+            None => break,
+        }
+    }
+    (map, tiles)
+}
+
+pub fn part1(input: &str) -> usize {
+    pipe_loop(input).1.len() / 2
+}
+
+/// Draws the loop three times as large, so there are gaps between pipes which run side by side,
+/// and floods the outside of it from the edge.
+pub fn part2(input: &str) -> usize {
+    let (map, tiles) = pipe_loop(input);
+    let (width, height) = (map[0].len() as i64 * 3, map.len() as i64 * 3);
+    let mut walls = HashSet::new();
+    for (x, y) in &tiles {
+        let centre = (x * 3 + 1, y * 3 + 1);
+        walls.insert(centre);
+        for (dx, dy) in connections(map[*y as usize][*x as usize]) {
+            walls.insert((centre.0 + dx, centre.1 + dy));
+        }
+    }
+    let mut outside = HashSet::from([(0, 0)]);
+    let mut queue = vec![(0, 0)];
+    while let Some((x, y)) = queue.pop() {
+        for next in [(x + 1, y), (x - 1, y), (x, y + 1), (x, y - 1)] {
+            let on_map = (0..width).contains(&next.0) && (0..height).contains(&next.1);
+            if on_map && !walls.contains(&next) && outside.insert(next) {
+                queue.push(next);
+            }
+        }
+    }
+    (0..map.len() as i64)
+        .flat_map(|y| (0..map[0].len() as i64).map(move |x| (x, y)))
+        .filter(|(x, y)| !tiles.contains(&(*x, *y)) && !outside.contains(&(x * 3 + 1, y * 3 + 1)))
+        .count()
+}
+
+mod tests {
+    use aoclib::gen;
+    use aoclib::reference::assert_agrees;
+    use proptest::prelude::*;
+
+    use super::*;
+    use crate::part1::Part1;
+    use crate::part2::Part2;
+
+    proptest! {
+        #[test]
+        fn test_part1(seed: u64, size in 1..6usize) {
+            let input = gen::generator(10).unwrap().generate(size, seed);
+            assert_agrees::<Part1>(&input, &[], part1(&input));
+        }
+
+        #[test]
+        fn test_part2(seed: u64, size in 1..6usize) {
+            let input = gen::generator(10).unwrap().generate(size, seed);
+            assert_agrees::<Part2>(&input, &[], part2(&input));
+        }
+    }
+}
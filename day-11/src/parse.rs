@@ -0,0 +1,25 @@
+use glam::IVec2;
+
+use aoclib::{Error, Grid};
+
+/// The galaxies in the image, and the rows and columns which have none in them.
+pub struct Image {
+    pub galaxies: Vec<IVec2>,
+    pub empty_rows: Vec<i32>,
+    pub empty_columns: Vec<i32>,
+}
+
+/// Parses `input` as a rectangle of galaxies and empty space, which the parts otherwise assume.
+pub fn parse_image(input: &str) -> Result<Image, Error> {
+    let grid = Grid::try_parse(input, "'#' or '.'", |c| matches!(c, '#' | '.').then_some(c))?;
+    let galaxies: Vec<IVec2> = grid.iter()
+        .filter_map(|(location, c)| (*c == '#').then_some(location))
+        .collect();
+    let empty_rows = (0..grid.height() as i32)
+        .filter(|y| galaxies.iter().all(|galaxy| galaxy.y != *y))
+        .collect();
+    let empty_columns = (0..grid.width() as i32)
+        .filter(|x| galaxies.iter().all(|galaxy| galaxy.x != *x))
+        .collect();
+    Ok(Image { galaxies, empty_rows, empty_columns })
+}
@@ -1,23 +1,28 @@
-use std::{collections::HashMap, ffi::NulError, str::Chars};
 use itertools::Itertools;
 
 use glam::I64Vec2;
 
-pub fn process<'a>(
-    input: &'a str,
+use aoclib::{Answer, Error, Solution};
+
+use crate::parse::{parse_image, Image};
+
+pub fn process(
+    input: &str,
 ) -> i64 {
-    let image = image(input);
-    let (empty_row_numbers, empty_column_numbers) = get_empty_row_and_column_numbers(input);
+    total_distance(&parse_image(input).expect("should parse"))
+}
 
-    let unexpanded_galaxies = image.filter_map(|(location, c)| match c {
-        '#' => Some(location),
-        _ => None
-    });
+/// Like [`process`], but reports an image which is not a rectangle of galaxies and empty space
+/// as an error.
+pub fn try_process(input: &str) -> Result<Answer, Error> {
+    Ok(total_distance(&parse_image(input)?).into())
+}
 
-    let galaxies = unexpanded_galaxies.map(|location| {
-        let x_expansion = empty_column_numbers.clone().filter(|column_number| location.x > ( *column_number as i64 ) ).count() * 999999;
-        let y_expansion = empty_row_numbers.clone().filter(|row_number| location.y > (*row_number as i64) ).count() * 999999;
-        location + I64Vec2{x: x_expansion.try_into().unwrap(), y: y_expansion.try_into().unwrap()}
+fn total_distance(image: &Image) -> i64 {
+    let galaxies = image.galaxies.iter().map(|location| {
+        let x_expansion = image.empty_columns.iter().filter(|column_number| location.x > **column_number ).count() * 999999;
+        let y_expansion = image.empty_rows.iter().filter(|row_number| location.y > **row_number ).count() * 999999;
+        location.as_i64vec2() + I64Vec2{x: x_expansion as i64, y: y_expansion as i64}
     });
 
     galaxies.combinations(2).map(|combination| {
@@ -26,51 +31,21 @@ pub fn process<'a>(
     }).sum()
 }
 
-fn image<'a>(input: &'a str) -> impl Iterator<Item = (I64Vec2, char)> + 'a {
-    input.lines().enumerate().map(|(y, line)|{
-        line.chars().enumerate().map(move |(x, c)|{
-            (I64Vec2{x: x.try_into().unwrap(), y: y.try_into().unwrap()}, c)
-        })
-    })
-    .flatten()
-}
-
-fn get_empty_row_and_column_numbers<'a>(input: &'a str) -> (impl Iterator <Item = usize> + Clone + 'a, impl Iterator <Item= usize> + Clone + 'a) {
-    let empty_row_numbers = input.lines()
-        .enumerate()
-        .filter_map(
-            |(row_number, line)|
-                if line.chars().all(|c| c == '.') { Some(row_number) }
-                else { None }
-        );
-
-    let number_of_columns = input.lines().next().unwrap().len();
 
-    let empty_column_numbers = (0..number_of_columns).filter(|n|{
-        input.lines().map( |line| line.chars().skip(*n).next().unwrap() ).all(|c| c == '.')
-    });
+pub struct Part2;
 
-    (empty_row_numbers, empty_column_numbers)
-}
+impl Solution for Part2 {
+    const DAY: u8 = 11;
+    const PART: u8 = 2;
 
+    type Input<'a> = Image;
+    type Params = ();
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    fn parse(input: &str) -> Result<Image, Error> {
+        parse_image(input)
+    }
 
-    #[test]
-    fn test_process() {
-        let lines = "...#......
-.......#..
-#.........
-..........
-......#...
-.#........
-.........#
-..........
-.......#..
-#...#.....";
-        let result = process(lines);
-        assert_eq!(result, 82000210);
+    fn solve(input: Image, _: &()) -> Result<Answer, Error> {
+        Ok(total_distance(&input).into())
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,95 @@
+//! Slow but obviously correct solutions, to check the real ones against on small inputs.
+
+fn image(input: &str) -> Vec<Vec<u8>> {
+    input.lines().map(|line| line.bytes().collect()).collect()
+}
+
+fn galaxies(image: &[Vec<u8>]) -> Vec<(usize, usize)> {
+    let mut galaxies = vec![];
+    for (y, row) in image.iter().enumerate() {
+        for (x, pixel) in row.iter().enumerate() {
+            if *pixel == b'#' {
+                galaxies.push((x, y));
+            }
+        }
+    }
+    galaxies
+}
+
+fn distances(
+    galaxies: &[(usize, usize)],
+    distance: impl Fn((usize, usize), (usize, usize)) -> usize,
+) -> usize {
+    let mut total = 0;
+    for (index, a) in galaxies.iter().enumerate() {
+        for b in &galaxies[index + 1..] {
+            total += distance(*a, *b);
+        }
+    }
+    total
+}
+
+/// Doubles every empty row and column of the image itself, then measures.
+pub fn part1(input: &str) -> usize {
+    let mut rows = vec![];
+    for row in image(input) {
+        if row.iter().all(|pixel| *pixel == b'.') {
+            rows.push(row.clone());
+        }
+        rows.push(row);
+    }
+    let mut columns: Vec<Vec<u8>> = vec![vec![]; rows.len()];
+    for x in 0..rows[0].len() {
+        let empty = rows.iter().all(|row| row[x] == b'.');
+        for (y, row) in rows.iter().enumerate() {
+            columns[y].push(row[x]);
+            if empty {
+                columns[y].push(row[x]);
+            }
+        }
+    }
+    distances(&galaxies(&columns), |a, b| {
+        a.0.abs_diff(b.0) + a.1.abs_diff(b.1)
+    })
+}
+
+/// Walks between every pair of galaxies, a million steps across each empty row or column.
+pub fn part2(input: &str) -> usize {
+    let image = image(input);
+    let empty_row = |y: usize| image[y].iter().all(|pixel| *pixel == b'.');
+    let empty_column = |x: usize| image.iter().all(|row| row[x] == b'.');
+    let step = |empty: bool| if empty { 1_000_000 } else { 1 };
+    distances(&galaxies(&image), |a, b| {
+        let across: usize = (a.0.min(b.0)..a.0.max(b.0))
+            .map(|x| step(empty_column(x)))
+            .sum();
+        let down: usize = (a.1.min(b.1)..a.1.max(b.1))
+            .map(|y| step(empty_row(y)))
+            .sum();
+        across + down
+    })
+}
+
+mod tests {
+    use aoclib::gen;
+    use aoclib::reference::assert_agrees;
+    use proptest::prelude::*;
+
+    use super::*;
+    use crate::part1::Part1;
+    use crate::part2::Part2;
+
+    proptest! {
+        #[test]
+        fn test_part1(seed: u64, size in 1..30usize) {
+            let input = gen::generator(11).unwrap().generate(size, seed);
+            assert_agrees::<Part1>(&input, &[], part1(&input));
+        }
+
+        #[test]
+        fn test_part2(seed: u64, size in 1..30usize) {
+            let input = gen::generator(11).unwrap().generate(size, seed);
+            assert_agrees::<Part2>(&input, &[], part2(&input));
+        }
+    }
+}
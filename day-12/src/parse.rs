@@ -0,0 +1,69 @@
+use aoclib::Error;
+
+/// A row of springs, and the sizes of its groups of damaged springs.
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct ConditionRecord<'a> {
+    pub row: &'a str,
+    pub count: Vec<usize>,
+}
+
+/// Parses every line of `input` as a row of springs followed by the sizes of the groups of
+/// damaged springs.
+pub fn parse_records(input: &str) -> Result<Vec<ConditionRecord<'_>>, Error> {
+    input
+        .lines()
+        .map(|line| {
+            let (row, counts) = line
+                .split_once(' ')
+                .ok_or_else(|| Error::parse(input, line, "a row and a list of group sizes"))?;
+            if let Some(index) = row.find(|c| !"?.#".contains(c)) {
+                return Err(Error::parse(input, &row[index..], "'?', '.' or '#'"));
+            }
+            let count = counts
+                .split(',')
+                .map(|count| {
+                    count
+                        .parse::<usize>()
+                        .map_err(|_| Error::parse(input, count, "a group size"))
+                })
+                .collect::<Result<_, _>>()?;
+            Ok(ConditionRecord { row, count })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case(
+        "???.### 1,1,3\n.??..??...?##. 1,2\n",
+        Ok(vec![
+            ConditionRecord { row: "???.###", count: vec![1, 1, 3] },
+            ConditionRecord { row: ".??..??...?##.", count: vec![1, 2] },
+        ])
+    )]
+    #[case(
+        "???.### 1,1,3\n.??..??...?##.\n",
+        Err("line 2, column 1: expected a row and a list of group sizes, found \".??..??...?##.\"")
+    )]
+    #[case(
+        "???.### 1,1,3\n.??..x 1,1,3\n",
+        Err("line 2, column 6: expected '?', '.' or '#', found \"x 1,1,3\"")
+    )]
+    #[case(
+        "???.### 1,1,3\n.??..?? 1,,3\n",
+        Err("line 2, column 11: expected a group size, found \",3\"")
+    )]
+    fn test_parse_records(
+        #[case] input: &str,
+        #[case] expected: Result<Vec<ConditionRecord>, &str>,
+    ) {
+        assert_eq!(
+            parse_records(input).map_err(|error| error.to_string()),
+            expected.map_err(str::to_string)
+        );
+    }
+}
@@ -1,12 +1,10 @@
-use std::{str::FromStr, collections::HashMap, ptr::null};
+use std::{collections::HashMap, ptr::null};
 
 use itertools::PeekingNext;
 
-#[derive(PartialEq, Eq, Debug, Clone)]
-struct ConditionRecord<'a> {
-    row: &'a str,
-    count: Vec<usize>
-}
+use aoclib::{Answer, Error, Solution};
+
+use crate::parse::{parse_records, ConditionRecord};
 
 #[derive(Debug, Hash, Eq, PartialEq, Clone)]
 struct State {
@@ -20,7 +18,16 @@ pub fn process<'a>(
     input: &'a str,
 ) -> usize {
 
-    parse_input(input).map(count_possible).sum()
+    total_possible(parse_records(input).expect("should parse"))
+}
+
+/// Like [`process`], but reports a malformed condition record as an error.
+pub fn try_process(input: &str) -> Result<Answer, Error> {
+    Ok(total_possible(parse_records(input)?).into())
+}
+
+fn total_possible(condition_records: Vec<ConditionRecord>) -> usize {
+    condition_records.into_iter().map(count_possible).sum()
 }
 
 fn count_possible(condition_record: ConditionRecord) -> usize {
@@ -116,16 +123,24 @@ fn not_at_end_of_run_of_damaged_springs(condition_record: &ConditionRecord, stat
         && state.num_of_consecutive_broken_springs == 0 
 }
 
-fn parse_input(input: &str) -> impl Iterator<Item = ConditionRecord> {
-    input.lines().map( |line| {
-        let (row, count_string) = line.split_once(' ').expect("the input to be correct");
-        ConditionRecord {
-            row,
-            count: count_string.split(',').map(usize::from_str).map(Result::unwrap).collect()
-        }
-    })
-}
 
+pub struct Part1;
+
+impl Solution for Part1 {
+    const DAY: u8 = 12;
+    const PART: u8 = 1;
+
+    type Input<'a> = Vec<ConditionRecord<'a>>;
+    type Params = ();
+
+    fn parse(input: &str) -> Result<Vec<ConditionRecord<'_>>, Error> {
+        parse_records(input)
+    }
+
+    fn solve(input: Vec<ConditionRecord<'_>>, _: &()) -> Result<Answer, Error> {
+        Ok(total_possible(input).into())
+    }
+}
 
 #[cfg(test)]
 mod tests {
@@ -146,16 +161,4 @@ mod tests {
     ) {
         assert_eq!(count_possible(condition_record), expected);
     }
-
-    #[test]
-    fn test_process() {
-        let lines = "???.### 1,1,3
-.??..??...?##. 1,1,3
-?#?#?#?#?#?#?#? 1,3,1,6
-????.#...#... 4,1,1
-????.######..#####. 1,6,5
-?###???????? 3,2,1";
-        let result = process(lines);
-        assert_eq!(result, 21);
-    }
 }
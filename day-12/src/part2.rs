@@ -1,7 +1,12 @@
-use std::{str::FromStr, collections::HashMap, iter::repeat};
+use std::{collections::HashMap, iter::repeat};
 
 use itertools::Itertools;
 
+use aoclib::parallel::prelude::*;
+use aoclib::{Answer, Error, Solution};
+
+use crate::parse::{self, parse_records};
+
 #[derive(PartialEq, Eq, Debug, Clone)]
 struct ConditionRecord {
     row: String,
@@ -20,7 +25,16 @@ pub fn process<'a>(
     input: &'a str,
 ) -> usize {
 
-    parse_input(input).map(count_possible).sum()
+    total_possible(parse_records(input).expect("should parse"))
+}
+
+/// Like [`process`], but reports a malformed condition record as an error.
+pub fn try_process(input: &str) -> Result<Answer, Error> {
+    Ok(total_possible(parse_records(input)?).into())
+}
+
+fn total_possible(condition_records: Vec<parse::ConditionRecord>) -> usize {
+    condition_records.iter().map(unfold).collect::<Vec<_>>().into_maybe_par_iter().map(count_possible).sum()
 }
 
 fn count_possible(condition_record: ConditionRecord) -> usize {
@@ -116,19 +130,32 @@ fn not_at_end_of_run_of_damaged_springs(condition_record: &ConditionRecord, stat
         && state.num_of_consecutive_broken_springs == 0 
 }
 
-fn parse_input<'a>(input: &'a str) -> impl Iterator<Item = ConditionRecord> + 'a {
-    input.lines().map(parse_line)
-}
-
-fn parse_line(line: &str) -> ConditionRecord {
-    let (row, count_string) = line.split_once(' ').expect("the input to be correct");
+fn unfold(condition_record: &parse::ConditionRecord) -> ConditionRecord {
     ConditionRecord {
-        row: repeat(row).take(5).join("?"),
-        count: repeat(count_string.split(',').map(usize::from_str).map(Result::unwrap)).take(5).flatten().collect()
+        row: repeat(condition_record.row).take(5).join("?"),
+        count: repeat(condition_record.count.iter().copied()).take(5).flatten().collect()
     }
 }
 
 
+pub struct Part2;
+
+impl Solution for Part2 {
+    const DAY: u8 = 12;
+    const PART: u8 = 2;
+
+    type Input<'a> = Vec<parse::ConditionRecord<'a>>;
+    type Params = ();
+
+    fn parse(input: &str) -> Result<Vec<parse::ConditionRecord<'_>>, Error> {
+        parse_records(input)
+    }
+
+    fn solve(input: Vec<parse::ConditionRecord<'_>>, _: &()) -> Result<Answer, Error> {
+        Ok(total_possible(input).into())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -147,18 +174,6 @@ mod tests {
         #[case] expected: usize
     ) {
 
-        assert_eq!(count_possible(parse_line(input)), expected);
-    }
-
-    #[test]
-    fn test_process() {
-        let lines = "???.### 1,1,3
-.??..??...?##. 1,1,3
-?#?#?#?#?#?#?#? 1,3,1,6
-????.#...#... 4,1,1
-????.######..#####. 1,6,5
-?###???????? 3,2,1";
-        let result = process(lines);
-        assert_eq!(result, 525152);
+        assert_eq!(count_possible(unfold(&parse_records(input).unwrap()[0])), expected);
     }
 }
@@ -0,0 +1,98 @@
+//! Slow but obviously correct solutions, to check the real ones against on small inputs.
+
+/// Tries every way of filling in the unknown springs, and keeps those matching the groups.
+fn arrangements(springs: &str, groups: &[usize]) -> usize {
+    let unknown: Vec<usize> = springs.match_indices('?').map(|(index, _)| index).collect();
+    (0..1usize << unknown.len())
+        .filter(|choice| {
+            let mut row = springs.as_bytes().to_vec();
+            for (bit, index) in unknown.iter().enumerate() {
+                row[*index] = if choice >> bit & 1 == 1 { b'#' } else { b'.' };
+            }
+            let found: Vec<usize> = row
+                .split(|spring| *spring == b'.')
+                .filter(|group| !group.is_empty())
+                .map(|group| group.len())
+                .collect();
+            found == groups
+        })
+        .count()
+}
+
+fn records(input: &str) -> impl Iterator<Item = (&str, Vec<usize>)> {
+    input.lines().map(|line| {
+        let (springs, groups) = line.split_once(' ').unwrap();
+        (
+            springs,
+            groups.split(',').map(|n| n.parse().unwrap()).collect(),
+        )
+    })
+}
+
+pub fn part1(input: &str) -> usize {
+    records(input)
+        .map(|(springs, groups)| arrangements(springs, &groups))
+        .sum()
+}
+
+pub fn part2(input: &str) -> usize {
+    records(input)
+        .map(|(springs, groups)| arrangements(&[springs; 5].join("?"), &groups.repeat(5)))
+        .sum()
+}
+
+mod tests {
+    use std::fmt::Write;
+
+    use aoclib::gen::Rng;
+    use aoclib::reference::assert_agrees;
+    use proptest::prelude::*;
+
+    use super::*;
+    use crate::part1::Part1;
+    use crate::part2::Part2;
+
+    /// Records like the generated ones, but short, with at most `unknown` springs forgotten in
+    /// each, so every arrangement can be tried even once they are unfolded.
+    fn small_records(seed: u64, records: usize, unknown: usize) -> String {
+        let mut rng = Rng::new(seed);
+        let mut input = String::new();
+        for _ in 0..records {
+            let length = rng.between(1, 8) as usize;
+            let mut springs: Vec<u8> = (0..length)
+                .map(|_| if rng.chance(50) { b'#' } else { b'.' })
+                .collect();
+            springs[rng.index(length)] = b'#';
+            let groups: Vec<String> = springs
+                .split(|spring| *spring == b'.')
+                .filter(|group| !group.is_empty())
+                .map(|group| group.len().to_string())
+                .collect();
+            for _ in 0..unknown {
+                springs[rng.index(length)] = b'?';
+            }
+            writeln!(
+                input,
+                "{} {}",
+                String::from_utf8(springs).unwrap(),
+                groups.join(",")
+            )
+            .unwrap();
+        }
+        input
+    }
+
+    proptest! {
+        #[test]
+        fn test_part1(seed: u64, records in 1..10usize, unknown in 0..8usize) {
+            let input = small_records(seed, records, unknown);
+            assert_agrees::<Part1>(&input, &[], part1(&input));
+        }
+
+        #[test]
+        fn test_part2(seed: u64, records in 1..4usize, unknown in 0..3usize) {
+            let input = small_records(seed, records, unknown);
+            assert_agrees::<Part2>(&input, &[], part2(&input));
+        }
+    }
+}
@@ -0,0 +1,42 @@
+use aoclib::{Error, Grid};
+
+/// Parses `input` as a list of rectangular patterns of ash and rocks separated by blank lines,
+/// which the parts otherwise assume.
+pub fn parse_patterns(input: &str) -> Result<Vec<Grid<char>>, Error> {
+    let mut line = 1;
+    let mut patterns = vec![];
+    for pattern in input.split("\n\n") {
+        patterns.push(
+            Grid::try_parse(pattern, "'.' or '#'", |c| matches!(c, '.' | '#').then_some(c))
+                .map_err(|error| error.on_line(line))?,
+        );
+        line += pattern.lines().count() + 1;
+    }
+    Ok(patterns)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("#.##\n..#.\n\n#...\n##.#\n", None)]
+    #[case(
+        "#.##\n..#.\n\n#...\n##.x\n",
+        Some("line 5, column 4: expected '.' or '#', found \"x\"")
+    )]
+    #[case(
+        "#.##\n..#.\n\n#...\n##.\n",
+        Some("line 5, column 4: expected 1 more cells, found the end of the line")
+    )]
+    fn test_parse_patterns(#[case] input: &str, #[case] expected: Option<&str>) {
+        assert_eq!(
+            parse_patterns(input)
+                .err()
+                .map(|error| error.to_string())
+                .as_deref(),
+            expected
+        );
+    }
+}
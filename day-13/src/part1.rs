@@ -1,37 +1,33 @@
-use std::fmt::Debug;
+use tracing::{debug, trace};
 
-pub fn process<'a>(
-    input: &'a str,
-) -> usize {
-    let patterns = input.split("\n\n");
-    let rotated_patterns = patterns.clone().map(|pattern|{
-        rotate_pattern(pattern)
-    });
+use aoclib::{Answer, Error, Grid, Solution};
 
-    let horizontal_mirrors : usize = patterns.filter_map(|puzzle_string| process_puzzle(puzzle_string.lines().map(|line| line.chars()))).sum();
-    println!("vertical");
-    let vertical_mirrors : usize  = rotated_patterns.filter_map(|puzzle_string| process_puzzle( puzzle_string.map(|line| line.into_iter() ) ) ).sum();
+use crate::parse::parse_patterns;
 
-    vertical_mirrors + horizontal_mirrors * 100
+pub fn process(
+    input: &str,
+) -> usize {
+    summarise(&parse_patterns(input).expect("should parse"))
 }
 
-fn rotate_pattern(pattern: &str) -> impl Iterator<Item = Vec<char>> + Debug + Clone + '_  {
-    let mut char_vecs = pattern.lines().map( |line| line.chars() ).collect::<Vec<_>>();
-    std::iter::from_fn(move || {
-        let mut column = vec![];
-        for iter in &mut char_vecs {
-            match iter.next() {
-                Some(item) => column.push(item),
-                None => return None
-            }
-        }
-        Some(column)
-    })
+/// Like [`process`], but reports a malformed pattern as an error.
+pub fn try_process(input: &str) -> Result<Answer, Error> {
+    Ok(summarise(&parse_patterns(input)?).into())
+}
+
+fn summarise(patterns: &[Grid<char>]) -> usize {
+    let horizontal_mirrors : usize = patterns.iter().filter_map(|pattern| process_puzzle(pattern.rows().map(|row| row.iter().copied()))).sum();
+    debug!(horizontal_mirrors, "looking for vertical mirrors");
+    let vertical_mirrors : usize  = patterns.iter().filter_map(|pattern| process_puzzle( pattern.columns().map(|column| column.copied().collect::<Vec<_>>().into_iter() ) ) ).sum();
+
+    vertical_mirrors + horizontal_mirrors * 100
 }
 
 fn process_puzzle(puzzle: impl Iterator<Item = impl DoubleEndedIterator<Item = char>>) -> Option<usize> {
     let puzzle_integers = convert_puzzle_to_integers(puzzle);
-    dbg!(find_reflection(&puzzle_integers))
+    let reflection = find_reflection(&puzzle_integers);
+    trace!(?reflection);
+    reflection
 }
 
 fn convert_puzzle_to_integers(puzzle: impl Iterator< Item = impl DoubleEndedIterator<Item = char>>) -> Vec<usize> {
@@ -55,28 +51,20 @@ fn find_reflection(integers: &[usize]) -> Option<usize> {
     None
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_process() {
-        let lines = "#.##..##.
-..#.##.#.
-##......#
-##......#
-..#.##.#.
-..##..##.
-#.#.##.#.
-
-#...##..#
-#....#..#
-..##..###
-#####.##.
-#####.##.
-..##..###
-#....#..#";
-        let result = process(lines);
-        assert_eq!(result, 405);
+pub struct Part1;
+
+impl Solution for Part1 {
+    const DAY: u8 = 13;
+    const PART: u8 = 1;
+
+    type Input<'a> = Vec<Grid<char>>;
+    type Params = ();
+
+    fn parse(input: &str) -> Result<Vec<Grid<char>>, Error> {
+        parse_patterns(input)
+    }
+
+    fn solve(input: Vec<Grid<char>>, _: &()) -> Result<Answer, Error> {
+        Ok(summarise(&input).into())
     }
 }
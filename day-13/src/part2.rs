@@ -1,35 +1,29 @@
+use glam::IVec2;
 use itertools::Itertools;
-use std::fmt::Debug;
+
+use aoclib::viz::{Colour, Frame};
+use aoclib::{Answer, Error, Grid, Solution};
+
+use crate::parse::parse_patterns;
 
 pub fn process(input: &str) -> usize {
-    let patterns = input.split("\n\n");
+    summarise(&parse_patterns(input).expect("should parse"))
+}
 
-    let (horizontal_mirrors, vertical_mirrors): (Vec<Option<usize>>, Vec<Option<usize>>) = patterns.map(process_pattern).unzip();
-    (horizontal_mirrors.into_iter().flatten().sum::<usize>() * 100)
-        + vertical_mirrors.into_iter().flatten().sum::<usize>()
+/// Like [`process`], but reports a malformed pattern as an error.
+pub fn try_process(input: &str) -> Result<Answer, Error> {
+    Ok(summarise(&parse_patterns(input)?).into())
 }
 
-fn rotate_pattern(pattern: &str) -> impl Iterator<Item = Vec<char>> + Debug + Clone + '_ {
-    let mut char_vecs = pattern.lines().map(|line| line.chars()).collect::<Vec<_>>();
-    std::iter::from_fn(move || {
-        let mut column = vec![];
-        for iter in &mut char_vecs {
-            match iter.next() {
-                Some(item) => column.push(item),
-                None => return None,
-            }
-        }
-        Some(column)
-    })
+fn summarise(patterns: &[Grid<char>]) -> usize {
+    let (horizontal_mirrors, vertical_mirrors): (Vec<Option<usize>>, Vec<Option<usize>>) = patterns.iter().map(process_pattern).unzip();
+    (horizontal_mirrors.into_iter().flatten().sum::<usize>() * 100)
+        + vertical_mirrors.into_iter().flatten().sum::<usize>()
 }
 
-fn process_pattern(pattern: &str) -> (Option<usize>, Option<usize>) {
-    let horizontal_integers = convert_pattern_to_integers(pattern.lines().map(|line| line.chars()));
-    let vertical_pattern = rotate_pattern(pattern)
-        .collect::<Vec<_>>()
-        .into_iter()
-        .map(|x| x.into_iter());
-    let vertical_integers = convert_pattern_to_integers(vertical_pattern);
+fn process_pattern(pattern: &Grid<char>) -> (Option<usize>, Option<usize>) {
+    let horizontal_integers = convert_pattern_to_integers(pattern.rows().map(|row| row.iter().copied()));
+    let vertical_integers = convert_pattern_to_integers(pattern.columns().map(|column| column.copied().collect::<Vec<_>>().into_iter()));
 
     let horizontal_reflection = find_smudged_reflection(horizontal_integers);
     let vertical_reflection = find_smudged_reflection(vertical_integers);
@@ -62,28 +56,19 @@ fn find_smudged_reflection(integers: Vec<usize>) -> Option<usize> {
             }
         };
 
-        if reflections.len() > 1 {
-            panic!("Found more than one reflection!");
-        };
-
-        return Some(*reflections.first().unwrap());
+        // A well-formed pattern has only one new line of reflection; otherwise take the first.
+        return reflections.first().copied();
     }
     None
 }
 
-#[allow(dead_code)]
-fn display(integers: Vec<usize>) {
-    let width = integers
-        .iter()
-        .map(|x| usize::BITS - x.leading_zeros())
-        .max()
-        .unwrap() as usize;
-    for integer in integers {
-        let mut string = format!("{:0width$b}", integer, width = width);
-        string = string.replace('0', ".");
-        string = string.replace('1', "#");
-        println!("{}", string);
-    }
+/// Highlights the rows or columns mirrored by the line of reflection before `line`, out to the
+/// nearer edge, in one colour on each side. `along` gives a position's row or column.
+fn highlight_reflection(frame: &mut Frame, pattern: &Grid<char>, line: usize, length: usize, along: impl Fn(IVec2) -> usize) {
+    let span = line.min(length - line);
+    frame
+        .highlight(pattern.positions().filter(|position| (line - span..line).contains(&along(*position))), Colour::Cyan)
+        .highlight(pattern.positions().filter(|position| (line..line + span).contains(&along(*position))), Colour::Magenta);
 }
 
 fn convert_pattern_to_integers(
@@ -113,6 +98,40 @@ fn find_reflections(integers: &[usize]) -> Option<Vec<usize>> {
     Some(retval)
 }
 
+pub struct Part2;
+
+impl Solution for Part2 {
+    const DAY: u8 = 13;
+    const PART: u8 = 2;
+
+    type Input<'a> = Vec<Grid<char>>;
+    type Params = ();
+
+    fn parse(input: &str) -> Result<Vec<Grid<char>>, Error> {
+        parse_patterns(input)
+    }
+
+    fn solve(input: Vec<Grid<char>>, _: &()) -> Result<Answer, Error> {
+        Ok(summarise(&input).into())
+    }
+
+    /// Each pattern, with the rows or columns on either side of its new line of reflection
+    /// highlighted.
+    fn visualise(patterns: Vec<Grid<char>>, _: &()) -> Result<Vec<Frame>, Error> {
+        Ok(patterns.iter().map(|pattern| {
+            let mut frame = Frame::new(pattern, |c| *c);
+            let (horizontal_reflection, vertical_reflection) = process_pattern(pattern);
+            if let Some(row) = horizontal_reflection {
+                highlight_reflection(&mut frame, pattern, row, pattern.height(), |position| position.y as usize);
+            }
+            if let Some(column) = vertical_reflection {
+                highlight_reflection(&mut frame, pattern, column, pattern.width(), |position| position.x as usize);
+            }
+            frame
+        }).collect())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -149,12 +168,12 @@ mod tests {
 #...#..#.", (Some(7), None))]
     #[test]
     fn test_patterns(#[case] pattern: &str, #[case] expected: (Option<usize>, Option<usize>)) {
-        assert_eq!(process_pattern(pattern), expected)
+        assert_eq!(process_pattern(&parse_patterns(pattern).unwrap()[0]), expected)
     }
 
     #[test]
-    fn test_process() {
-        let lines = "#.##..##.
+    fn test_visualise() {
+        let frames = Part2::visualise(Part2::parse("#.##..##.
 ..#.##.#.
 ##......#
 ##......#
@@ -168,8 +187,12 @@ mod tests {
 #####.##.
 #####.##.
 ..##..###
-#....#..#";
-        let result = process(lines);
-        assert_eq!(result, 400);
+#....#..#").unwrap(), &()).unwrap();
+        let colours = frames.iter().map(|frame| frame.cells().column(0).map(|cell| cell.colour).collect::<Vec<_>>()).collect::<Vec<_>>();
+        let (cyan, magenta) = (Some(Colour::Cyan), Some(Colour::Magenta));
+        assert_eq!(colours, [
+            vec![cyan, cyan, cyan, magenta, magenta, magenta, None],
+            vec![cyan, magenta, None, None, None, None, None],
+        ]);
     }
 }
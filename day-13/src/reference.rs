@@ -0,0 +1,83 @@
+//! Slow but obviously correct solutions, to check the real ones against on small inputs.
+
+fn patterns(input: &str) -> Vec<Vec<Vec<u8>>> {
+    input
+        .split("\n\n")
+        .map(|pattern| pattern.lines().map(|line| line.bytes().collect()).collect())
+        .collect()
+}
+
+/// The summary of every line `pattern` reflects in, compared tile by tile.
+fn reflections(pattern: &[Vec<u8>]) -> Vec<usize> {
+    let (width, height) = (pattern[0].len(), pattern.len());
+    let mut summaries = vec![];
+    for left in 1..width {
+        let mirrored = (0..left)
+            .filter(|x| 2 * left - 1 - x < width)
+            .all(|x| pattern.iter().all(|row| row[x] == row[2 * left - 1 - x]));
+        if mirrored {
+            summaries.push(left);
+        }
+    }
+    for above in 1..height {
+        let mirrored = (0..above)
+            .filter(|y| 2 * above - 1 - y < height)
+            .all(|y| pattern[y] == pattern[2 * above - 1 - y]);
+        if mirrored {
+            summaries.push(100 * above);
+        }
+    }
+    summaries
+}
+
+pub fn part1(input: &str) -> usize {
+    patterns(input)
+        .iter()
+        .map(|pattern| reflections(pattern)[0])
+        .sum()
+}
+
+/// Cleans every tile in turn until a new line of reflection turns up.
+pub fn part2(input: &str) -> usize {
+    patterns(input)
+        .into_iter()
+        .map(|mut pattern| {
+            let old = reflections(&pattern)[0];
+            for y in 0..pattern.len() {
+                for x in 0..pattern[0].len() {
+                    let tile = pattern[y][x];
+                    pattern[y][x] = if tile == b'#' { b'.' } else { b'#' };
+                    if let Some(new) = reflections(&pattern).into_iter().find(|new| *new != old) {
+                        return new;
+                    }
+                    pattern[y][x] = tile;
+                }
+            }
+            panic!("every pattern should have a smudge")
+        })
+        .sum()
+}
+
+mod tests {
+    use aoclib::gen;
+    use aoclib::reference::assert_agrees;
+    use proptest::prelude::*;
+
+    use super::*;
+    use crate::part1::Part1;
+    use crate::part2::Part2;
+
+    proptest! {
+        #[test]
+        fn test_part1(seed: u64, size in 1..6usize) {
+            let input = gen::generator(13).unwrap().generate(size, seed);
+            assert_agrees::<Part1>(&input, &[], part1(&input));
+        }
+
+        #[test]
+        fn test_part2(seed: u64, size in 1..6usize) {
+            let input = gen::generator(13).unwrap().generate(size, seed);
+            assert_agrees::<Part2>(&input, &[], part2(&input));
+        }
+    }
+}
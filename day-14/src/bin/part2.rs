@@ -1,7 +1,9 @@
+use aoclib::input::{self, InputError};
 use day_14::part2::process;
 
-fn main() {
-    let file = include_str!("../../input.txt");
-    let result = process(file);
+fn main() -> Result<(), InputError> {
+    let file = input::load(14, 2)?;
+    let result = process(&file, 1000000000);
     println!("{}", result);
-}
\ No newline at end of file
+    Ok(())
+}
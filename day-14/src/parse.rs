@@ -0,0 +1,21 @@
+use std::collections::HashMap;
+
+use glam::UVec2;
+
+use aoclib::{Error, Grid};
+
+/// The width and length of the platform, and its rocks by where they are.
+pub struct Platform {
+    pub dimension: UVec2,
+    pub rocks: HashMap<UVec2, char>,
+}
+
+/// Parses `input` as a rectangle of rocks and empty space, which the parts otherwise assume.
+pub fn parse_platform(input: &str) -> Result<Platform, Error> {
+    let grid = Grid::try_parse(input, "'O', '#' or '.'", |c| matches!(c, 'O' | '#' | '.').then_some(c))?;
+    let rocks = grid.iter()
+        .filter(|(_, c)| **c != '.')
+        .map(|(location, c)| (location.as_uvec2(), *c))
+        .collect();
+    Ok(Platform { dimension: grid.dimensions().as_uvec2(), rocks })
+}
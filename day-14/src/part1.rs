@@ -1,23 +1,24 @@
-use std::collections::HashMap;
-
 use glam::UVec2;
 
-pub fn process<'a>(
-    input: &'a str,
-) -> u32 {
+use aoclib::{Answer, Error, Solution};
 
-    let length = input.lines().count() as u32;
-    let width = input.lines().next().unwrap().len() as u32;
+use crate::parse::{parse_platform, Platform};
+
+pub fn process(
+    input: &str,
+) -> u32 {
+    north_load(&parse_platform(input).expect("should parse"))
+}
 
-    let platform: HashMap<UVec2, char> = input.lines().enumerate().map(|(y, line)| 
-        line.chars().enumerate().filter_map(
-            move |(x, c)| match c { 
-                '.' => None,
-                 _ => Some((UVec2{x: x.try_into().unwrap(), y: y.try_into().unwrap()}, c))
-            } 
-        ) 
-    ).flatten().collect();
+/// Like [`process`], but reports a platform which is not a rectangle of rocks and empty space as
+/// an error.
+pub fn try_process(input: &str) -> Result<Answer, Error> {
+    Ok(north_load(&parse_platform(input)?).into())
+}
 
+fn north_load(platform: &Platform) -> u32 {
+    let length = platform.dimension.y;
+    let width = platform.dimension.x;
 
     let mut load = 0;    
     for x in 0..width {
@@ -25,7 +26,7 @@ pub fn process<'a>(
         let mut number_of_rounds_since_last_cube = 0;
         for y in 0..length {
             let location = UVec2{x, y};
-            match platform.get(&location) {
+            match platform.rocks.get(&location) {
                 None => continue,
                 Some('#') => {
                     increment_load(most_recent_cube, length, &mut load, number_of_rounds_since_last_cube);
@@ -44,6 +45,7 @@ pub fn process<'a>(
     load
 }
 
+
 fn increment_load(most_recent_cube: Option<UVec2>, length: u32, load: &mut u32, number_of_rounds_since_last_cube: u32) {
     let cube_load: u32;
     if most_recent_cube.is_none() {
@@ -60,6 +62,24 @@ fn consecutive_sum(end: u32, n: u32) -> u32{
     ( end * ( end - 1 ) / 2 ) - ( start * (start - 1) / 2 )
 }
 
+pub struct Part1;
+
+impl Solution for Part1 {
+    const DAY: u8 = 14;
+    const PART: u8 = 1;
+
+    type Input<'a> = Platform;
+    type Params = ();
+
+    fn parse(input: &str) -> Result<Platform, Error> {
+        parse_platform(input)
+    }
+
+    fn solve(input: Platform, _: &()) -> Result<Answer, Error> {
+        Ok(north_load(&input).into())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -70,18 +90,10 @@ mod tests {
     }
 
     #[test]
-    fn test_process() {
-        let lines = "O....#....
-O.OO#....#
-.....##...
-OO.#O....O
-.O.....O#.
-O.#..O.#.#
-..O..#O..O
-.......O..
-#....###..
-#OO..#....";
-        let result = process(lines);
-        assert_eq!(result, 136);
+    fn test_try_process() {
+        assert_eq!(
+            try_process("O.#\n.0.\n").unwrap_err().to_string(),
+            "line 2, column 2: expected 'O', '#' or '.', found \"0.\""
+        );
     }
 }
@@ -2,64 +2,43 @@ use std::collections::HashMap;
 
 use glam::UVec2;
 
-pub fn process<'a>(
-    input: &'a str,
+use aoclib::cycle;
+use aoclib::params::ParamError;
+use aoclib::viz::{Colour, Frame};
+use aoclib::{Answer, Error, FromParams, Params, Solution};
+
+use crate::parse::{parse_platform, Platform};
+
+pub fn process(
+    input: &str,
+    cycles: usize,
 ) -> u32 {
+    load_after(&parse_platform(input).expect("should parse"), cycles).expect("the platform should repeat within the budget")
+}
 
-    let dimension = UVec2{ y: input.lines().count() as u32,
-        x: input.lines().next().unwrap().len() as u32};
-
-    let state: HashMap<UVec2, char> = input.lines().enumerate().map(|(y, line)| 
-        line.chars().enumerate().filter_map(
-            move |(x, c)| match c { 
-                '.' => None,
-                 _ => Some((UVec2{x: x.try_into().unwrap(), y: y.try_into().unwrap()}, c))
-            } 
-        ) 
-    ).flatten().collect();
-
-    let mut states = vec![];
-    let mut loads = vec![];
-
-    let mut new_state = state;
-    let mut new_load: u32 = 1;
-    
-    // display_state(&dimension, &new_state);
-    while ! states.contains(&new_state) { 
-        states.push(new_state.clone());
-        loads.push(new_load);
-    // while old_load != new_load {
-        new_state = tilt_north(dimension, &new_state);
-        // display_state(&dimension, &new_state);
-        new_state.keys().filter(|loc| loc.x >= dimension.x || loc.y >= dimension.y).inspect(|x| {dbg!(x);}).for_each(drop);
-        assert!(new_state.keys().all(|loc| loc.x < dimension.x && loc.y < dimension.y));
-        
-        new_state = tilt_west(dimension, &new_state);
-        // display_state(&dimension, &new_state);
-        new_state.keys().filter(|loc| loc.x >= dimension.x || loc.y >= dimension.y).inspect(|x| {dbg!(x);}).for_each(drop);
-        assert!(new_state.keys().all(|loc| loc.x < dimension.x && loc.y < dimension.y));
-        
-        new_state = tilt_south(dimension, &new_state);
-        // display_state(&dimension, &new_state);
-        new_state.keys().filter(|loc| loc.x >= dimension.x || loc.y >= dimension.y).inspect(|x| {dbg!(x);}).for_each(drop);
-        assert!(new_state.keys().all(|loc| loc.x < dimension.x && loc.y < dimension.y));
-        
-        new_state = tilt_east(dimension, &new_state);
-        // display_state(&dimension, &new_state);
-        new_state.keys().filter(|loc| loc.x >= dimension.x || loc.y >= dimension.y).inspect(|x| {dbg!(x);}).for_each(drop);
-        assert!(new_state.keys().all(|loc| loc.x < dimension.x && loc.y < dimension.y));
-        
-        // display_state(&dimension, &new_state);
-        
-        new_load = calculate_load(dimension, &new_state);
-        println!("Load: {}",new_load);
-    }
-    let loop_start_point = states.iter().position(|state| *state == new_state).expect("Exiting the above while loop guarantees a result");
-    dbg!(loop_start_point);
-    let loop_length = states.iter().count() - loop_start_point;
-    dbg!(loop_length);
-    let result_position = (1000000000 - loop_start_point) % loop_length;
-    dbg!(loads[dbg!(result_position + loop_start_point)])
+/// Like [`process`], but reports a platform which is not a rectangle of rocks and empty space, or
+/// which does not repeat within the budget, as an error.
+pub fn try_process(input: &str, cycles: usize) -> Result<Answer, Error> {
+    Ok(load_after(&parse_platform(input)?, cycles)?.into())
+}
+
+/// The load on the north beams after `cycles` spin cycles, found by spinning the platform until
+/// it repeats.
+fn load_after(platform: &Platform, cycles: usize) -> Result<u32, Error> {
+    let dimension = platform.dimension;
+    let spin = |state: &HashMap<UVec2, char>| spin(dimension, state);
+
+    let cycle = cycle::brent(platform.rocks.clone(), spin)?;
+    let final_state = cycle.state_at(platform.rocks.clone(), spin, cycles);
+    Ok(calculate_load(dimension, &final_state))
+}
+
+/// Tilts the platform north, then west, then south, then east.
+fn spin(dimension: UVec2, state: &HashMap<UVec2, char>) -> HashMap<UVec2, char> {
+    let state = tilt_north(dimension, state);
+    let state = tilt_west(dimension, &state);
+    let state = tilt_south(dimension, &state);
+    tilt_east(dimension, &state)
 }
 
 fn calculate_load(dimension: UVec2, state: &HashMap<UVec2, char>) -> u32 {
@@ -165,36 +144,76 @@ fn tilt_east(dimension: UVec2, state: &HashMap<UVec2, char>) -> HashMap<UVec2, c
     new_state
 }
 
-fn display_state(dimension: &UVec2, state: &HashMap<UVec2, char>) {
-    for y in 0..dimension.y {
-        for x in 0..dimension.x {
-            match state.get(&UVec2{x, y}) {
-                None => print!("."),
-                Some(value) => print!("{}", value)
-            }
-        }
-        print!("\n");
+fn draw_state(dimension: UVec2, state: &HashMap<UVec2, char>) -> Frame {
+    let mut frame = Frame::blank(dimension.x as usize, dimension.y as usize);
+    for (location, c) in state {
+        let colour = if *c == 'O' { Colour::White } else { Colour::Grey };
+        frame.mark([location.as_ivec2()], *c, colour);
+    }
+    frame
+}
+
+pub struct Cycles(pub usize);
+
+impl FromParams for Cycles {
+    const DEFAULTS: &'static [(&'static str, &'static str)] = &[("cycles", "1000000000")];
+
+    fn from_params(params: &Params) -> Result<Self, ParamError> {
+        Ok(Cycles(params.get("cycles")?))
+    }
+}
+
+pub struct Part2;
+
+impl Solution for Part2 {
+    const DAY: u8 = 14;
+    const PART: u8 = 2;
+
+    type Input<'a> = Platform;
+    type Params = Cycles;
+
+    fn parse(input: &str) -> Result<Platform, Error> {
+        parse_platform(input)
+    }
+
+    fn solve(input: Platform, params: &Cycles) -> Result<Answer, Error> {
+        Ok(load_after(&input, params.0)?.into())
+    }
+
+    /// The platform after each spin cycle, until it either repeats an earlier arrangement or
+    /// has been spun the given number of times.
+    fn visualise(platform: Platform, params: &Cycles) -> Result<Vec<Frame>, Error> {
+        let Platform { dimension, rocks: state } = platform;
+        let spin = |state: &HashMap<UVec2, char>| spin(dimension, state);
+        let cycle = cycle::brent(state.clone(), spin)?;
+
+        let states = std::iter::successors(Some(state), |state| Some(spin(state)));
+        Ok(states
+            .take((cycle.tail + cycle.period).min(params.0) + 1)
+            .map(|state| draw_state(dimension, &state))
+            .collect())
     }
-    print!("\n");
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use aoclib::budget::Budget;
+
+    #[test]
+    fn test_visualise() {
+        let frames = Part2::visualise(Part2::parse("O.\n.#\n").unwrap(), &Cycles(10)).unwrap();
+        let frames = frames.iter().map(Frame::to_ascii).collect::<Vec<_>>();
+        assert_eq!(frames, ["O.\n.#\n", "..\nO#\n", "..\nO#\n"]);
+        assert_eq!(Part2::visualise(Part2::parse("O.\n.#\n").unwrap(), &Cycles(0)).unwrap().len(), 1);
+    }
 
     #[test]
-    fn test_process() {
-        let lines = "O....#....
-O.OO#....#
-.....##...
-OO.#O....O
-.O.....O#.
-O.#..O.#.#
-..O..#O..O
-.......O..
-#....###..
-#OO..#....";
-        let result = process(lines);
-        assert_eq!(result, 64);
+    fn test_budget() {
+        let budget = Budget::unlimited().with_max_steps(2);
+        assert_eq!(
+            budget.run(|| try_process("O.O\n.#.\nO..\n", 1_000_000_000)),
+            Err(Error::BudgetExceeded { steps: 2 })
+        );
     }
 }
@@ -0,0 +1,78 @@
+//! Slow but obviously correct solutions, to check the real ones against on small inputs.
+
+/// Rolls every rock a tile at a time by `(dx, dy)` until none can move.
+fn tilt(platform: &mut [Vec<u8>], (dx, dy): (i64, i64)) {
+    let (width, height) = (platform[0].len() as i64, platform.len() as i64);
+    let mut moved = true;
+    while moved {
+        moved = false;
+        for y in 0..height {
+            for x in 0..width {
+                let (nx, ny) = (x + dx, y + dy);
+                if (0..width).contains(&nx)
+                    && (0..height).contains(&ny)
+                    && platform[y as usize][x as usize] == b'O'
+                    && platform[ny as usize][nx as usize] == b'.'
+                {
+                    platform[y as usize][x as usize] = b'.';
+                    platform[ny as usize][nx as usize] = b'O';
+                    moved = true;
+                }
+            }
+        }
+    }
+}
+
+fn load(platform: &[Vec<u8>]) -> usize {
+    platform
+        .iter()
+        .enumerate()
+        .map(|(y, row)| (platform.len() - y) * row.iter().filter(|tile| **tile == b'O').count())
+        .sum()
+}
+
+fn platform(input: &str) -> Vec<Vec<u8>> {
+    input.lines().map(|line| line.bytes().collect()).collect()
+}
+
+pub fn part1(input: &str) -> usize {
+    let mut platform = platform(input);
+    tilt(&mut platform, (0, -1));
+    load(&platform)
+}
+
+/// Spins the platform every one of the `cycles` times.
+pub fn part2(input: &str, cycles: usize) -> usize {
+    let mut platform = platform(input);
+    for _ in 0..cycles {
+        for direction in [(0, -1), (-1, 0), (0, 1), (1, 0)] {
+            tilt(&mut platform, direction);
+        }
+    }
+    load(&platform)
+}
+
+mod tests {
+    use aoclib::gen;
+    use aoclib::reference::assert_agrees;
+    use proptest::prelude::*;
+
+    use super::*;
+    use crate::part1::Part1;
+    use crate::part2::Part2;
+
+    proptest! {
+        #[test]
+        fn test_part1(seed: u64, size in 1..12usize) {
+            let input = gen::generator(14).unwrap().generate(size, seed);
+            assert_agrees::<Part1>(&input, &[], part1(&input));
+        }
+
+        #[test]
+        fn test_part2(seed: u64, size in 1..8usize, cycles in 0..100usize) {
+            let input = gen::generator(14).unwrap().generate(size, seed);
+            let reference = part2(&input, cycles);
+            assert_agrees::<Part2>(&input, &[("cycles", &cycles.to_string())], reference);
+        }
+    }
+}
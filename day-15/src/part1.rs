@@ -1,18 +1,84 @@
+use std::io::BufRead;
+
+use aoclib::stream::{self, Split};
+use aoclib::{Answer, Error, Solution};
+
 pub fn process<'a>(
     input: &'a str,
 ) -> u32 {
-    input.split(",").map(|cs| cs.chars().fold(0, |acc, c| ((acc + c as u32) * 17) % 256 )).sum()
+    parse_steps(input).expect("should be ASCII").into_iter().map(hash).sum()
+}
+
+/// Like [`process`], but reports a character which has no ASCII code to hash as an error.
+pub fn try_process(input: &str) -> Result<Answer, Error> {
+    Ok(parse_steps(input)?.into_iter().map(hash).sum::<u32>().into())
+}
+
+/// Splits `input` into its steps, each of which must be ASCII to be hashed.
+fn parse_steps(input: &str) -> Result<Vec<&str>, Error> {
+    input.trim_end().split(",").map(|step| match step.find(|c: char| !c.is_ascii()) {
+        Some(index) => Err(Error::parse(input, &step[index..], "an ASCII character")),
+        None => Ok(step),
+    }).collect()
 }
 
+/// Like [`try_process`], but for the steps a piece at a time, so they needn't all be in memory.
+pub fn try_process_steps<R: BufRead>(steps: &mut Split<R>) -> Result<Answer, Error> {
+    let mut total = 0;
+    while let Some(step) = steps.next() {
+        if let Some(index) = step.find(|c: char| !c.is_ascii()) {
+            return Err(steps.locate(Error::parse(&step, &step[index..], "an ASCII character")));
+        }
+        total += hash(&step);
+    }
+    Ok(total.into())
+}
+
+fn hash(cs: &str) -> u32 {
+    cs.chars().fold(0, |acc, c| ((acc + c as u32) * 17) % 256 )
+}
+
+
+pub struct Part1;
+
+impl Solution for Part1 {
+    const DAY: u8 = 15;
+    const PART: u8 = 1;
+
+    type Input<'a> = Vec<&'a str>;
+    type Params = ();
+
+    fn parse(input: &str) -> Result<Vec<&str>, Error> {
+        parse_steps(input)
+    }
+
+    fn solve(input: Vec<&str>, _: &()) -> Result<Answer, Error> {
+        Ok(input.into_iter().map(hash).sum::<u32>().into())
+    }
+
+    fn solve_reader(reader: &mut dyn BufRead, _: &()) -> Result<Option<Answer>, Error> {
+        stream::with_split(reader, b',', try_process_steps).map(Some)
+    }
+}
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
-    fn test_process() {
-        let lines = "rn=1,cm-,qp=3,cm=2,qp-,pc=4,ot=9,ab=5,pc-,pc=6,ot=7";
-        let result = process(lines);
-        assert_eq!(result, 1320);
+    fn test_try_process() {
+        assert_eq!(
+            try_process("rn=1,cm-,qp=3\ncm=2,qé-").unwrap_err().to_string(),
+            "line 2, column 7: expected an ASCII character, found \"é-\""
+        );
+    }
+
+    #[test]
+    fn test_try_process_steps() {
+        let input = "rn=1,cm-,qp=3\ncm=2,qé-";
+        assert_eq!(
+            stream::with_split(input.as_bytes(), b',', try_process_steps),
+            try_process(input)
+        );
     }
 }
@@ -1,76 +1,163 @@
 use std::collections::VecDeque;
+use std::io::BufRead;
 
-use nom::{character::complete::{alpha1, self}, bytes::complete::tag, branch::alt, combinator::opt};
+use nom::{character::complete::{alpha1, self}, branch::alt, combinator::{cut, map, value}, error::context, multi::separated_list1, sequence::{pair, preceded}};
+
+use aoclib::parse::{parse_all, tag, ParseResult, Span};
+use aoclib::stream::{self, Split};
+use aoclib::{Answer, Error, Solution};
 
 pub fn process<'a>(
     input: &'a str,
 ) -> u32 {
+    focusing_power(parse_operations(input).expect("should parse"))
+}
 
-    let mut lens_boxes: [VecDeque<Lens>; 256] = std::array::from_fn(|_| VecDeque::<Lens>::new());
-    let operations = input.split(",").map(parse);
+/// Like [`process`], but reports a step which is not an insertion or a removal as an error.
+pub fn try_process(input: &str) -> Result<Answer, Error> {
+    Ok(focusing_power(parse_operations(input)?).into())
+}
 
+fn focusing_power(operations: Vec<Operation>) -> u32 {
+    let mut lens_boxes = LensBoxes::new();
     for operation in operations {
+        lens_boxes.apply(operation);
+    }
+    lens_boxes.focusing_power()
+}
+
+/// Like [`try_process`], but for the steps a piece at a time, so they needn't all be in memory.
+pub fn try_process_steps<R: BufRead>(steps: &mut Split<R>) -> Result<Answer, Error> {
+    let mut lens_boxes = LensBoxes::new();
+    while let Some(operation) = steps.next() {
+        lens_boxes.apply(parse_all(&operation, step).map_err(|e| steps.locate(e))?);
+    }
+    Ok(lens_boxes.focusing_power().into())
+}
+
+/// The lenses in each of the 256 boxes, which own their labels so that the steps which put them
+/// there needn't be kept.
+struct LensBoxes([VecDeque<Lens>; 256]);
+
+impl LensBoxes {
+    fn new() -> Self {
+        Self(std::array::from_fn(|_| VecDeque::<Lens>::new()))
+    }
+
+    fn apply(&mut self, operation: Operation) {
         use Operation::*;
         match operation {
             Insert(position, label, focal_length) => {
-                let lens_box = &mut lens_boxes[position];
+                let lens_box = &mut self.0[position];
                 match lens_box.iter().position(|lens| lens.label == label) {
-                    None => lens_box.push_back(Lens{label, focal_length}),
+                    None => lens_box.push_back(Lens{label: label.to_string(), focal_length}),
                     Some(idx) => lens_box[idx].focal_length = focal_length,
                 };
             },
             Remove(position, label) => {
-                let lens_box = &mut lens_boxes[position];
+                let lens_box = &mut self.0[position];
                 lens_box.iter().position(|lens| lens.label == label).and_then(|idx| lens_box.remove(idx));
             }
         }
     }
 
-    lens_boxes.iter().enumerate().map(|(box_number, lens_box)| {
-        lens_box.iter().enumerate().map(move |(lens_position, lens)| {
-            (box_number as u32 + 1) * (lens_position as u32+ 1) * lens.focal_length
-        }).sum::<u32>()
-    }).sum()
+    fn focusing_power(&self) -> u32 {
+        self.0.iter().enumerate().map(|(box_number, lens_box)| {
+            lens_box.iter().enumerate().map(move |(lens_position, lens)| {
+                (box_number as u32 + 1) * (lens_position as u32+ 1) * lens.focal_length
+            }).sum::<u32>()
+        }).sum()
+    }
 }
 
 fn hash(cs: &str) -> u32 {
     cs.chars().fold(0, |acc, c| ((acc + c as u32) * 17) % 256 )
 }
 
-#[derive(Debug, PartialEq, Eq, Clone, Copy)]
-struct Lens<'a> {
-    label: &'a str,
+#[derive(Debug, PartialEq, Eq, Clone)]
+struct Lens {
+    label: String,
     focal_length: u32
 }
 
+/// A step, which puts a lens with a label and focal length into a box, or takes the lens with a
+/// label out of it.
 #[derive(Debug, Clone, Copy)]
-enum Operation<'a> {
+pub enum Operation<'a> {
     Insert(usize, &'a str, u32),
     Remove(usize, &'a str)
 }
 
-fn parse(input: &str) -> Operation {
-    let (input, label) = alpha1::<_, nom::error::Error<_>>(input).expect("should parse");
-    let (input, operation) = alt::<_, _, nom::error::Error<_>,_>((tag("="), tag("-")))(input).expect("should parse");
-    let (_, focal_length) = opt::<_, _, nom::error::Error<_>, _>(complete::u32)(input).expect("should parse");
+/// Parses `input` as the steps, separated by commas.
+fn parse_operations(input: &str) -> Result<Vec<Operation<'_>>, Error> {
+    parse_all(input.trim_end(), steps)
+}
+
+// rn=1,cm-,qp=3
+fn steps(input: Span) -> ParseResult<Vec<Operation>> {
+    separated_list1(tag(","), cut(step))(input)
+}
+
+/// A label followed by `-`, or by `=` and a focal length.
+fn step(input: Span) -> ParseResult<Operation> {
+    let (input, (label, focal_length)) = context("step", pair(
+        alpha1,
+        alt((value(None, tag("-")), map(preceded(tag("="), complete::u32), Some)))
+    ))(input)?;
+    let label = *label.fragment();
     let hash = hash(label) as usize;
 
-    match operation { 
-        "=" => Operation::Insert(hash, label, focal_length.expect("Insert operations should provide focal lengths")),
-        "-" => Operation::Remove(hash, label),
-        value => panic!("Received invalid character: {}", value)
+    match focal_length {
+        Some(focal_length) => Ok((input, Operation::Insert(hash, label, focal_length))),
+        None => Ok((input, Operation::Remove(hash, label))),
     }
 }
 
+pub struct Part2;
+
+impl Solution for Part2 {
+    const DAY: u8 = 15;
+    const PART: u8 = 2;
+
+    type Input<'a> = Vec<Operation<'a>>;
+    type Params = ();
+
+    fn parse(input: &str) -> Result<Vec<Operation<'_>>, Error> {
+        parse_operations(input)
+    }
+
+    fn solve(input: Vec<Operation<'_>>, _: &()) -> Result<Answer, Error> {
+        Ok(focusing_power(input).into())
+    }
+
+    fn solve_reader(reader: &mut dyn BufRead, _: &()) -> Result<Option<Answer>, Error> {
+        stream::with_split(reader, b',', try_process_steps).map(Some)
+    }
+}
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
-    fn test_process() {
-        let lines = "rn=1,cm-,qp=3,cm=2,qp-,pc=4,ot=9,ab=5,pc-,pc=6,ot=7";
-        let result = process(lines);
-        assert_eq!(result, 145);
+    fn test_try_process() {
+        assert_eq!(
+            try_process("rn=1,cm-,qp=x").unwrap_err().to_string(),
+            "line 1, column 13: expected a number in step, found \"x\""
+        );
+        assert_eq!(
+            try_process("rn=1,cm-,2=1").unwrap_err().to_string(),
+            "line 1, column 10: expected a letter in step, found \"2=1\""
+        );
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_try_process_steps() {
+        for input in ["rn=1,cm-,qp=x", "rn=1,cm-,2=1"] {
+            assert_eq!(
+                stream::with_split(input.as_bytes(), b',', try_process_steps),
+                try_process(input)
+            );
+        }
+    }
+}
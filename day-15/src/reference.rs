@@ -0,0 +1,63 @@
+//! Slow but obviously correct solutions, to check the real ones against on small inputs.
+
+fn hash(step: &str) -> usize {
+    step.bytes()
+        .fold(0, |value, byte| (value + byte as usize) * 17 % 256)
+}
+
+fn steps(input: &str) -> impl Iterator<Item = &str> {
+    input.trim_end().split(',')
+}
+
+pub fn part1(input: &str) -> usize {
+    steps(input).map(hash).sum()
+}
+
+/// Follows every step with the boxes as lists of labelled lenses.
+pub fn part2(input: &str) -> usize {
+    let mut boxes: Vec<Vec<(&str, usize)>> = vec![vec![]; 256];
+    for step in steps(input) {
+        if let Some(label) = step.strip_suffix('-') {
+            boxes[hash(label)].retain(|(lens, _)| *lens != label);
+        } else {
+            let (label, focal_length) = step.split_once('=').unwrap();
+            let focal_length = focal_length.parse().unwrap();
+            let lenses = &mut boxes[hash(label)];
+            match lenses.iter_mut().find(|(lens, _)| *lens == label) {
+                Some(lens) => lens.1 = focal_length,
+                None => lenses.push((label, focal_length)),
+            }
+        }
+    }
+    let mut power = 0;
+    for (number, lenses) in boxes.iter().enumerate() {
+        for (slot, (_, focal_length)) in lenses.iter().enumerate() {
+            power += (number + 1) * (slot + 1) * focal_length;
+        }
+    }
+    power
+}
+
+mod tests {
+    use aoclib::gen;
+    use aoclib::reference::assert_agrees;
+    use proptest::prelude::*;
+
+    use super::*;
+    use crate::part1::Part1;
+    use crate::part2::Part2;
+
+    proptest! {
+        #[test]
+        fn test_part1(seed: u64, size in 1..100usize) {
+            let input = gen::generator(15).unwrap().generate(size, seed);
+            assert_agrees::<Part1>(&input, &[], part1(&input));
+        }
+
+        #[test]
+        fn test_part2(seed: u64, size in 1..100usize) {
+            let input = gen::generator(15).unwrap().generate(size, seed);
+            assert_agrees::<Part2>(&input, &[], part2(&input));
+        }
+    }
+}
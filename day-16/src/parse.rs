@@ -0,0 +1,49 @@
+use aoclib::{Error, Grid};
+
+pub enum SplitterOrientation {
+    NorthSouth,
+    EastWest,
+}
+
+pub enum MirrorOrientation {
+    TopLeft,
+    TopRight
+}
+
+pub enum CellType {
+    EmptySpace,
+    Mirror(MirrorOrientation),
+    Splitter(SplitterOrientation)
+}
+
+impl CellType {
+    /// The character the tile is drawn with in the input.
+    pub fn symbol(&self) -> char {
+        use MirrorOrientation::*;
+        use SplitterOrientation::*;
+        use CellType::*;
+        match self {
+            EmptySpace => '.',
+            Mirror(TopLeft) => '\\',
+            Mirror(TopRight) => '/',
+            Splitter(EastWest) => '-',
+            Splitter(NorthSouth) => '|',
+        }
+    }
+}
+
+/// Parses `input` as a rectangle of mirrors, splitters and empty space, which the parts otherwise
+/// assume.
+pub fn parse_contraption(input: &str) -> Result<Grid<CellType>, Error> {
+    use MirrorOrientation::*;
+    use SplitterOrientation::*;
+    use CellType::*;
+    Grid::try_parse(input, "a mirror, a splitter or '.'", |c| match c {
+        '.' => Some(EmptySpace),
+        '\\' => Some(Mirror(TopLeft)),
+        '/' => Some(Mirror(TopRight)),
+        '-' => Some(Splitter(EastWest)),
+        '|' => Some(Splitter(NorthSouth)),
+        _ => None,
+    })
+}
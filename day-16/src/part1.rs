@@ -1,98 +1,84 @@
-use std::collections::{HashMap, HashSet};
+use std::collections::HashSet;
 
 use glam::IVec2;
 
-pub fn process<'a>(
-    input: &'a str,
+use aoclib::viz::{Colour, Frame};
+use aoclib::{Answer, Direction, Error, Grid, Solution};
+
+use crate::parse::{parse_contraption, CellType, MirrorOrientation, SplitterOrientation};
+
+pub fn process(
+    input: &str,
 ) -> u32 {
-    let grid = input.lines().zip(0..).map(|(line, y)|
-        line.chars().zip(0..).map( |(char, x)| {
-            use MirrorOrientation::*;
-            use SplitterOrientation::*;
-            use CellType::*;
-            let position = IVec2{x,y};
-            match char {
-                '.' => (position, EmptySpace),
-                '\\' => (position, Mirror(TopLeft)),
-                '/' => (position, Mirror(TopRight)),
-                '-' => (position, Splitter(EastWest)),
-                '|' => (position, Splitter(NorthSouth)),
-                value => panic!("Received invalid character {}", value)
-            }
-        }).collect::<Vec<_>>()
-        ).flatten().collect::<HashMap<_, _>>();
-
-
-    get_energized_cells(&grid, State{ position: IVec2::ZERO, direction: EAST })
+    energized_from_top_left(&parse_contraption(input).expect("should parse"))
+}
+
+/// Like [`process`], but reports a contraption which is not a rectangle of mirrors, splitters and
+/// empty space as an error.
+pub fn try_process(input: &str) -> Result<Answer, Error> {
+    Ok(energized_from_top_left(&parse_contraption(input)?).into())
+}
+
+fn energized_from_top_left(grid: &Grid<CellType>) -> u32 {
+    get_energized_cells(grid, State{ position: IVec2::ZERO, direction: Direction::East })
 }
 
-fn get_energized_cells(grid: &HashMap<IVec2, CellType>, state: State) -> u32 {
+fn get_energized_cells(grid: &Grid<CellType>, state: State) -> u32 {
+    energized_positions(grid, state).len() as u32
+}
+
+fn energized_positions(grid: &Grid<CellType>, state: State) -> HashSet<IVec2> {
     let mut steps = HashSet::<State>::new();
 
     let mut states: Vec<State> = vec![state];
     while states.len() > 0 {
         steps.extend(states);
         let new_states = steps.iter().filter_map(|state| 
-            step(*state, &grid).and_then(|ss| Some(ss.filter(|s| ! steps.contains(s))))
+            step(*state, grid).and_then(|ss| Some(ss.filter(|s| ! steps.contains(s))))
         ).flatten().collect();
         states = new_states;
     }
 
-    let positions = HashSet::<IVec2>::from_iter(steps.into_iter().filter_map(
+    HashSet::<IVec2>::from_iter(steps.into_iter().filter_map(
         |state| {
-            grid.get(&state.position)?;
+            grid.get(state.position)?;
             Some(state.position)
         }
-    ));
-    positions.into_iter().count() as u32
+    ))
 }
 
-fn step(state: State, grid: &HashMap<IVec2, CellType>) -> Option<Box<dyn Iterator<Item = State>>> {
+fn step(state: State, grid: &Grid<CellType>) -> Option<Box<dyn Iterator<Item = State>>> {
     use MirrorOrientation::*;
     use SplitterOrientation::*;
     use CellType::*;
-    match grid.get(&state.position)? {
+    match grid.get(state.position)? {
         EmptySpace => {
             move_forward(state)
         },
-        Mirror(TopLeft) => match state.direction {
-            NORTH => turn(WEST, state),
-            SOUTH => turn(EAST, state),
-            EAST  => turn(SOUTH, state),
-            WEST  => turn(NORTH, state),
-            value => panic!("Invalid direction {}", value),
-        },
-        Mirror(TopRight) => match state.direction {
-            NORTH => turn(EAST, state),
-            SOUTH => turn(WEST, state),
-            EAST  => turn(NORTH, state),
-            WEST  => turn(SOUTH, state),
-            value => panic!("Invalid direction {}", value),
-        },
-        Splitter(EastWest) => match state.direction {
-            NORTH => turn_east_and_west(state),
-            SOUTH => turn_east_and_west(state), 
-            EAST | WEST => move_forward(state),
-            value => panic!("Invalid direction {}", value),
+        Mirror(TopLeft) => turn(state.direction.reflect_backslash(), state),
+        Mirror(TopRight) => turn(state.direction.reflect_slash(), state),
+        Splitter(EastWest) => if state.direction.is_horizontal() {
+            move_forward(state)
+        } else {
+            turn_east_and_west(state)
         },
-        Splitter(NorthSouth) => match state.direction {
-            EAST => turn_north_and_south(state),
-            WEST => turn_north_and_south(state),
-            NORTH | SOUTH => move_forward(state),
-            value => panic!("Invalid direction {}", value),
+        Splitter(NorthSouth) => if state.direction.is_vertical() {
+            move_forward(state)
+        } else {
+            turn_north_and_south(state)
         },
     }
 }
 
 fn turn_east_and_west(state: State) -> Option<Box<dyn Iterator<Item = State>>> {
-    Some(Box::new(turn(EAST, state)?.chain(turn(WEST, state)?)))
+    Some(Box::new(turn(Direction::East, state)?.chain(turn(Direction::West, state)?)))
 }
 
 fn turn_north_and_south(state: State) -> Option<Box<dyn Iterator<Item = State>>> {
-    Some(Box::new(turn(NORTH, state)?.chain(turn(SOUTH, state)?)))
+    Some(Box::new(turn(Direction::North, state)?.chain(turn(Direction::South, state)?)))
 }
 
-fn turn(direction: IVec2, state: State) -> Option<Box<dyn Iterator <Item = State>>> {
+fn turn(direction: Direction, state: State) -> Option<Box<dyn Iterator <Item = State>>> {
     let mut new_state = state;
     new_state.direction = direction;
     new_state.position += new_state.direction;
@@ -105,31 +91,36 @@ fn move_forward(state: State) -> Option<Box<dyn Iterator <Item = State>>> {
     Some(Box::new(vec![new_state].into_iter()))
 }
 
-const NORTH: IVec2 = IVec2{x:  0, y: -1};
-const SOUTH: IVec2 = IVec2{x:  0, y:  1};
-const EAST:  IVec2 = IVec2{x:  1, y:  0};
-const WEST:  IVec2 = IVec2{x: -1, y:  0};
-
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
 struct State {
     position: IVec2,
-    direction: IVec2
+    direction: Direction
 }
 
-enum SplitterOrientation {
-    NorthSouth,
-    EastWest,
-}
+pub struct Part1;
 
-enum MirrorOrientation {
-    TopLeft,
-    TopRight
-}
+impl Solution for Part1 {
+    const DAY: u8 = 16;
+    const PART: u8 = 1;
+
+    type Input<'a> = Grid<CellType>;
+    type Params = ();
+
+    fn parse(input: &str) -> Result<Grid<CellType>, Error> {
+        parse_contraption(input)
+    }
 
-enum CellType {
-    EmptySpace,
-    Mirror(MirrorOrientation),
-    Splitter(SplitterOrientation)
+    fn solve(input: Grid<CellType>, _: &()) -> Result<Answer, Error> {
+        Ok(energized_from_top_left(&input).into())
+    }
+
+    /// The contraption, with the tiles the beam energizes highlighted.
+    fn visualise(grid: Grid<CellType>, _: &()) -> Result<Vec<Frame>, Error> {
+        let energized = energized_positions(&grid, State{ position: IVec2::ZERO, direction: Direction::East });
+        let mut frame = Frame::new(&grid, CellType::symbol);
+        frame.highlight(energized, Colour::Yellow);
+        Ok(vec![frame])
+    }
 }
 
 #[cfg(test)]
@@ -137,18 +128,20 @@ mod tests {
     use super::*;
 
     #[test]
-    fn test_process() {
-        let lines = r".|...\....
-|.-.\.....
-.....|-...
-........|.
-..........
-.........\
-..../.\\..
-.-.-/..|..
-.|....-|.\
-..//.|....";
-        let result = process(lines);
-        assert_eq!(result, 46);
+    fn test_visualise() {
+        let frames = Part1::visualise(Part1::parse(".\\.\n\\/.\n").unwrap(), &()).unwrap();
+        let energized = frames[0].cells().iter()
+            .filter_map(|(position, cell)| cell.colour.map(|_| position))
+            .collect::<Vec<_>>();
+        assert_eq!(frames[0].to_ascii(), ".\\.\n\\/.\n");
+        assert_eq!(energized, [IVec2::new(0, 0), IVec2::new(1, 0), IVec2::new(0, 1), IVec2::new(1, 1)]);
+    }
+
+    #[test]
+    fn test_try_process() {
+        assert_eq!(
+            try_process(".|.\n.+.\n").unwrap_err().to_string(),
+            "line 2, column 2: expected a mirror, a splitter or '.', found \"+.\""
+        );
     }
 }
@@ -1,112 +1,96 @@
-use std::collections::{HashMap, HashSet};
+use std::collections::HashSet;
 
 use glam::IVec2;
 
-pub fn process<'a>(
-    input: &'a str,
+use aoclib::parallel::prelude::*;
+use aoclib::{Answer, Direction, Error, Grid, Solution};
+
+use crate::parse::{parse_contraption, CellType, MirrorOrientation, SplitterOrientation};
+
+pub fn process(
+    input: &str,
 ) -> u32 {
-    let grid = input.lines().zip(0..).map(|(line, y)|
-        line.chars().zip(0..).map( |(char, x)| {
-            use MirrorOrientation::*;
-            use SplitterOrientation::*;
-            use CellType::*;
-            let position = IVec2{x,y};
-            match char {
-                '.' => (position, EmptySpace),
-                '\\' => (position, Mirror(TopLeft)),
-                '/' => (position, Mirror(TopRight)),
-                '-' => (position, Splitter(EastWest)),
-                '|' => (position, Splitter(NorthSouth)),
-                value => panic!("Received invalid character {}", value)
-            }
-        }).collect::<Vec<_>>()
-        ).flatten().collect::<HashMap<_, _>>();
-
-    let dimension = IVec2{x: input.lines().next().unwrap().len() as i32, y: input.lines().into_iter().count() as i32 };
+    most_energized(&parse_contraption(input).expect("should parse"))
+}
+
+/// Like [`process`], but reports a contraption which is not a rectangle of mirrors, splitters and
+/// empty space as an error.
+pub fn try_process(input: &str) -> Result<Answer, Error> {
+    Ok(most_energized(&parse_contraption(input)?).into())
+}
+
+fn most_energized(grid: &Grid<CellType>) -> u32 {
+    let dimension = grid.dimensions();
     let mut initial_states = Vec::<State>::new();
 
     for x in 0..dimension.x {
-        initial_states.push( State{ position: IVec2{x, y: 0}, direction: SOUTH });
-        initial_states.push( State{ position: IVec2{x, y: dimension.y}, direction: NORTH });
+        initial_states.push( State{ position: IVec2{x, y: 0}, direction: Direction::South });
+        initial_states.push( State{ position: IVec2{x, y: dimension.y}, direction: Direction::North });
     }
 
     for y in 0..dimension.y {
-        initial_states.push( State{ position: IVec2{x: 0, y}, direction: WEST });
-        initial_states.push( State{ position: IVec2{x: dimension.x, y}, direction: EAST });
+        initial_states.push( State{ position: IVec2{x: 0, y}, direction: Direction::West });
+        initial_states.push( State{ position: IVec2{x: dimension.x, y}, direction: Direction::East });
     }
 
-    initial_states.into_iter().map(
-        |state| get_energized_cells(&grid, state)
-    ).max().unwrap()
+    initial_states.into_maybe_par_iter().map(
+        |state| get_energized_cells(grid, state)
+    ).max().expect("the contraption is not empty")
 }
 
-fn get_energized_cells(grid: &HashMap<IVec2, CellType>, state: State) -> u32 {
+fn get_energized_cells(grid: &Grid<CellType>, state: State) -> u32 {
     let mut steps = HashSet::<State>::new();
 
     let mut states: Vec<State> = vec![state];
     while states.len() > 0 {
         steps.extend(states);
         let new_states = steps.iter().filter_map(|state| 
-            step(*state, &grid).and_then(|ss| Some(ss.filter(|s| ! steps.contains(s))))
+            step(*state, grid).and_then(|ss| Some(ss.filter(|s| ! steps.contains(s))))
         ).flatten().collect();
         states = new_states;
     }
 
     let positions = HashSet::<IVec2>::from_iter(steps.into_iter().filter_map(
         |state| {
-            grid.get(&state.position)?;
+            grid.get(state.position)?;
             Some(state.position)
         }
     ));
     positions.into_iter().count() as u32
 }
 
-fn step(state: State, grid: &HashMap<IVec2, CellType>) -> Option<Box<dyn Iterator<Item = State>>> {
+fn step(state: State, grid: &Grid<CellType>) -> Option<Box<dyn Iterator<Item = State>>> {
     use MirrorOrientation::*;
     use SplitterOrientation::*;
     use CellType::*;
-    match grid.get(&state.position)? {
+    match grid.get(state.position)? {
         EmptySpace => {
             move_forward(state)
         },
-        Mirror(TopLeft) => match state.direction {
-            NORTH => turn(WEST, state),
-            SOUTH => turn(EAST, state),
-            EAST  => turn(SOUTH, state),
-            WEST  => turn(NORTH, state),
-            value => panic!("Invalid direction {}", value),
-        },
-        Mirror(TopRight) => match state.direction {
-            NORTH => turn(EAST, state),
-            SOUTH => turn(WEST, state),
-            EAST  => turn(NORTH, state),
-            WEST  => turn(SOUTH, state),
-            value => panic!("Invalid direction {}", value),
-        },
-        Splitter(EastWest) => match state.direction {
-            NORTH => turn_east_and_west(state),
-            SOUTH => turn_east_and_west(state), 
-            EAST | WEST => move_forward(state),
-            value => panic!("Invalid direction {}", value),
+        Mirror(TopLeft) => turn(state.direction.reflect_backslash(), state),
+        Mirror(TopRight) => turn(state.direction.reflect_slash(), state),
+        Splitter(EastWest) => if state.direction.is_horizontal() {
+            move_forward(state)
+        } else {
+            turn_east_and_west(state)
         },
-        Splitter(NorthSouth) => match state.direction {
-            EAST => turn_north_and_south(state),
-            WEST => turn_north_and_south(state),
-            NORTH | SOUTH => move_forward(state),
-            value => panic!("Invalid direction {}", value),
+        Splitter(NorthSouth) => if state.direction.is_vertical() {
+            move_forward(state)
+        } else {
+            turn_north_and_south(state)
         },
     }
 }
 
 fn turn_east_and_west(state: State) -> Option<Box<dyn Iterator<Item = State>>> {
-    Some(Box::new(turn(EAST, state)?.chain(turn(WEST, state)?)))
+    Some(Box::new(turn(Direction::East, state)?.chain(turn(Direction::West, state)?)))
 }
 
 fn turn_north_and_south(state: State) -> Option<Box<dyn Iterator<Item = State>>> {
-    Some(Box::new(turn(NORTH, state)?.chain(turn(SOUTH, state)?)))
+    Some(Box::new(turn(Direction::North, state)?.chain(turn(Direction::South, state)?)))
 }
 
-fn turn(direction: IVec2, state: State) -> Option<Box<dyn Iterator <Item = State>>> {
+fn turn(direction: Direction, state: State) -> Option<Box<dyn Iterator <Item = State>>> {
     let mut new_state = state;
     new_state.direction = direction;
     new_state.position += new_state.direction;
@@ -119,50 +103,26 @@ fn move_forward(state: State) -> Option<Box<dyn Iterator <Item = State>>> {
     Some(Box::new(vec![new_state].into_iter()))
 }
 
-const NORTH: IVec2 = IVec2{x:  0, y: -1};
-const SOUTH: IVec2 = IVec2{x:  0, y:  1};
-const EAST:  IVec2 = IVec2{x:  1, y:  0};
-const WEST:  IVec2 = IVec2{x: -1, y:  0};
-
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
 struct State {
     position: IVec2,
-    direction: IVec2
+    direction: Direction
 }
 
-enum SplitterOrientation {
-    NorthSouth,
-    EastWest,
-}
+pub struct Part2;
 
-enum MirrorOrientation {
-    TopLeft,
-    TopRight
-}
+impl Solution for Part2 {
+    const DAY: u8 = 16;
+    const PART: u8 = 2;
 
-enum CellType {
-    EmptySpace,
-    Mirror(MirrorOrientation),
-    Splitter(SplitterOrientation)
-}
+    type Input<'a> = Grid<CellType>;
+    type Params = ();
+
+    fn parse(input: &str) -> Result<Grid<CellType>, Error> {
+        parse_contraption(input)
+    }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_process() {
-        let lines = r".|...\....
-|.-.\.....
-.....|-...
-........|.
-..........
-.........\
-..../.\\..
-.-.-/..|..
-.|....-|.\
-..//.|....";
-        let result = process(lines);
-        assert_eq!(result, 51);
+    fn solve(input: Grid<CellType>, _: &()) -> Result<Answer, Error> {
+        Ok(most_energized(&input).into())
     }
 }
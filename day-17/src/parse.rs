@@ -0,0 +1,7 @@
+use aoclib::{Error, Grid};
+
+/// Parses `input` as a rectangle of digits, each the heat lost entering that block, which the
+/// parts otherwise assume.
+pub fn parse_heat_losses(input: &str) -> Result<Grid<u32>, Error> {
+    Grid::try_parse(input, "a digit", |c| c.to_digit(10))
+}
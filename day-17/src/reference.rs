@@ -0,0 +1,71 @@
+//! Slow but obviously correct solutions, to check the real ones against on small inputs.
+
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashSet};
+
+/// Searches a block at a time, remembering how far the crucible has gone in a straight line,
+/// which must be at least `least` blocks before it turns or stops and at most `most`.
+fn least_heat_loss(input: &str, least: usize, most: usize) -> u32 {
+    let map: Vec<Vec<u32>> = input
+        .lines()
+        .map(|line| line.chars().map(|c| c.to_digit(10).unwrap()).collect())
+        .collect();
+    let (width, height) = (map[0].len() as i64, map.len() as i64);
+    let mut seen = HashSet::new();
+    let mut queue = BinaryHeap::from([
+        Reverse((0, (0, 0), (1, 0), 0)),
+        Reverse((0, (0, 0), (0, 1), 0)),
+    ]);
+    while let Some(Reverse((loss, (x, y), (dx, dy), run))) = queue.pop() {
+        if (x, y) == (width - 1, height - 1) && run >= least {
+            return loss;
+        }
+        if !seen.insert(((x, y), (dx, dy), run)) {
+            continue;
+        }
+        for (direction, turning) in [((dx, dy), false), ((dy, dx), true), ((-dy, -dx), true)] {
+            let next_run = if turning { 1 } else { run + 1 };
+            if (turning && run < least) || next_run > most {
+                continue;
+            }
+            let (nx, ny) = (x + direction.0, y + direction.1);
+            if (0..width).contains(&nx) && (0..height).contains(&ny) {
+                let next_loss = loss + map[ny as usize][nx as usize];
+                queue.push(Reverse((next_loss, (nx, ny), direction, next_run)));
+            }
+        }
+    }
+    panic!("the crucible should always reach the factory")
+}
+
+pub fn part1(input: &str) -> u32 {
+    least_heat_loss(input, 1, 3)
+}
+
+pub fn part2(input: &str) -> u32 {
+    least_heat_loss(input, 4, 10)
+}
+
+mod tests {
+    use aoclib::gen;
+    use aoclib::reference::assert_agrees;
+    use proptest::prelude::*;
+
+    use super::*;
+    use crate::part1::Part1;
+    use crate::part2::Part2;
+
+    proptest! {
+        #[test]
+        fn test_part1(seed: u64, size in 2..15usize) {
+            let input = gen::generator(17).unwrap().generate(size, seed);
+            assert_agrees::<Part1>(&input, &[], part1(&input));
+        }
+
+        #[test]
+        fn test_part2(seed: u64, size in 5..15usize) {
+            let input = gen::generator(17).unwrap().generate(size, seed);
+            assert_agrees::<Part2>(&input, &[], part2(&input));
+        }
+    }
+}
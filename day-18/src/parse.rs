@@ -0,0 +1,126 @@
+use nom::{
+    branch::alt,
+    bytes::complete::take_while_m_n,
+    character::complete,
+    combinator::{map, map_res, value},
+    error::context,
+    sequence::{delimited, pair, preceded, tuple},
+};
+
+use aoclib::parse::{lines, parse_all, tag, ParseError, ParseResult, Span};
+use aoclib::{Direction, Error};
+
+/// A step of the dig plan: which way to dig, and how far.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Step {
+    pub direction: Direction,
+    pub distance: i64,
+}
+
+/// An instruction of the dig plan: part 1's step, and part 2's step hidden in the colour, whose
+/// first five hex digits are the distance and whose last digit is the direction.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Instruction {
+    pub step: Step,
+    pub colour: Step,
+}
+
+fn instruction(line: Span) -> ParseResult<Instruction> {
+    map(
+        context(
+            "instruction",
+            tuple((
+                alt((
+                    value(Direction::West, tag("L")),
+                    value(Direction::East, tag("R")),
+                    value(Direction::North, tag("U")),
+                    value(Direction::South, tag("D")),
+                )),
+                preceded(tag(" "), complete::u32),
+                preceded(
+                    tag(" "),
+                    delimited(
+                        tag("(#"),
+                        pair(
+                            hex_digits,
+                            alt((
+                                value(Direction::East, tag("0")),
+                                value(Direction::South, tag("1")),
+                                value(Direction::West, tag("2")),
+                                value(Direction::North, tag("3")),
+                            )),
+                        ),
+                        tag(")"),
+                    ),
+                ),
+            )),
+        ),
+        |(direction, distance, (colour_distance, colour_direction))| Instruction {
+            step: Step {
+                direction,
+                distance: distance.into(),
+            },
+            colour: Step {
+                direction: colour_direction,
+                distance: colour_distance,
+            },
+        },
+    )(line)
+}
+
+/// The five hex digits of a colour's distance.
+fn hex_digits(input: Span) -> ParseResult<i64> {
+    map_res(
+        take_while_m_n(5, 5, |c: char| c.is_ascii_hexdigit()),
+        |digits: Span| i64::from_str_radix(digits.fragment(), 16),
+    )(input)
+    .map_err(|_: nom::Err<ParseError>| nom::Err::Error(ParseError::new(input, "five hex digits")))
+}
+
+/// Parses every line of `input` as an instruction of the dig plan.
+pub fn parse_plan(input: &str) -> Result<Vec<Instruction>, Error> {
+    parse_all(input, lines(instruction))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case(
+        "R 6 (#70c710)\nD 5 (#0dc571)\n",
+        Ok(vec![
+            Instruction {
+                step: Step { direction: Direction::East, distance: 6 },
+                colour: Step { direction: Direction::East, distance: 0x70c71 },
+            },
+            Instruction {
+                step: Step { direction: Direction::South, distance: 5 },
+                colour: Step { direction: Direction::South, distance: 0x0dc57 },
+            },
+        ])
+    )]
+    #[case(
+        "R 6 (#70c710)\nX 5 (#0dc571)\n",
+        Err("line 2, column 1: expected \"L\" or \"R\" or \"U\" or \"D\" in instruction, found \"X 5 (#0dc571)\"")
+    )]
+    #[case(
+        "R 6 (#70c710)\nD five (#0dc571)\n",
+        Err("line 2, column 3: expected a number in instruction, found \"five (#0dc571)\"")
+    )]
+    #[case(
+        "R 6 (#70c710)\nD 5 (#0dc574)\n",
+        Err("line 2, column 12: expected \"0\" or \"1\" or \"2\" or \"3\" in instruction, found \"4)\"")
+    )]
+    #[case(
+        "R 6 (#70c710)\nD 5 (#0dcx71)\n",
+        Err("line 2, column 7: expected five hex digits in instruction, found \"0dcx71)\"")
+    )]
+    fn test_parse_plan(#[case] input: &str, #[case] expected: Result<Vec<Instruction>, &str>) {
+        assert_eq!(
+            parse_plan(input).map_err(|error| error.to_string()),
+            expected.map_err(str::to_string)
+        );
+    }
+}
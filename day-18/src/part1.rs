@@ -1,80 +1,57 @@
-use glam::IVec2;
-use itertools::Itertools;
+use aoclib::geometry::Polygon;
+use aoclib::{Answer, Error, Solution};
+
+use crate::parse::{parse_plan, Instruction};
 
 pub fn process<'a>(
     input: &'a str,
 ) -> i32 {
-    let instructions = input.lines().map(|line| {
-        let (direction, distance, _) = line.split(" ").collect_tuple().unwrap();
-        Instruction{
-            direction: match direction {
-                "L" => IVec2::NEG_X,
-                "R" => IVec2::X,
-                "U" => IVec2::NEG_Y,
-                "D" => IVec2::Y,
-                value => panic!("Got invalid character: {}", value)
-            },
-            distance: distance.parse().unwrap()
-        }
-    });
-    
-    let path = instructions.clone().scan(IVec2::ZERO, |state, instruction|{
-        *state = *state + instruction.direction * instruction.distance;
-        Some(state.clone())
-    }).collect::<Vec<_>>();
-
-    let pairs_of_points = path.iter().zip(path.iter().skip(1));
-    
-    let perimeter = instructions.map(|x| x.distance).sum::<i32>();
+    lagoon(&parse_plan(input).expect("should parse")).expect("the lagoon should fit in an i32")
+}
 
-    dbg!(perimeter);
+/// Like [`process`], but reports a malformed dig plan, or a lagoon too large for an `i32`, as an
+/// error.
+pub fn try_process(input: &str) -> Result<Answer, Error> {
+    Ok(lagoon(&parse_plan(input)?)?.into())
+}
 
-    let area = pairs_of_points.fold(0,
-    |area: i32, (a, b): (&IVec2, &IVec2)| {
-        area + b.perp_dot(*a)
-    }).abs() / 2;
+fn lagoon(plan: &[Instruction]) -> Result<i32, Error> {
+    let polygon = Polygon::from_steps(plan.iter().map(|instruction| {
+        (instruction.step.direction.into(), instruction.step.distance)
+    }));
 
-    area + (perimeter / 2) + 1
+    i32::try_from(polygon.area_including_boundary())
+        .map_err(|_| Error::Overflow("the lagoon".to_string()))
 }
 
-#[derive(Debug)]
-struct Instruction {
-    direction: IVec2,
-    distance: i32,
-}
 
+pub struct Part1;
+
+impl Solution for Part1 {
+    const DAY: u8 = 18;
+    const PART: u8 = 1;
+
+    type Input<'a> = Vec<Instruction>;
+    type Params = ();
+
+    fn parse(input: &str) -> Result<Vec<Instruction>, Error> {
+        parse_plan(input)
+    }
+
+    fn solve(input: Vec<Instruction>, _: &()) -> Result<Answer, Error> {
+        Ok(lagoon(&input)?.into())
+    }
+}
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
-    fn test_little_process() {
-        let lines = "R 6 (#70c710)
-D 2 (#0dc571)
-L 6 (#5713f0)
-U 2 (#d2c081)";
-        let result = process(lines);
-        assert_eq!(result, 21);
-    }
-
-    #[test]
-    fn test_process() {
-        let lines = "R 6 (#70c710)
-D 5 (#0dc571)
-L 2 (#5713f0)
-D 2 (#d2c081)
-R 2 (#59c680)
-D 2 (#411b91)
-L 5 (#8ceee2)
-U 2 (#caa173)
-L 1 (#1b58a2)
-U 2 (#caa171)
-R 2 (#7807d2)
-U 3 (#a77fa3)
-L 2 (#015232)
-U 2 (#7a21e3)";
-        let result = process(lines);
-        assert_eq!(result, 62);
+    fn test_try_process() {
+        assert_eq!(
+            try_process("R 60000 (#70c710)\nD 60000 (#0dc571)\nL 60000 (#5713f0)\nU 60000 (#d2c081)").unwrap_err(),
+            Error::Overflow("the lagoon".to_string())
+        );
     }
 }
@@ -0,0 +1,103 @@
+//! Slow but obviously correct solutions, to check the real ones against on small inputs.
+
+use std::collections::HashSet;
+
+/// Digs the trench a cube at a time, floods the ground around it from beyond its corners,
+/// and counts everything the flood does not reach.
+fn lagoon(plan: &[(char, i64)]) -> i64 {
+    let mut trench = HashSet::from([(0, 0)]);
+    let (mut x, mut y) = (0i64, 0i64);
+    for (direction, length) in plan {
+        let (dx, dy) = match direction {
+            'R' => (1, 0),
+            'D' => (0, 1),
+            'L' => (-1, 0),
+            _ => (0, -1),
+        };
+        for _ in 0..*length {
+            (x, y) = (x + dx, y + dy);
+            trench.insert((x, y));
+        }
+    }
+    let (left, right) = (
+        trench.iter().map(|t| t.0).min().unwrap() - 1,
+        trench.iter().map(|t| t.0).max().unwrap() + 1,
+    );
+    let (top, bottom) = (
+        trench.iter().map(|t| t.1).min().unwrap() - 1,
+        trench.iter().map(|t| t.1).max().unwrap() + 1,
+    );
+    let mut outside = HashSet::from([(left, top)]);
+    let mut queue = vec![(left, top)];
+    while let Some((x, y)) = queue.pop() {
+        for next in [(x + 1, y), (x - 1, y), (x, y + 1), (x, y - 1)] {
+            let in_bounds = (left..=right).contains(&next.0) && (top..=bottom).contains(&next.1);
+            if in_bounds && !trench.contains(&next) && outside.insert(next) {
+                queue.push(next);
+            }
+        }
+    }
+    (right - left + 1) * (bottom - top + 1) - outside.len() as i64
+}
+
+pub fn part1(input: &str) -> i64 {
+    let plan: Vec<(char, i64)> = input
+        .lines()
+        .map(|line| {
+            let mut fields = line.split(' ');
+            let direction = fields.next().unwrap().chars().next().unwrap();
+            (direction, fields.next().unwrap().parse().unwrap())
+        })
+        .collect();
+    lagoon(&plan)
+}
+
+pub fn part2(input: &str) -> i64 {
+    let plan: Vec<(char, i64)> = input
+        .lines()
+        .map(|line| {
+            let code = line.split_once("(#").unwrap().1.trim_end_matches(')');
+            let direction = b"RDLU"[code[5..].parse::<usize>().unwrap()] as char;
+            (direction, i64::from_str_radix(&code[..5], 16).unwrap())
+        })
+        .collect();
+    lagoon(&plan)
+}
+
+mod tests {
+    use aoclib::gen;
+    use aoclib::reference::assert_agrees;
+    use proptest::prelude::*;
+
+    use super::*;
+    use crate::part1::Part1;
+    use crate::part2::Part2;
+
+    /// The generated plan, with its colours replaced by its own small instructions, so the
+    /// second part digs a lagoon small enough to flood.
+    fn small_colours(input: &str) -> String {
+        input
+            .lines()
+            .map(|line| {
+                let mut fields = line.split(' ');
+                let code = "RDLU".find(fields.next().unwrap()).unwrap();
+                let length: i64 = fields.next().unwrap().parse().unwrap();
+                format!("U 1 (#{length:05x}{code})\n")
+            })
+            .collect()
+    }
+
+    proptest! {
+        #[test]
+        fn test_part1(seed: u64, size in 1..10usize) {
+            let input = gen::generator(18).unwrap().generate(size, seed);
+            assert_agrees::<Part1>(&input, &[], part1(&input));
+        }
+
+        #[test]
+        fn test_part2(seed: u64, size in 1..10usize) {
+            let input = small_colours(&gen::generator(18).unwrap().generate(size, seed));
+            assert_agrees::<Part2>(&input, &[], part2(&input));
+        }
+    }
+}
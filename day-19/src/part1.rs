@@ -1,37 +1,79 @@
 use std::{collections::BTreeMap, cmp::Ordering};
 
-use nom::{sequence::{pair, tuple, terminated, delimited, preceded, separated_pair}, bytes::complete::tag, IResult, character::complete::{self, alpha1, one_of}, combinator::opt, multi::separated_list1};
+use nom::{sequence::{pair, tuple, terminated, delimited, preceded, separated_pair}, branch::alt, character::complete::{self, alpha1}, combinator::{cut, value}, error::context, multi::{many0, separated_list1}};
 use nom::Parser;
 
+use aoclib::parse::{parse_all, tag, ParseResult, Span};
+use aoclib::{Answer, Error, Solution};
+
 pub fn process<'a>(
     input: &'a str,
 ) -> u32 {
-    let (_, (workflowlist, partlist)) = separated_pair(workflow_list, tag("\n\n"), part_list)(input).expect("Should be given a valid input.");
+    let (workflowlist, partlist) = parse_system(input).expect("should parse");
+    total_rating(&workflowlist, &partlist).expect("the workflows should not send a part round in a loop")
+}
+
+/// Like [`process`], but reports malformed workflows or parts, a missing `in` workflow, a rule
+/// which sends parts to a workflow which does not exist, or workflows which send a part round in
+/// a loop, as an error.
+pub fn try_process(input: &str) -> Result<Answer, Error> {
+    let (workflowlist, partlist) = parse_system(input)?;
+    Ok(total_rating(&workflowlist, &partlist)?.into())
+}
+
+/// Parses the workflows and the parts, checking that the workflows can be followed from `in`.
+fn parse_system(input: &str) -> Result<(WorkflowList<'_>, PartList), Error> {
+    let (workflowlist, partlist) = parse_all(input, system)?;
+    workflowlist.validate(input)?;
+    Ok((workflowlist, partlist))
+}
 
-    partlist.0.iter().filter_map(|part| match workflowlist.apply(part) {
-        Destination::Accept => Some(part.x + part.m + part.a + part.s),
-        _ => None
-    }).sum()
+fn total_rating(workflowlist: &WorkflowList, partlist: &PartList) -> Result<u32, Error> {
+    let mut total = 0;
+    for part in &partlist.0 {
+        if let Destination::Accept = workflowlist.apply(part)? {
+            total += part.x + part.m + part.a + part.s;
+        }
+    }
+    Ok(total)
 }
 
 
-struct WorkflowList<'a>(BTreeMap<&'a str, Workflow<'a>>);
+/// The workflows, by name.
+pub struct WorkflowList<'a>(BTreeMap<&'a str, Workflow<'a>>);
 
 impl<'a> WorkflowList<'a> {
-    fn apply(&self, part: &Part) -> &Destination {
+    fn apply(&self, part: &Part) -> Result<&Destination, Error> {
         let mut workflow_name = "in";
-        loop {
-            let workflow = self.0.get(workflow_name).unwrap_or_else(||
-                panic!("Cannot find workflow with name 'in'")
-            );
-            let destination = workflow.apply(part);
+        // A part sent through more workflows than there are has gone round in a loop.
+        for _ in 0..=self.0.len() {
+            let destination = self.0[workflow_name].apply(part);
             use Destination::*;
             match destination {
                 Forward(other_workflow_name) => workflow_name = other_workflow_name,
-                Accept => return &Accept,
-                Reject => return &Reject
+                Accept => return Ok(&Accept),
+                Reject => return Ok(&Reject)
+            }
+        }
+        Err(Error::NoSolution(format!("the workflows send a part round in a loop through {}", workflow_name)))
+    }
+
+    /// Checks that there is an `in` workflow, and that every rule sends parts to a workflow
+    /// which exists.
+    fn validate(&self, input: &str) -> Result<(), Error> {
+        if !self.0.contains_key("in") {
+            return Err(Error::MissingStart("workflow in".to_string()));
+        }
+        for workflow in self.0.values() {
+            for rule in workflow.0.iter() {
+                if let Destination::Forward(name) = rule.destination {
+                    if !self.0.contains_key(name) {
+                        return Err(Error::parse(input, name, "the name of a workflow"));
+                    }
+                }
             }
         }
+        Ok(())
     }
 }
 
@@ -39,12 +81,8 @@ struct Workflow<'a>(Vec<Rule<'a>>);
 
 impl<'a> Workflow<'a> {
     fn apply(&self, part: &Part) -> &Destination {
-        for rule in self.0.iter() {
-            if let Some(destination) = rule.apply(part) {
-                return destination
-            }
-        }
-        panic!("No valid destination found!");
+        // The parser only accepts a workflow whose last rule applies to every part.
+        self.0.iter().find_map(|rule| rule.apply(part)).unwrap_or(&Destination::Reject)
     }
 }
 
@@ -89,8 +127,9 @@ impl<'a> Rule<'a> {
     }
 }
 
+/// The parts' ratings.
 #[derive(Eq, PartialEq, Debug)]
-struct PartList(Vec<Part>);
+pub struct PartList(Vec<Part>);
 
 #[derive(Eq, PartialEq, Debug)]
 struct Part {
@@ -100,31 +139,44 @@ struct Part {
     s: u32
 }
 
-fn workflow_list(input: &str) -> IResult<&str, WorkflowList> {
-    let (input, named_workflows) = separated_list1(tag("\n"), pair(
-        alpha1,
-        delimited(tag("{"), workflow, tag("}"))
-    ))(input)?;
+fn system(input: Span) -> ParseResult<(WorkflowList, PartList)> {
+    separated_pair(workflow_list, tag("\n\n"), part_list)(input)
+}
+
+fn workflow_list<'a>(input: Span<'a>) -> ParseResult<'a, WorkflowList<'a>> {
+    let (input, named_workflows) = separated_list1(tag("\n"), context("workflow", pair(
+        alpha1.map(|name: Span<'a>| *name.fragment()),
+        cut(delimited(tag("{"), workflow, tag("}")))
+    )))(input)?;
     Ok((input, WorkflowList(BTreeMap::from_iter(named_workflows.into_iter()))))
 }
 
 // a<2006:qkq,m>2090:A,rfg
-fn workflow(input: &str) -> IResult<&str, Workflow> {
-    separated_list1(tag(","), rule).map(|x| Workflow(x)).parse(input)
+fn workflow(input: Span) -> ParseResult<Workflow> {
+    // A workflow ends with the one rule which applies to every part.
+    pair(many0(terminated(conditional_rule, tag(","))), default_rule)
+        .map(|(mut rules, default)| {
+            rules.push(default);
+            Workflow(rules)
+        })
+        .parse(input)
 }
 
 // a<2006:qkq
-fn rule(input: &str) -> IResult<&str, Rule> {
-    let (input, result) = 
-        opt(terminated(
-            tuple((one_of("xmas"), one_of("<>"), complete::u32)), 
-            tag(":")
-        )).and(alpha1).parse(input)?;
-    
-    match result {
-        (None, destination) => Ok((input, Rule::default(Destination::from_str(destination)))),
-        (Some((parameter, operation, threshold)), destination)=> Ok((input, non_default_rule(parameter, operation, threshold, destination))),
-    }
+fn conditional_rule(input: Span) -> ParseResult<Rule> {
+    let parameter = alt((value('x', tag("x")), value('m', tag("m")), value('a', tag("a")), value('s', tag("s"))));
+    let operation = alt((value('<', tag("<")), value('>', tag(">"))));
+    context("workflow rule",
+        tuple((parameter, operation, cut(terminated(complete::u32, tag(":"))), cut(alpha1)))
+            .map(|(parameter, operation, threshold, destination)| non_default_rule(parameter, operation, threshold, destination.fragment())),
+    )(input)
+}
+
+// rfg
+fn default_rule(input: Span) -> ParseResult<Rule> {
+    context("workflow rule",
+        alpha1.map(|destination: Span| Rule::default(Destination::from_str(destination.fragment()))),
+    )(input)
 }
 
 fn non_default_rule(parameter: char, operation: char, threshold: u32, destination: &str) -> Rule{
@@ -150,34 +202,49 @@ fn non_default_rule(parameter: char, operation: char, threshold: u32, destinatio
     )
 }
 
-fn part_list(input: &str) -> IResult<&str, PartList>{
+fn part_list(input: Span) -> ParseResult<PartList>{
     separated_list1(tag("\n"), part).map(|x| PartList(x)).parse(input)
 }
 
 //{a<2006:qkq,m>2090:A,rfg}
-fn part(input: &str) -> IResult<&str, Part> {
-    let (input, parameter) = delimited(
+fn part(input: Span) -> ParseResult<Part> {
+    let (input, (x, m, a, s)) = context("part", delimited(
         tag("{"),
-        separated_list1(
-            tag(","),
-            preceded(
-                pair(one_of("xmas"), tag("=")),
-                complete::u32
-            )
-        ),
-        tag("}")
-    )(input)?;
-    Ok((input, Part{
-        x: parameter[0],
-        m: parameter[1],
-        a: parameter[2],
-        s: parameter[3]
-    }))
+        cut(tuple((
+            preceded(tag("x="), complete::u32),
+            preceded(tag(",m="), complete::u32),
+            preceded(tag(",a="), complete::u32),
+            preceded(tag(",s="), complete::u32),
+        ))),
+        cut(tag("}"))
+    ))(input)?;
+    Ok((input, Part{x, m, a, s}))
+}
+
+pub struct Part1;
+
+impl Solution for Part1 {
+    const DAY: u8 = 19;
+    const PART: u8 = 1;
+
+    type Input<'a> = (WorkflowList<'a>, PartList);
+    type Params = ();
+
+    fn parse(input: &str) -> Result<(WorkflowList<'_>, PartList), Error> {
+        parse_system(input)
+    }
+
+    fn solve(input: (WorkflowList<'_>, PartList), _: &()) -> Result<Answer, Error> {
+        let (workflowlist, partlist) = input;
+        Ok(total_rating(&workflowlist, &partlist)?.into())
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use rstest::rstest;
+
     #[test]
     fn test_workflow_list() {
         let input = "px{a<2006:qkq,m>2090:A,rfg}
@@ -191,8 +258,8 @@ in{s<1351:px,qqz}
 qqz{s>2770:qs,m<1801:hdj,R}
 gd{a>3333:R,R}
 hdj{m>838:A,pv}";
-        let (input, _) = workflow_list(input).unwrap();
-        assert_eq!(input, "");
+        let (input, _) = workflow_list(Span::new(input)).unwrap();
+        assert_eq!(*input.fragment(), "");
     }
 
     #[test]
@@ -202,8 +269,8 @@ hdj{m>838:A,pv}";
 {x=2036,m=264,a=79,s=2244}
 {x=2461,m=1339,a=466,s=291}
 {x=2127,m=1623,a=2188,s=1013}";
-        let (input, partlist) = part_list(input).unwrap();
-        assert_eq!(input, "");
+        let (input, partlist) = part_list(Span::new(input)).unwrap();
+        assert_eq!(*input.fragment(), "");
         assert_eq!(partlist, 
             PartList(vec![
             Part{x:787, m:2655, a:1222, s:2876},
@@ -214,26 +281,15 @@ hdj{m>838:A,pv}";
         ]))
     }
 
-    #[test]
-    fn test_process() {
-        let lines = "px{a<2006:qkq,m>2090:A,rfg}
-pv{a>1716:R,A}
-lnx{m>1548:A,A}
-rfg{s<537:gd,x>2440:R,A}
-qs{s>3448:A,lnx}
-qkq{x<1416:A,crn}
-crn{x>2662:A,R}
-in{s<1351:px,qqz}
-qqz{s>2770:qs,m<1801:hdj,R}
-gd{a>3333:R,R}
-hdj{m>838:A,pv}
-
-{x=787,m=2655,a=1222,s=2876}
-{x=1679,m=44,a=2067,s=496}
-{x=2036,m=264,a=79,s=2244}
-{x=2461,m=1339,a=466,s=291}
-{x=2127,m=1623,a=2188,s=1013}";
-        let result = process(lines);
-        assert_eq!(result, 19114);
+    #[rstest]
+    #[case("px{a<2006:qkq,rfg}\nin{s<1351:px,A}\nrfg{A}\n\n{x=787,m=2655,a=1222,s=2876}", "line 1, column 11: expected the name of a workflow, found \"qkq,rfg}\"")]
+    #[case("px{a<2006:A,R}\n\n{x=787,m=2655,a=1222,s=2876}", "the input has no start (workflow in)")]
+    #[case("in{a<2006:A,R}\n\n{x=787,m=2655,a=1222,s=2876}\n{x=1}", "line 4, column 5: expected \",m=\" in part, found \"}\"")]
+    #[case("in{a<2006:A,R}\n\n{x=787,m=2655,a=1222,s=2876}\n\n{x=1}", "line 5, column 1: expected the end of the input, found \"{x=1}\"")]
+    #[case("in{a!2006:A,R}\n\n{x=787,m=2655,a=1222,s=2876}", "line 1, column 5: expected \"}\" in workflow, found \"!2006:A,R}\"")]
+    #[case("in{a<2006:A}\n\n{x=787,m=2655,a=1222,s=2876}", "line 1, column 5: expected \"}\" in workflow, found \"<2006:A}\"")]
+    #[case("in{a<2006:px,R}\npx{in}\n\n{x=787,m=2655,a=1222,s=2876}", "no solution: the workflows send a part round in a loop through px")]
+    fn test_try_process(#[case] input: &str, #[case] expected: &str) {
+        assert_eq!(try_process(input).unwrap_err().to_string(), expected);
     }
-}
\ No newline at end of file
+}
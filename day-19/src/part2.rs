@@ -1,13 +1,37 @@
 use std::{collections::BTreeMap, ops::RangeInclusive};
 
-use nom::{sequence::{pair, tuple, terminated, delimited}, bytes::complete::tag, IResult, character::complete::{self, alpha1, one_of}, combinator::opt, multi::separated_list1};
+use nom::{sequence::{pair, tuple, terminated, delimited}, branch::alt, character::complete::{self, alpha1}, combinator::{cut, value}, error::context, multi::{many0, separated_list1}};
 use nom::Parser;
+use tracing::trace;
+
+use aoclib::dot::Graph;
+use aoclib::parse::{parse_prefix, tag, ParseResult, Span};
+use aoclib::{Answer, Error, Solution};
 
 pub fn process<'a>(
     input: &'a str,
 ) -> u64 {
-    let (_, workflowlist) = terminated(workflow_list, tag("\n\n"))(input).expect("Should be given a valid input.");
+    combinations(&parse_workflows(input).expect("should parse"))
+        .expect("the workflows should not send parts round in a loop")
+}
+
+/// Like [`process`], but reports malformed workflows, a missing `in` workflow, a rule which sends
+/// parts to a workflow which does not exist, or workflows which send parts round in a loop, as an
+/// error.
+pub fn try_process(input: &str) -> Result<Answer, Error> {
+    Ok(combinations(&parse_workflows(input)?)?.into())
+}
+
+/// Parses the workflows, which are followed by a blank line and the part ratings, checking that
+/// they can be followed from `in`. Only the workflows matter for part 2, so the ratings are not
+/// parsed.
+fn parse_workflows(input: &str) -> Result<WorkflowList<'_>, Error> {
+    let (_, workflowlist) = parse_prefix(input, terminated(workflow_list, tag("\n\n")))?;
+    workflowlist.validate(input)?;
+    Ok(workflowlist)
+}
 
+fn combinations(workflowlist: &WorkflowList) -> Result<u64, Error> {
     let initial_part = Part{
         x: 1..=4000,
         m: 1..=4000,
@@ -15,39 +39,62 @@ pub fn process<'a>(
         s: 1..=4000,
     };
 
-    workflowlist.apply(initial_part).iter().inspect(|x| { println!("{:?}", x); }).map(Part::count).inspect(|x| { println!("{:?}", x); }).sum()
+    Ok(workflowlist.apply(initial_part)?.iter().map(|part| {
+        let count = part.count();
+        trace!(?part, count, "accepted");
+        count
+    }).sum())
 }
 
 
-struct WorkflowList<'a>(BTreeMap<&'a str, Workflow<'a>>);
+/// The workflows, by name.
+pub struct WorkflowList<'a>(BTreeMap<&'a str, Workflow<'a>>);
 
 impl<'a> WorkflowList<'a> {
-    fn apply(&self, part: Part) -> Vec<Part> {
-        
-        let workflow = self.get_workflow_by_name("in");
-        
-        let accepted_parts = self.apply_workflow_to_part(workflow, &part);
-        accepted_parts.into_iter().map(|(p, _)| p).collect()
+    fn apply(&self, part: Part) -> Result<Vec<Part>, Error> {
+        let accepted_parts = self.apply_workflow_to_part("in", &part, &mut vec![])?;
+        Ok(accepted_parts.into_iter().map(|(p, _)| p).collect())
     }
 
-    fn apply_workflow_to_part(&'a self, workflow: &'a Workflow, part: &Part) -> Vec<(Part, Destination<'a>)> {
+    /// The parts accepted after `part` is sent to the workflow `name`, by way of the workflows on
+    /// `path`.
+    fn apply_workflow_to_part(&'a self, name: &'a str, part: &Part, path: &mut Vec<&'a str>) -> Result<Vec<(Part, Destination<'a>)>, Error> {
+        if path.contains(&name) {
+            return Err(Error::NoSolution(format!("the workflows send parts round in a loop through {}", name)));
+        }
+        path.push(name);
         let mut accepted_parts = vec![];
-        let destinations = workflow.apply(part.clone());
+        let destinations = self.0[name].apply(part.clone());
         for (part, destination) in destinations {
             use Destination::*;
             match destination {
                 Forward(other_workflow_name) => accepted_parts.extend(
-                    self.apply_workflow_to_part(self.get_workflow_by_name(other_workflow_name), &part)
+                    self.apply_workflow_to_part(other_workflow_name, &part, path)?
                 ),
                 Accept => accepted_parts.push((part, destination)),
                 Reject => ()
             }
         }
-        accepted_parts
+        path.pop();
+        Ok(accepted_parts)
     }
-    
-    fn get_workflow_by_name(&self, name: &str) -> &Workflow {
-        self.0.get(name).expect(&format!("Cannot find workflow with name {}", name))
+
+    /// Checks that there is an `in` workflow, and that every rule sends parts to a workflow
+    /// which exists.
+    fn validate(&self, input: &str) -> Result<(), Error> {
+        if !self.0.contains_key("in") {
+            return Err(Error::MissingStart("workflow in".to_string()));
+        }
+        for workflow in self.0.values() {
+            for rule in workflow.0.iter() {
+                if let Destination::Forward(name) = rule.destination {
+                    if !self.0.contains_key(name) {
+                        return Err(Error::parse(input, name, "the name of a workflow"));
+                    }
+                }
+            }
+        }
+        Ok(())
     }
 }
 
@@ -59,7 +106,9 @@ impl<'a> Workflow<'a> {
         let mut part = Some(part.clone());
         let mut retval = vec![];
         for rule in self.0.iter() {
-            let (cont, send, destination) = rule.apply(part.unwrap());
+            // Nothing is left once the last rule, which applies to every part, has been applied.
+            let Some(current) = part else { break };
+            let (cont, send, destination) = rule.apply(current);
             retval.push((send, destination));
             part = cont;            
         }
@@ -158,31 +207,40 @@ fn len(range: &RangeInclusive<u32>) -> u64 {
     (range.end() - range.start() + 1) as u64
 }
 
-fn workflow_list(input: &str) -> IResult<&str, WorkflowList> {
-    let (input, named_workflows) = separated_list1(tag("\n"), pair(
-        alpha1,
-        delimited(tag("{"), workflow, tag("}"))
-    ))(input)?;
+fn workflow_list<'a>(input: Span<'a>) -> ParseResult<'a, WorkflowList<'a>> {
+    let (input, named_workflows) = separated_list1(tag("\n"), context("workflow", pair(
+        alpha1.map(|name: Span<'a>| *name.fragment()),
+        cut(delimited(tag("{"), workflow, tag("}")))
+    )))(input)?;
     Ok((input, WorkflowList(BTreeMap::from_iter(named_workflows.into_iter()))))
 }
 
 // a<2006:qkq,m>2090:A,rfg
-fn workflow(input: &str) -> IResult<&str, Workflow> {
-    separated_list1(tag(","), rule).map(|x| Workflow(x)).parse(input)
+fn workflow(input: Span) -> ParseResult<Workflow> {
+    // A workflow ends with the one rule which applies to every part.
+    pair(many0(terminated(conditional_rule, tag(","))), default_rule)
+        .map(|(mut rules, default)| {
+            rules.push(default);
+            Workflow(rules)
+        })
+        .parse(input)
 }
 
 // a<2006:qkq
-fn rule(input: &str) -> IResult<&str, Rule> {
-    let (input, result) = 
-        opt(terminated(
-            tuple((one_of("xmas"), one_of("<>"), complete::u32)), 
-            tag(":")
-        )).and(alpha1).parse(input)?;
-    
-    match result {
-        (None, destination) => Ok((input, Rule::default(Destination::from_str(destination)))),
-        (Some((parameter, operation, threshold)), destination)=> Ok((input, non_default_rule(parameter, operation, threshold, destination))),
-    }
+fn conditional_rule(input: Span) -> ParseResult<Rule> {
+    let parameter = alt((value('x', tag("x")), value('m', tag("m")), value('a', tag("a")), value('s', tag("s"))));
+    let operation = alt((value('<', tag("<")), value('>', tag(">"))));
+    context("workflow rule",
+        tuple((parameter, operation, cut(terminated(complete::u32, tag(":"))), cut(alpha1)))
+            .map(|(parameter, operation, threshold, destination)| non_default_rule(parameter, operation, threshold, destination.fragment())),
+    )(input)
+}
+
+// rfg
+fn default_rule(input: Span) -> ParseResult<Rule> {
+    context("workflow rule",
+        alpha1.map(|destination: Span| Rule::default(Destination::from_str(destination.fragment()))),
+    )(input)
 }
 
 fn non_default_rule(parameter: char, operation: char, threshold: u32, destination: &str) -> Rule{
@@ -202,9 +260,53 @@ fn non_default_rule(parameter: char, operation: char, threshold: u32, destinatio
 
 
 
+pub struct Part2;
+
+impl Solution for Part2 {
+    const DAY: u8 = 19;
+    const PART: u8 = 2;
+
+    type Input<'a> = WorkflowList<'a>;
+    type Params = ();
+
+    fn parse(input: &str) -> Result<WorkflowList<'_>, Error> {
+        parse_workflows(input)
+    }
+
+    fn solve(input: WorkflowList<'_>, _: &()) -> Result<Answer, Error> {
+        Ok(combinations(&input)?.into())
+    }
+
+    /// The workflows, with an edge for each rule labelled by its condition, leading to the
+    /// accepted (`A`) and rejected (`R`) parts.
+    fn export_graph(workflowlist: WorkflowList<'_>, _: &()) -> Result<Option<Graph>, Error> {
+        let mut graph = Graph::directed("day19");
+        graph.node("in").set("shape", "doublecircle");
+        for (name, workflow) in &workflowlist.0 {
+            for rule in &workflow.0 {
+                let destination = match rule.destination {
+                    Destination::Accept => "A",
+                    Destination::Reject => "R",
+                    Destination::Forward(name) => name,
+                };
+                let edge = graph.edge(name, destination);
+                match rule.operation {
+                    Operation::Above(c, threshold) => edge.set("label", format!("{}>{}", c, threshold)),
+                    Operation::Below(c, threshold) => edge.set("label", format!("{}<{}", c, threshold)),
+                    Operation::Default => edge,
+                };
+            }
+        }
+        graph.node("A").set("shape", "box").set("color", "green");
+        graph.node("R").set("shape", "box").set("color", "red");
+        Ok(Some(graph))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use rstest::rstest;
     #[test]
     fn test_workflow_list() {
         let input = "px{a<2006:qkq,m>2090:A,rfg}
@@ -218,31 +320,8 @@ in{s<1351:px,qqz}
 qqz{s>2770:qs,m<1801:hdj,R}
 gd{a>3333:R,R}
 hdj{m>838:A,pv}";
-        let (input, _) = workflow_list(input).unwrap();
-        assert_eq!(input, "");
-    }
-
-    #[test]
-    fn test_process() {
-        let lines = "px{a<2006:qkq,m>2090:A,rfg}
-pv{a>1716:R,A}
-lnx{m>1548:A,A}
-rfg{s<537:gd,x>2440:R,A}
-qs{s>3448:A,lnx}
-qkq{x<1416:A,crn}
-crn{x>2662:A,R}
-in{s<1351:px,qqz}
-qqz{s>2770:qs,m<1801:hdj,R}
-gd{a>3333:R,R}
-hdj{m>838:A,pv}
-
-{x=787,m=2655,a=1222,s=2876}
-{x=1679,m=44,a=2067,s=496}
-{x=2036,m=264,a=79,s=2244}
-{x=2461,m=1339,a=466,s=291}
-{x=2127,m=1623,a=2188,s=1013}";
-        let result = process(lines);
-        assert_eq!(result, 167409079868000);
+        let (input, _) = workflow_list(Span::new(input)).unwrap();
+        assert_eq!(*input.fragment(), "");
     }
 
     #[test]
@@ -266,4 +345,31 @@ hdj{m>838:A,pv}
         }.count();
         assert_eq!(value, 240000)
     }
-}
\ No newline at end of file
+
+    #[rstest]
+    #[case("in{s<1351px,R}\n\n", "line 1, column 10: expected \":\" in workflow rule, found \"px,R}\"")]
+    #[case("in{s<:px,R}\n\n", "line 1, column 6: expected a number in workflow rule, found \":px,R}\"")]
+    #[case("in{s<1351:A,px}\npx{a-2006:A,R}\n\n", "line 2, column 5: expected \"}\" in workflow, found \"-2006:A,R}\"")]
+    #[case("in{s<1351:A,R}\n{x=1,m=2,a=3,s=4}\n", "line 1, column 15: expected \"\\n\\n\", found the end of the line")]
+    #[case("in{A,R}\n\n", "line 1, column 5: expected \"}\" in workflow, found \",R}\"")]
+    #[case("in{s<1351:px,R}\npx{a>2006:A,in}\n\n", "no solution: the workflows send parts round in a loop through in")]
+    fn test_try_process_error(#[case] input: &str, #[case] expected: &str) {
+        assert_eq!(try_process(input).unwrap_err().to_string(), expected);
+    }
+
+    #[test]
+    fn test_export_graph() {
+        let graph = Part2::export_graph(Part2::parse("in{s<1351:px,R}\npx{a>2006:A,in}\n\n{x=1,m=2,a=3,s=4}").unwrap(), &()).unwrap().unwrap();
+        assert_eq!(graph.to_string(), r#"digraph "day19" {
+    "in" [shape="doublecircle"];
+    "px";
+    "R" [shape="box", color="red"];
+    "A" [shape="box", color="green"];
+    "in" -> "px" [label="s<1351"];
+    "in" -> "R";
+    "px" -> "A" [label="a>2006"];
+    "px" -> "in";
+}
+"#);
+    }
+}
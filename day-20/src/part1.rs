@@ -8,16 +8,32 @@ use std::{
 
 use nom::{
     branch::alt,
-    bytes::complete::tag,
     character::complete::{alpha1, newline},
+    combinator::cut,
+    error::context,
     multi::separated_list1,
-    sequence::separated_pair,
-    IResult,
+    sequence::{preceded, separated_pair},
 };
 
+use aoclib::budget;
+use aoclib::dot::Graph;
+use aoclib::parse::{parse_prefix, tag, ParseResult, Span};
+use aoclib::{Answer, Error, Solution};
+
 pub fn process(input: &str) -> u32 {
-    let (_, modules_and_receivers) = modules(input).expect("should parse");
+    count_pulses(try_modules(input).expect("should parse"))
+        .expect("the pulses should die down within the budget")
+}
 
+/// Like [`process`], but reports malformed modules or a missing broadcaster as an error, as
+/// well as pulses which do not die down within the budget. Each pulse counts as a step of the
+/// budget.
+pub fn try_process(input: &str) -> Result<Answer, Error> {
+    Ok(count_pulses(try_modules(input)?)?.into())
+}
+
+/// The number of low pulses sent in a thousand presses of the button, times the number of high.
+fn count_pulses(modules_and_receivers: Vec<(Module, Vec<&str>)>) -> Result<u32, Error> {
     #[allow(clippy::type_complexity)]
     let (module_receiver_map, mut name_module_map): (
         HashMap<String, Vec<&str>>,
@@ -42,7 +58,7 @@ pub fn process(input: &str) -> u32 {
                 None => {
                     {
                         let new_module = Rc::new(RefCell::new(Module {
-                            name: module_name.clone(),
+                            name: String::from(receiver_name),
                             receivers: vec![],
                             module_type: ModuleType::Untyped,
                         }));
@@ -66,6 +82,7 @@ pub fn process(input: &str) -> u32 {
             transmitter_name: String::from("button"),
         });
         while !queue.is_empty() {
+            budget::tick()?;
             let signal = &queue
                 .pop_front()
                 .expect("Queue can't be empty, as while loop would have exited");
@@ -85,7 +102,7 @@ pub fn process(input: &str) -> u32 {
             }
         }
     }
-    num_low * num_high
+    Ok(num_low * num_high)
 }
 
 #[derive(Eq, PartialEq, Debug, Clone, Copy)]
@@ -130,7 +147,8 @@ enum ModuleType {
     Untyped,
 }
 
-struct Module {
+/// A module, and the modules it sends pulses to.
+pub struct Module {
     name: String,
     receivers: Vec<Rc<RefCell<Module>>>,
     module_type: ModuleType,
@@ -199,12 +217,16 @@ impl Module {
     }
 
     fn add_receiver(&mut self, module: Rc<RefCell<Module>>) {
-        module.borrow_mut().register_input(self.name.clone());
+        // the only module already borrowed is this one, when it sends pulses to itself
+        match module.try_borrow_mut() {
+            Ok(mut receiver) => receiver.register_input(self.name.clone()),
+            Err(_) => self.register_input(self.name.clone()),
+        }
         self.receivers.push(module);
     }
 }
 
-fn modules(input: &str) -> IResult<&str, Vec<(Module, Vec<&str>)>> {
+fn modules<'a>(input: Span<'a>) -> ParseResult<'a, Vec<(Module, Vec<&'a str>)>> {
     let (input, modules_and_receivers) = separated_list1(
         newline, 
         alt((
@@ -217,9 +239,22 @@ fn modules(input: &str) -> IResult<&str, Vec<(Module, Vec<&str>)>> {
     Ok((input, modules_and_receivers))
 }
 
-fn broadcaster(input: &str) -> IResult<&str, (Module, Vec<&str>)> {
-    let (input, _) = tag("broadcaster -> ")(input)?;
-    let (input, outputs) = separated_list1(tag(", "), alpha1)(input)?;
+/// Parses the modules like [`modules`], but reports anything left over, or a missing
+/// broadcaster, as an error.
+fn try_modules(input: &str) -> Result<Vec<(Module, Vec<&str>)>, Error> {
+    let (rest, modules_and_receivers) = parse_prefix(input, modules)?;
+    if !rest.fragment().trim().is_empty() {
+        return Err(Error::parse(input, rest.fragment().trim_start(), "a module"));
+    }
+    if !modules_and_receivers.iter().any(|(module, _)| module.name == "broadcaster") {
+        return Err(Error::MissingStart("the broadcaster".to_string()));
+    }
+    Ok(modules_and_receivers)
+}
+
+fn broadcaster<'a>(input: Span<'a>) -> ParseResult<'a, (Module, Vec<&'a str>)> {
+    let (input, outputs) =
+        context("broadcaster", preceded(tag("broadcaster -> "), cut(names)))(input)?;
     Ok((
         input,
         (
@@ -233,15 +268,16 @@ fn broadcaster(input: &str) -> IResult<&str, (Module, Vec<&str>)> {
     ))
 }
 
-fn flip_flop(input: &str) -> IResult<&str, (Module, Vec<&str>)> {
-    let (input, _) = tag("%")(input)?;
-    let (input, (name, outputs)) =
-        separated_pair(alpha1, tag(" -> "), separated_list1(tag(", "), alpha1))(input)?;
+fn flip_flop<'a>(input: Span<'a>) -> ParseResult<'a, (Module, Vec<&'a str>)> {
+    let (input, (name, outputs)) = context(
+        "flip-flop",
+        preceded(tag("%"), cut(separated_pair(alpha1, tag(" -> "), names))),
+    )(input)?;
     Ok((
         input,
         (
             Module {
-                name: String::from(name),
+                name: String::from(*name.fragment()),
                 receivers: vec![],
                 module_type: ModuleType::FlipFlop { state: Pulse::Low },
             },
@@ -250,15 +286,16 @@ fn flip_flop(input: &str) -> IResult<&str, (Module, Vec<&str>)> {
     ))
 }
 
-fn conjunction(input: &str) -> IResult<&str, (Module, Vec<&str>)> {
-    let (input, _) = tag("&")(input)?;
-    let (input, (name, outputs)) =
-        separated_pair(alpha1, tag(" -> "), separated_list1(tag(", "), alpha1))(input)?;
+fn conjunction<'a>(input: Span<'a>) -> ParseResult<'a, (Module, Vec<&'a str>)> {
+    let (input, (name, outputs)) = context(
+        "conjunction",
+        preceded(tag("&"), cut(separated_pair(alpha1, tag(" -> "), names))),
+    )(input)?;
     Ok((
         input,
         (
             Module {
-                name: String::from(name),
+                name: String::from(*name.fragment()),
                 receivers: vec![],
                 module_type: ModuleType::Conjunction {
                     state: HashMap::from([]),
@@ -269,32 +306,95 @@ fn conjunction(input: &str) -> IResult<&str, (Module, Vec<&str>)> {
     ))
 }
 
+/// The names of the modules which a module sends pulses to.
+fn names<'a>(input: Span<'a>) -> ParseResult<'a, Vec<&'a str>> {
+    let (input, names) = separated_list1(tag(", "), alpha1)(input)?;
+    Ok((input, names.iter().map(|name| *name.fragment()).collect()))
+}
+
+pub struct Part1;
+
+impl Solution for Part1 {
+    const DAY: u8 = 20;
+    const PART: u8 = 1;
+
+    type Input<'a> = Vec<(Module, Vec<&'a str>)>;
+    type Params = ();
+
+    fn parse(input: &str) -> Result<Vec<(Module, Vec<&'_ str>)>, Error> {
+        try_modules(input)
+    }
+
+    fn solve(input: Vec<(Module, Vec<&'_ str>)>, _: &()) -> Result<Answer, Error> {
+        Ok(count_pulses(input)?.into())
+    }
+
+    /// The wiring between the modules, with a shape for each type of module. Modules which are
+    /// only ever sent pulses, such as `rx`, are drawn as plain text.
+    fn export_graph(modules_and_receivers: Vec<(Module, Vec<&'_ str>)>, _: &()) -> Result<Option<Graph>, Error> {
+        let mut graph = Graph::directed("day20");
+        graph.attributes().set("rankdir", "LR");
+        for (module, _) in &modules_and_receivers {
+            let shape = match module.module_type {
+                ModuleType::FlipFlop { .. } => "box",
+                ModuleType::Conjunction { .. } => "invtrapezium",
+                ModuleType::Broadcaster => "doubleoctagon",
+                ModuleType::Untyped => "plaintext",
+            };
+            graph.node(&module.name).set("shape", shape);
+        }
+        for (module, receivers) in &modules_and_receivers {
+            for receiver in receivers {
+                graph.edge(&module.name, receiver);
+                if !modules_and_receivers.iter().any(|(module, _)| module.name == *receiver) {
+                    graph.node(receiver).set("shape", "plaintext");
+                }
+            }
+        }
+        Ok(Some(graph))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use aoclib::budget::Budget;
     use rstest::rstest;
 
     #[rstest]
-    #[case(
-        "broadcaster -> a, b, c
-%a -> b
-%b -> c
-%c -> inv
-&inv -> a",
-        32000000
-    )]
-    #[case(
-        "broadcaster -> a
-%a -> inv, con
-&inv -> b
-%b -> con
-&con -> output",
-        11687500
-    )]
+    #[case("broadcaster -> a\n%a -> b\n&b -> \n", "line 3, column 7: expected a letter in conjunction, found the end of the line")]
+    #[case("%a -> b\n&b -> a\n", "the input has no start (the broadcaster)")]
+    fn test_try_process(#[case] input: &str, #[case] expected: &str) {
+        assert_eq!(try_process(input).unwrap_err().to_string(), expected);
+    }
+
     #[test]
-    fn test_process(#[case] input: &str, #[case] expected: u32) {
-        let lines = input;
-        let result = process(lines);
-        assert_eq!(result, expected);
+    fn test_budget() {
+        let budget = Budget::unlimited().with_max_steps(1000);
+        assert_eq!(
+            budget.run(|| try_process("broadcaster -> a\n&a -> a\n")),
+            Err(Error::BudgetExceeded { steps: 1000 })
+        );
+    }
+
+    #[test]
+    fn test_export_graph() {
+        let graph = Part1::export_graph(Part1::parse("broadcaster -> a\n%a -> inv, con\n&inv -> b\n%b -> con\n&con -> output").unwrap(), &()).unwrap().unwrap();
+        assert_eq!(graph.to_string(), r#"digraph "day20" {
+    graph [rankdir="LR"];
+    "broadcaster" [shape="doubleoctagon"];
+    "a" [shape="box"];
+    "inv" [shape="invtrapezium"];
+    "b" [shape="box"];
+    "con" [shape="invtrapezium"];
+    "output" [shape="plaintext"];
+    "broadcaster" -> "a";
+    "a" -> "inv";
+    "a" -> "con";
+    "inv" -> "b";
+    "b" -> "con";
+    "con" -> "output";
+}
+"#);
     }
 }
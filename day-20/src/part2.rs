@@ -1,6 +1,6 @@
 use std::{
     cell::RefCell,
-    collections::{HashMap, VecDeque},
+    collections::{HashMap, HashSet, VecDeque},
     fmt::Debug,
     ops::Not,
     rc::Rc,
@@ -8,25 +8,91 @@ use std::{
 
 use nom::{
     branch::alt,
-    bytes::complete::tag,
     character::complete::{alpha1, newline},
+    combinator::cut,
+    error::context,
     multi::separated_list1,
-    sequence::separated_pair,
-    IResult,
+    sequence::{preceded, separated_pair},
 };
+use tracing::debug;
+
+use aoclib::budget::{self, Budget};
+use aoclib::math;
+use aoclib::parallel::prelude::*;
+use aoclib::parse::{parse_prefix, tag, ParseResult, Span};
+use aoclib::{Answer, Error, Solution};
 
 pub fn process(input: &str) -> u64 {
-    let (_, modules_and_receivers) = modules(input).expect("should parse");
+    let loop_counts = loop_counts(&try_modules(input).expect("should parse"))
+        .expect("each module should be sent a low pulse within the budget");
+    math::lcm_all(loop_counts.into_iter().map(u64::from)).expect("the cycles should line up within a u64")
+}
+
+/// Like [`process`], but reports malformed modules, a missing broadcaster, no module sending
+/// pulses to `rx`, modules feeding `rx` which are not wired as counters into a conjunction, a
+/// module not sent a low pulse within the budget, or an answer too large for a `u64` as an error.
+/// Each pulse counts as a step of the budget.
+pub fn try_process(input: &str) -> Result<Answer, Error> {
+    button_presses(&try_modules(input)?)
+}
+
+/// The number of button presses before `rx` is sent a low pulse, when every module which feeds it
+/// does so at once.
+fn button_presses(declarations: &[Declaration]) -> Result<Answer, Error> {
+    math::lcm_all(loop_counts(declarations)?.into_iter().map(u64::from))
+        .map(Answer::from)
+        .ok_or_else(|| Error::Overflow("the number of button presses".to_string()))
+}
+
+/// The number of button presses before each of the modules which feed `rx` sends it a pulse.
+fn loop_counts(declarations: &[Declaration]) -> Result<Vec<u32>, Error> {
+    if !declarations.iter().any(|declaration| declaration.receivers.contains(&"rx")) {
+        return Err(Error::NoSolution("no module sends pulses to rx".to_string()));
+    }
+    let name_module_map = network(declarations);
+    // rx is fed through a chain of conjunctions with one sender each, down to one with several
+    let mut destination_module_names = vec![String::from("rx")];
+    let mut walked = HashSet::new();
+    while destination_module_names.len() == 1 {
+        budget::tick()?;
+        let destination_module_name = destination_module_names.remove(0);
+        if !walked.insert(destination_module_name.clone()) {
+            return Err(Error::NoSolution(format!("the modules feeding rx loop back to {}", destination_module_name)));
+        }
+        destination_module_names = get_upstream_machine_names(&name_module_map, &destination_module_name)?;
+    }
+
+    debug!(modules = ?destination_module_names, "counting presses until each module is sent a low pulse");
+
+    // the modules cannot be shared between threads, so each count wires up a network of its own,
+    // and takes the budget along with it
+    let budget = Budget::current();
+    let loop_counts = destination_module_names.into_maybe_par_iter()
+        .map(|destination_machine_name| budget.run(|| {
+            let name_module_map = network(declarations);
+            get_button_presses(&name_module_map["broadcaster"], destination_machine_name)
+        }))
+        .collect::<Result<Vec<_>, _>>()?;
+    debug!(?loop_counts);
+    Ok(loop_counts)
+}
 
+/// Every module, wired up to the modules it sends pulses to.
+fn network(declarations: &[Declaration]) -> HashMap<String, Rc<RefCell<Module>>> {
     #[allow(clippy::type_complexity)]
     let (module_receiver_map, mut name_module_map): (
         HashMap<String, Vec<&str>>,
         HashMap<String, Rc<RefCell<Module>>>,
-    ) = modules_and_receivers
-        .into_iter()
-        .map(|(module, receivers)| {
+    ) = declarations
+        .iter()
+        .map(|declaration| {
+            let module = Module {
+                name: String::from(declaration.name),
+                receivers: vec![],
+                module_type: declaration.module_type.clone(),
+            };
             (
-                (module.name.clone(), receivers),
+                (module.name.clone(), declaration.receivers.clone()),
                 (module.name.clone(), Rc::new(RefCell::new(module))),
             )
         })
@@ -42,7 +108,7 @@ pub fn process(input: &str) -> u64 {
                 None => {
                     {
                         let new_module = Rc::new(RefCell::new(Module {
-                            name: module_name.clone(),
+                            name: String::from(receiver_name),
                             receivers: vec![],
                             module_type: ModuleType::Conjunction {
                                 state: HashMap::new(),
@@ -58,64 +124,29 @@ pub fn process(input: &str) -> u64 {
         }
     }
 
-    let first_module = &name_module_map["broadcaster"].clone();
-    let mut destination_module_names = vec![String::from("rx")];
-    while destination_module_names.len() <= 1 {
-        destination_module_names = get_upstream_machine_names(&name_module_map, destination_module_names[0].clone());
-    } 
-
-    dbg!(&destination_module_names);
-
-    let loop_counts = destination_module_names.into_iter()
-        .map(|destination_machine_name| {
-            for (_, module) in name_module_map.iter() {
-                module.borrow_mut().reset()
-            }
-            get_button_presses(first_module, destination_machine_name.clone())
-        })
-        .collect::<Vec<_>>();
-    dbg!(&loop_counts);
-    loop_counts.into_iter().map(u64::from).reduce(lcm).unwrap()
-    
-}
-
-fn lcm(first: u64, second: u64) -> u64 {
-    first * second / gcd(first, second)
-}
-
-fn gcd(first: u64, second: u64) -> u64 {
-    let mut max = first;
-    let mut min = second;
-    if min > max {
-        std::mem::swap(&mut min, &mut max);
-    }
-
-    loop {
-        let res = max % min;
-        if res == 0 {
-            return min;
-        }
-
-        max = min;
-        min = res;
-    }
+    name_module_map
 }
 
-fn get_upstream_machine_names(name_module_map: &HashMap<String, Rc<RefCell<Module>>>, destination_module_name: String) -> Vec<String> {
-    let destination_module = name_module_map[&destination_module_name].borrow();
+/// The modules which send pulses to `destination_module_name`. Only a conjunction remembers its
+/// senders, and only a conjunction lines up their cycles.
+fn get_upstream_machine_names(name_module_map: &HashMap<String, Rc<RefCell<Module>>>, destination_module_name: &str) -> Result<Vec<String>, Error> {
+    let destination_module = name_module_map[destination_module_name].borrow();
     let ModuleType::Conjunction {
         state: ref destination_machine_names,
     } = destination_module.module_type
     else {
-        panic!("Cannot find the '{}' module.", destination_module_name);
+        return Err(Error::NoSolution(format!("{} feeds rx but is not a conjunction", destination_module_name)));
     };
-    destination_machine_names.keys().cloned().collect()
+    if destination_machine_names.is_empty() {
+        return Err(Error::NoSolution(format!("no module sends pulses to {}", destination_module_name)));
+    }
+    Ok(destination_machine_names.keys().cloned().collect())
 }
 
 fn get_button_presses(
     first_module: &Rc<RefCell<Module>>,
     destination_machine_name: String,
-) -> u32 {
+) -> Result<u32, Error> {
     let mut queue = VecDeque::new();
     let mut button_presses = 1;
     'outer: loop {
@@ -124,7 +155,9 @@ fn get_button_presses(
             receiver: first_module.clone(),
             transmitter_name: String::from("button"),
         });
+        // one press can set off pulses which never die down, so each pulse is a step
         while !queue.is_empty() {
+            budget::tick()?;
             let signal = &queue
                 .pop_front()
                 .expect("Queue can't be empty, as while loop would have exited");
@@ -142,7 +175,7 @@ fn get_button_presses(
         }
         button_presses += 1;
     }
-    button_presses
+    Ok(button_presses)
 }
 
 #[derive(Eq, PartialEq, Debug, Clone, Copy)]
@@ -179,13 +212,20 @@ impl Debug for Signal {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 enum ModuleType {
     FlipFlop { state: Pulse },
     Conjunction { state: HashMap<String, Pulse> },
     Broadcaster,
 }
 
+/// A module as the input declares it: its name, its type, and the modules it sends pulses to.
+pub struct Declaration<'a> {
+    name: &'a str,
+    module_type: ModuleType,
+    receivers: Vec<&'a str>,
+}
+
 struct Module {
     name: String,
     receivers: Vec<Rc<RefCell<Module>>>,
@@ -245,19 +285,6 @@ impl Module {
             .collect()
     }
 
-    fn reset(&mut self) {
-        match self.module_type {
-            ModuleType::FlipFlop { ref mut state } => *state = Pulse::Low,
-            ModuleType::Conjunction { ref mut state } => {
-                let module_names = state.keys().cloned().collect::<Vec<_>>();
-                for module_name in module_names {
-                    state.insert(module_name.clone(), Pulse::Low);
-                }
-            },
-            ModuleType::Broadcaster => (),
-        }
-    }
-
     fn register_input(&mut self, receiver_name: String) {
         if let ModuleType::Conjunction { ref mut state, .. } = self.module_type {
             state.insert(receiver_name, Pulse::Low);
@@ -265,66 +292,124 @@ impl Module {
     }
 
     fn add_receiver(&mut self, module: Rc<RefCell<Module>>) {
-        module.borrow_mut().register_input(self.name.clone());
+        // the only module already borrowed is this one, when it sends pulses to itself
+        match module.try_borrow_mut() {
+            Ok(mut receiver) => receiver.register_input(self.name.clone()),
+            Err(_) => self.register_input(self.name.clone()),
+        }
         self.receivers.push(module);
     }
 }
 
-fn modules(input: &str) -> IResult<&str, Vec<(Module, Vec<&str>)>> {
-    let (input, modules_and_receivers) =
+fn modules<'a>(input: Span<'a>) -> ParseResult<'a, Vec<Declaration<'a>>> {
+    let (input, declarations) =
         separated_list1(newline, alt((flip_flop, conjunction, broadcaster)))(input)?;
 
-    Ok((input, modules_and_receivers))
+    Ok((input, declarations))
 }
 
-fn broadcaster(input: &str) -> IResult<&str, (Module, Vec<&str>)> {
-    let (input, _) = tag("broadcaster -> ")(input)?;
-    let (input, outputs) = separated_list1(tag(", "), alpha1)(input)?;
+/// Parses the modules like [`modules`], but reports anything left over, or a missing
+/// broadcaster, as an error.
+fn try_modules(input: &str) -> Result<Vec<Declaration<'_>>, Error> {
+    let (rest, declarations) = parse_prefix(input, modules)?;
+    if !rest.fragment().trim().is_empty() {
+        return Err(Error::parse(input, rest.fragment().trim_start(), "a module"));
+    }
+    if !declarations.iter().any(|declaration| declaration.name == "broadcaster") {
+        return Err(Error::MissingStart("the broadcaster".to_string()));
+    }
+    Ok(declarations)
+}
+
+fn broadcaster<'a>(input: Span<'a>) -> ParseResult<'a, Declaration<'a>> {
+    let (input, outputs) =
+        context("broadcaster", preceded(tag("broadcaster -> "), cut(names)))(input)?;
     Ok((
         input,
-        (
-            Module {
-                name: String::from("broadcaster"),
-                receivers: vec![],
-                module_type: ModuleType::Broadcaster,
-            },
-            outputs,
-        ),
+        Declaration {
+            name: "broadcaster",
+            module_type: ModuleType::Broadcaster,
+            receivers: outputs,
+        },
     ))
 }
 
-fn flip_flop(input: &str) -> IResult<&str, (Module, Vec<&str>)> {
-    let (input, _) = tag("%")(input)?;
-    let (input, (name, outputs)) =
-        separated_pair(alpha1, tag(" -> "), separated_list1(tag(", "), alpha1))(input)?;
+fn flip_flop<'a>(input: Span<'a>) -> ParseResult<'a, Declaration<'a>> {
+    let (input, (name, outputs)) = context(
+        "flip-flop",
+        preceded(tag("%"), cut(separated_pair(alpha1, tag(" -> "), names))),
+    )(input)?;
     Ok((
         input,
-        (
-            Module {
-                name: String::from(name),
-                receivers: vec![],
-                module_type: ModuleType::FlipFlop { state: Pulse::Low },
-            },
-            outputs,
-        ),
+        Declaration {
+            name: name.fragment(),
+            module_type: ModuleType::FlipFlop { state: Pulse::Low },
+            receivers: outputs,
+        },
     ))
 }
 
-fn conjunction(input: &str) -> IResult<&str, (Module, Vec<&str>)> {
-    let (input, _) = tag("&")(input)?;
-    let (input, (name, outputs)) =
-        separated_pair(alpha1, tag(" -> "), separated_list1(tag(", "), alpha1))(input)?;
+fn conjunction<'a>(input: Span<'a>) -> ParseResult<'a, Declaration<'a>> {
+    let (input, (name, outputs)) = context(
+        "conjunction",
+        preceded(tag("&"), cut(separated_pair(alpha1, tag(" -> "), names))),
+    )(input)?;
     Ok((
         input,
-        (
-            Module {
-                name: String::from(name),
-                receivers: vec![],
-                module_type: ModuleType::Conjunction {
-                    state: HashMap::from([]),
-                },
+        Declaration {
+            name: name.fragment(),
+            module_type: ModuleType::Conjunction {
+                state: HashMap::from([]),
             },
-            outputs,
-        ),
+            receivers: outputs,
+        },
     ))
 }
+
+/// The names of the modules which a module sends pulses to.
+fn names<'a>(input: Span<'a>) -> ParseResult<'a, Vec<&'a str>> {
+    let (input, names) = separated_list1(tag(", "), alpha1)(input)?;
+    Ok((input, names.iter().map(|name| *name.fragment()).collect()))
+}
+
+pub struct Part2;
+
+impl Solution for Part2 {
+    const DAY: u8 = 20;
+    const PART: u8 = 2;
+
+    type Input<'a> = Vec<Declaration<'a>>;
+    type Params = ();
+
+    fn parse(input: &str) -> Result<Vec<Declaration<'_>>, Error> {
+        try_modules(input)
+    }
+
+    fn solve(input: Vec<Declaration<'_>>, _: &()) -> Result<Answer, Error> {
+        button_presses(&input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("broadcaster -> a\n%a -> b\n&b -> rx\n", "no solution: a feeds rx but is not a conjunction")]
+    #[case("broadcaster -> a\n%a -> c\n&b -> rx\n", "no solution: no module sends pulses to b")]
+    #[case("broadcaster -> a\n%a -> c\n&b -> b, rx\n", "no solution: the modules feeding rx loop back to b")]
+    fn test_try_process(#[case] input: &str, #[case] expected: &str) {
+        assert_eq!(try_process(input).unwrap_err().to_string(), expected);
+    }
+
+    #[test]
+    fn test_budget() {
+        // d is never sent a pulse, and each press sets a pulsing back and forth through a for ever
+        let input = "broadcaster -> a, c\n&a -> a\n&c -> rx\n%d -> c\n";
+        assert!(matches!(
+            Budget::unlimited().with_max_steps(1000).run(|| try_process(input)),
+            Err(Error::BudgetExceeded { .. })
+        ));
+    }
+}
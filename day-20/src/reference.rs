@@ -0,0 +1,126 @@
+//! Slow but obviously correct solutions, to check the real ones against on small inputs.
+
+use std::collections::{HashMap, VecDeque};
+
+/// Every module's type and outputs, and each conjunction's memory of its inputs.
+struct Modules<'a> {
+    kinds: HashMap<&'a str, char>,
+    outputs: HashMap<&'a str, Vec<&'a str>>,
+    on: HashMap<&'a str, bool>,
+    memory: HashMap<&'a str, HashMap<&'a str, bool>>,
+}
+
+impl<'a> Modules<'a> {
+    fn new(input: &'a str) -> Self {
+        let mut modules = Modules {
+            kinds: HashMap::new(),
+            outputs: HashMap::new(),
+            on: HashMap::new(),
+            memory: HashMap::new(),
+        };
+        for line in input.lines() {
+            let (module, outputs) = line.split_once(" -> ").unwrap();
+            let (kind, name) = match module.strip_prefix(['%', '&']) {
+                Some(name) => (module.chars().next().unwrap(), name),
+                None => ('b', module),
+            };
+            modules.kinds.insert(name, kind);
+            modules.outputs.insert(name, outputs.split(", ").collect());
+        }
+        for (name, outputs) in &modules.outputs {
+            for output in outputs {
+                modules
+                    .memory
+                    .entry(output)
+                    .or_default()
+                    .insert(name, false);
+            }
+        }
+        modules
+    }
+
+    /// Pushes the button, calling `sent` with every pulse in the order they are sent.
+    fn push(&mut self, mut sent: impl FnMut(&str, &str, bool)) {
+        let mut queue = VecDeque::from([("button", "broadcaster", false)]);
+        while let Some((from, to, high)) = queue.pop_front() {
+            sent(from, to, high);
+            let pulse = match self.kinds.get(to) {
+                Some('b') => high,
+                Some('%') if !high => {
+                    let on = self.on.entry(to).or_default();
+                    *on = !*on;
+                    *on
+                }
+                Some('&') => {
+                    let memory = self.memory.get_mut(to).unwrap();
+                    memory.insert(from, high);
+                    !memory.values().all(|high| *high)
+                }
+                _ => continue,
+            };
+            for output in &self.outputs[to] {
+                queue.push_back((to, *output, pulse));
+            }
+        }
+    }
+}
+
+pub fn part1(input: &str) -> u64 {
+    let mut modules = Modules::new(input);
+    let (mut low, mut high) = (0, 0);
+    for _ in 0..1000 {
+        modules.push(|_, _, pulse| if pulse { high += 1 } else { low += 1 });
+    }
+    low * high
+}
+
+/// Pushes the button until rx gets a low pulse.
+pub fn part2(input: &str) -> u64 {
+    let mut modules = Modules::new(input);
+    let mut pushes = 0;
+    loop {
+        pushes += 1;
+        let mut done = false;
+        modules.push(|_, to, high| done |= to == "rx" && !high);
+        if done {
+            return pushes;
+        }
+    }
+}
+
+mod tests {
+    use aoclib::gen::{self, Rng};
+    use aoclib::reference::assert_agrees;
+    use proptest::prelude::*;
+
+    use super::*;
+    use crate::part1::Part1;
+    use crate::part2::Part2;
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(32))]
+
+        #[test]
+        fn test_part1(seed: u64, size in 1..5usize) {
+            let input = gen::generator(20).unwrap().generate(size, seed);
+            assert_agrees::<Part1>(&input, &[], part1(&input));
+        }
+
+        // Counters of five flip-flops reach their primes soon enough to push the button until
+        // they all do.
+        #[test]
+        fn test_part2(seed: u64, counters in 2..4usize) {
+            let input = gen::counter_network(&mut Rng::new(seed), counters, 5);
+            assert_agrees::<Part2>(&input, &[], part2(&input));
+        }
+    }
+
+    // Part 2 looks upstream of rx for the first module fed by more than one other, so with a
+    // single counter it goes past the conjunction and counts the pushes to its flip-flops.
+    #[test]
+    #[should_panic(expected = "disagrees with the reference")]
+    fn test_part2_single_counter() {
+        let input = gen::counter_network(&mut Rng::new(0), 1, 5);
+        assert_agrees::<Part2>(&input, &[], part2(&input));
+    }
+}
@@ -1,7 +1,9 @@
+use aoclib::input::{self, InputError};
 use day_21::part1::process;
 
-fn main() {
-    let file = include_str!("../../input.txt");
-    let result = process(file, 64);
+fn main() -> Result<(), InputError> {
+    let file = input::load(21, 1)?;
+    let result = process(&file, 64);
     println!("{}", result);
-}
\ No newline at end of file
+    Ok(())
+}
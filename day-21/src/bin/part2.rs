@@ -1,7 +1,9 @@
+use aoclib::input::{self, InputError};
 use day_21::part2::process;
 
-fn main() {
-    let file = include_str!("../../input.txt");
-    let result = process(file, 26501365);
+fn main() -> Result<(), InputError> {
+    let file = input::load(21, 2)?;
+    let result = process(&file, 26501365);
     println!("{}", result);
-}
\ No newline at end of file
+    Ok(())
+}
@@ -0,0 +1,17 @@
+use glam::IVec2;
+
+use aoclib::{Error, Grid};
+
+/// The garden's plots and rocks, and the plot the elf starts on.
+pub struct Garden {
+    pub plots: Grid<char>,
+    pub start: IVec2,
+}
+
+/// Parses `input` as a rectangle of plots and rocks with a start, which the parts otherwise
+/// assume.
+pub fn parse_garden(input: &str) -> Result<Garden, Error> {
+    let plots = Grid::try_parse(input, "'.', '#' or 'S'", |c| matches!(c, '.' | '#' | 'S').then_some(c))?;
+    let start = plots.find(|&c| c == 'S').ok_or_else(|| Error::MissingStart("S".to_string()))?;
+    Ok(Garden { plots, start })
+}
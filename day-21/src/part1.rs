@@ -2,68 +2,84 @@ use std::{collections::HashSet, iter::from_fn};
 
 use glam::IVec2;
 
+use aoclib::params::ParamError;
+use aoclib::grid::NEIGHBOURS_4;
+use aoclib::{Answer, Error, FromParams, Grid, Params, Solution};
+
+use crate::parse::{parse_garden, Garden};
+
 pub fn process(
     input: &str, steps: usize
 ) -> usize {
-    let mut start: Option<IVec2> = None;
+    plots_reached(&parse_garden(input).expect("should parse"), steps)
+}
 
-    let garden_plots: HashSet<_> = garden(input).filter_map(|(location, c)| match c {
-        '.' => Some(location),
-        'S' => {
-            start = Some(location);
-            Some(location)
-        }
-        _ => None
-    }).collect();
+/// Like [`process`], but reports a garden which is not a rectangle of plots and rocks, or which
+/// has no start, as an error.
+pub fn try_process(input: &str, steps: usize) -> Result<Answer, Error> {
+    Ok(plots_reached(&parse_garden(input)?, steps).into())
+}
 
+fn plots_reached(garden: &Garden, steps: usize) -> usize {
+    let Garden { plots: garden, start } = garden;
 
-    let mut current_neighbours = HashSet::from([start.expect("There should be a start position.")]);
+    let mut current_neighbours = HashSet::from([*start]);
     from_fn(|| {
         current_neighbours = current_neighbours
             .iter()
-            .flat_map(|neighbour| neighbours(neighbour, &garden_plots ).collect::<HashSet<_>>())
+            .flat_map(|neighbour| neighbours(neighbour, garden).collect::<HashSet<_>>())
             // .inspect(|x| { dbg!(x); })
             .collect::<HashSet<_>>();
         Some(current_neighbours.len())
-    }).take(steps).last().unwrap()
+    // without taking a step, the elf is only on the start
+    }).take(steps).last().unwrap_or(1)
 }
 
-fn garden(input: &str) -> impl Iterator<Item = (IVec2, char)> + '_{
-    input.lines().enumerate().flat_map(|(y, line)|{
-        line.chars().enumerate().map(move |(x, c)|{
-            (IVec2{x: x.try_into().unwrap(), y: y.try_into().unwrap()}, c)
-        })
-    })
+fn neighbours<'a>(location: &'a IVec2, garden: &'a Grid<char>) -> impl Iterator<Item = IVec2> + 'a {
+    NEIGHBOURS_4
+        .into_iter()
+        .map(|offset| *location + offset)
+        .filter(|location| garden.get(*location).is_some_and(|&c| c != '#'))
 }
 
-fn neighbours<'a>(location: &'a IVec2, garden_plots: &'a HashSet<IVec2> ) -> impl Iterator<Item = IVec2> + 'a {
-    vec![
-        IVec2{x: location.x + 1, ..*location},
-        IVec2{y: location.y + 1, ..*location},
-        IVec2{x: location.x - 1, ..*location},
-        IVec2{y: location.y - 1, ..*location},
-    ].into_iter().filter(|location| {garden_plots.contains(location)})
+/// The number of steps the elf takes.
+pub struct Steps(pub usize);
+
+impl FromParams for Steps {
+    const DEFAULTS: &'static [(&'static str, &'static str)] = &[("steps", "64")];
+
+    fn from_params(params: &Params) -> Result<Self, ParamError> {
+        Ok(Steps(params.get("steps")?))
+    }
 }
 
+pub struct Part1;
+
+impl Solution for Part1 {
+    const DAY: u8 = 21;
+    const PART: u8 = 1;
+
+    type Input<'a> = Garden;
+    type Params = Steps;
+
+    fn parse(input: &str) -> Result<Garden, Error> {
+        parse_garden(input)
+    }
+
+    fn solve(input: Garden, params: &Steps) -> Result<Answer, Error> {
+        Ok(plots_reached(&input, params.0).into())
+    }
+}
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
-    fn test_process() {
-        let lines = "...........
-.....###.#.
-.###.##..#.
-..#.#...#..
-....#.#....
-.##..S####.
-.##..#...#.
-.......##..
-.##.#.####.
-.##..##.##.
-...........";
-        let result = process(lines, 6);
-        assert_eq!(result, 16);
+    fn test_try_process() {
+        assert_eq!(
+            try_process("...\n.#.\n...\n", 6).unwrap_err(),
+            Error::MissingStart("S".to_string())
+        );
     }
 }
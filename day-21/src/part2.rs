@@ -1,37 +1,40 @@
 use std::collections::HashSet;
 
 use glam::IVec2;
+use tracing::debug;
+
+use aoclib::params::ParamError;
+use aoclib::grid::NEIGHBOURS_4;
+use aoclib::{Answer, Error, FromParams, Grid, Params, Solution};
+
+use crate::parse::{parse_garden, Garden};
 
 pub fn process(input: &str, steps: i32) -> i64 {
-    let dimension = IVec2 {
-        y: input.lines().count() as i32,
-        x: input.lines().next().unwrap().len() as i32,
-    };
-
-    let mut start: Option<IVec2> = None;
-
-    let garden_plots: HashSet<_> = garden(input)
-        .filter_map(|(location, c)| match c {
-            '.' => Some(location),
-            'S' => {
-                start = Some(location);
-                Some(location)
-            }
-            _ => None,
-        })
-        .collect();
+    plots_reached(&parse_garden(input).expect("should parse"), steps)
+        .expect("there should be enough steps to extrapolate from")
+}
+
+/// Like [`process`], but reports a garden which is not a rectangle of plots and rocks, which has
+/// no start, or which is crossed too few times in `steps` to extrapolate from, as an error.
+pub fn try_process(input: &str, steps: i32) -> Result<Answer, Error> {
+    Ok(plots_reached(&parse_garden(input)?, steps)?.into())
+}
+
+fn plots_reached(garden: &Garden, steps: i32) -> Result<i64, Error> {
+    let Garden { plots: garden, start } = garden;
+    let dimension = garden.dimensions();
 
     let mut points = vec![];
     let mut current_neighbours: Box<dyn Iterator<Item = IVec2>> = Box::new(
-        vec![start.expect("should be a start node")]
+        vec![*start]
             .into_iter()
-            .flat_map(|neighbour| neighbours(neighbour, &garden_plots, &dimension)),
+            .flat_map(|neighbour| neighbours(neighbour, garden)),
     );
     let mut num_points = 0;
     
     for i in 2..steps {
         current_neighbours = Box::new(current_neighbours
-            .flat_map(|neighbour| neighbours(neighbour, &garden_plots, &dimension))
+            .flat_map(|neighbour| neighbours(neighbour, garden))
             .collect::<HashSet<_>>()
             .into_iter()
             // .inspect(|x| { print!("{}", x); } )
@@ -51,68 +54,59 @@ pub fn process(input: &str, steps: i32) -> i64 {
             }
         }
     }
-    dbg!(&points);
+    debug!(?points, "extrapolating from the plots reached");
     quadratic_extrapolate(points.into_iter(), steps / dimension.y)
+        .ok_or_else(|| Error::NoSolution("the steps cross the garden too few times to extrapolate from".to_string()))
 }
 
-fn quadratic_extrapolate(mut points: impl Iterator<Item = IVec2>, x: i32 ) -> i64 {
-    let y0 = points.next().expect("Quadratic extrapolation needs at least three points").y as i64;
-    let y1 = points.next().expect("Quadratic extrapolation needs at least three points").y as i64;
-    let y2 = points.next().expect("Quadratic extrapolation needs at least three points").y as i64;
+/// Extrapolates the quadratic through three points to `x`, or `None` if there are fewer.
+fn quadratic_extrapolate(mut points: impl Iterator<Item = IVec2>, x: i32 ) -> Option<i64> {
+    let y0 = points.next()?.y as i64;
+    let y1 = points.next()?.y as i64;
+    let y2 = points.next()?.y as i64;
 
     let a = (y2 + y0 - 2*y1) / 2;
     let b = y1 - y0 - a;
     let c = y0;
 
     let x = x as i64;
-    a * x * x + b * x + c
+    Some(a * x * x + b * x + c)
+}
+
+fn neighbours(location: IVec2, garden: &Grid<char>) -> impl Iterator<Item = IVec2> + '_ {
+    NEIGHBOURS_4
+        .into_iter()
+        .map(move |offset| location + offset)
+        .filter(|location| *garden.get_wrapped(*location) != '#')
 }
 
-fn garden(input: &str) -> impl Iterator<Item = (IVec2, char)> + '_ {
-    input.lines().enumerate().flat_map(|(y, line)| {
-        line.chars().enumerate().map(move |(x, c)| {
-            (
-                IVec2 {
-                    x: x.try_into().unwrap(),
-                    y: y.try_into().unwrap(),
-                },
-                c,
-            )
-        })
-    })
+/// The number of steps the elf takes.
+pub struct Steps(pub i32);
+
+impl FromParams for Steps {
+    const DEFAULTS: &'static [(&'static str, &'static str)] = &[("steps", "26501365")];
+
+    fn from_params(params: &Params) -> Result<Self, ParamError> {
+        Ok(Steps(params.get("steps")?))
+    }
 }
 
-fn neighbours<'a>(
-    location: IVec2,
-    garden_plots: &'a HashSet<IVec2>,
-    dimension: &'a IVec2,
-) -> impl Iterator<Item = IVec2> + 'a {
-    vec![
-        IVec2 {
-            x: location.x + 1,
-            ..location
-        },
-        IVec2 {
-            y: location.y + 1,
-            ..location
-        },
-        IVec2 {
-            x: location.x - 1,
-            ..location
-        },
-        IVec2 {
-            y: location.y - 1,
-            ..location
-        },
-    ]
-    .into_iter()
-    .filter(|location| {
-        let reduced_location = IVec2 {
-            x: location.x.rem_euclid(dimension.x),
-            y: location.y.rem_euclid(dimension.y),
-        };
-        garden_plots.contains(&reduced_location)
-    })
+pub struct Part2;
+
+impl Solution for Part2 {
+    const DAY: u8 = 21;
+    const PART: u8 = 2;
+
+    type Input<'a> = Garden;
+    type Params = Steps;
+
+    fn parse(input: &str) -> Result<Garden, Error> {
+        parse_garden(input)
+    }
+
+    fn solve(input: Garden, params: &Steps) -> Result<Answer, Error> {
+        Ok(plots_reached(&input, params.0)?.into())
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,77 @@
+//! Slow but obviously correct solutions, to check the real ones against on small inputs.
+
+use std::collections::HashSet;
+
+/// Takes every step from every plot reached so far, and counts the plots reached after the
+/// last. The map is repeated in every direction if `repeated`, and otherwise ends at its edges.
+fn plots(input: &str, steps: usize, repeated: bool) -> usize {
+    let map: Vec<Vec<u8>> = input.lines().map(|line| line.bytes().collect()).collect();
+    let (width, height) = (map[0].len() as i64, map.len() as i64);
+    let start = (0..height)
+        .flat_map(|y| (0..width).map(move |x| (x, y)))
+        .find(|(x, y)| map[*y as usize][*x as usize] == b'S')
+        .unwrap();
+    let mut reached = HashSet::from([start]);
+    for _ in 0..steps {
+        reached = reached
+            .iter()
+            .flat_map(|(x, y)| [(x + 1, *y), (x - 1, *y), (*x, y + 1), (*x, y - 1)])
+            .filter(|(x, y)| repeated || ((0..width).contains(x) && (0..height).contains(y)))
+            .filter(|(x, y)| {
+                map[y.rem_euclid(height) as usize][x.rem_euclid(width) as usize] != b'#'
+            })
+            .collect();
+    }
+    reached.len()
+}
+
+pub fn part1(input: &str, steps: usize) -> usize {
+    plots(input, steps, false)
+}
+
+pub fn part2(input: &str, steps: usize) -> usize {
+    plots(input, steps, true)
+}
+
+mod tests {
+    use aoclib::gen;
+    use aoclib::reference::assert_agrees;
+    use proptest::prelude::*;
+
+    use super::*;
+    use crate::part1::Part1;
+    use crate::part2::Part2;
+
+    proptest! {
+        #[test]
+        fn test_part1(seed: u64, size in 1..8usize, steps in 1..8usize) {
+            let input = gen::generator(21).unwrap().generate(size, seed);
+            assert_agrees::<Part1>(&input, &[("steps", &steps.to_string())], part1(&input, steps));
+        }
+
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(32))]
+
+        // Part 2 extrapolates from the plots reached after three step counts a whole number of
+        // copies of the map apart, the first of them less than a copy in.
+        #[test]
+        fn test_part2(seed: u64, size in 1..6usize, copies in 3..6usize, extra: usize) {
+            let input = gen::generator(21).unwrap().generate(size, seed);
+            let side = 2 * size + 1;
+            let steps = copies * side + 2 + extra % (side - 2);
+            assert_agrees::<Part2>(&input, &[("steps", &steps.to_string())], part2(&input, steps));
+        }
+    }
+
+    // It only counts the plots from the second step on, so when the steps are a whole number of
+    // copies of the map and one or none over, its first count is a copy further out than it
+    // extrapolates from.
+    #[test]
+    #[should_panic(expected = "disagrees with the reference")]
+    fn test_part2_whole_copies() {
+        let input = gen::generator(21).unwrap().generate(2, 0);
+        assert_agrees::<Part2>(&input, &[("steps", "20")], part2(&input, 20));
+    }
+}
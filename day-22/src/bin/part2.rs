@@ -1,10 +1,12 @@
 use std::time::Instant;
 
+use aoclib::input::{self, InputError};
 use day_22::part2::process;
 
-fn main() {
-    let file = include_str!("../../input.txt");
+fn main() -> Result<(), InputError> {
+    let file = input::load(22, 2)?;
     let now = Instant::now();
-    let result = process(file);
+    let result = process(&file);
     println!("{} : {}s", result, now.elapsed().as_secs());
-}
\ No newline at end of file
+    Ok(())
+}
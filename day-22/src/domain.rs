@@ -20,9 +20,9 @@ impl Brick {
         }
     }
 
-    pub fn move_down(&mut self) {
-        if self.z.start == 1 { panic!("This brick is on the floor!") }
-        self.z = self.z.start - 1 .. self.z.end - 1
+    /// Moves the brick down so that its bottom is at height `z`.
+    pub fn drop_to(&mut self, z: u32) {
+        self.z = z .. z + self.z.len() as u32
     }
 }
 
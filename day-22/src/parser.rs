@@ -1,23 +1,40 @@
 use std::{cell::RefCell, rc::Rc};
 
-use nom::{character::complete::{self, newline}, bytes::complete::tag, IResult, sequence::Tuple, multi::separated_list1};
+use nom::{character::complete, sequence::{tuple, Tuple}, error::context};
 use glam::UVec3;
 use crate::domain::{Brick, Bricks, BrickRef};
 
+use aoclib::parse::{lines, parse_all, tag, ParseError, ParseResult, Span};
+use aoclib::Error;
 
-pub fn bricks(input: &str) -> IResult<&str, Bricks> {
-    let (input, brick_list) = separated_list1(newline, brick)(input)?;
+
+pub fn bricks(input: Span) -> ParseResult<Bricks> {
+    let (input, brick_list) = lines(brick)(input)?;
     Ok((input, brick_list.into_iter().map(|brick| BrickRef(Rc::new(RefCell::new(brick)))).collect()))
 }
 
+/// Parses every line of `input` as a whole brick.
+pub fn parse_bricks(input: &str) -> Result<Bricks, Error> {
+    parse_all(input, bricks)
+}
+
 // 1,0,1~1,2,1
-fn brick(input: &str) -> IResult<&str, Brick> {
-    let (input, (start, _, end)) = (uvec3, tag("~"), uvec3).parse(input)?;
-    Ok((input, Brick::new(start, end)))
+fn brick(input: Span) -> ParseResult<Brick> {
+    let (rest, (start, _, end)) = context("brick", tuple((uvec3, tag("~"), uvec3)))(input)?;
+    let expected = if start.z == 0 {
+        "a brick above the ground"
+    } else if !start.cmple(end).all() {
+        "a brick which ends no lower than it starts"
+    } else if end.max_element() == u32::MAX {
+        "a brick which ends below 4294967295"
+    } else {
+        return Ok((rest, Brick::new(start, end)));
+    };
+    Err(nom::Err::Error(ParseError::new(input, expected)))
 }
 
 // 1,0,1
-fn uvec3(input: &str) -> IResult<&str, UVec3> {
+fn uvec3(input: Span) -> ParseResult<UVec3> {
     let (input, (x,_,y,_,z)) = (complete::u32, tag(","), complete::u32, tag(","), complete::u32).parse(input)?;
     Ok((input, UVec3 { x, y, z }))
 }
@@ -49,8 +66,8 @@ mod tests {
         #[case] input: &str,
         #[case] expected: Vec<BrickRef>
     ) {
-        let (input, result) = bricks(input).expect("Should parse");
-        assert_eq!(input, "");
+        let (input, result) = bricks(Span::new(input)).expect("Should parse");
+        assert_eq!(*input.fragment(), "");
         assert_eq!(result, expected);
     }
 
@@ -66,8 +83,8 @@ mod tests {
         #[case] input: &str,
         #[case] expected: Brick
     ) {
-        let (input, result) = brick(input).expect("Should parse");
-        assert_eq!(input, "");
+        let (input, result) = brick(Span::new(input)).expect("Should parse");
+        assert_eq!(*input.fragment(), "");
         assert_eq!(result, expected);
     }
 
@@ -90,9 +107,29 @@ mod tests {
         #[case] input: &str,
         #[case] expected: UVec3
     ) {
-        let (input, result) = uvec3(input).expect("Should parse");
-        assert_eq!(input, "");
+        let (input, result) = uvec3(Span::new(input)).expect("Should parse");
+        assert_eq!(*input.fragment(), "");
         assert_eq!(result, expected);
     }
 
-}
\ No newline at end of file
+    #[test]
+    fn test_parse_bricks_error() {
+        assert_eq!(
+            parse_bricks("1,0,1~1,2,1\n0,0,2~2,0\n").map(|_| ()),
+            Err(Error::Parse {
+                line: 2,
+                column: 10,
+                snippet: "".to_string(),
+                expected: "\",\" in brick".to_string(),
+            })
+        );
+        assert_eq!(
+            parse_bricks("1,0,1~1,2,1\n0,0,0~2,0,0\n").unwrap_err().to_string(),
+            "line 2, column 1: expected a brick above the ground, found \"0,0,0~2,0,0\""
+        );
+        assert_eq!(
+            parse_bricks("1,2,1~1,0,1\n").unwrap_err().to_string(),
+            "line 1, column 1: expected a brick which ends no lower than it starts, found \"1,2,1~1,0,1\""
+        );
+    }
+}
@@ -2,15 +2,25 @@ use std::cmp::{min, max};
 use std::ops::Range;
 use glam::UVec3;
 
-use crate::parser::bricks;
+use crate::parser::parse_bricks;
 use crate::domain::{Brick, Bricks, BrickRef};
 
+use aoclib::{Answer, Error, Solution};
+
 pub fn process(
     input: &str,
 ) -> u32 {
-    let (_, bricks): (_, Bricks) = bricks(input).expect("should parse");
+    vapourisable_bricks(&parse_bricks(input).expect("should parse"))
+}
+
+/// Like [`process`], but reports a line which is not a brick as an error.
+pub fn try_process(input: &str) -> Result<Answer, Error> {
+    Ok(vapourisable_bricks(&parse_bricks(input)?).into())
+}
+
+fn vapourisable_bricks(bricks: &Bricks) -> u32 {
     
-    stabilize(&bricks);
+    stabilize(bricks);
     
     let mut vapourisable_bricks_count = 0;
     for brick in bricks.iter() {
@@ -28,12 +38,26 @@ fn stabilize(bricks: &Bricks) {
         let mut newer_bricks = bricks.clone();
         for brick in newer_bricks.iter_mut() {
             if !is_supported(brick.clone(), bricks) {
-                brick.borrow_mut().move_down();
+                let z = resting_height(brick, bricks);
+                brick.borrow_mut().drop_to(z);
             }
         }
     }
 }
 
+/// The height of the top of the highest brick under `brick`, which it would fall onto, or of the
+/// ground if there is no brick under it.
+fn resting_height(brick: &BrickRef, bricks: &Bricks) -> u32 {
+    let falling = brick.borrow();
+    bricks.iter()
+        .filter(|other| *other != brick)
+        .map(|other| other.borrow())
+        .filter(|other| other.z.end <= falling.z.start && overlap(&other.x, &falling.x) && overlap(&other.y, &falling.y))
+        .map(|other| other.z.end)
+        .max()
+        .unwrap_or(1)
+}
+
 fn space_below_brick(brick: BrickRef) -> Option<Brick> {
     let brick = brick.borrow().clone();
     if brick.z.start == 1 { return None };
@@ -94,24 +118,29 @@ fn bounds<'a>(bricks: impl Iterator<Item = &'a Brick>) -> (UVec3, UVec3) {
     (minimum, maximum)
 }
 
+pub struct Part1;
+
+impl Solution for Part1 {
+    const DAY: u8 = 22;
+    const PART: u8 = 1;
+
+    type Input<'a> = Bricks;
+    type Params = ();
+
+    fn parse(input: &str) -> Result<Bricks, Error> {
+        parse_bricks(input)
+    }
+
+    fn solve(input: Bricks, _: &()) -> Result<Answer, Error> {
+        Ok(vapourisable_bricks(&input).into())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use rstest::rstest;
 
-    #[test]
-    fn test_process() {
-        let lines = "1,0,1~1,2,1
-0,0,2~2,0,2
-0,2,3~2,2,3
-0,0,4~0,2,4
-2,0,5~2,2,5
-0,1,6~2,1,6
-1,1,8~1,1,9";
-        let result = process(lines);
-        assert_eq!(result, 5);
-    }
-
     #[rstest]
     #[case(0..6, 0..6, true)]
     #[case(0..1, 0..1, true)]
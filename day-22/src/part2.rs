@@ -2,17 +2,29 @@ use std::cmp::{min, max};
 use std::collections::{HashSet, HashMap};
 use std::ops::{Range, Not};
 
-use glam::UVec3;
+use glam::{IVec2, UVec3};
+use tracing::{debug, trace, trace_span};
 
-use crate::parser::bricks;
+use crate::parser::parse_bricks;
 use crate::domain::{Brick, Bricks, BrickRef};
 
+use aoclib::viz::{Colour, Frame};
+use aoclib::{Answer, Error, Solution};
+
 pub fn process(
     input: &str,
 ) -> u32 {
-    let (_, bricks): (_, Bricks) = bricks(input).expect("should parse");
-    stabilize(&bricks);
-    let support_structure = analyse_support_structure(&bricks);
+    bricks_falling(&parse_bricks(input).expect("should parse"))
+}
+
+/// Like [`process`], but reports a line which is not a brick as an error.
+pub fn try_process(input: &str) -> Result<Answer, Error> {
+    Ok(bricks_falling(&parse_bricks(input)?).into())
+}
+
+fn bricks_falling(bricks: &Bricks) -> u32 {
+    stabilize(bricks);
+    let support_structure = analyse_support_structure(bricks);
     let removable_bricks = support_structure.iter()
     .filter_map(|(_, obs)| match obs {
         Some(bs) => (bs.len() == 1).then_some( bs.first().unwrap() ),
@@ -20,7 +32,7 @@ pub fn process(
     }).cloned()
     .collect::<HashSet<_>>();
 
-    println!("{}", removable_bricks.len());
+    debug!(count = removable_bricks.len(), "found the bricks holding others up");
 
     removable_bricks.into_iter().map(|removable_brick|
         count_falling_bricks(removable_brick, support_structure.clone())
@@ -28,27 +40,10 @@ pub fn process(
 
 }
 
-#[allow(dead_code)]
-fn display(bricks: &Bricks) {
-    for z in (0..7).rev() {
-        for y in 0..3 {
-            for x in 0..3 {
-                if bricks.iter().any(|brick| brick.borrow().x.contains(&x) && brick.borrow().y.contains(&y) && brick.borrow().z.contains(&z) ) {
-                    print!("#");
-                } else {
-                    print!(".");
-                }
-            }
-            println!()
-        }
-        println!()
-    }
-}
-
 fn count_falling_bricks(removed_brick: BrickRef, mut support_structure: HashMap<BrickRef, Option<Bricks>>) -> u32 {
     let mut falling_bricks = vec![removed_brick.clone()];
     let mut brick_count = 0;
-    dbg!(removed_brick);
+    let _span = trace_span!("remove", brick = ?removed_brick).entered();
 
     while !falling_bricks.is_empty() {
         
@@ -58,9 +53,7 @@ fn count_falling_bricks(removed_brick: BrickRef, mut support_structure: HashMap<
         .filter_map(|(brick, supporting_bricks)|
             ( supporting_bricks.iter().all( |supporting_brick| support_structure.contains_key(supporting_brick).not() || falling_bricks.contains(supporting_brick) ) ).then_some(brick)
         ).cloned().collect::<Vec<_>>();
-        dbg!(&new_falling_bricks);
-
-        dbg!(brick_count);
+        trace!(brick_count, falling = ?new_falling_bricks);
         for falling_brick in falling_bricks {
             support_structure.remove(&falling_brick);
         }        
@@ -80,7 +73,8 @@ fn stabilize(bricks: &Bricks) {
         let mut newer_bricks = bricks.clone();
         for brick in newer_bricks.iter_mut() {
             if get_supporting_bricks(brick.clone(), bricks).is_some_and(|bs| bs.is_empty()) {
-                brick.borrow_mut().move_down();
+                let z = resting_height(brick, bricks);
+                brick.borrow_mut().drop_to(z);
             }
         }
     }
@@ -108,6 +102,19 @@ fn get_supporting_bricks(brick: BrickRef, bricks: &Bricks) -> Option<Bricks> {
     Some(supporting_bricks)
 }
 
+/// The height of the top of the highest brick under `brick`, which it would fall onto, or of the
+/// ground if there is no brick under it.
+fn resting_height(brick: &BrickRef, bricks: &Bricks) -> u32 {
+    let falling = brick.borrow();
+    bricks.iter()
+        .filter(|other| *other != brick)
+        .map(|other| other.borrow())
+        .filter(|other| other.z.end <= falling.z.start && overlap(&other.x, &falling.x) && overlap(&other.y, &falling.y))
+        .map(|other| other.z.end)
+        .max()
+        .unwrap_or(1)
+}
+
 fn space_below_brick(brick: BrickRef) -> Option<Brick> {
     let brick = brick.borrow().clone();
     if brick.z.start == 1 { return None };
@@ -126,8 +133,6 @@ fn overlap<Idx>(lhs: &Range<Idx>, rhs: &Range<Idx>) -> bool where Idx: Ord {
     lhs.start < rhs.end && rhs.start < lhs.end
 }
 
-
-#[allow(dead_code)]
 fn bounds<'a>(bricks: impl Iterator<Item = &'a Brick>) -> (UVec3, UVec3) {
     let mut maximum = UVec3::ZERO;
     let mut minimum = UVec3::MAX;
@@ -147,24 +152,70 @@ fn bounds<'a>(bricks: impl Iterator<Item = &'a Brick>) -> (UVec3, UVec3) {
     (minimum, maximum)
 }
 
+/// The stack seen from the front and from the side.
+fn draw_views(bricks: &Bricks) -> [Frame; 2] {
+    let bricks = bricks.iter().map(|brick| brick.borrow().clone()).collect::<Vec<_>>();
+    let (_, maximum) = bounds(bricks.iter());
+    [
+        draw_view(&bricks, |brick| brick.x.clone(), maximum.x, maximum.z - 1),
+        draw_view(&bricks, |brick| brick.y.clone(), maximum.y, maximum.z - 1),
+    ]
+}
+
+/// The stack seen along one horizontal axis, drawn like the puzzle's pictures: each brick is a
+/// letter, `?` marks where several bricks line up, and the ground is `-`. `across` gives the
+/// extent of a brick along the other horizontal axis.
+fn draw_view(bricks: &[Brick], across: impl Fn(&Brick) -> Range<u32>, width: u32, top: u32) -> Frame {
+    let mut frame = Frame::blank(width as usize, top as usize + 1);
+    frame.mark((0..width).map(|a| IVec2::new(a as i32, top as i32)), '-', Colour::Grey);
+    for (index, brick) in bricks.iter().enumerate() {
+        let glyph = char::from(b'A' + (index % 26) as u8);
+        let colour = Colour::BRIGHT[index % Colour::BRIGHT.len()];
+        for a in across(brick) {
+            for z in brick.z.clone() {
+                let position = IVec2::new(a as i32, (top - z) as i32);
+                if frame.cells()[position].glyph == '.' {
+                    frame.mark([position], glyph, colour);
+                } else {
+                    frame.mark([position], '?', Colour::White);
+                }
+            }
+        }
+    }
+    frame
+}
+
+pub struct Part2;
+
+impl Solution for Part2 {
+    const DAY: u8 = 22;
+    const PART: u8 = 2;
+
+    type Input<'a> = Bricks;
+    type Params = ();
+
+    fn parse(input: &str) -> Result<Bricks, Error> {
+        parse_bricks(input)
+    }
+
+    fn solve(input: Bricks, _: &()) -> Result<Answer, Error> {
+        Ok(bricks_falling(&input).into())
+    }
+
+    /// The stack from the front and the side as the snapshot shows it, then again once the
+    /// bricks have settled.
+    fn visualise(bricks: Bricks, _: &()) -> Result<Vec<Frame>, Error> {
+        let snapshot = draw_views(&bricks);
+        stabilize(&bricks);
+        Ok(snapshot.into_iter().chain(draw_views(&bricks)).collect())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use rstest::rstest;
 
-    #[test]
-    fn test_process() {
-        let lines = "1,0,1~1,2,1
-0,0,2~2,0,2
-0,2,3~2,2,3
-0,0,4~0,2,4
-2,0,5~2,2,5
-0,1,6~2,1,6
-1,1,8~1,1,9";
-        let result = process(lines);
-        assert_eq!(result, 7);
-    }
-
     #[rstest]
     #[case(0..6, 0..6, true)]
     #[case(0..1, 0..1, true)]
@@ -179,4 +230,19 @@ mod tests {
     ){
         assert_eq!(overlap(&lhs, &rhs), expected)
     }
+
+    #[test]
+    fn test_visualise() {
+        let frames = Part2::visualise(Part2::parse("1,0,1~1,2,1
+0,0,2~2,0,2
+0,2,3~2,2,3
+0,0,4~0,2,4
+2,0,5~2,2,5
+0,1,6~2,1,6
+1,1,8~1,1,9").unwrap(), &()).unwrap();
+        let frames = frames.iter().map(Frame::to_ascii).collect::<Vec<_>>();
+        assert_eq!(frames[0], ".G.\n.G.\n...\nFFF\n..E\nD..\nCCC\nBBB\n.A.\n---\n");
+        assert_eq!(frames[1], ".G.\n.G.\n...\n.F.\nEEE\nDDD\n..C\nB..\nAAA\n---\n");
+        assert_eq!(frames[2], ".G.\n.G.\nFFF\nD.E\n???\n.A.\n---\n");
+    }
 }
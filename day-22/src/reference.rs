@@ -0,0 +1,97 @@
+//! Slow but obviously correct solutions, to check the real ones against on small inputs.
+
+use std::collections::HashSet;
+
+type Brick = Vec<[i64; 3]>;
+
+fn bricks(input: &str) -> Vec<Brick> {
+    input
+        .lines()
+        .map(|line| {
+            let (start, end) = line.split_once('~').unwrap();
+            let corner = |corner: &str| -> Vec<i64> {
+                corner.split(',').map(|n| n.parse().unwrap()).collect()
+            };
+            let (start, end) = (corner(start), corner(end));
+            let mut cubes = vec![];
+            for x in start[0]..=end[0] {
+                for y in start[1]..=end[1] {
+                    for z in start[2]..=end[2] {
+                        cubes.push([x, y, z]);
+                    }
+                }
+            }
+            cubes
+        })
+        .collect()
+}
+
+/// Drops any brick with nothing under it a cube at a time until none can fall, and returns how
+/// many fell.
+fn settle(bricks: &mut [Brick]) -> usize {
+    let mut fallen = HashSet::new();
+    let mut moved = true;
+    while moved {
+        moved = false;
+        let filled: HashSet<[i64; 3]> = bricks.iter().flatten().copied().collect();
+        for (index, brick) in bricks.iter_mut().enumerate() {
+            let free = brick.iter().all(|[x, y, z]| {
+                *z > 1 && (!filled.contains(&[*x, *y, z - 1]) || brick.contains(&[*x, *y, z - 1]))
+            });
+            if free {
+                for cube in brick.iter_mut() {
+                    cube[2] -= 1;
+                }
+                fallen.insert(index);
+                moved = true;
+                break;
+            }
+        }
+    }
+    fallen.len()
+}
+
+/// How many other bricks fall when each brick is taken away from the settled snapshot.
+fn falls(input: &str) -> Vec<usize> {
+    let mut bricks = bricks(input);
+    settle(&mut bricks);
+    (0..bricks.len())
+        .map(|index| {
+            let mut rest = bricks.clone();
+            rest.remove(index);
+            settle(&mut rest)
+        })
+        .collect()
+}
+
+pub fn part1(input: &str) -> usize {
+    falls(input).into_iter().filter(|falls| *falls == 0).count()
+}
+
+pub fn part2(input: &str) -> usize {
+    falls(input).into_iter().sum()
+}
+
+mod tests {
+    use aoclib::gen;
+    use aoclib::reference::assert_agrees;
+    use proptest::prelude::*;
+
+    use super::*;
+    use crate::part1::Part1;
+    use crate::part2::Part2;
+
+    proptest! {
+        #[test]
+        fn test_part1(seed: u64, size in 1..12usize) {
+            let input = gen::generator(22).unwrap().generate(size, seed);
+            assert_agrees::<Part1>(&input, &[], part1(&input));
+        }
+
+        #[test]
+        fn test_part2(seed: u64, size in 1..12usize) {
+            let input = gen::generator(22).unwrap().generate(size, seed);
+            assert_agrees::<Part2>(&input, &[], part2(&input));
+        }
+    }
+}
@@ -0,0 +1,18 @@
+use glam::IVec2;
+
+use aoclib::{Error, Grid};
+
+/// The map of the island, and the path in its top row where the hike starts.
+pub struct Island {
+    pub map: Grid<char>,
+    pub start: IVec2,
+}
+
+/// Parses `input` as a rectangle of paths, slopes and forest, with a path into it from the top
+/// row.
+pub fn parse_island(input: &str) -> Result<Island, Error> {
+    let map = Grid::try_parse(input, "a path, a slope or '#'", |c| "#.^v<>".contains(c).then_some(c))?;
+    let start = map.row(0).iter().position(|c| *c == '.')
+        .ok_or_else(|| Error::MissingStart("a path in the top row".to_string()))?;
+    Ok(Island { map, start: IVec2::new(start as i32, 0) })
+}
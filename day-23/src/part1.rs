@@ -2,40 +2,58 @@ use std::collections::HashMap;
 
 use glam::IVec2;
 
-pub fn process<'a>(
-    input: &'a str,
+use aoclib::viz::{Colour, Frame};
+use aoclib::{Answer, Error, Grid, Solution};
+
+use crate::parse::{parse_island, Island};
+
+pub fn process(
+    input: &str,
 ) -> i32 {
-    let start = IVec2::new(input.lines().next().unwrap().chars().enumerate().find(|(_, c)| *c == '.').unwrap().0 as i32, 0);
+    longest_hike_from_start(&parse_island(input).expect("should parse"))
+}
+
+/// Like [`process`], but reports a map which is not a rectangle of paths, slopes and forest, or
+/// which has no path into it from the top row, as an error.
+pub fn try_process(input: &str) -> Result<Answer, Error> {
+    Ok(longest_hike_from_start(&parse_island(input)?).into())
+}
+
+fn longest_hike_from_start(island: &Island) -> i32 {
+    let tiles = tiles(&island.map);
+    longest_hike(&island.start, &tiles.iter().map(|(k, v)| (k, *v)).collect())
+}
 
-    let island: HashMap<IVec2, TileType> = garden(input).filter_map(|(location, c)| match c {
+/// Every tile which can be walked on.
+fn tiles(map: &Grid<char>) -> HashMap<IVec2, TileType> {
+    map.iter().filter_map(|(location, c)| match c {
         '.' => Some((location, TileType::Path)),
         '^' => Some((location, TileType::Slope(Direction::North))),
         'v' => Some((location, TileType::Slope(Direction::South))),
         '>' => Some((location, TileType::Slope(Direction::East ))),
         '<' => Some((location, TileType::Slope(Direction::West ))),
         _ => None
-    }).collect();
-    
-    longest_hike(&start, &island.iter().map(|(k, v)| (k, *v)).collect())
+    }).collect()
 }
 
-fn garden(input: &str) -> impl Iterator<Item = (IVec2, char)> + '_{
-    input.lines().enumerate().flat_map(|(y, line)|{
-        line.chars().enumerate().map(move |(x, c)|{
-            (IVec2{x: x.try_into().unwrap(), y: y.try_into().unwrap()}, c)
-        })
-    })
+fn longest_hike<'a>(location: &IVec2, island: &'a HashMap<&'a IVec2, TileType>) -> i32 {
+    longest_hike_path(location, island).len() as i32
 }
 
-fn longest_hike<'a>(location: &IVec2, island: &'a HashMap<&'a IVec2, TileType>) -> i32 {
+/// The tiles after `location` on the longest hike from it.
+fn longest_hike_path<'a>(location: &IVec2, island: &'a HashMap<&'a IVec2, TileType>) -> Vec<IVec2> {
     if let Some(neighbours) = neighbours(location, island) {
         let mut new_island = island.clone();
         new_island.remove(location);
         neighbours.iter()
-            .map(|neighbour| 1 + longest_hike(neighbour, &new_island) )
-            .max().expect("there should be a maximum")
+            .map(|neighbour| {
+                let mut path = vec![*neighbour];
+                path.extend(longest_hike_path(neighbour, &new_island));
+                path
+            })
+            .max_by_key(Vec::len).expect("there should be a maximum")
     } else {
-        0
+        vec![]
     }
     
 }
@@ -75,36 +93,50 @@ enum Direction {
     West
 }
 
+pub struct Part1;
+
+impl Solution for Part1 {
+    const DAY: u8 = 23;
+    const PART: u8 = 1;
+
+    type Input<'a> = Island;
+    type Params = ();
+
+    fn parse(input: &str) -> Result<Island, Error> {
+        parse_island(input)
+    }
+
+    fn solve(input: Island, _: &()) -> Result<Answer, Error> {
+        Ok(longest_hike_from_start(&input).into())
+    }
+
+    /// The map, with the longest hike drawn over it.
+    fn visualise(island: Island, _: &()) -> Result<Vec<Frame>, Error> {
+        let Island { map, start } = island;
+        let tiles = tiles(&map);
+        let mut hike = vec![start];
+        hike.extend(longest_hike_path(&start, &tiles.iter().map(|(k, v)| (k, *v)).collect()));
+        let mut frame = Frame::new(&map, |c| *c);
+        frame.path(&hike, Colour::Red);
+        Ok(vec![frame])
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
-    fn test_process() {
-        let lines = "#.#####################
-#.......#########...###
-#######.#########.#.###
-###.....#.>.>.###.#.###
-###v#####.#v#.###.#.###
-###.>...#.#.#.....#...#
-###v###.#.#.#########.#
-###...#.#.#.......#...#
-#####.#.#.#######.#.###
-#.....#.#.#.......#...#
-#.#####.#.#.#########v#
-#.#...#...#...###...>.#
-#.#.#v#######v###.###v#
-#...#.>.#...>.>.#.###.#
-#####v#.#.###v#.#.###.#
-#.....#...#...#.#.#...#
-#.#########.###.#.#.###
-#...###...#...#...#.###
-###.###.#.###v#####v###
-#...#...#.#.>.>.#.>.###
-#.###.###.#.###.#.#v###
-#.....###...###...#...#
-#####################.#";
-        let result = process(lines);
-        assert_eq!(result, 94);
+    fn test_visualise() {
+        let frames = Part1::visualise(Part1::parse("#.###\n#...#\n###.#\n").unwrap(), &()).unwrap();
+        assert_eq!(frames[0].to_ascii(), "#v###\n#>>v#\n###.#\n");
+    }
+
+    #[test]
+    fn test_try_process() {
+        assert_eq!(
+            try_process("###\n#.#\n#.#\n").unwrap_err(),
+            Error::MissingStart("a path in the top row".to_string())
+        );
     }
 }
@@ -2,13 +2,46 @@ use std::collections::{HashSet, HashMap};
 
 use glam::IVec2;
 
+use aoclib::budget;
+use aoclib::dot::Graph;
+use aoclib::{Answer, Error, Solution};
+
+use crate::parse::{parse_island, Island};
+
 pub fn process(
     input: &str,
 ) -> u32 {
-    let start = IVec2::new(input.lines().next().unwrap().chars().enumerate().find(|(_, c)| *c == '.').unwrap().0 as i32, 0);
-    let end = IVec2::new(input.lines().last().unwrap().chars().enumerate().find(|(_, c)| *c == '.').unwrap().0 as i32, input.lines().count() as i32 - 1);
+    longest_hike_across(&parse_island(input).expect("should parse"))
+        .expect("there to be a hike across the island within the budget")
+}
+
+/// Like [`process`], but reports a map which is not a rectangle of paths, slopes and forest,
+/// which has no path into it from the top row or out of it from the bottom row, or whose longest
+/// hike is not found within the budget, as an error.
+pub fn try_process(input: &str) -> Result<Answer, Error> {
+    Ok(longest_hike_across(&parse_island(input)?)?.into())
+}
+
+fn longest_hike_across(island: &Island) -> Result<u32, Error> {
+    let (start, end, trails) = trail_network(island)?;
+    longest_hike((&start, 0), &end, &trails, HashSet::new())?
+        .ok_or_else(|| Error::NoSolution("there is no hike from the start to the end".to_string()))
+}
 
-    let island: HashSet<IVec2> = garden(input).filter_map(|(location, c)| match c {
+/// Each fork in the path (along with the start and end) and the forks it leads to, with how many
+/// steps away they are.
+type Trails = HashMap<IVec2, HashSet<(IVec2, u32)>>;
+
+/// The start and end of the hike, and the trails between them, or an error if there is no path
+/// out of the bottom row to end the hike on.
+fn trail_network(island: &Island) -> Result<(IVec2, IVec2, Trails), Error> {
+    let Island { map, start } = island;
+    let bottom = map.height() - 1;
+    let end = map.row(bottom).iter().position(|c| *c == '.')
+        .ok_or_else(|| Error::NoSolution("there is no path out of the bottom row".to_string()))?;
+    let end = IVec2::new(end as i32, bottom as i32);
+
+    let island: HashSet<IVec2> = map.iter().filter_map(|(location, c)| match c {
         '.' | '^' | 'v' | '>' | '<' => Some(location),
         _ => None
     }).collect();
@@ -18,10 +51,8 @@ pub fn process(
             (location, neighbours(location, &island))
         ).collect();
     
-    dbg!(&island_map.len());
-    
     let mut optimized_map: HashMap<&IVec2, HashSet<(&IVec2, u32)>> = HashMap::new();
-    let mut to_visit = vec![&start];
+    let mut to_visit = vec![start];
 
     while let Some(location) = to_visit.pop() {
         if let Some(Some(neighbours)) = island_map.get(location) {
@@ -34,8 +65,10 @@ pub fn process(
         }
     }
 
-    // render_map(optimized_map);
-    longest_hike((&start, 0), &end, &optimized_map, HashSet::new()).expect("there to be a solution")
+    let trails = optimized_map.into_iter()
+        .map(|(location, forks)| (*location, forks.into_iter().map(|(fork, cost)| (*fork, cost)).collect()))
+        .collect();
+    Ok((*start, end, trails))
 }
 
 fn get_next_forks<'a>(location: &'a IVec2, island_map: &'a HashMap<&'a IVec2, Option<Vec<&IVec2>>>, end: &IVec2, mut visited: HashSet<&'a IVec2>) -> Vec<(&'a IVec2, u32)> {
@@ -52,19 +85,12 @@ fn get_next_forks<'a>(location: &'a IVec2, island_map: &'a HashMap<&'a IVec2, Op
     }
 }
 
-fn garden(input: &str) -> impl Iterator<Item = (IVec2, char)> + '_{
-    input.lines().enumerate().flat_map(|(y, line)|{
-        line.chars().enumerate().map(move |(x, c)|{
-            (IVec2{x: x.try_into().unwrap(), y: y.try_into().unwrap()}, c)
-        })
-    })
-}
-
-fn longest_hike<'a>((location, cost): (&'a IVec2, u32), end: &IVec2, optimized_map: &HashMap<&IVec2, HashSet<(&IVec2, u32)>>, mut visited: HashSet<&'a IVec2>) -> Option<u32> {
-    // println!("{}",location);
+fn longest_hike<'a>((location, cost): (&'a IVec2, u32), end: &IVec2, optimized_map: &'a Trails, mut visited: HashSet<&'a IVec2>) -> Result<Option<u32>, Error> {
+    // every path between the forks is tried, which takes exponential time on a large enough map
+    budget::tick()?;
 
     if location == end { 
-        return Some(cost)
+        return Ok(Some(cost))
     }
 
     if let Some(neighbours) = optimized_map.get(location) {
@@ -72,15 +98,16 @@ fn longest_hike<'a>((location, cost): (&'a IVec2, u32), end: &IVec2, optimized_m
         let filtered_neighbours = neighbours.iter().filter(|(neighbour, _)| ! visited.contains(neighbour)).collect::<Vec<_>>();
         
         if filtered_neighbours.is_empty() {
-            // println!("Neighours are empty!");
-            return None 
+            return Ok(None)
         }; 
 
-        filtered_neighbours.into_iter()
-            .filter_map(|neighbour| longest_hike(*neighbour, end, optimized_map, visited.clone()) )
-            .max().map(|m| m + cost)
+        let mut longest = None;
+        for (neighbour, cost) in filtered_neighbours {
+            longest = longest.max(longest_hike((neighbour, *cost), end, optimized_map, visited.clone())?);
+        }
+        Ok(longest.map(|m| m + cost))
     } else {
-        None
+        Ok(None)
     }
 }
 
@@ -99,46 +126,73 @@ fn neighbours<'a>(location: &'a IVec2, island: &'a HashSet<IVec2> ) -> Option<Ve
     (!retvec.is_empty()).then_some(retvec)
 }
 
-fn render_map( map: HashMap<&IVec2, HashSet<(&IVec2, u32)>> ) {
-    println!("digraph {{");
-    for (key, values) in map {   
-        for (location, cost) in values {
-            println!("\"{:}\" -> \"{:}\"[label=\"{}\"]", key, location, cost)
+pub struct Part2;
+
+impl Solution for Part2 {
+    const DAY: u8 = 23;
+    const PART: u8 = 2;
+
+    type Input<'a> = Island;
+    type Params = ();
+
+    fn parse(input: &str) -> Result<Island, Error> {
+        parse_island(input)
+    }
+
+    fn solve(input: Island, _: &()) -> Result<Answer, Error> {
+        Ok(longest_hike_across(&input)?.into())
+    }
+
+    /// The trails between the forks in the path, each labelled with its length, from the start
+    /// of the hike to its end (both drawn as double circles).
+    fn export_graph(island: Island, _: &()) -> Result<Option<Graph>, Error> {
+        let (start, end, trails) = trail_network(&island)?;
+
+        // the trails can be walked either way, so each one is listed from both of its ends
+        let mut trails: Vec<_> = trails.into_iter()
+            .flat_map(|(location, forks)| forks.into_iter().map(move |(fork, cost)| (location, fork, cost)))
+            .filter(|(location, fork, _)| (location.y, location.x) < (fork.y, fork.x))
+            .collect();
+        trails.sort_by_key(|(location, fork, _)| (location.y, location.x, fork.y, fork.x));
+
+        let mut graph = Graph::undirected("day23");
+        for location in [start, end] {
+            graph.node(&location.to_string()).set("shape", "doublecircle");
+        }
+        for (location, fork, cost) in trails {
+            graph.edge(&location.to_string(), &fork.to_string()).set("label", cost);
         }
+        Ok(Some(graph))
     }
-    println!("}}");
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use aoclib::budget::Budget;
+
+    #[test]
+    fn test_budget() {
+        let input = "#.###\n#...#\n#.#.#\n#...#\n###.#\n";
+        assert_eq!(Budget::unlimited().with_max_steps(10).run(|| try_process(input)), Ok(Answer::U32(6)));
+        assert_eq!(
+            Budget::unlimited().with_max_steps(1).run(|| try_process(input)),
+            Err(Error::BudgetExceeded { steps: 1 })
+        );
+    }
 
     #[test]
-    fn test_process() {
-        let lines = "#.#####################
-#.......#########...###
-#######.#########.#.###
-###.....#.>.>.###.#.###
-###v#####.#v#.###.#.###
-###.>...#.#.#.....#...#
-###v###.#.#.#########.#
-###...#.#.#.......#...#
-#####.#.#.#######.#.###
-#.....#.#.#.......#...#
-#.#####.#.#.#########v#
-#.#...#...#...###...>.#
-#.#.#v#######v###.###v#
-#...#.>.#...>.>.#.###.#
-#####v#.#.###v#.#.###.#
-#.....#...#...#.#.#...#
-#.#########.###.#.#.###
-#...###...#...#...#.###
-###.###.#.###v#####v###
-#...#...#.#.>.>.#.>.###
-#.###.###.#.###.#.#v###
-#.....###...###...#...#
-#####################.#";
-        let result = process(lines);
-        assert_eq!(result, 154);
+    fn test_export_graph() {
+        let graph = Part2::export_graph(Part2::parse("#.###\n#...#\n#.#.#\n#...#\n###.#").unwrap(), &()).unwrap().unwrap();
+        assert_eq!(graph.to_string(), r#"graph "day23" {
+    "[1, 0]" [shape="doublecircle"];
+    "[3, 4]" [shape="doublecircle"];
+    "[1, 1]";
+    "[3, 3]";
+    "[1, 0]" -- "[1, 1]" [label="1"];
+    "[1, 1]" -- "[3, 3]" [label="4"];
+    "[3, 3]" -- "[3, 4]" [label="1"];
+}
+"#);
     }
 }
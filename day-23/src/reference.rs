@@ -0,0 +1,150 @@
+//! Slow but obviously correct solutions, to check the real ones against on small inputs.
+
+use std::collections::HashSet;
+
+/// Tries every hike a tile at a time, never stepping on the same tile twice, and returns the
+/// longest to reach the bottom row. Slopes can only be stepped off downhill if `slippery`.
+fn longest_hike(input: &str, slippery: bool) -> usize {
+    let map: Vec<Vec<u8>> = input.lines().map(|line| line.bytes().collect()).collect();
+    let start = (
+        map[0].iter().position(|tile| *tile == b'.').unwrap() as i64,
+        0,
+    );
+    let mut visited = HashSet::from([start]);
+    hike(&map, start, slippery, &mut visited).unwrap()
+}
+
+fn hike(
+    map: &[Vec<u8>],
+    (x, y): (i64, i64),
+    slippery: bool,
+    visited: &mut HashSet<(i64, i64)>,
+) -> Option<usize> {
+    if y as usize == map.len() - 1 {
+        return Some(visited.len() - 1);
+    }
+    let steps = match map[y as usize][x as usize] {
+        b'>' if slippery => vec![(1, 0)],
+        b'<' if slippery => vec![(-1, 0)],
+        b'v' if slippery => vec![(0, 1)],
+        b'^' if slippery => vec![(0, -1)],
+        _ => vec![(1, 0), (-1, 0), (0, 1), (0, -1)],
+    };
+    let mut longest = None;
+    for (dx, dy) in steps {
+        let next = (x + dx, y + dy);
+        if next.1 < 0 || map[next.1 as usize][next.0 as usize] == b'#' || !visited.insert(next) {
+            continue;
+        }
+        longest = longest.max(hike(map, next, slippery, visited));
+        visited.remove(&next);
+    }
+    longest
+}
+
+pub fn part1(input: &str) -> usize {
+    longest_hike(input, true)
+}
+
+pub fn part2(input: &str) -> usize {
+    longest_hike(input, false)
+}
+
+mod tests {
+    use aoclib::gen;
+    use aoclib::reference::assert_agrees;
+    use proptest::prelude::*;
+
+    use super::*;
+    use crate::part1::Part1;
+    use crate::part2::Part2;
+
+    /// The map with every dead end filled in with forest, as there are none in the real map.
+    fn without_dead_ends(input: &str) -> String {
+        let mut map: Vec<Vec<u8>> = input.lines().map(|line| line.bytes().collect()).collect();
+        let (width, height) = (map[0].len(), map.len());
+        let mut filled = true;
+        while filled {
+            filled = false;
+            for y in 1..height - 1 {
+                for x in 1..width - 1 {
+                    let open = [(x + 1, y), (x - 1, y), (x, y + 1), (x, y - 1)]
+                        .into_iter()
+                        .filter(|(x, y)| map[*y][*x] != b'#')
+                        .count();
+                    if map[y][x] != b'#' && open < 2 {
+                        map[y][x] = b'#';
+                        filled = true;
+                    }
+                }
+            }
+        }
+        map.into_iter()
+            .map(|row| String::from_utf8(row).unwrap() + "\n")
+            .collect()
+    }
+
+    /// Whether any walk down the slippery slopes ends somewhere other than the bottom row, which
+    /// never happens in the real map.
+    fn can_get_stuck(input: &str) -> bool {
+        fn walk(map: &[Vec<u8>], (x, y): (i64, i64), visited: &mut HashSet<(i64, i64)>) -> bool {
+            if y as usize == map.len() - 1 {
+                return false;
+            }
+            let steps = match map[y as usize][x as usize] {
+                b'>' => vec![(1, 0)],
+                b'<' => vec![(-1, 0)],
+                b'v' => vec![(0, 1)],
+                b'^' => vec![(0, -1)],
+                _ => vec![(1, 0), (-1, 0), (0, 1), (0, -1)],
+            };
+            let mut moved = false;
+            for (dx, dy) in steps {
+                let next = (x + dx, y + dy);
+                if next.1 < 0
+                    || map[next.1 as usize][next.0 as usize] == b'#'
+                    || !visited.insert(next)
+                {
+                    continue;
+                }
+                moved = true;
+                let stuck = walk(map, next, visited);
+                visited.remove(&next);
+                if stuck {
+                    return true;
+                }
+            }
+            !moved
+        }
+        let map: Vec<Vec<u8>> = input.lines().map(|line| line.bytes().collect()).collect();
+        let start = (
+            map[0].iter().position(|tile| *tile == b'.').unwrap() as i64,
+            0,
+        );
+        walk(&map, start, &mut HashSet::from([start]))
+    }
+
+    proptest! {
+        #[test]
+        fn test_part1(seed: u64, size in 2..5usize) {
+            let input = without_dead_ends(&gen::generator(23).unwrap().generate(size, seed));
+            prop_assume!(!can_get_stuck(&input));
+            assert_agrees::<Part1>(&input, &[], part1(&input));
+        }
+
+        #[test]
+        fn test_part2(seed: u64, size in 2..5usize) {
+            let input = gen::generator(23).unwrap().generate(size, seed);
+            assert_agrees::<Part2>(&input, &[], part2(&input));
+        }
+    }
+
+    // Part 1 takes the longest walk it can find, wherever it ends, so a walk which gets stuck
+    // further away than the bottom row is taken for the hike.
+    #[test]
+    #[should_panic(expected = "disagrees with the reference")]
+    fn test_part1_dead_end() {
+        let input = "#.###\n#.#.#\n#.#^#\n#.>.#\n###.#\n";
+        assert_agrees::<Part1>(input, &[], part1(input));
+    }
+}
@@ -1,7 +1,9 @@
+use aoclib::input::{self, InputError};
 use day_24::part1::process;
 
-fn main() {
-    let file = include_str!("../../input.txt");
-    let result = process(file, 200000000000000.0, 400000000000000.0);
+fn main() -> Result<(), InputError> {
+    let file = input::load(24, 1)?;
+    let result = process(&file, 200000000000000.0, 400000000000000.0);
     println!("{}", result);
-}
\ No newline at end of file
+    Ok(())
+}
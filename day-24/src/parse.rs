@@ -0,0 +1,59 @@
+use glam::DVec3;
+use nom::character::complete::{self, space1};
+use nom::error::context;
+use nom::sequence::{Tuple, separated_pair, terminated, preceded, tuple};
+
+use aoclib::parse::{lines, parse_all, tag, ParseResult, Span};
+use aoclib::Error;
+
+/// Where a hailstone is, and how far it moves each nanosecond.
+#[derive(PartialEq, Debug, Clone)]
+pub struct Hailstone {
+    pub position: DVec3,
+    pub velocity: DVec3,
+}
+
+/// Parses every line of `input` as a whole hailstone.
+pub fn parse_hailstones(input: &str) -> Result<Vec<Hailstone>, Error> {
+    parse_all(input, lines(hailstone))
+}
+
+// 19, 13, 30 @ -2,  1, -2
+fn hailstone(input: Span) -> ParseResult<Hailstone> {
+    let (input, ((px,py,pz),(vx,vy,vz))) =  
+        context("hailstone", separated_pair(
+            |input| (terminated(complete::i64, preceded(tag(","), space1)), 
+            terminated(complete::i64, preceded(tag(","), space1)), 
+            complete::i64).parse(input),
+            tuple((space1, tag("@"), space1)), 
+            |input| (terminated(complete::i64, preceded(tag(","), space1)), 
+            terminated(complete::i64, preceded(tag(","), space1)), 
+            complete::i64).parse(input)
+            ))(input)?;
+
+    Ok((
+        input, 
+        Hailstone {
+            position: DVec3::new(px as f64, py as f64, pz as f64),
+            velocity: DVec3::new(vx as f64, vy as f64, vz as f64),
+        }
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("19, 13, 30 @ -2,  1, -2", Hailstone { position: DVec3::new(19.0, 13.0, 30.0), velocity: DVec3::new(-2.0, 1.0, -2.0) })]
+    fn test_hailstone(
+        #[case] input: &str,
+        #[case] expected: Hailstone
+    ) {
+        let (input, hail_stone) = hailstone(Span::new(input)).expect("Should Parse");
+        assert_eq!(*input.fragment(), "");
+        assert_eq!(hail_stone, expected);
+    }
+}
@@ -1,17 +1,25 @@
 use glam::DVec2;
-use nom::bytes::complete::tag;
-use nom::character::complete::{self, space1, newline};
-use nom::multi::separated_list1;
-use nom::sequence::{Tuple, separated_pair, terminated, preceded};
-use nom::IResult;
 use itertools::Itertools;
+use tracing::trace;
 
-pub fn process<'a>(
-    input: &'a str, lower_bound: f64, upper_bound: f64
+use aoclib::params::ParamError;
+use aoclib::{Answer, Error, FromParams, Params, Solution};
+
+use crate::parse::{parse_hailstones, Hailstone};
+
+pub fn process(
+    input: &str, lower_bound: f64, upper_bound: f64
 ) -> u32 {
-    let (_, hailstones) = separated_list1(newline,hailstone)(input).expect("should parse");
+    collisions_in_area(&parse_hailstones(input).expect("should parse"), lower_bound, upper_bound)
+}
 
-    hailstones.into_iter().combinations(2).filter_map(|h| {
+/// Like [`process`], but reports a line which is not a hailstone as an error.
+pub fn try_process(input: &str, lower_bound: f64, upper_bound: f64) -> Result<Answer, Error> {
+    Ok(collisions_in_area(&parse_hailstones(input)?, lower_bound, upper_bound).into())
+}
+
+fn collisions_in_area(hailstones: &[Hailstone], lower_bound: f64, upper_bound: f64) -> u32 {
+    hailstones.iter().map(HailStone::from).combinations(2).filter_map(|h| {
         collision_point(&h[0], &h[1])
  
     })
@@ -24,16 +32,26 @@ pub fn process<'a>(
 
 fn collision_point(h0: &HailStone, h1: &HailStone) -> Option<DVec2> {
     let denominator = h0.velocity.perp_dot(h1.velocity);
-    if dbg!(denominator) == 0.0 { println!("Singular matrix!"); return None };
+    if denominator == 0.0 {
+        trace!("the paths are parallel");
+        return None;
+    }
     
     let t0 = (h1.position - h0.position).perp_dot(h1.velocity) / denominator;
-    if dbg!(t0) < 0.0 { println!("Colision in past for h0!"); return None };
+    if t0 < 0.0 {
+        trace!(t0, "the paths crossed in the first hailstone's past");
+        return None;
+    }
 
     let t1 = (h1.position - h0.position).perp_dot(h0.velocity) / denominator;
-    if dbg!(t1) < 0.0 { println!("Collisionn in past for h1!"); return None };
+    if t1 < 0.0 {
+        trace!(t1, "the paths crossed in the second hailstone's past");
+        return None;
+    }
 
     let collision_point = h0.position + t0 * h0.velocity;
-    Some(dbg!(collision_point))
+    trace!(t0, t1, ?collision_point, "the paths cross");
+    Some(collision_point)
 }
 
 
@@ -52,23 +70,49 @@ impl HailStone {
     }
 }
 
-// 19, 13, 30 @ -2,  1, -2
-fn hailstone(input: &str) -> IResult<&str, HailStone> {
-    let (input, ((px,py,pz),(vx,vy,vz))) =  
-        separated_pair(
-            |input| (terminated(complete::i64, preceded(tag(","), space1)), 
-            terminated(complete::i64, preceded(tag(","), space1)), 
-            complete::i64).parse(input),
-            tag(" @ "), 
-            |input| (terminated(complete::i64, preceded(tag(","), space1)), 
-            terminated(complete::i64, preceded(tag(","), space1)), 
-            complete::i64).parse(input)
-            )(input)?;
-
-    Ok((
-        input, 
-        HailStone::new(px as f64, py as f64, pz as f64, vx as f64, vy as f64, vz as f64)
-    ))
+impl From<&Hailstone> for HailStone {
+    fn from(hailstone: &Hailstone) -> Self {
+        let Hailstone { position: p, velocity: v } = hailstone;
+        Self::new(p.x, p.y, p.z, v.x, v.y, v.z)
+    }
+}
+
+/// The test area, which applies to both the x and y axes.
+pub struct Bounds {
+    pub lower: f64,
+    pub upper: f64,
+}
+
+impl FromParams for Bounds {
+    const DEFAULTS: &'static [(&'static str, &'static str)] = &[
+        ("lower_bound", "200000000000000"),
+        ("upper_bound", "400000000000000"),
+    ];
+
+    fn from_params(params: &Params) -> Result<Self, ParamError> {
+        Ok(Bounds {
+            lower: params.get("lower_bound")?,
+            upper: params.get("upper_bound")?,
+        })
+    }
+}
+
+pub struct Part1;
+
+impl Solution for Part1 {
+    const DAY: u8 = 24;
+    const PART: u8 = 1;
+
+    type Input<'a> = Vec<Hailstone>;
+    type Params = Bounds;
+
+    fn parse(input: &str) -> Result<Vec<Hailstone>, Error> {
+        parse_hailstones(input)
+    }
+
+    fn solve(input: Vec<Hailstone>, params: &Bounds) -> Result<Answer, Error> {
+        Ok(collisions_in_area(&input, params.lower, params.upper).into())
+    }
 }
 
 #[cfg(test)]
@@ -91,25 +135,11 @@ mod tests {
         assert_eq!(result.is_some(), expected)
     }
 
-    #[rstest]
-    #[case("19, 13, 30 @ -2,  1, -2", HailStone::new(19.0, 13.0, 30.0, -2.0,  1.0, -2.0))]
-    fn test_hailstone(
-        #[case] input: &str,
-        #[case] expected: HailStone
-    ) {
-        let (input, hail_stone) = hailstone(input).expect("Should Parse");
-        assert_eq!(input, "");
-        assert_eq!(hail_stone, expected);
-    }
-
     #[test]
-    fn test_process() {
-        let lines = "19, 13, 30 @ -2,  1, -2
-18, 19, 22 @ -1, -1, -2
-20, 25, 34 @ -2, -2, -4
-12, 31, 28 @ -1, -2, -1
-20, 19, 15 @  1, -5, -3";
-        let result = process(lines, 7.0, 27.0);
-        assert_eq!(result, 2);
+    fn test_try_process() {
+        assert_eq!(
+            try_process("19, 13, 30 @ -2, 1, -2\n18, 19, 22 @ -1 -1, -2\n", 7.0, 27.0).unwrap_err().to_string(),
+            "line 2, column 16: expected \",\" in hailstone, found \" -1, -2\""
+        );
     }
 }
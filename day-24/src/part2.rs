@@ -1,14 +1,31 @@
 use nalgebra::{Vector3, Matrix6, Vector6};
-use nom::bytes::complete::tag;
-use nom::character::complete::{self, space1, newline};
-use nom::multi::separated_list1;
-use nom::sequence::{Tuple, separated_pair, terminated, preceded};
-use nom::IResult;
+use tracing::debug;
+
+use aoclib::{Answer, Error, Solution};
+
+use crate::parse::{parse_hailstones, Hailstone};
+use crate::part1::Bounds;
 
 pub fn process(
     input: &str, _: f64, _: f64
 ) -> f64 {
-    let (_, h) = separated_list1(newline,hailstone)(input).expect("should parse");
+    rock_position_total(&parse_hailstones(input).expect("should parse"))
+        .expect("the hailstones should fix the rock's path")
+}
+
+/// Like [`process`], but reports a line which is not a hailstone, or hailstones which do not
+/// fix a single path for the rock, as an error.
+pub fn try_process(input: &str, _: f64, _: f64) -> Result<Answer, Error> {
+    Ok(rock_position_total(&parse_hailstones(input)?)?.into())
+}
+
+/// The sum of the coordinates the rock is thrown from, worked out from the first three
+/// hailstones.
+fn rock_position_total(hailstones: &[Hailstone]) -> Result<f64, Error> {
+    if hailstones.len() < 3 {
+        return Err(Error::NoSolution("the rock's path needs at least three hailstones".to_string()));
+    }
+    let h = hailstones.iter().take(3).map(HailStone::from).collect::<Vec<_>>();
 
     let n = 0; let m = 1; let l = 2;
 
@@ -39,12 +56,13 @@ pub fn process(
         p_l.x * v_l.y + p_n.y * v_n.x - p_l.y * v_l.x - p_n.x * v_n.y,
     );
 
-    let answer = s.try_inverse().unwrap() * t;
+    let inverse = s.try_inverse()
+        .ok_or_else(|| Error::NoSolution("the first three hailstones do not fix a single path for the rock".to_string()))?;
+    let answer = inverse * t;
     let answer = Vector3::from(answer.fixed_view::<3, 1>(0, 0));
-    dbg!(answer);
-
-    answer.sum()
+    debug!(rock = ?answer, "solved for the rock's position");
 
+    Ok(answer.sum())
 }
 
 #[derive(PartialEq, Debug, Clone)]
@@ -62,51 +80,40 @@ impl HailStone {
     }
 }
 
-// 19, 13, 30 @ -2,  1, -2
-fn hailstone(input: &str) -> IResult<&str, HailStone> {
-    let (input, ((px,py,pz),(vx,vy,vz))) =  
-        separated_pair(
-            |input| (terminated(complete::i64, preceded(tag(","), space1)), 
-            terminated(complete::i64, preceded(tag(","), space1)), 
-            complete::i64).parse(input),
-            tag(" @ "), 
-            |input| (terminated(complete::i64, preceded(tag(","), space1)), 
-            terminated(complete::i64, preceded(tag(","), space1)), 
-            complete::i64).parse(input)
-            )(input)?;
-
-    Ok((
-        input, 
-        HailStone::new(px as f64, py as f64, pz as f64, vx as f64, vy as f64, vz as f64)
-    ))
+impl From<&Hailstone> for HailStone {
+    fn from(hailstone: &Hailstone) -> Self {
+        let Hailstone { position: p, velocity: v } = hailstone;
+        Self::new(p.x, p.y, p.z, v.x, v.y, v.z)
+    }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+pub struct Part2;
 
-    use rstest::rstest;
+impl Solution for Part2 {
+    const DAY: u8 = 24;
+    const PART: u8 = 2;
 
+    type Input<'a> = Vec<Hailstone>;
+    type Params = Bounds;
 
-    #[rstest]
-    #[case("19, 13, 30 @ -2,  1, -2", HailStone::new(19.0, 13.0, 30.0, -2.0,  1.0, -2.0))]
-    fn test_hailstone(
-        #[case] input: &str,
-        #[case] expected: HailStone
-    ) {
-        let (input, hail_stone) = hailstone(input).expect("Should Parse");
-        assert_eq!(input, "");
-        assert_eq!(hail_stone, expected);
+    fn parse(input: &str) -> Result<Vec<Hailstone>, Error> {
+        parse_hailstones(input)
     }
 
+    fn solve(input: Vec<Hailstone>, _: &Bounds) -> Result<Answer, Error> {
+        Ok(rock_position_total(&input)?.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
     #[test]
-    fn test_process() {
-        let lines = "19, 13, 30 @ -2,  1, -2
-18, 19, 22 @ -1, -1, -2
-20, 25, 34 @ -2, -2, -4
-12, 31, 28 @ -1, -2, -1
-20, 19, 15 @  1, -5, -3";
-        let result = process(lines, 7.0, 27.0);
-        assert_eq!(result, 47.0);
+    fn test_try_process() {
+        assert_eq!(
+            try_process("19, 13, 30 @ -2, 1, -2\n19, 13, 30 @ -2, 1, -2\n19, 13, 30 @ -2, 1, -2\n", 7.0, 27.0),
+            Err(Error::NoSolution("the first three hailstones do not fix a single path for the rock".to_string()))
+        );
     }
 }
@@ -0,0 +1,48 @@
+use std::collections::{HashSet, HashMap};
+
+use petgraph::graph::UnGraph;
+use nom::{sequence::separated_pair, multi::separated_list1, character::complete::{space1, alpha1}, error::context};
+
+use aoclib::parse::{lines, parse_all, tag, ParseResult, Span};
+use aoclib::Error;
+
+/// Parses every line of `input` as a component and the components it is wired to, into a graph
+/// of the components with an edge for each wire.
+pub fn parse_components(input: &str) -> Result<UnGraph<&str, ()>, Error> {
+    parse_all(input, components)
+}
+
+fn components<'a>(input: Span<'a>) -> ParseResult<'a, UnGraph<&'a str,()>>{
+    let (input, result) = lines(node)(input)?;
+    let mut graph = UnGraph::default();
+
+    // get a unique list of nodes
+    let nodes: HashSet<&str> = result.iter().flat_map(|(node, adjacencies)| {
+        let mut nodes = adjacencies.clone();
+        nodes.push(node);
+        nodes
+    }).collect();
+    
+    // create the nodes in the graph
+    let node_map: HashMap<_,_>= nodes.iter()
+        .map(|node| (*node, graph.add_node(*node)))
+        .collect();
+
+    // add the edges
+    for (node, adjacencies) in result {
+        for adjacency in adjacencies {
+            graph.add_edge(
+                node_map[node],
+                node_map[adjacency], 
+                ());
+        }
+    }
+
+    Ok((input, graph))
+}
+
+// cmg: qnr nvd lhk bvb
+fn node<'a>(input: Span<'a>) -> ParseResult<'a, (&'a str, Vec<&'a str>)> {
+    let (input, (node, adjacencies)) = context("component", separated_pair(alpha1, tag(": "), separated_list1(space1, alpha1)))(input)?;
+    Ok((input, (*node.fragment(), adjacencies.into_iter().map(|adjacency| *adjacency.fragment()).collect())))
+}
@@ -1,55 +1,84 @@
-use std::collections::{HashSet, HashMap};
+use std::collections::HashSet;
 
 use petgraph::graph::UnGraph;
+use petgraph::visit::EdgeRef;
 
-use nom::{sequence::separated_pair, multi::separated_list1, character::complete::{space1, alpha1, newline}, IResult, bytes::complete::tag};
 use rustworkx_core::connectivity::stoer_wagner_min_cut;
 
+use aoclib::dot::Graph;
+use aoclib::{Answer, Error, Solution};
+
+use crate::parse::parse_components;
+
 
 pub fn process(
     input: &str,
 ) -> i32 {
-    let (_, graph) = parse_input(input).expect("should parse!");
+    group_size_product(&parse_components(input).expect("should parse"))
+        .expect("the components should split into two groups")
+}
+
+/// Like [`process`], but reports a line which is not a component and its connections, or too few
+/// components to split into two groups, as an error.
+pub fn try_process(input: &str) -> Result<Answer, Error> {
+    Ok(group_size_product(&parse_components(input)?)?.into())
+}
+
+fn group_size_product(graph: &UnGraph<&str, ()>) -> Result<i32, Error> {
     let total_nodes = graph.node_count() as i32;
-    let cut: rustworkx_core::Result<_> = stoer_wagner_min_cut(&graph, |_| Ok(1));
-    let (_, nodes_in_partition) = cut.unwrap().unwrap();
+    let cut: rustworkx_core::Result<_> = stoer_wagner_min_cut(graph, |_| Ok(1));
+    let Ok(Some((_, nodes_in_partition))) = cut else {
+        return Err(Error::NoSolution("there are too few components to split into two groups".to_string()));
+    };
     
-    (total_nodes - nodes_in_partition.len() as i32) * (nodes_in_partition.len() as i32)
+    Ok((total_nodes - nodes_in_partition.len() as i32) * (nodes_in_partition.len() as i32))
 }
 
-fn parse_input(input: &str) -> IResult<&str, UnGraph<&str,()>>{
-    let (input, result) = separated_list1(newline, node)(input)?;
-    let mut graph = UnGraph::default();
+pub struct Part1;
 
-    // get a unique list of nodes
-    let nodes: HashSet<&str> = result.iter().flat_map(|(node, adjacencies)| {
-        let mut nodes = adjacencies.clone();
-        nodes.push(node);
-        nodes
-    }).collect();
-    
-    // create the nodes in the graph
-    let node_map: HashMap<_,_>= nodes.iter()
-        .map(|node| (*node, graph.add_node(*node)))
-        .collect();
-
-    // add the edges
-    for (node, adjacencies) in result {
-        for adjacency in adjacencies {
-            graph.add_edge(
-                node_map[node],
-                node_map[adjacency], 
-                ());
-        }
+impl Solution for Part1 {
+    const DAY: u8 = 25;
+    const PART: u8 = 1;
+
+    type Input<'a> = UnGraph<&'a str, ()>;
+    type Params = ();
+
+    fn parse(input: &str) -> Result<UnGraph<&'_ str, ()>, Error> {
+        parse_components(input)
     }
 
-    Ok((input, graph))
-}
+    fn solve(input: UnGraph<&'_ str, ()>, _: &()) -> Result<Answer, Error> {
+        Ok(group_size_product(&input)?.into())
+    }
+
+    /// The components and their wires, with the two groups left by the minimum cut coloured
+    /// differently and the wires to disconnect drawn dashed.
+    fn export_graph(components: UnGraph<&'_ str, ()>, _: &()) -> Result<Option<Graph>, Error> {
+        let cut: rustworkx_core::Result<_> = stoer_wagner_min_cut(&components, |_| Ok(1));
+        let partition: HashSet<_> = match cut {
+            Ok(Some((_, partition))) => partition.into_iter().collect(),
+            _ => HashSet::new(),
+        };
+
+        // the components are added in a different order each run, so sort them, and colour the
+        // group with the first of them the same way every time
+        let mut indices: Vec<_> = components.node_indices().collect();
+        indices.sort_by_key(|index| components[*index]);
+        let first_group = indices.first().is_some_and(|index| partition.contains(index));
 
-// cmg: qnr nvd lhk bvb
-fn node(input: &str) -> IResult<&str, (&str, Vec<&str>)> {
-    let (input, (node, adjacencies)) = separated_pair(alpha1, tag(": "), separated_list1(space1, alpha1))(input)?;
-    Ok((input, (node, adjacencies.into_iter().collect())))
+        let mut graph = Graph::undirected("day25");
+        for index in indices {
+            let colour = if partition.contains(&index) == first_group { "lightblue" } else { "lightpink" };
+            graph.node(components[index]).set("style", "filled").set("fillcolor", colour);
+        }
+        for edge in components.edge_references() {
+            let wire = graph.edge(components[edge.source()], components[edge.target()]);
+            if partition.contains(&edge.source()) != partition.contains(&edge.target()) {
+                wire.set("style", "dashed").set("color", "red");
+            }
+        }
+        Ok(Some(graph))
+    }
 }
 
 #[cfg(test)]
@@ -57,21 +86,35 @@ mod tests {
     use super::*;
 
     #[test]
-    fn test_process() {
-        let lines = "jqt: rhn xhk nvd
-rsh: frs pzl lsr
-xhk: hfx
-cmg: qnr nvd lhk bvb
-rhn: xhk bvb hfx
-bvb: xhk hfx
-pzl: lsr hfx nvd
-qnr: nvd
-ntq: jqt hfx bvb xhk
-nvd: lhk
-lsr: lhk
-rzs: qnr cmg lsr rsh
-frs: qnr lhk lsr";
-        let result = process(lines);
-        assert_eq!(result,54);
+    fn test_try_process() {
+        assert_eq!(
+            try_process("jqt: rhn xhk nvd\nrsh frs pzl lsr\n").unwrap_err().to_string(),
+            "line 2, column 4: expected \": \" in component, found \" frs pzl lsr\""
+        );
+        assert_eq!(
+            try_process(""),
+            Err(Error::NoSolution("there are too few components to split into two groups".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_export_graph() {
+        let graph = Part1::export_graph(Part1::parse("abc: abd abe\nabd: abe\nabe: xyz\nxyz: xya xyb\nxya: xyb\n").unwrap(), &()).unwrap().unwrap();
+        assert_eq!(graph.to_string(), r#"graph "day25" {
+    "abc" [style="filled", fillcolor="lightblue"];
+    "abd" [style="filled", fillcolor="lightblue"];
+    "abe" [style="filled", fillcolor="lightblue"];
+    "xya" [style="filled", fillcolor="lightpink"];
+    "xyb" [style="filled", fillcolor="lightpink"];
+    "xyz" [style="filled", fillcolor="lightpink"];
+    "abc" -- "abd";
+    "abc" -- "abe";
+    "abd" -- "abe";
+    "abe" -- "xyz" [style="dashed", color="red"];
+    "xyz" -- "xya";
+    "xyz" -- "xyb";
+    "xya" -- "xyb";
+}
+"#);
     }
 }
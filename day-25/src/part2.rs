@@ -1,9 +1,34 @@
+use aoclib::{Answer, Error, Solution};
+
 pub fn process<'a>(
     _input: &'a str,
 ) -> u32 {
     todo!()
 }
 
+/// Reports that there is no puzzle to solve, since the last day only has one part.
+pub fn try_process(_input: &str) -> Result<Answer, Error> {
+    Err(Error::NoSolution("the last day has only one puzzle".to_string()))
+}
+
+pub struct Part2;
+
+impl Solution for Part2 {
+    const DAY: u8 = 25;
+    const PART: u8 = 2;
+
+    type Input<'a> = &'a str;
+    type Params = ();
+
+    fn parse(input: &str) -> Result<&str, Error> {
+        Ok(input)
+    }
+
+    fn solve(input: &str, _: &()) -> Result<Answer, Error> {
+        try_process(input)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
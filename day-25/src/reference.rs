@@ -0,0 +1,67 @@
+//! A slow but obviously correct solution, to check the real one against on small inputs. The
+//! last day has no second puzzle to check.
+
+use std::collections::HashSet;
+
+/// How many components can be reached from `start` without crossing the `cut` wires.
+fn reachable(wires: &[(&str, &str)], cut: &[usize], start: &str) -> usize {
+    let mut seen = HashSet::from([start]);
+    let mut queue = vec![start];
+    while let Some(component) = queue.pop() {
+        for (index, (a, b)) in wires.iter().enumerate() {
+            let next = if *a == component {
+                b
+            } else if *b == component {
+                a
+            } else {
+                continue;
+            };
+            if !cut.contains(&index) && seen.insert(next) {
+                queue.push(next);
+            }
+        }
+    }
+    seen.len()
+}
+
+/// Cuts every three wires there are, until the components fall into two groups.
+pub fn part1(input: &str) -> usize {
+    let wires: Vec<(&str, &str)> = input
+        .lines()
+        .flat_map(|line| {
+            let (component, others) = line.split_once(": ").unwrap();
+            others.split(' ').map(move |other| (component, other))
+        })
+        .collect();
+    let total = reachable(&wires, &[], wires[0].0);
+    for first in 0..wires.len() {
+        for second in first + 1..wires.len() {
+            for third in second + 1..wires.len() {
+                let group = reachable(&wires, &[first, second, third], wires[0].0);
+                if group < total {
+                    return group * (total - group);
+                }
+            }
+        }
+    }
+    panic!("three wires should divide the components")
+}
+
+mod tests {
+    use aoclib::gen;
+    use aoclib::reference::assert_agrees;
+    use proptest::prelude::*;
+
+    use super::*;
+    use crate::part1::Part1;
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(32))]
+
+        #[test]
+        fn test_part1(seed: u64, size in 10..13usize) {
+            let input = gen::generator(25).unwrap().generate(size, seed);
+            assert_agrees::<Part1>(&input, &[], part1(&input));
+        }
+    }
+}
@@ -0,0 +1,366 @@
+//! The routes, which all answer with JSON:
+//!
+//! - `GET /days` lists each day's implemented parts and their parameters' defaults.
+//! - `POST /solve/{day}/{part}` solves the puzzle input in the body, with any parameters from the
+//!   query string, e.g. `/solve/21/1?steps=6`. With `Content-Type: application/json` the body is
+//!   instead `{"input": "...", "params": {"steps": 6}}`. Either way, line endings are normalised
+//!   as they are for the runner.
+//!
+//! A solved part gives its answer and how long it took. Otherwise there is an `error`, and for
+//! input which does not parse, a `diagnostic` pointing at where.
+
+use std::collections::BTreeMap;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::sync::Arc;
+use std::thread;
+use std::time::Instant;
+
+use aoc::limits::Limits;
+use aoc::solvers;
+use aoclib::input::normalise;
+use aoclib::{Error, Params, Solver};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tiny_http::{Header, Method, Request, Response, Server};
+use tracing::{info, warn};
+
+#[derive(Serialize)]
+struct Day {
+    day: u8,
+    parts: Vec<Part>,
+}
+
+#[derive(Serialize)]
+struct Part {
+    part: u8,
+    params: BTreeMap<&'static str, &'static str>,
+}
+
+#[derive(Serialize)]
+struct Solved {
+    day: u8,
+    part: u8,
+    answer: String,
+    nanoseconds: u64,
+}
+
+#[derive(Serialize)]
+struct Failed {
+    error: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    diagnostic: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct SolveRequest {
+    input: String,
+    #[serde(default)]
+    params: BTreeMap<String, Value>,
+}
+
+struct Reply {
+    status: u16,
+    body: String,
+}
+
+impl Reply {
+    fn ok(body: &impl Serialize) -> Self {
+        Self::with_status(200, body)
+    }
+
+    fn error(status: u16, error: impl Into<String>) -> Self {
+        Self::with_status(
+            status,
+            &Failed {
+                error: error.into(),
+                diagnostic: None,
+            },
+        )
+    }
+
+    fn with_status(status: u16, body: &impl Serialize) -> Self {
+        Reply {
+            status,
+            body: serde_json::to_string(body).expect("replies should serialise"),
+        }
+    }
+}
+
+/// Answers requests until the server shuts down, each on its own thread so that a slow part
+/// does not hold up the rest.
+pub fn serve(server: Server, limits: Limits) {
+    let solvers = Arc::new(solvers::all());
+    for request in server.incoming_requests() {
+        let solvers = Arc::clone(&solvers);
+        thread::spawn(move || respond(request, &solvers, limits));
+    }
+}
+
+fn respond(mut request: Request, solvers: &[Solver], limits: Limits) {
+    let json = request.headers().iter().any(|header| {
+        header.field.equiv("Content-Type") && header.value.as_str().starts_with("application/json")
+    });
+    let mut body = String::new();
+    let reply = match request.as_reader().read_to_string(&mut body) {
+        Ok(_) => route(request.method(), request.url(), json, body, solvers, limits),
+        Err(error) => Reply::error(400, format!("cannot read the request body: {}", error)),
+    };
+    info!(
+        method = %request.method(),
+        url = request.url(),
+        status = reply.status,
+        "answered a request"
+    );
+    let content_type =
+        Header::from_bytes("Content-Type", "application/json").expect("the header should be valid");
+    let response = Response::from_string(reply.body)
+        .with_status_code(reply.status)
+        .with_header(content_type);
+    if let Err(error) = request.respond(response) {
+        warn!(%error, "cannot send a reply");
+    }
+}
+
+fn route(
+    method: &Method,
+    url: &str,
+    json: bool,
+    body: String,
+    solvers: &[Solver],
+    limits: Limits,
+) -> Reply {
+    let (path, query) = url.split_once('?').unwrap_or((url, ""));
+    let segments = path.trim_matches('/').split('/').collect::<Vec<_>>();
+    match (method, segments.as_slice()) {
+        (Method::Get, ["days"]) => Reply::ok(&days(solvers)),
+        (Method::Post, ["solve", day, part]) => {
+            let Some(solver) = find(solvers, day, part) else {
+                return Reply::error(404, format!("day {} part {} is not implemented", day, part));
+            };
+            let request = if json {
+                serde_json::from_str::<SolveRequest>(&body)
+                    .map_err(|error| format!("invalid request: {}", error))
+                    .and_then(|request| Ok((request.input, json_params(&request.params)?)))
+            } else {
+                query_params(query).map(|params| (body, params))
+            };
+            match request {
+                Ok((input, params)) => solve(solver, &normalise(&input), &params, limits),
+                Err(error) => Reply::error(400, error),
+            }
+        }
+        (_, ["days"] | ["solve", _, _]) => Reply::error(405, "method not allowed"),
+        _ => Reply::error(404, format!("no such route: {}", path)),
+    }
+}
+
+fn days(solvers: &[Solver]) -> Vec<Day> {
+    let mut days: Vec<Day> = vec![];
+    for solver in solvers
+        .iter()
+        .filter(|solver| solvers::is_implemented(solver))
+    {
+        let part = Part {
+            part: solver.part,
+            params: solver.defaults.iter().copied().collect(),
+        };
+        match days.last_mut() {
+            Some(day) if day.day == solver.day => day.parts.push(part),
+            _ => days.push(Day {
+                day: solver.day,
+                parts: vec![part],
+            }),
+        }
+    }
+    days
+}
+
+fn find<'a>(solvers: &'a [Solver], day: &str, part: &str) -> Option<&'a Solver> {
+    let (day, part) = (day.parse::<u8>().ok()?, part.parse::<u8>().ok()?);
+    solvers
+        .iter()
+        .find(|solver| solver.day == day && solver.part == part && solvers::is_implemented(solver))
+}
+
+/// Reads `key=value` pairs separated by `&`. Parameters are all numbers, so nothing is decoded.
+fn query_params(query: &str) -> Result<Params, String> {
+    let mut params = Params::new();
+    for pair in query.split('&').filter(|pair| !pair.is_empty()) {
+        let (key, value) = pair
+            .split_once('=')
+            .ok_or_else(|| format!("{:?} is not of the form key=value", pair))?;
+        params.set(key, value);
+    }
+    Ok(params)
+}
+
+fn json_params(values: &BTreeMap<String, Value>) -> Result<Params, String> {
+    let mut params = Params::new();
+    for (key, value) in values {
+        match value {
+            Value::String(value) => params.set(key, value),
+            Value::Number(value) => params.set(key, value.to_string()),
+            _ => return Err(format!("parameter {} should be a number or a string", key)),
+        }
+    }
+    Ok(params)
+}
+
+fn solve(solver: &Solver, input: &str, overrides: &Params, limits: Limits) -> Reply {
+    let params = match solver.params(overrides, true) {
+        Ok(params) => params,
+        Err(error) => return Reply::error(400, error.to_string()),
+    };
+    let start = Instant::now();
+    let answer = limits
+        .budget()
+        .run(|| catch_unwind(AssertUnwindSafe(|| solver.solve(input, &params))));
+    let elapsed = start.elapsed();
+    match answer {
+        Ok(Ok(answer)) => Reply::ok(&Solved {
+            day: solver.day,
+            part: solver.part,
+            answer: answer.to_string(),
+            nanoseconds: elapsed.as_nanos() as u64,
+        }),
+        Ok(Err(error @ Error::Params(_))) => Reply::error(400, error.to_string()),
+        Ok(Err(error)) => Reply::with_status(
+            422,
+            &Failed {
+                error: error.to_string(),
+                diagnostic: matches!(error, Error::Parse { .. })
+                    .then(|| error.render("input", input)),
+            },
+        ),
+        Err(_) => Reply::error(500, "solver panicked"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoclib::example::Example;
+
+    /// Starts a server on a free port, returning its base URL.
+    fn start(limits: Limits) -> String {
+        let server = Server::http("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", server.server_addr());
+        thread::spawn(move || serve(server, limits));
+        url
+    }
+
+    /// Sends `request`, returning the status and the parsed reply whether or not it succeeded.
+    fn call(request: Result<ureq::Response, ureq::Error>) -> (u16, Value) {
+        let response = match request {
+            Ok(response) => response,
+            Err(ureq::Error::Status(_, response)) => response,
+            Err(error) => panic!("request failed: {}", error),
+        };
+        assert_eq!(response.content_type(), "application/json");
+        let status = response.status();
+        (
+            status,
+            serde_json::from_str(&response.into_string().unwrap()).unwrap(),
+        )
+    }
+
+    #[test]
+    fn test_days() {
+        let url = start(Limits::default());
+        let (status, days) = call(ureq::get(&format!("{}/days", url)).call());
+        assert_eq!(status, 200);
+        let days = days.as_array().unwrap();
+        assert_eq!(days.len(), 25);
+        assert_eq!(days[20]["parts"][0]["params"]["steps"], "64");
+        assert_eq!(days[24]["parts"].as_array().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_solve() {
+        let url = start(Limits::default());
+        let example = Example::parse(include_str!("../../day-01/examples/part1.txt")).unwrap();
+        let (status, solved) =
+            call(ureq::post(&format!("{}/solve/1/1", url)).send_string(&example.input));
+        assert_eq!(status, 200);
+        assert_eq!(solved["answer"], "142");
+        assert!(solved["nanoseconds"].is_u64());
+
+        let example = Example::parse(include_str!("../../day-21/examples/example.txt")).unwrap();
+        let (status, solved) =
+            call(ureq::post(&format!("{}/solve/21/1?steps=6", url)).send_string(&example.input));
+        assert_eq!((status, &solved["answer"]), (200, &Value::from("16")));
+        let body = serde_json::json!({"input": example.input, "params": {"steps": 6}});
+        let (status, solved) = call(
+            ureq::post(&format!("{}/solve/21/1", url))
+                .set("Content-Type", "application/json")
+                .send_string(&body.to_string()),
+        );
+        assert_eq!((status, &solved["answer"]), (200, &Value::from("16")));
+    }
+
+    #[test]
+    fn test_line_endings() {
+        let url = start(Limits::default());
+        let example = Example::parse(include_str!("../../day-05/examples/example.txt")).unwrap();
+        let crlf = example.input.replace('\n', "\r\n");
+        let unterminated = example.input.trim_end();
+        for part in [1, 2] {
+            let solve = format!("{}/solve/5/{}", url, part);
+            let expected = Value::from(example.answer(part).unwrap());
+            for input in [crlf.as_str(), unterminated] {
+                let (status, solved) = call(ureq::post(&solve).send_string(input));
+                assert_eq!((status, &solved["answer"]), (200, &expected));
+            }
+            let body = serde_json::json!({"input": crlf});
+            let (status, solved) = call(
+                ureq::post(&solve)
+                    .set("Content-Type", "application/json")
+                    .send_string(&body.to_string()),
+            );
+            assert_eq!((status, &solved["answer"]), (200, &expected));
+        }
+    }
+
+    #[test]
+    fn test_errors() {
+        let url = start(Limits {
+            max_steps: Some(5),
+            ..Limits::default()
+        });
+        let (status, failed) =
+            call(ureq::post(&format!("{}/solve/2/1", url)).send_string("junk here\n"));
+        assert_eq!(status, 422);
+        assert_eq!(
+            failed["error"],
+            "line 1, column 1: expected \"Game \" in game, found \"junk here\""
+        );
+        assert!(failed["diagnostic"]
+            .as_str()
+            .unwrap()
+            .starts_with("input:1:1: expected \"Game \" in game\n"));
+
+        let example = Example::parse(include_str!("../../day-14/examples/example.txt")).unwrap();
+        let (status, failed) =
+            call(ureq::post(&format!("{}/solve/14/2", url)).send_string(&example.input));
+        assert_eq!(status, 422);
+        assert_eq!(failed["error"], "budget exceeded after 5 steps");
+
+        let (status, _) = call(ureq::post(&format!("{}/solve/21/1?size=6", url)).send_string(""));
+        assert_eq!(status, 400);
+        let (status, _) = call(
+            ureq::post(&format!("{}/solve/1/1", url))
+                .set("Content-Type", "application/json")
+                .send_string("1abc2"),
+        );
+        assert_eq!(status, 400);
+        let (status, _) = call(ureq::post(&format!("{}/solve/25/2", url)).send_string(""));
+        assert_eq!(status, 404);
+        let (status, _) = call(ureq::post(&format!("{}/solve/26/1", url)).send_string(""));
+        assert_eq!(status, 404);
+        let (status, _) = call(ureq::get(&format!("{}/solve/1/1", url)).call());
+        assert_eq!(status, 405);
+        let (status, _) = call(ureq::get(&format!("{}/", url)).call());
+        assert_eq!(status, 404);
+    }
+}
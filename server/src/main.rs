@@ -0,0 +1,51 @@
+use std::io::{self, IsTerminal};
+use std::process::ExitCode;
+
+use aoc::limits::Limits;
+use clap::Parser;
+use tiny_http::Server;
+use tracing_subscriber::filter::{EnvFilter, LevelFilter};
+
+mod api;
+
+#[derive(Parser)]
+#[command(
+    name = "server",
+    about = "Serves the Advent of Code 2023 solutions over HTTP"
+)]
+struct Cli {
+    /// The address to listen on, with port 0 for any free port
+    #[arg(long, default_value = "127.0.0.1:8080")]
+    address: String,
+
+    #[command(flatten)]
+    limits: Limits,
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    init_logging();
+    let server = match Server::http(&cli.address) {
+        Ok(server) => server,
+        Err(error) => {
+            eprintln!("error: cannot listen on {}: {}", cli.address, error);
+            return ExitCode::FAILURE;
+        }
+    };
+    eprintln!("listening on http://{}", server.server_addr());
+    api::serve(server, cli.limits);
+    ExitCode::SUCCESS
+}
+
+/// Logs each request to stderr, along with anything the solvers warn about. `RUST_LOG` changes
+/// what is logged, as for the runner.
+fn init_logging() {
+    let filter = EnvFilter::builder()
+        .with_default_directive(LevelFilter::INFO.into())
+        .from_env_lossy();
+    tracing_subscriber::fmt()
+        .with_env_filter(filter)
+        .with_ansi(io::stderr().is_terminal())
+        .with_writer(io::stderr)
+        .init();
+}